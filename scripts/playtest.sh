@@ -32,7 +32,8 @@ run_script() {
     local name=$1
     echo -n "Running $name ... "
     # Bot mode still initializes the window backend, so CI may wrap this script in xvfb-run.
-    "${RUN_PREFIX[@]}" ./target/debug/rustler --bot "$name" 2>&1 | tee "/tmp/bot_$name.log"
+    "${RUN_PREFIX[@]}" ./target/debug/rustler --bot "$name" \
+        --bot-report "/tmp/bot_$name.json" --bot-junit "/tmp/bot_$name.xml" 2>&1 | tee "/tmp/bot_$name.log"
     local exitcode=${PIPESTATUS[0]}
 
     if [ $exitcode -eq 0 ]; then
@@ -50,18 +51,10 @@ run_script() {
 if [ $# -gt 0 ]; then
     run_script "$1"
 else
-    run_script groove_dash
-    run_script menu_to_game
-    run_script campaign_escape
-    run_script campaign_loss
-    run_script campaign_tutorial
-    run_script campaign_full
-    run_script npc_steal
-    run_script player_steal
-    run_script steal_defense
-    run_script steal_dodge
-    run_script revenge
-    run_script npc_vs_npc
+    # The built-in scenarios are the files under resources/bots.
+    for name in $(./target/debug/rustler --list-bots | cut -d' ' -f1); do
+        run_script "$name"
+    done
 fi
 
 echo ""
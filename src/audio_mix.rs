@@ -7,7 +7,6 @@
 //! structural move — no behaviour change; the caller still owns the surrounding
 //! simulation and invokes these at the same points, with the same `dt`.
 
-use ggez::Context;
 use ggez::audio::SoundSource;
 
 use crate::beat::downbeat_started;
@@ -15,7 +14,7 @@ use crate::*;
 
 impl MainState {
     pub(crate) fn pause_gameplay_music(&self) {
-        let pause_if_playing = |source: &ggez::audio::Source| {
+        let pause_if_playing = |source: &crate::sounds::Sfx| {
             if source.playing() {
                 source.pause();
             }
@@ -46,7 +45,7 @@ impl MainState {
 
     /// Spatial King Crab boss rumble + intensity-scaled music layers. Runs once per
     /// frame from `update`, right after boss spawning and before the game-over tally.
-    pub(crate) fn update_boss_and_music_audio(&mut self, _ctx: &mut Context, dt: f32) {
+    pub(crate) fn update_boss_and_music_audio(&mut self, dt: f32) {
         // Spatial audio for King Crab boss crabs.
         //
         // Three looping stereo sources are blended by boss distance and angle each frame:
@@ -196,7 +195,7 @@ impl MainState {
     /// Ambient field audio: steal stings, the NPC-train rumble + per-rival motifs, and
     /// the archetype crab-theme loops. Runs once per frame from `update`, right after
     /// `update_npc_trains` and before the camera recompute.
-    pub(crate) fn update_ambient_audio(&mut self, ctx: &mut Context, dt: f32) {
+    pub(crate) fn update_ambient_audio(&mut self, dt: f32) {
         // Steal stings: the splice logic above runs without `ctx`, so it just latches a one-frame
         // flag when crabs change hands. Play the matching sting here — a descending thud when a
         // rival rustles from you, a rising sparkle when you rustle back — so the core steal moment
@@ -271,7 +270,7 @@ impl MainState {
             } else {
                 (0.0, 0.0)
             };
-            let smooth = |src: &mut ggez::audio::Source, tgt: f32| {
+            let smooth = |src: &mut crate::sounds::Sfx, tgt: f32| {
                 let cur = src.volume();
                 let v = (cur + (tgt - cur) * (dt * 2.0).min(1.0)).clamp(0.0, 1.0);
                 src.set_volume(v);
@@ -423,7 +422,7 @@ impl MainState {
                 };
                 counts[theme] += 1;
             }
-            let dt_audio = self.frame_dt(ctx);
+            let dt_audio = dt;
             for (i, theme) in self.sounds.crab_themes.iter_mut().enumerate() {
                 let target = if self.music_muted || counts[i] == 0 {
                     0.0
@@ -0,0 +1,382 @@
+//! Monte-Carlo balance runs: one scenario played across many seeds, with a table of outcomes.
+//!
+//! Tuning `INTENSITY_STAGES`, `panic_snap_links`, `STEAL_MAX_LINKS`, `pen_worth` or the crab
+//! rarities by one playtest means tuning to one lucky (or unlucky) run. `--balance <subject>` plays
+//! the subject headlessly on `--runs N` consecutive seeds starting at `--seed S` (default 1), as
+//! fast as the machine allows and spread over `--jobs` threads (the game RNG is per-thread, so
+//! each run is still reproducible from its seed alone), then prints a distribution summary and, with
+//! `--out <file>`, writes every run's outcome as CSV (or JSON for a `.json` file) to compare
+//! before and after a change.
+//!
+//! The subject is a bot scenario — a built-in name or a `.bot` file, run to the end of its script
+//! — or an autopilot policy that just plays:
+//!
+//! - `seek_catch`: the catch autopilot, banking only when the train happens past the pen;
+//! - `seek_delivery`: the catch autopilot plus steering to the pen whenever a train is hauled.
+//!
+//! A policy plays an endless run from the title screen, or the campaign node picked with
+//! `--node <index>`, until the run ends or `--duration` game seconds (default 180) pass.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::bot::{BotAction, BotEvent, BotScenario};
+use crate::replay::key_from_name;
+use crate::simulation::Simulation;
+
+/// Default game-time budget for a policy run, in seconds.
+const DEFAULT_DURATION: f32 = 180.0;
+
+/// The autopilot policies `--balance` accepts besides scenarios.
+pub const POLICIES: [&str; 2] = ["seek_catch", "seek_delivery"];
+
+/// What one run came to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunOutcome {
+    pub seed: u64,
+    /// For a scenario, whether its script passed; for a policy, whether the run survived to the
+    /// end of its duration (or won its level).
+    pub passed: bool,
+    pub game_time: f32,
+    pub score: usize,
+    pub banked_crabs: usize,
+    pub longest_train: usize,
+    pub crabs_lost_to_rivals: usize,
+    pub snaps: usize,
+    pub bosses_caught: usize,
+    /// Mean seconds from a boss arriving to its capture, over the bosses caught.
+    pub boss_time_to_kill: Option<f32>,
+    /// When the campaign level's win condition was met, if it was.
+    pub win_time: Option<f32>,
+}
+
+/// A `--balance` subject made runnable: the scenario to play and whether the run stops when the
+/// game run itself ends (policies) or only when the script does (scenarios).
+pub struct Subject {
+    pub scenario: BotScenario,
+    pub stop_when_run_ends: bool,
+}
+
+impl Subject {
+    /// Resolve `--balance <what>`: a policy name, a `.bot` file, or a built-in scenario.
+    pub fn resolve(what: &str, duration: Option<f32>, node: Option<usize>) -> Result<Self, String> {
+        if POLICIES.contains(&what) {
+            let duration = duration.unwrap_or(DEFAULT_DURATION);
+            return Ok(Subject {
+                scenario: policy_scenario(what, duration, node),
+                stop_when_run_ends: true,
+            });
+        }
+        if duration.is_some() || node.is_some() {
+            return Err("--duration and --node only apply to the autopilot policies".into());
+        }
+        let scenario = if what.ends_with(".bot") {
+            BotScenario::load(Path::new(what))?
+        } else {
+            BotScenario::find(what)?
+        };
+        Ok(Subject {
+            scenario,
+            stop_when_run_ends: false,
+        })
+    }
+
+    /// Play one run on `seed`.
+    pub fn run(&self, seed: u64) -> RunOutcome {
+        let mut scenario = self.scenario.clone();
+        scenario.seed = seed;
+        let mut sim = Simulation::bot(&scenario);
+        let mut win_time = None;
+        let mut entered = false;
+        let passed = loop {
+            let state = sim.state();
+            if state.level_complete {
+                win_time = Some(state.win_time);
+            }
+            let bot = state.bot.as_ref().expect("bot installed");
+            if bot.done {
+                break bot.failed.is_none();
+            }
+            if self.stop_when_run_ends {
+                let in_run = !state.show_instructions && !state.show_world_map;
+                if entered && (state.game_over || !in_run) {
+                    break !state.game_over;
+                }
+                entered |= in_run;
+            }
+            sim.step_bot();
+        };
+        let state = sim.state();
+        let bosses_caught = state.boss_catch_times.len();
+        RunOutcome {
+            seed,
+            passed,
+            game_time: state.time_elapsed,
+            score: state.score,
+            banked_crabs: state.banked_crabs_run,
+            longest_train: state.longest_train_run,
+            crabs_lost_to_rivals: state.crabs_stolen_by_npc,
+            snaps: state.snaps_run,
+            bosses_caught,
+            boss_time_to_kill: (bosses_caught > 0)
+                .then(|| state.boss_catch_times.iter().sum::<f32>() / bosses_caught as f32),
+            win_time,
+        }
+    }
+}
+
+/// The scripted prologue for a policy: into a run (an endless one from the title, or campaign node
+/// `node` via the world map), then the autopilot on until `duration`.
+fn policy_scenario(policy: &str, duration: f32, node: Option<usize>) -> BotScenario {
+    let key = |name: &str| key_from_name(name).expect("known key");
+    let mut script = Vec::new();
+    let mut at = 0.5;
+    let mut tap = |at: &mut f32, name: &str, gap: f32| {
+        script.push(BotEvent {
+            at: *at,
+            action: BotAction::TapKey(key(name)),
+        });
+        *at += gap;
+    };
+    match node {
+        None => {
+            // Title -> play recommendation -> mutator screen (on START) -> run, as the arcade
+            // scenarios do.
+            tap(&mut at, "Space", 0.5);
+            tap(&mut at, "Space", 0.3);
+            tap(&mut at, "Space", 1.0);
+        }
+        Some(node) => {
+            tap(&mut at, "KeyC", 0.5);
+            for _ in 0..node {
+                tap(&mut at, "ArrowRight", 0.1);
+            }
+            // The second Enter confirms the skip-ahead warning a locked node shows.
+            tap(&mut at, "Enter", 0.4);
+            tap(&mut at, "Enter", 1.0);
+        }
+    }
+    script.push(BotEvent {
+        at,
+        action: BotAction::SeekCatch(true),
+    });
+    if policy == "seek_delivery" {
+        script.push(BotEvent {
+            at,
+            action: BotAction::SeekDelivery(true),
+        });
+    }
+    // Holds the script open (and so the run going) until the duration is up.
+    script.push(BotEvent {
+        at: duration,
+        action: BotAction::Log(format!("{policy}: {duration}s up")),
+    });
+    BotScenario {
+        name: policy.to_string(),
+        script,
+        time_limit: duration + 1.0,
+        time_scale: 3.0,
+        seed: 0,
+    }
+}
+
+/// Play `runs` seeds from `first_seed` on `jobs` threads. Outcomes come back in seed order.
+pub fn run_batch(subject: &Subject, first_seed: u64, runs: usize, jobs: usize) -> Vec<RunOutcome> {
+    let jobs = jobs.clamp(1, runs.max(1));
+    let mut outcomes: Vec<RunOutcome> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|job| {
+                scope.spawn(move || {
+                    (job..runs)
+                        .step_by(jobs)
+                        .map(|i| subject.run(first_seed + i as u64))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().expect("balance run panicked"))
+            .collect()
+    });
+    outcomes.sort_by_key(|o| o.seed);
+    outcomes
+}
+
+const COLUMNS: [&str; 11] = [
+    "seed",
+    "passed",
+    "game_time",
+    "score",
+    "banked_crabs",
+    "longest_train",
+    "crabs_lost_to_rivals",
+    "snaps",
+    "bosses_caught",
+    "boss_time_to_kill",
+    "win_time",
+];
+
+impl RunOutcome {
+    /// The outcome's cells, in `COLUMNS` order; `None` for a value the run never produced.
+    fn cells(&self) -> [Option<String>; 11] {
+        let secs = |t: f32| format!("{t:.2}");
+        [
+            Some(self.seed.to_string()),
+            Some(self.passed.to_string()),
+            Some(secs(self.game_time)),
+            Some(self.score.to_string()),
+            Some(self.banked_crabs.to_string()),
+            Some(self.longest_train.to_string()),
+            Some(self.crabs_lost_to_rivals.to_string()),
+            Some(self.snaps.to_string()),
+            Some(self.bosses_caught.to_string()),
+            self.boss_time_to_kill.map(secs),
+            self.win_time.map(secs),
+        ]
+    }
+}
+
+pub fn to_csv(outcomes: &[RunOutcome]) -> String {
+    let mut out = COLUMNS.join(",");
+    out.push('\n');
+    for o in outcomes {
+        let cells: Vec<String> = o
+            .cells()
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+pub fn to_json(outcomes: &[RunOutcome]) -> String {
+    let mut out = String::from("[");
+    for (i, o) in outcomes.iter().enumerate() {
+        out.push_str(if i == 0 { "\n  {" } else { ",\n  {" });
+        for (j, (name, cell)) in COLUMNS.iter().zip(o.cells()).enumerate() {
+            let sep = if j == 0 { "" } else { ", " };
+            let _ = write!(
+                out,
+                "{sep}\"{name}\": {}",
+                cell.as_deref().unwrap_or("null")
+            );
+        }
+        out.push('}');
+    }
+    out.push_str(if outcomes.is_empty() { "]\n" } else { "\n]\n" });
+    out
+}
+
+/// One summarised column, read off an outcome; `None` when the run never produced it.
+type Metric = fn(&RunOutcome) -> Option<f32>;
+
+/// Per-metric distribution over the runs: how many produced it, mean, 10th/50th/90th percentile.
+pub fn summary(outcomes: &[RunOutcome]) -> String {
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    let mut out = format!("{} runs, {passed} passed\n", outcomes.len());
+    let _ = writeln!(
+        out,
+        "{:<22} {:>5} {:>9} {:>9} {:>9} {:>9}",
+        "metric", "runs", "mean", "p10", "median", "p90"
+    );
+    let metrics: [(&str, Metric); 9] = [
+        ("game_time", |o| Some(o.game_time)),
+        ("score", |o| Some(o.score as f32)),
+        ("banked_crabs", |o| Some(o.banked_crabs as f32)),
+        ("longest_train", |o| Some(o.longest_train as f32)),
+        ("crabs_lost_to_rivals", |o| {
+            Some(o.crabs_lost_to_rivals as f32)
+        }),
+        ("snaps", |o| Some(o.snaps as f32)),
+        ("bosses_caught", |o| Some(o.bosses_caught as f32)),
+        ("boss_time_to_kill", |o| o.boss_time_to_kill),
+        ("win_time", |o| o.win_time),
+    ];
+    for (name, metric) in metrics {
+        let mut values: Vec<f32> = outcomes.iter().filter_map(metric).collect();
+        if values.is_empty() {
+            let _ = writeln!(out, "{name:<22} {:>5}", 0);
+            continue;
+        }
+        values.sort_by(f32::total_cmp);
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let _ = writeln!(
+            out,
+            "{name:<22} {:>5} {mean:>9.2} {:>9.2} {:>9.2} {:>9.2}",
+            values.len(),
+            percentile(&values, 0.1),
+            percentile(&values, 0.5),
+            percentile(&values, 0.9),
+        );
+    }
+    out
+}
+
+/// Nearest-rank percentile of sorted, non-empty `values`.
+pub(crate) fn percentile(values: &[f32], p: f32) -> f32 {
+    let rank = (p * values.len() as f32).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(seed: u64, score: usize, win_time: Option<f32>) -> RunOutcome {
+        RunOutcome {
+            seed,
+            passed: true,
+            game_time: 60.0,
+            score,
+            banked_crabs: score / 2,
+            longest_train: 7,
+            crabs_lost_to_rivals: 2,
+            snaps: 1,
+            bosses_caught: 0,
+            boss_time_to_kill: None,
+            win_time,
+        }
+    }
+
+    #[test]
+    fn outcome_tables_leave_missing_values_blank() {
+        let runs = [outcome(1, 40, None), outcome(2, 90, Some(41.5))];
+        let csv = to_csv(&runs);
+        let mut lines = csv.lines();
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("seed,passed,game_time,score,")
+        );
+        assert_eq!(lines.next(), Some("1,true,60.00,40,20,7,2,1,0,,"));
+        assert_eq!(lines.next(), Some("2,true,60.00,90,45,7,2,1,0,,41.50"));
+        let json = to_json(&runs);
+        assert!(json.contains("\"boss_time_to_kill\": null, \"win_time\": 41.50}"));
+        let summary = summary(&runs);
+        assert!(summary.starts_with("2 runs, 2 passed"));
+        assert!(summary.contains("win_time                   1"));
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        assert_eq!(percentile(&values, 0.1), 1.0);
+        assert_eq!(percentile(&values, 0.5), 5.0);
+        assert_eq!(percentile(&values, 0.9), 9.0);
+        assert_eq!(percentile(&[3.0], 0.9), 3.0);
+    }
+
+    #[test]
+    fn a_policy_batch_is_reproducible_per_seed() {
+        let subject = Subject::resolve("seek_delivery", Some(20.0), None).unwrap();
+        let batch = run_batch(&subject, 5, 3, 2);
+        assert_eq!(batch.iter().map(|o| o.seed).collect::<Vec<_>>(), [5, 6, 7]);
+        assert!(batch.iter().all(|o| o.game_time > 5.0));
+        // The same seed on its own thread plays out identically.
+        assert_eq!(subject.run(6), batch[1]);
+    }
+}
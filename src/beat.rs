@@ -7,7 +7,6 @@
 //! file navigable. Pure structural move — no behaviour change; the caller still owns the
 //! `beat_timer` countdown and only invokes this when a beat actually lands.
 
-use ggez::Context;
 use ggez::glam::Vec2;
 use rand::Rng;
 
@@ -39,7 +38,7 @@ impl MainState {
     /// Hitstop and cinematic slow-motion freeze the simulation, not the backing track. Keeping
     /// this clock ahead of those early returns prevents repeated catches and dashes from letting
     /// the live kick/snare grid fall behind the looping melody.
-    pub(crate) fn update_master_beat(&mut self, ctx: &mut Context, dt: f32) {
+    pub(crate) fn update_master_beat(&mut self, dt: f32) {
         if self.beat_interval <= 1e-4 {
             return;
         }
@@ -57,22 +56,22 @@ impl MainState {
             if frac + 1e-6 >= onset && gstep > self.hat_last_step {
                 self.hat_last_step = gstep;
                 if local == 2 {
-                    self.beat_synth.play_hihat(ctx, base_vol);
+                    self.beat_synth.play_hihat(base_vol);
                 } else if busy {
-                    self.beat_synth.play_hihat(ctx, base_vol * 0.55);
+                    self.beat_synth.play_hihat(base_vol * 0.55);
                 }
             }
         }
 
         self.beat_timer -= dt;
         while self.beat_timer <= 0.0 {
-            self.on_beat(ctx);
+            self.on_beat();
         }
     }
 
     /// Runs once per beat, immediately after `beat_timer` wraps. `ctx` is needed for the
     /// synthesised percussion voices; all other state lives on `self`.
-    pub(crate) fn on_beat(&mut self, ctx: &mut Context) {
+    pub(crate) fn on_beat(&mut self) {
         self.beat_timer += self.beat_interval;
         self.beat_intensity = 1.0;
         self.beat_count = self.beat_count.wrapping_add(1);
@@ -81,7 +80,7 @@ impl MainState {
         // not just seen. The heavier, lower voice lands on the downbeat so the bar has a clear
         // accent structure. This block only runs during live gameplay (the update guard returns
         // early on menu/upgrade/game-over screens), so the kick never thumps through menus.
-        self.beat_synth.play_kick(ctx, downbeat);
+        self.beat_synth.play_kick(downbeat);
         // Keep the music loop tempo- AND phase-locked to the master beat clock. The intensity
         // ramp speeds the clock up (`beat_interval = BEAT_INTERVAL / tempo_mul`), but the groove
         // is a pre-baked loop that can't re-pitch itself — so without this it drifts off the beat
@@ -134,7 +133,7 @@ impl MainState {
         // stakes audibly as the fight escalates. Fades back out once the boss is caught.
         let boss_present = self.crabs.iter().any(|c| c.is_boss() && !c.caught);
         self.beat_synth.update_snare_volume(boss_present);
-        self.beat_synth.play_snare(ctx, self.beat_count);
+        self.beat_synth.play_snare(self.beat_count);
         // On-beat catch bloom: every beat the train's catch window blooms wide, then settles back
         // before the next hit (decayed in update_crabs). The downbeat blooms hardest so the "1"
         // is the widest scoop of the bar — a groove-savvy player learns to cross a drifting crab
@@ -0,0 +1,537 @@
+//! Stress benchmarks for the simulation's hot paths: `--bench <preset>`.
+//!
+//! The herd has no size cap and the rival trains multiply the bodies everything has to test
+//! against, so the per-crab loops are where a careless change turns quadratic. A bench run builds a
+//! headless world on a `MapSize::Large` map (or `--map medium`) — `--crabs` free crabs drawn from
+//! an archetype `--mix`, a player train of `--chained` links sweeping a circle through them, and
+//! `--trains` rival `NpcCongaTrain`s of `--train-len` followers — then steps it `--steps` times,
+//! timing each target on its own every step:
+//!
+//! - `rebuild_crab_index`, the shared spatial index every proximity query below reads;
+//! - `update_crabs`, the per-crab movement/AI pass;
+//! - `catch_by_chain`, the chain-link catch sweep;
+//! - `beat_startle_contagion`, the panic ripple (run every step here rather than once a beat);
+//! - `deflect_fleeing_off_chain`, the train-body wall;
+//! - `update_npc_trains`, the rival trains.
+//!
+//! Between steps (untimed) crabs caught past the starting train length are banked and the free herd
+//! is topped back up, so the load stays at the configured size for the whole run rather than
+//! draining into the train. Every step is seeded (`--seed`), so two builds bench the same world.
+//!
+//! The result is a table of per-step microseconds (mean, median, p95, max) per target; `--out`
+//! writes it as CSV and `--baseline <csv>` compares the medians against an earlier run, exiting
+//! non-zero when any target slowed by more than `--tolerance` percent (default 25). Absolute
+//! timings only compare on one machine; the scaling test at the bottom of this file is what CI
+//! runs, checking each target's cost grows no faster than the load does at a fixed density.
+//!
+//! ```text
+//! cargo run --release -- --bench stress --out before.csv
+//! cargo run --release -- --bench stress --baseline before.csv
+//! cargo run --release -- --bench stress --crabs 8000 --trains 24 --mix armored:3,dancer:2,golden:1
+//! ```
+
+use std::fmt::Write as _;
+use std::time::Instant;
+
+use ggez::glam::Vec2;
+use rand::Rng;
+
+use crate::MainState;
+use crate::balance::percentile;
+use crate::bot::BOT_DT;
+use crate::constants::{CHAIN_LINK_FRAMES, PLAYER_SIZE};
+use crate::enemies::CrabType;
+use crate::levels::MapSize;
+use crate::npc_conga_train::NpcCongaTrain;
+use crate::spawnings::spawn_scattered_crab;
+
+/// Steps run before timing starts, so first-touch allocations in the reused scratch buffers don't
+/// land in the numbers.
+const WARMUP_STEPS: usize = 30;
+
+/// The archetypes a free herd can hold: everything `CrabType::random` rolls (no bosses).
+const HERD_TYPES: [CrabType; 11] = [
+    CrabType::Normal,
+    CrabType::Fast,
+    CrabType::Big,
+    CrabType::Sneaky,
+    CrabType::Armored,
+    CrabType::Dancer,
+    CrabType::Magnet,
+    CrabType::Thief,
+    CrabType::Hermit,
+    CrabType::Golden,
+    CrabType::Splitter,
+];
+
+/// The presets `--bench` accepts; every field can be overridden from the command line.
+pub const PRESETS: [&str; 3] = ["smoke", "stress", "extreme"];
+
+/// Which archetypes the herd is drawn from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mix {
+    /// The game's own herd roll (`CrabType::random`).
+    Herd,
+    /// Archetypes in proportion to their weights.
+    Weighted(Vec<(CrabType, u32)>),
+}
+
+impl Mix {
+    /// Parse `--mix`: `herd`, or comma-separated `archetype:weight` pairs like `normal:6,armored:2`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        if text == "herd" {
+            return Ok(Mix::Herd);
+        }
+        let mut weights = Vec::new();
+        for part in text.split(',') {
+            let (name, weight) = part
+                .split_once(':')
+                .ok_or_else(|| format!("`{part}` is not archetype:weight"))?;
+            let crab_type = HERD_TYPES
+                .into_iter()
+                .find(|t| format!("{t:?}").eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| format!("unknown archetype `{name}`"))?;
+            let weight = weight
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("`{weight}` is not a whole-number weight"))?;
+            weights.push((crab_type, weight));
+        }
+        if weights.iter().all(|(_, w)| *w == 0) {
+            return Err("the mix needs at least one non-zero weight".into());
+        }
+        Ok(Mix::Weighted(weights))
+    }
+
+    fn roll(&self, rng: &mut impl Rng) -> CrabType {
+        match self {
+            Mix::Herd => CrabType::random(rng),
+            Mix::Weighted(weights) => {
+                let total: u32 = weights.iter().map(|(_, w)| w).sum();
+                let mut pick = rng.random_range(0..total);
+                for &(crab_type, weight) in weights {
+                    if pick < weight {
+                        return crab_type;
+                    }
+                    pick -= weight;
+                }
+                unreachable!("pick is below the weight total")
+            }
+        }
+    }
+}
+
+/// The world a bench run builds and how long it runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Free crabs on the map, held at this count every step.
+    pub crabs: usize,
+    /// Links in the player's train.
+    pub chained: usize,
+    pub trains: usize,
+    /// Followers behind each rival King Crab.
+    pub train_len: usize,
+    pub mix: Mix,
+    pub map: MapSize,
+    /// Share of the free herd spawned already panicking, so the contagion and deflection passes
+    /// have carriers to work with from the first step.
+    pub panicked: f32,
+    pub steps: usize,
+    pub seed: u64,
+}
+
+impl BenchConfig {
+    pub fn preset(name: &str) -> Result<Self, String> {
+        let (crabs, chained, trains, train_len, steps) = match name {
+            "smoke" => (300, 30, 3, 6, 60),
+            "stress" => (3000, 160, 8, 12, 600),
+            "extreme" => (10_000, 160, 24, 20, 300),
+            _ => {
+                return Err(format!(
+                    "unknown preset `{name}` (expected one of {})",
+                    PRESETS.join(", ")
+                ));
+            }
+        };
+        Ok(BenchConfig {
+            crabs,
+            chained,
+            trains,
+            train_len,
+            mix: Mix::Herd,
+            map: MapSize::Large,
+            panicked: 0.1,
+            steps,
+            seed: 1,
+        })
+    }
+}
+
+/// A hot path a bench run times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    CrabIndex,
+    UpdateCrabs,
+    CatchByChain,
+    StartleContagion,
+    DeflectOffChain,
+    NpcTrains,
+}
+
+impl Target {
+    pub const ALL: [Target; 6] = [
+        Target::CrabIndex,
+        Target::UpdateCrabs,
+        Target::CatchByChain,
+        Target::StartleContagion,
+        Target::DeflectOffChain,
+        Target::NpcTrains,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Target::CrabIndex => "rebuild_crab_index",
+            Target::UpdateCrabs => "update_crabs",
+            Target::CatchByChain => "catch_by_chain",
+            Target::StartleContagion => "beat_startle_contagion",
+            Target::DeflectOffChain => "deflect_fleeing_off_chain",
+            Target::NpcTrains => "update_npc_trains",
+        }
+    }
+}
+
+/// One target's per-step cost over a run, in microseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timing {
+    pub target: Target,
+    pub mean: f32,
+    pub median: f32,
+    pub p95: f32,
+    pub max: f32,
+}
+
+/// What a bench run measured, and the world it ended on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchResult {
+    pub timings: Vec<Timing>,
+    pub final_crabs: usize,
+    pub final_chain: usize,
+}
+
+/// Build the configured world and time its hot paths.
+pub fn run(config: &BenchConfig) -> BenchResult {
+    crate::rng::seed(config.seed);
+    let mut state = MainState::new_headless();
+    let mut bench = Bench::new(&mut state, config);
+    let mut samples = vec![Vec::with_capacity(config.steps); Target::ALL.len()];
+    for step in 0..WARMUP_STEPS + config.steps {
+        let micros = bench.step();
+        if step >= WARMUP_STEPS {
+            for (column, us) in samples.iter_mut().zip(micros) {
+                column.push(us);
+            }
+        }
+    }
+    let timings = Target::ALL
+        .into_iter()
+        .zip(samples)
+        .map(|(target, mut us)| {
+            us.sort_by(f32::total_cmp);
+            Timing {
+                target,
+                mean: us.iter().sum::<f32>() / us.len().max(1) as f32,
+                median: percentile(&us, 0.5),
+                p95: percentile(&us, 0.95),
+                max: us.last().copied().unwrap_or(0.0),
+            }
+        })
+        .collect();
+    BenchResult {
+        timings,
+        final_crabs: state.crabs.len(),
+        final_chain: state.chain_count,
+    }
+}
+
+/// A world under benchmark: the state plus the orbit the player's train sweeps.
+struct Bench<'a> {
+    state: &'a mut MainState,
+    config: &'a BenchConfig,
+    center: Vec2,
+    radius: f32,
+    angle: f32,
+}
+
+impl<'a> Bench<'a> {
+    fn new(state: &'a mut MainState, config: &'a BenchConfig) -> Self {
+        state.resize_world(config.map);
+        let (width, height) = (state.world_width, state.world_height);
+        let mut rng = crate::rng::rng();
+        state.show_instructions = false;
+        state.crabs.clear();
+        state.chain_count = 0;
+        state.npc_trains = (0..config.trains)
+            .map(|i| {
+                let mut train = NpcCongaTrain::new_at(width, height, i % 3);
+                let home = Vec2::new(
+                    rng.random_range(0.1..0.9) * width,
+                    rng.random_range(0.1..0.9) * height,
+                );
+                train.leader_pos = home;
+                train.territory_center = home;
+                train.target = home;
+                train.path_history.clear();
+                train.path_history.push_back(home);
+                train.follower_types = (0..config.train_len)
+                    .map(|_| config.mix.roll(&mut rng))
+                    .collect();
+                train
+            })
+            .collect();
+        let center = Vec2::new(width, height) * 0.5;
+        let mut bench = Bench {
+            state,
+            config,
+            center,
+            radius: width.min(height) * 0.3,
+            angle: 0.0,
+        };
+        // Lay the train along the orbit already travelled, so it starts strung out rather than
+        // piled on the player.
+        bench.state.position_history.clear();
+        for i in 0..2000 {
+            let back = bench.orbit_point(-(i as f32) * BOT_DT);
+            bench.state.position_history.push_back(back);
+        }
+        bench.state.player_pos = bench.orbit_point(0.0);
+        for idx in 0..config.chained {
+            let slot = bench.state.position_history[((idx + 1) * CHAIN_LINK_FRAMES).min(1999)];
+            let mut crab = spawn_scattered_crab(slot, Vec2::ZERO, CrabType::Normal, &mut rng);
+            crab.caught = true;
+            crab.chain_index = Some(idx);
+            bench.state.crabs.push(crab);
+        }
+        bench.state.chain_count = config.chained;
+        bench.top_up();
+        bench
+    }
+
+    /// Where the player is `dt` seconds from the current point of the orbit.
+    fn orbit_point(&self, dt: f32) -> Vec2 {
+        // Roughly the player's walking speed around the circle.
+        let angle = self.angle + dt * 240.0 / self.radius;
+        self.center + Vec2::from_angle(angle) * self.radius - Vec2::splat(PLAYER_SIZE / 2.0)
+    }
+
+    /// Bank links caught beyond the starting train and refill the free herd to its size.
+    fn top_up(&mut self) {
+        let chained = self.config.chained;
+        self.state
+            .crabs
+            .retain(|c| c.chain_index.is_none_or(|i| i < chained));
+        self.state.chain_count = self.state.chain_count.min(chained);
+        let free = self.state.crabs.iter().filter(|c| !c.caught).count();
+        let (width, height) = (self.state.world_width, self.state.world_height);
+        let mut rng = crate::rng::rng();
+        for _ in free..self.config.crabs {
+            let pos = Vec2::new(rng.random_range(0.0..width), rng.random_range(0.0..height));
+            let crab_type = self.config.mix.roll(&mut rng);
+            let mut crab = spawn_scattered_crab(pos, Vec2::ZERO, crab_type, &mut rng);
+            crab.boss_health = crab_type.initial_shell();
+            crab.boss_max_health = crab.boss_health.max(0.0001);
+            if rng.random_range(0.0..1.0) < self.config.panicked {
+                crab.startle_timer = 1.0;
+                crab.fleeing = true;
+            }
+            self.state.crabs.push(crab);
+        }
+    }
+
+    /// One step: move the player on, time every target, restore the load. Returns microseconds
+    /// per target in `Target::ALL` order.
+    fn step(&mut self) -> [f32; Target::ALL.len()] {
+        let dt = BOT_DT;
+        self.angle += dt * 240.0 / self.radius;
+        let player_pos = self.orbit_point(0.0);
+        let state = &mut *self.state;
+        state.player_pos = player_pos;
+        state.position_history.push_front(state.player_pos);
+        state.position_history.truncate(2000);
+        state.time_elapsed += dt;
+        state.beat_timer = (state.beat_timer - dt).rem_euclid(state.beat_interval);
+        let area = (state.world_width, state.world_height);
+        let mut micros = [0.0; Target::ALL.len()];
+        for (slot, target) in micros.iter_mut().zip(Target::ALL) {
+            let started = Instant::now();
+            match target {
+                Target::CrabIndex => state.rebuild_crab_index(),
+                Target::UpdateCrabs => state.update_crabs(dt, area),
+                Target::CatchByChain => state.catch_by_chain(),
+                Target::StartleContagion => state.beat_startle_contagion(),
+                Target::DeflectOffChain => state.deflect_fleeing_off_chain(),
+                Target::NpcTrains => state.update_npc_trains(dt),
+            }
+            *slot = started.elapsed().as_secs_f32() * 1e6;
+        }
+        // Upgrade picks and milestones pile up as the herd streams into the train; neither
+        // matters to a bench, and the step never gets to clear them.
+        state.pending_upgrade = false;
+        self.top_up();
+        micros
+    }
+}
+
+/// The result as a table for the terminal.
+pub fn summary(config: &BenchConfig, result: &BenchResult) -> String {
+    let mut out = format!(
+        "{} free crabs, {}-link train, {} rival trains of {} on a {:?} map; {} steps\n",
+        config.crabs, config.chained, config.trains, config.train_len, config.map, config.steps
+    );
+    let _ = writeln!(
+        out,
+        "{:<26} {:>9} {:>9} {:>9} {:>9}",
+        "target (us/step)", "mean", "median", "p95", "max"
+    );
+    for t in &result.timings {
+        let _ = writeln!(
+            out,
+            "{:<26} {:>9.1} {:>9.1} {:>9.1} {:>9.1}",
+            t.target.name(),
+            t.mean,
+            t.median,
+            t.p95,
+            t.max
+        );
+    }
+    let _ = writeln!(
+        out,
+        "ended on {} crabs, {} chained",
+        result.final_crabs, result.final_chain
+    );
+    out
+}
+
+/// The timings as CSV, the format `--baseline` reads back.
+pub fn to_csv(result: &BenchResult) -> String {
+    let mut out = String::from("target,mean_us,median_us,p95_us,max_us\n");
+    for t in &result.timings {
+        let _ = writeln!(
+            out,
+            "{},{:.2},{:.2},{:.2},{:.2}",
+            t.target.name(),
+            t.mean,
+            t.median,
+            t.p95,
+            t.max
+        );
+    }
+    out
+}
+
+/// Compare median timings against a `to_csv` baseline. `Ok` holds the comparison table; `Err`
+/// holds it too, when any target is more than `tolerance` percent slower.
+pub fn compare(result: &BenchResult, baseline: &str, tolerance: f32) -> Result<String, String> {
+    let mut out = String::new();
+    let mut regressed = false;
+    for t in &result.timings {
+        let before = baseline.lines().skip(1).find_map(|line| {
+            let mut cells = line.split(',');
+            (cells.next() == Some(t.target.name()))
+                .then(|| cells.nth(1)?.parse::<f32>().ok())
+                .flatten()
+        });
+        let Some(before) = before else {
+            let _ = writeln!(out, "{:<26} not in the baseline", t.target.name());
+            continue;
+        };
+        let change = (t.median / before.max(0.01) - 1.0) * 100.0;
+        let slower = change > tolerance;
+        regressed |= slower;
+        let _ = writeln!(
+            out,
+            "{:<26} {:>9.1} -> {:>9.1} us  {:+6.1}%{}",
+            t.target.name(),
+            before,
+            t.median,
+            change,
+            if slower { "  REGRESSED" } else { "" }
+        );
+    }
+    if regressed { Err(out) } else { Ok(out) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixes_parse_and_roll_only_their_archetypes() {
+        let mix = Mix::parse("Armored:1, golden:0").unwrap();
+        let mut rng = crate::rng::rng();
+        assert!((0..50).all(|_| mix.roll(&mut rng) == CrabType::Armored));
+        assert!(Mix::parse("boss:1").is_err());
+        assert!(Mix::parse("normal:0").is_err());
+        assert!(Mix::parse("normal").is_err());
+        assert_eq!(Mix::parse("herd"), Ok(Mix::Herd));
+    }
+
+    #[test]
+    fn baselines_flag_only_slowdowns_past_the_tolerance() {
+        let timing = |target, median| Timing {
+            target,
+            mean: median,
+            median,
+            p95: median,
+            max: median,
+        };
+        let before = BenchResult {
+            timings: vec![
+                timing(Target::UpdateCrabs, 100.0),
+                timing(Target::NpcTrains, 10.0),
+            ],
+            final_crabs: 0,
+            final_chain: 0,
+        };
+        let baseline = to_csv(&before);
+        let mut after = before.clone();
+        after.timings[0].median = 120.0;
+        assert!(compare(&after, &baseline, 25.0).is_ok());
+        after.timings[1].median = 20.0;
+        let report = compare(&after, &baseline, 25.0).unwrap_err();
+        assert!(report.contains("update_npc_trains"));
+        assert!(report.contains("REGRESSED"));
+    }
+
+    // The CI guard: go from a medium map to a large one (4x the area) with 4x the herd, train and
+    // rival trains — the same density, so crowding can't excuse the growth — and no target may
+    // cost more than about twice the load increase. A linear pass lands near 4x (less, once fixed
+    // costs count); an accidental all-pairs loop lands near 16x. Medians keep one noisy step from
+    // deciding it. It times wall-clock work, so the parallel debug `cargo test` skips it and CI
+    // runs it alone on a release build (see .github/workflows/test.yml).
+    #[test]
+    #[ignore = "wall-clock timing; run alone with --release"]
+    fn hot_paths_scale_with_the_load() {
+        let mut small = BenchConfig::preset("smoke").unwrap();
+        small.map = MapSize::Medium;
+        small.steps = 40;
+        let mut large = small.clone();
+        large.map = MapSize::Large;
+        large.crabs *= 4;
+        large.chained *= 4;
+        large.trains *= 4;
+        let before = run(&small);
+        let after = run(&large);
+        assert!(after.final_crabs >= large.crabs);
+        for (s, l) in before.timings.iter().zip(&after.timings) {
+            // Sub-microsecond passes are all noise; floor them.
+            let ratio = l.median / s.median.max(5.0);
+            assert!(
+                ratio < 8.0,
+                "{} went from {:.1}us to {:.1}us per step at 4x the load",
+                s.target.name(),
+                s.median,
+                l.median
+            );
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Player actions and the key each one is bound to.
+//!
+//! Gameplay code asks "is Whistle pressed / held?" rather than "is E?", so a player on an AZERTY
+//! (or Dvorak, or one-handed) layout can move any action onto a key that suits them from the
+//! CONTROLS page of the menu. Keys are physical positions (`KeyCode`), the same as the rest of the
+//! input path, and the table persists in the save as `bind <action> <key>` lines — only actions
+//! moved off their default are written, so changing a default later still reaches players who
+//! never touched it.
+//!
+//! Movement additionally always answers to the arrow keys, and Sprint to the right Shift, so the
+//! menus (which navigate on the arrows) can never be rebound out from under the player. Those
+//! fixed alternates, Esc, M (music mute), 1–3 (upgrade picks) and the F2–F4 debug keys are reserved
+//! and can't be bound.
+
+use ggez::input::keyboard::KeyCode;
+
+use crate::MainState;
+use crate::gamepad::pad_action;
+use crate::replay::{key_from_name, key_name};
+
+/// Something the player can do from the keyboard during a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Action {
+    MoveUp,
+    MoveLeft,
+    MoveDown,
+    MoveRight,
+    Sprint,
+    Dash,
+    Whistle,
+    Stomp,
+    Wave,
+    Flashlight,
+    Call,
+    Cycle,
+    GrooveCall,
+    Slam,
+    Bank,
+    /// Hold to wind up, release to throw — the keyboard twin of holding the mouse button.
+    Lasso,
+}
+
+impl Action {
+    /// Every action, in the order the CONTROLS page lists them.
+    pub(crate) const ALL: [Action; 16] = [
+        Action::MoveUp,
+        Action::MoveLeft,
+        Action::MoveDown,
+        Action::MoveRight,
+        Action::Sprint,
+        Action::Dash,
+        Action::Whistle,
+        Action::Stomp,
+        Action::Wave,
+        Action::Flashlight,
+        Action::Call,
+        Action::Cycle,
+        Action::GrooveCall,
+        Action::Slam,
+        Action::Bank,
+        Action::Lasso,
+    ];
+
+    /// The name used in the save file.
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveLeft => "move_left",
+            Action::MoveDown => "move_down",
+            Action::MoveRight => "move_right",
+            Action::Sprint => "sprint",
+            Action::Dash => "dash",
+            Action::Whistle => "whistle",
+            Action::Stomp => "stomp",
+            Action::Wave => "wave",
+            Action::Flashlight => "flashlight",
+            Action::Call => "call",
+            Action::Cycle => "cycle",
+            Action::GrooveCall => "groove_call",
+            Action::Slam => "slam",
+            Action::Bank => "bank",
+            Action::Lasso => "lasso",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    /// The name shown to the player.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move Up",
+            Action::MoveLeft => "Move Left",
+            Action::MoveDown => "Move Down",
+            Action::MoveRight => "Move Right",
+            Action::Sprint => "Sprint",
+            Action::Dash => "Dash",
+            Action::Whistle => "Whistle",
+            Action::Stomp => "Stomp",
+            Action::Wave => "Wave",
+            Action::Flashlight => "Flashlight",
+            Action::Call => "Call",
+            Action::Cycle => "Cycle",
+            Action::GrooveCall => "Groove Call",
+            Action::Slam => "Slam",
+            Action::Bank => "Bank",
+            Action::Lasso => "Lasso",
+        }
+    }
+
+    fn default_key(self) -> KeyCode {
+        match self {
+            Action::MoveUp => KeyCode::KeyW,
+            Action::MoveLeft => KeyCode::KeyA,
+            Action::MoveDown => KeyCode::KeyS,
+            Action::MoveRight => KeyCode::KeyD,
+            Action::Sprint => KeyCode::ShiftLeft,
+            Action::Dash => KeyCode::Space,
+            Action::Whistle => KeyCode::KeyE,
+            Action::Stomp => KeyCode::KeyR,
+            Action::Wave => KeyCode::KeyQ,
+            Action::Flashlight => KeyCode::KeyF,
+            Action::Call => KeyCode::KeyT,
+            Action::Cycle => KeyCode::KeyX,
+            Action::GrooveCall => KeyCode::KeyV,
+            Action::Slam => KeyCode::KeyG,
+            Action::Bank => KeyCode::KeyB,
+            Action::Lasso => KeyCode::KeyC,
+        }
+    }
+
+    /// The key that always triggers this action on top of its binding (see the module docs).
+    fn fixed_alternate(self) -> Option<KeyCode> {
+        match self {
+            Action::MoveUp => Some(KeyCode::ArrowUp),
+            Action::MoveLeft => Some(KeyCode::ArrowLeft),
+            Action::MoveDown => Some(KeyCode::ArrowDown),
+            Action::MoveRight => Some(KeyCode::ArrowRight),
+            Action::Sprint => Some(KeyCode::ShiftRight),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|a| *a == self).unwrap_or(0)
+    }
+}
+
+/// Whether `key` is off-limits for binding (see the module docs).
+pub(crate) fn is_reserved(key: KeyCode) -> bool {
+    let menu_key = matches!(
+        key,
+        KeyCode::Escape
+            | KeyCode::F2
+            | KeyCode::F3
+            | KeyCode::F4
+            | KeyCode::KeyM
+            | KeyCode::Digit1
+            | KeyCode::Digit2
+            | KeyCode::Digit3
+    );
+    menu_key
+        || Action::ALL.iter().any(|a| a.fixed_alternate() == Some(key))
+        || key_name(key).is_none()
+}
+
+/// Short on-screen name for a key: `E`, `Space`, `Shift`, `Up`, `7`.
+pub(crate) fn key_label(key: KeyCode) -> &'static str {
+    match key {
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift",
+        KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl",
+        KeyCode::AltLeft | KeyCode::AltRight => "Alt",
+        KeyCode::SuperLeft | KeyCode::SuperRight => "Super",
+        _ => match key_name(key) {
+            Some(name) => name
+                .strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .or_else(|| name.strip_prefix("Arrow"))
+                .unwrap_or(name),
+            None => "?",
+        },
+    }
+}
+
+/// The key bound to every action.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Bindings {
+    keys: [KeyCode; Action::ALL.len()],
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.map(Action::default_key),
+        }
+    }
+}
+
+impl Bindings {
+    pub(crate) fn key(&self, action: Action) -> KeyCode {
+        self.keys[action.index()]
+    }
+
+    /// On-screen name of the key bound to `action`.
+    pub(crate) fn label(&self, action: Action) -> &'static str {
+        key_label(self.key(action))
+    }
+
+    /// Whether a press of `key` triggers `action`.
+    pub(crate) fn matches(&self, action: Action, key: KeyCode) -> bool {
+        self.key(action) == key || action.fixed_alternate() == Some(key)
+    }
+
+    /// Bind `key` to `action`. An action that already had `key` takes over `action`'s old key, so
+    /// no action is ever left without one and no key ever triggers two. Fails for reserved keys.
+    pub(crate) fn rebind(&mut self, action: Action, key: KeyCode) -> Result<(), String> {
+        if is_reserved(key) {
+            return Err(format!("{} is reserved", key_label(key)));
+        }
+        let old = self.key(action);
+        if let Some(other) = self.keys.iter().position(|k| *k == key) {
+            self.keys[other] = old;
+        }
+        self.keys[action.index()] = key;
+        Ok(())
+    }
+
+    /// Put `action` back on its default key (swapping, like [`Bindings::rebind`]).
+    pub(crate) fn reset(&mut self, action: Action) {
+        let _ = self.rebind(action, action.default_key());
+    }
+
+    /// The actions moved off their default key, for the save.
+    pub(crate) fn overrides(&self) -> impl Iterator<Item = (Action, KeyCode)> + '_ {
+        Action::ALL
+            .into_iter()
+            .map(|a| (a, self.key(a)))
+            .filter(|(a, key)| *key != a.default_key())
+    }
+
+    /// Apply a `bind` save line's value, `<action> <key>`. An action this build doesn't know (a
+    /// newer build wrote it) is skipped; returns `false` only for a key that can't be bound.
+    pub(crate) fn apply_save_value(&mut self, value: &str) -> bool {
+        let Some((id, name)) = value.split_once(' ') else {
+            return false;
+        };
+        let Some(action) = Action::from_id(id.trim()) else {
+            return true;
+        };
+        key_from_name(name.trim()).is_some_and(|key| self.rebind(action, key).is_ok())
+    }
+}
+
+impl MainState {
+    /// Whether `action`'s key is held this frame — by the player or a bot script's synthetic keys —
+    /// or a gamepad button that performs it is.
+    pub(crate) fn action_held(&self, action: Action) -> bool {
+        let held = |code: KeyCode| {
+            self.input.is_held(code)
+                || self
+                    .bot
+                    .as_ref()
+                    .is_some_and(|b| b.keys_held.contains(&code))
+        };
+        held(self.bindings.key(action))
+            || action.fixed_alternate().is_some_and(held)
+            || self
+                .input
+                .pad
+                .held
+                .iter()
+                .any(|b| pad_action(*b) == Some(action))
+    }
+
+    /// Controls page: bind `key` to the highlighted action and save. Esc cancels the capture.
+    pub(crate) fn capture_rebind(&mut self, key: KeyCode) {
+        self.rebind_capture = false;
+        if key == KeyCode::Escape {
+            return;
+        }
+        let action = Action::ALL[self.rebind_selection];
+        match self.bindings.rebind(action, key) {
+            Ok(()) => {
+                self.rebind_notice = None;
+                self.save_career();
+            }
+            Err(reason) => self.rebind_notice = Some(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_swaps_a_taken_key() {
+        let mut bindings = Bindings::default();
+        // AZERTY: Z up, Q left — Q was Wave's, so Wave inherits Move Left's old A.
+        bindings.rebind(Action::MoveUp, KeyCode::KeyZ).unwrap();
+        bindings.rebind(Action::MoveLeft, KeyCode::KeyQ).unwrap();
+        assert_eq!(bindings.key(Action::MoveUp), KeyCode::KeyZ);
+        assert_eq!(bindings.key(Action::MoveLeft), KeyCode::KeyQ);
+        assert_eq!(bindings.key(Action::Wave), KeyCode::KeyA);
+        assert!(bindings.matches(Action::MoveLeft, KeyCode::ArrowLeft));
+        assert!(bindings.rebind(Action::Dash, KeyCode::Escape).is_err());
+        assert!(bindings.rebind(Action::Dash, KeyCode::ArrowUp).is_err());
+
+        let moved: Vec<Action> = bindings.overrides().map(|(a, _)| a).collect();
+        assert_eq!(moved, [Action::MoveUp, Action::MoveLeft, Action::Wave]);
+        bindings.reset(Action::MoveLeft);
+        bindings.reset(Action::MoveUp);
+        assert_eq!(bindings, Bindings::default());
+    }
+
+    #[test]
+    fn key_labels_are_short() {
+        assert_eq!(key_label(KeyCode::KeyE), "E");
+        assert_eq!(key_label(KeyCode::Digit7), "7");
+        assert_eq!(key_label(KeyCode::ShiftLeft), "Shift");
+        assert_eq!(key_label(KeyCode::Space), "Space");
+    }
+}
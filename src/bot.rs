@@ -1,8 +1,50 @@
+//! Scripted bot playtests: timed key presses, autopilot switches, staged mechanics and asserts
+//! on the outcome.
+//!
+//! A scenario is a data file, so a new regression check needs no rebuild. The built-in ones live
+//! in `resources/bots/<name>.bot` and run with `--bot <name>` (and as `cargo test`s, see
+//! `simulation.rs`); `--bot-file <path>` runs one from anywhere, `--list-bots` lists the built-ins.
+//! The format is line-based text, like a replay:
+//!
+//! ```text
+//! rustler-bot 1
+//! # Comments and blank lines are ignored.
+//! time_scale 3
+//! time_limit 58
+//! seed 42
+//! 0.5 tap Space
+//! 2.0 seek_catch on
+//! every 0.9 from 14 until 46
+//!   +0 force_npc_cross
+//!   +0.45 force_hunt_commit
+//! end
+//! 48.0 assert stolen_at_least 1
+//! ```
+//!
+//! `time_limit` (game-time seconds) is required; `time_scale` defaults to 1 and `seed` to a hash of
+//! the scenario's name. Event lines are a game-time timestamp and an action: `hold`/`release`/
+//! `tap <key>` (replay key names, on the default bindings), `mouse <x> <y>`, `log <message>`,
+//! `seek_catch`/`seek_lasso`/`seek_delivery on|off`, the staging actions (`fire_lasso`,
+//! `force_npc_cross`, …, named after the [`BotAction`] variants), `set_goal <condition>` (written
+//! like a level file's `win` line) and `assert <check> [value]`, named after the [`BotAssert`]
+//! variants. An `every` block repeats its `+offset` actions each period from its start time while
+//! that is before its end time.
+//!
+//! Events fire in file order, each once its time has come *and* every event above it has fired —
+//! an event listed after a later-timed one waits for it. List them in time order unless that wait
+//! is the point.
+
 use ggez::glam::Vec2;
 use ggez::input::keyboard::KeyCode;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use crate::bot_report::{CheckRecord, ReportPaths};
 use crate::constants::STEAL_MAX_LINKS;
+use crate::levels::{WinCondition, parse_win};
+use crate::replay::key_from_name;
+use crate::rival_taunts::RivalPersonality;
 
 #[derive(Clone, Debug)]
 pub enum BotAction {
@@ -11,7 +53,7 @@ pub enum BotAction {
     TapKey(KeyCode), // hold for 1 frame then release
     MouseMove(Vec2),
     Assert(BotAssert),
-    Log(&'static str),
+    Log(String),
     // Closed-loop autopilot: while active the bot steers the player toward the nearest catchable
     // crab, auto-whistles it into range, and stomps any shelled crab it walks up to, so a catch
     // test exercises the real catching loop (movement + whistle charm/pull + stomp crack + proximity
@@ -80,13 +122,31 @@ pub enum BotAction {
     /// hunt_intercepts rises. Deterministic — the natural stalk→strike commit is patience/RNG-paced and
     /// can't be counted on inside a headless budget, so we stage the commit and let the real steering run.
     ForceHuntCommit,
+    /// Guards the rival economy (rival_economy.rs): top the nearest rival up to a full haul and park
+    /// it a short walk from its pen, so the real haul trigger, the trip home and the bank all run in
+    /// the next few seconds. Staged because a wandering rival growing a full train by chance can't be
+    /// counted on inside a headless budget.
+    ForceRivalHaul,
+    /// Thread the player's head through the middle of a rival hauling home, so the next update's
+    /// steal-back is a raid and pays the raid bonus. A no-op while no rival is hauling.
+    ForceHaulRaid,
+    /// Hand the rival nearest the player a name with this personality (rival_ai.rs) and stage the
+    /// moment its signature move wants: a Pirate hidden in the cover nearest the player, a Serious
+    /// rival at home with the player inside its territory, anyone else parked in pursuit range. The
+    /// player's train is primed to 6 and the rival's to 4, so a Pompous rival is outsized. The move
+    /// itself is left to the real `update_npc_trains`.
+    ForcePersonality(RivalPersonality),
     /// End the current run immediately (sets game_over). Used by the campaign_loss scenario to prove
     /// that LOSING a campaign level does not complete its world-map node (#182): the win condition,
     /// not merely finishing, is what unlocks the next level.
     ForceGameOver,
+    /// Swap the goal of the world-map node being played for this one, mid-run, crowning a rival
+    /// for a DethroneKing goal. Lets a scenario drive a goal no shipped level carries through the
+    /// real win check, HUD line and return to the map.
+    SetGoal(WinCondition),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum BotAssert {
     GameNotOver,
     ChainAtLeast(usize),
@@ -138,8 +198,22 @@ pub enum BotAssert {
     /// committed hunter leads its aim by the player's velocity to cut off the vulnerable back half where
     /// it's heading, not merely trail where it's been — so that AI path can't silently regress.
     HuntInterceptAtLeast(usize),
+    /// Monotonic count of crabs rival trains have banked at their own pens (see
+    /// MainState::rival_crabs_banked). Asserts a rival hauled a full train home and banked it.
+    RivalBankedAtLeast(usize),
+    /// Monotonic count of steal-backs off a rival hauling home (see MainState::haul_raids). Asserts
+    /// the raid bonus paid out.
+    HaulRaidAtLeast(usize),
+    /// Monotonic count of a personality's signature moves (see MainState::rival_signatures): Pirate
+    /// ambushes, Pompous snubs, Raver downbeat steals, Serious guards, Silly opportunistic steals.
+    SignatureAtLeast(RivalPersonality, usize),
+    /// Rivals on the nemesis roster (see nemesis.rs). Asserts a steal, clash or strip was remembered.
+    NemesesAtLeast(usize),
+    /// Monotonic count of nemeses handed a rival train when a beach started (see
+    /// MainState::nemeses_recalled). Asserts a remembered rival came back.
+    NemesisReturnedAtLeast(usize),
     ScoreAtLeast(usize),
-    /// Whether the world-map node AFTER the currently selected one is unlocked. Asserts campaign
+    /// Whether a world-map node a route leads to from the selected one is unlocked. Asserts campaign
     /// progression gating: after LOSING a campaign level the next node must still be locked
     /// (`false`), so only meeting the win condition unlocks the next level (#182). (The played node
     /// itself is already completed here because it was reached via a skip-confirm, so its own flag
@@ -154,6 +228,11 @@ pub enum BotAssert {
     TutorialActive,
     TutorialDone, // tutorial field is None and show_world_map is true
     InGame,       // not on menu, not game_over, not world_map
+    /// The campaign level's goal was met this run. Stays true through the return to the map, until
+    /// the next run starts.
+    LevelComplete,
+    /// Whether the pause menu (or its resume count-in) is holding the run.
+    Paused(bool),
 }
 
 #[derive(Clone, Debug)]
@@ -163,6 +242,8 @@ pub struct BotEvent {
 }
 
 pub struct BotState {
+    /// The scenario's name, for reports.
+    pub name: String,
     pub script: Vec<BotEvent>,
     pub cursor: usize,
     pub time_limit: f32,
@@ -181,11 +262,18 @@ pub struct BotState {
     // making the forced steal-back intermittently miss. Holding the head still for that one frame lets
     // the detection see it exactly where it was placed. Cleared right after it's consumed.
     pub hold_position: bool,
+    /// Every assert evaluated so far, passed or not (see `bot_report.rs`).
+    pub checks: Vec<CheckRecord>,
+    /// Simulation steps taken.
+    pub frames: u64,
+    pub started: Instant,
+    pub report_paths: ReportPaths,
 }
 
 impl BotState {
-    pub fn new(script: Vec<BotEvent>, time_limit: f32) -> Self {
+    pub fn new(name: &str, script: Vec<BotEvent>, time_limit: f32) -> Self {
         Self {
+            name: name.to_string(),
             script,
             cursor: 0,
             time_limit,
@@ -198,928 +286,409 @@ impl BotState {
             seek_lasso: false,
             seek_delivery: false,
             hold_position: false,
+            checks: Vec::new(),
+            frames: 0,
+            started: Instant::now(),
+            report_paths: ReportPaths::default(),
         }
     }
 }
 
-pub fn script_menu_to_game() -> Vec<BotEvent> {
-    // Verifies the core catching verb still works from a cold start: enter the game, then hand the
-    // player to the seek-catch autopilot, which steers toward the nearest catchable crab and
-    // auto-whistles it into range. A blind fixed sweep can't reliably find one of only a handful of
-    // early crabs scattered randomly across the 2× scrolling world — the failure that had this test
-    // disabled — so we close the loop instead of gambling on RNG. This still exercises the real
-    // movement, whistle charm/pull, stomp crack, and proximity-catch code; only the pathfinding is
-    // automated. menu_to_game runs at 3× time_scale (see the setup in main.rs).
-    vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting menu->game test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 0.8,
-            action: BotAction::Assert(BotAssert::PlayRecommendation),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 8.0,
-            action: BotAction::Assert(BotAssert::GameNotOver),
-        },
-        // Give the autopilot a generous window: the whistle recharges every 4.5 s, so 22 s of
-        // seeking guarantees several catch attempts even when the only reachable crab is a far,
-        // fast one on the far side of the scrolling world. (menu_to_game runs at 3× time_scale.)
-        // Assert on total_caught, not the live chain: by 22 s the autopilot has caught many crabs,
-        // but the chain resets to 0 on a bank/snap/scatter, so a ChainAtLeast check here flakes.
-        BotEvent {
-            at: 22.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-    ]
-}
+/// Fixed simulation timestep for a bot run, in seconds — the same `SIM_DT` interactive play
+/// steps at. `RUSTLER_BOT_DT` overrides it for the windowed run; the headless tests always use
+/// this.
+pub const BOT_DT: f32 = crate::constants::SIM_DT;
 
-pub fn script_campaign_tutorial() -> Vec<BotEvent> {
-    // Drives the campaign on-ramp end to end: title -> world map (C) -> enter the first node, which
-    // is the BeatTiming tutorial (world_map.rs) -> clear it -> confirm it hands control back to the
-    // world map. The first node's pass condition is 3 ON-BEAT catches, so a blind Right/Up walk (the
-    // old script) could never clear it — worse, at 8× time_scale the player teleported past crabs
-    // between frames and caught nothing at all, the failure that had this test disabled. We now hand
-    // the player to the seek-catch autopilot, which in a BeatTiming tutorial stages just outside
-    // catch range and closes the final step on the beat (see handle_player_movement), so the on-beat
-    // catches actually land — and we run at 3× (like menu_to_game) so the proximity catch fires
-    // often enough to register. The beat-timed final approach isn't just polish: without it the
-    // autopilot fires the whistle the instant its 4.5 s cooldown clears, which is EXACTLY 9 beats
-    // (BEAT_INTERVAL 0.5 s), so every reeled-in catch phase-locks to one beat phase — when that
-    // phase is off-beat a whole run banks zero on-beat catches and this test flaked ~1 run in 3.
-    // This exercises the real world-map -> tutorial -> pass -> world-map transition, the
-    // "tutorial->world-map" flow this test exists to guard.
-    vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting campaign tutorial test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::KeyC),
-        },
-        BotEvent {
-            at: 1.5,
-            action: BotAction::Assert(BotAssert::ShowWorldMap),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::Assert(BotAssert::TutorialActive),
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::SeekCatch(true),
-        },
-        // Mid-run sanity: the tutorial is alive and the autopilot is landing catches (total_caught
-        // never drops, unlike the live chain, so this can't race a bank/snap reset).
-        BotEvent {
-            at: 16.0,
-            action: BotAction::Assert(BotAssert::GameNotOver),
-        },
-        BotEvent {
-            at: 16.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-        // By now the 3 on-beat catches are in, the "PASSED!" celebration has played, and the ~2.2s
-        // (real-time) exit hold has returned us to the world map. Wide margin so even an unlucky
-        // low-on-beat-rate run banks its 3rd on-beat catch and completes the exit hold well before
-        // we check — the failure mode we're guarding against is a race, not a missing capability.
-        BotEvent {
-            at: 62.0,
-            action: BotAction::Assert(BotAssert::TutorialDone),
-        },
-        BotEvent {
-            at: 62.0,
-            action: BotAction::Assert(BotAssert::ShowWorldMap),
-        },
-    ]
+/// A bot playtest: its script, the game-time budget it must finish inside, the simulation speed it
+/// runs at and the RNG seed it starts from. Shared by `--bot <name>`, `--bot-file <path>` and the
+/// headless cargo tests.
+#[derive(Clone, Debug)]
+pub struct BotScenario {
+    pub name: String,
+    pub script: Vec<BotEvent>,
+    pub time_limit: f32,
+    pub time_scale: f32,
+    pub seed: u64,
 }
 
-pub fn script_campaign_full() -> Vec<BotEvent> {
-    // Walk the complete campaign on-ramp in one process: all four tutorial nodes, then the first
-    // two real maps. Assertions intentionally sit after every return-to-map transition so a level
-    // that completes but leaves stale tutorial/menu state cannot hide behind the final result.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting full campaign test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::KeyC),
-        },
-        BotEvent {
-            at: 1.5,
-            action: BotAction::Assert(BotAssert::ShowWorldMap),
-        },
-        // BeatTiming.
-        BotEvent {
-            at: 2.0,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::Assert(BotAssert::TutorialActive),
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 62.0,
-            action: BotAction::Assert(BotAssert::TutorialDone),
-        },
-        // LassoGrab.
-        BotEvent {
-            at: 63.0,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 63.5,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 65.0,
-            action: BotAction::Assert(BotAssert::TutorialActive),
-        },
-        BotEvent {
-            at: 65.0,
-            action: BotAction::SeekLasso(true),
-        },
-    ];
-    let mut t = 66.0;
-    while t < 100.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::FireLasso,
-        });
-        t += 1.5;
+impl BotScenario {
+    /// The built-in scenario called `name`, from `resources/bots/<name>.bot`.
+    pub fn find(name: &str) -> Result<Self, String> {
+        let path = builtin_dir().join(format!("{name}.bot"));
+        if !path.is_file() {
+            return Err(format!("unknown bot script `{name}` (see --list-bots)"));
+        }
+        Self::load(&path)
+    }
+
+    /// A scenario file anywhere on disk, named after the file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::parse(&name, &text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Every built-in scenario, by name.
+    pub fn builtin() -> Result<Vec<Self>, String> {
+        let dir = builtin_dir();
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| format!("could not read {}: {e}", dir.display()))?;
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "bot"))
+            .collect();
+        paths.sort();
+        paths.iter().map(|p| Self::load(p)).collect()
+    }
+
+    pub fn parse(name: &str, text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, line)) if line.trim() == HEADER => {}
+            _ => return Err(format!("missing `{HEADER}` header")),
+        }
+        let mut scenario = BotScenario {
+            name: name.to_string(),
+            script: Vec::new(),
+            time_limit: 0.0,
+            time_scale: 1.0,
+            seed: name_seed(name),
+        };
+        let mut time_limit = None;
+        let mut block: Option<EveryBlock> = None;
+        for (index, line) in lines {
+            let fail = |what: &str| format!("line {}: {what}", index + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if let Some(open) = &mut block {
+                match parts.as_slice() {
+                    ["end"] => {
+                        let mut t = open.from;
+                        while t < open.until {
+                            for (offset, action) in &open.actions {
+                                scenario.script.push(BotEvent {
+                                    at: t + offset,
+                                    action: action.clone(),
+                                });
+                            }
+                            t += open.step;
+                        }
+                        block = None;
+                    }
+                    [offset, action @ ..] if offset.starts_with('+') => {
+                        let offset = parse_time(&offset[1..]).ok_or_else(|| fail("bad offset"))?;
+                        let action = parse_action(action, line).map_err(|e| fail(&e))?;
+                        open.actions.push((offset, action));
+                    }
+                    _ => return Err(fail("expected `+<offset> <action>` or `end`")),
+                }
+                continue;
+            }
+            match parts.as_slice() {
+                ["time_scale", value] => {
+                    scenario.time_scale = value
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|s| *s > 0.0)
+                        .ok_or_else(|| fail("bad time scale"))?
+                }
+                ["time_limit", value] => {
+                    time_limit = Some(parse_time(value).ok_or_else(|| fail("bad time limit"))?)
+                }
+                ["seed", value] => scenario.seed = value.parse().map_err(|_| fail("bad seed"))?,
+                ["every", step, "from", from, "until", until] => {
+                    let step = parse_time(step)
+                        .filter(|s| *s > 0.0)
+                        .ok_or_else(|| fail("bad period"))?;
+                    let from = parse_time(from).ok_or_else(|| fail("bad start time"))?;
+                    let until = parse_time(until).ok_or_else(|| fail("bad end time"))?;
+                    block = Some(EveryBlock {
+                        step,
+                        from,
+                        until,
+                        actions: Vec::new(),
+                    });
+                }
+                [at, action @ ..] => {
+                    let at = parse_time(at).ok_or_else(|| fail("bad timestamp"))?;
+                    let action = parse_action(action, line).map_err(|e| fail(&e))?;
+                    scenario.script.push(BotEvent { at, action });
+                }
+                [] => {}
+            }
+        }
+        if block.is_some() {
+            return Err("`every` block is missing its `end`".into());
+        }
+        scenario.time_limit = time_limit.ok_or("missing time_limit")?;
+        Ok(scenario)
     }
-    script.extend([
-        BotEvent {
-            at: 103.0,
-            action: BotAction::Assert(BotAssert::TutorialDone),
-        },
-        // ChainDeliver.
-        BotEvent {
-            at: 104.0,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 104.5,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 106.0,
-            action: BotAction::Assert(BotAssert::TutorialActive),
-        },
-        BotEvent {
-            at: 106.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 106.0,
-            action: BotAction::SeekDelivery(true),
-        },
-        BotEvent {
-            at: 180.0,
-            action: BotAction::Assert(BotAssert::TutorialDone),
-        },
-        // ShellCrack.
-        BotEvent {
-            at: 181.0,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 181.5,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 182.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 200.0,
-            action: BotAction::Assert(BotAssert::TutorialDone),
-        },
-        // First real map: enter the BankCrabs goal and keep the run alive through several waves,
-        // then verify that Escape returns the player to the campaign map.
-        BotEvent {
-            at: 201.0,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 201.5,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 203.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 203.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 203.0,
-            action: BotAction::SeekDelivery(true),
-        },
-        BotEvent {
-            at: 260.0,
-            action: BotAction::Assert(BotAssert::GameNotOver),
-        },
-        BotEvent {
-            at: 261.0,
-            action: BotAction::TapKey(KeyCode::Escape),
-        },
-        BotEvent {
-            at: 262.0,
-            action: BotAction::Assert(BotAssert::MainMenu),
-        },
-        // Re-enter the campaign and select the second real map: verify its BuildTrain goal can be
-        // reached without relying on the first map's completion state.
-        BotEvent {
-            at: 262.5,
-            action: BotAction::TapKey(KeyCode::KeyC),
-        },
-        BotEvent {
-            at: 263.5,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 264.0,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 264.5,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 266.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 266.0,
-            action: BotAction::SeekDelivery(false),
-        },
-        BotEvent {
-            at: 310.0,
-            action: BotAction::Assert(BotAssert::GameNotOver),
-        },
-        BotEvent {
-            at: 315.0,
-            action: BotAction::TapKey(KeyCode::Escape),
-        },
-        BotEvent {
-            at: 316.0,
-            action: BotAction::Assert(BotAssert::MainMenu),
-        },
-    ]);
-    let mut delivery_at = 108.0;
-    while delivery_at < 260.0 {
-        script.insert(
-            script.len().saturating_sub(1),
-            BotEvent {
-                at: delivery_at,
-                action: BotAction::ForceDelivery,
-            },
-        );
-        delivery_at += 1.0;
+
+    pub fn bot_state(&self) -> BotState {
+        BotState::new(&self.name, self.script.clone(), self.time_limit)
     }
-    script.sort_by(|a, b| a.at.total_cmp(&b.at));
-    script
 }
 
-pub fn script_campaign_escape() -> Vec<BotEvent> {
-    // Campaign Escape must return to the main menu from an active regular level instead of quitting
-    // the application. Select the first regular campaign node, confirm the soft skip warning, then
-    // leave the started level with Escape.
-    vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting campaign Escape test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::KeyC),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.1,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.2,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.3,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.6,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 2.5,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 3.0,
-            action: BotAction::TapKey(KeyCode::Escape),
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::Assert(BotAssert::MainMenu),
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::Assert(BotAssert::TitleMenuReady),
-        },
-    ]
+const HEADER: &str = "rustler-bot 1";
+
+/// An `every` block being read: its period, start and end, and the `+offset` actions it repeats.
+struct EveryBlock {
+    step: f32,
+    from: f32,
+    until: f32,
+    actions: Vec<(f32, BotAction)>,
 }
 
-pub fn script_campaign_loss() -> Vec<BotEvent> {
-    // Regression guard for the campaign win-condition gate (#182): LOSING a level must NOT complete
-    // its world-map node — only meeting the WinCondition unlocks the next level. The bug was that
-    // return_to_world_map called complete_selected unconditionally, so dismissing the game-over
-    // screen after a loss still unlocked the next node. Mirror campaign_escape's navigation into the
-    // first regular campaign node (skip-confirm past the tutorials), then force a game over, dismiss
-    // it with Space, and assert we're back on the map with the NEXT node still locked.
-    vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting campaign loss test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::KeyC),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.1,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.2,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.3,
-            action: BotAction::TapKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 1.6,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::TapKey(KeyCode::Enter),
-        },
-        BotEvent {
-            at: 2.5,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        // Lose the run, then dismiss the game-over screen (Space in campaign returns to the map).
-        BotEvent {
-            at: 3.0,
-            action: BotAction::ForceGameOver,
-        },
-        BotEvent {
-            at: 3.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        // Back on the map — and crucially losing did NOT unlock the next level (the win condition,
-        // not merely finishing the run, is what advances the campaign).
-        BotEvent {
-            at: 4.0,
-            action: BotAction::Assert(BotAssert::ShowWorldMap),
-        },
-        BotEvent {
-            at: 4.0,
-            action: BotAction::Assert(BotAssert::SelectedNextUnlocked(false)),
-        },
-    ]
+/// Where the built-in scenarios live: `resources/bots`, next to the game's other resources.
+fn builtin_dir() -> PathBuf {
+    crate::resource_dir().join("bots")
 }
 
-pub fn script_npc_steal() -> Vec<BotEvent> {
-    // Guards the reverse-Snake train-vs-train steal — the core conga-ecology mechanic (see ROADMAP.md
-    // headline and INSPIRATION.md "The core steal mechanic"). The steal path (rival NPC King Crab
-    // train crosses the player's chain -> back section detaches -> snaps onto the rival) is live in
-    // update_npc_trains but had no coverage, so a refactor could silently break it. This test builds a
-    // real player chain with the seek-catch autopilot, then repeatedly forces the nearest rival to
-    // thread through it (ForceNpcCross) and asserts a splice actually fired (crabs_stolen_by_npc rises)
-    // without crashing the run. Forcing keeps it deterministic — the rival's natural pursuit is too
-    // RNG-dependent to land inside a headless time budget. Runs at 3x time_scale like menu_to_game so
-    // the proximity catch fires often enough for the autopilot to grow a chain.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting NPC steal test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-        // Let the autopilot build a chain first. Catching is genuinely slow/RNG (the whistle
-        // recharges every 4.5s and the world is 2x the viewport), so give it the same generous
-        // window menu_to_game proves reliable before asserting a catch has landed.
-        BotEvent {
-            at: 24.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-    ];
-    // Force a crossing every 0.9s across a wide window. Each attempt is a no-op unless a stealable
-    // chain (>= 2 links) exists that frame, so firing many times across ~30s makes it near-certain at
-    // least one lands on a chain moment — the seek-catch chain grows and resets as it banks/snaps.
-    let mut t = 14.0_f32;
-    while t < 46.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::ForceNpcCross,
-        });
-        // Interleave a committed-hunt setup between the crossings (mirrors how npc_vs_npc interleaves
-        // ForceRivalHunt): park the nearest rival in pursuit range and force it into the STRIKE phase so
-        // the real intercept-steering branch runs and hunt_intercepts rises. Lagged 0.45s off the cross
-        // so the two stagings never fight over the same rival on the same frame.
-        script.push(BotEvent {
-            at: t + 0.45,
-            action: BotAction::ForceHuntCommit,
-        });
-        t += 0.9;
-    }
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::GameNotOver),
-    });
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::StolenAtLeast(1)),
-    });
-    // ...and that a committed rival actually intercepted the routing player (#160's "it read my routing"
-    // strike): the staged commits above drive the real lead-the-aim steering, so hunt_intercepts rising
-    // guards that AI path can't silently regress while every other steal assert stays green.
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::HuntInterceptAtLeast(1)),
-    });
-    // The steal must stay a recoverable bite: across every forced crossing above (the seek-catch
-    // chain grows well past the cap), no single splice may take more than STEAL_MAX_LINKS. Guards the
-    // "fun, not punishing" cap against a regression that lets a rival wipe the whole tail in one hit.
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::MaxSingleStealAtMost(STEAL_MAX_LINKS)),
-    });
-    script
+/// Default RNG seed for a scenario: distinct streams keep scenarios independent while each stays
+/// reproducible. A hash of the name gives a stable, unique-per-scenario u64.
+fn name_seed(name: &str) -> u64 {
+    name.bytes().fold(0xC5AB_1234_5678_9ABC_u64, |h, b| {
+        h.rotate_left(7) ^ (b as u64).wrapping_mul(0x100000001B3)
+    })
 }
 
-pub fn script_player_steal() -> Vec<BotEvent> {
-    // Guards the player's "steal to win" reverse-Snake steal-BACK — driving your train's head through
-    // a rival NPC King Crab's line rustles the rival's back section onto your own train (shipped in
-    // #32; see ROADMAP.md headline "before the player can steal back" and INSPIRATION.md "The core
-    // steal mechanic"). That mechanic landed with no bot coverage, so a refactor could silently break
-    // it. Mirrors script_npc_steal: build a real player chain with the seek-catch autopilot, then
-    // repeatedly force the player's head onto the nearest rival's mid-follower (ForcePlayerCross) and
-    // assert a steal-back actually fired (crabs_stolen_by_player rises) without crashing the run.
-    // Forcing keeps it deterministic — threading the head into a wandering rival by chance is too
-    // RNG-dependent for a headless budget. Runs at 3x time_scale like menu_to_game so the autopilot's
-    // proximity catch fires often enough to grow a chain first.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting player steal-back test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-        // Same generous window menu_to_game proves reliable before asserting a catch has landed.
-        BotEvent {
-            at: 24.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-    ];
-    // Force a crossing every 0.9s across a wide window. Each attempt is a no-op unless the player has
-    // a train (>= 1 link) and a rival still has followers, so firing many times across ~30s makes it
-    // near-certain at least one lands while the seek-catch chain is alive.
-    let mut t = 14.0_f32;
-    while t < 46.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::ForcePlayerCross,
-        });
-        t += 0.9;
-    }
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::GameNotOver),
-    });
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::StolenByPlayerAtLeast(1)),
-    });
-    script
+/// A non-negative, finite number of seconds.
+fn parse_time(value: &str) -> Option<f32> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|t| t.is_finite() && *t >= 0.0)
 }
 
-pub fn script_steal_defense() -> Vec<BotEvent> {
-    // Guards the defensive parry — the skill half of the steal fight (ROADMAP headline "make the
-    // defense a real on-beat play"). An on-beat Stomp/Wave cast on a rival threading your tail cancels
-    // its armed splice (try_defend_steal). That counter-play had no coverage, so a refactor could
-    // silently break it. Mirrors script_npc_steal: build a real player chain with the seek-catch
-    // autopilot, then repeatedly stage "arm a steal, then parry it on-beat" (ForceStealDefense) and
-    // assert the parry fired (steals_parried rises) without crashing the run. Forcing keeps it
-    // deterministic — timing an on-beat cast against an RNG-armed steal isn't reliable headless. Runs
-    // at 3x time_scale like npc_steal so the autopilot's proximity catch grows a chain first.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting steal-defense (parry) test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 24.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-    ];
-    // Stage arm+parry every 0.9s across a wide window. Each attempt is a no-op unless a stealable
-    // chain (>= 2 links) exists that frame, so firing many times makes it near-certain at least one
-    // lands while the seek-catch chain is alive.
-    let mut t = 14.0_f32;
-    while t < 46.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::ForceStealDefense,
-        });
-        t += 0.9;
+fn parse_switch(value: &str) -> Result<bool, String> {
+    match value {
+        "on" | "true" => Ok(true),
+        "off" | "false" => Ok(false),
+        _ => Err(format!("expected on/off, got `{value}`")),
     }
-    // Also exercise the Wave's proactive shove (fire_wave) a few times across the window — each stages
-    // the nearest rival beside the player and casts, so the shove path is regression-covered too.
-    for wt in [20.0_f32, 28.0, 36.0, 44.0] {
-        script.push(BotEvent {
-            at: wt,
-            action: BotAction::ForceWaveShove,
-        });
-    }
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::GameNotOver),
-    });
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::ParriedAtLeast(1)),
-    });
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::WaveShovedAtLeast(1)),
-    });
-    script
 }
 
-pub fn script_steal_dodge() -> Vec<BotEvent> {
-    // Guards the movement dodge — the reroute half of the defense (INSPIRATION.md item 2 promises TWO
-    // defenses: "an on-beat defensive reroute OR a tool hit"). Juking the threaded tail link clear of
-    // the rival before the snap breaks the thread, so the splice fizzles with nothing to cut. That
-    // second defense had no coverage, so a refactor could silently break it. Mirrors
-    // script_steal_defense: build a real chain with the seek-catch autopilot, then repeatedly stage
-    // "arm a steal, then yank the tail clear" (ForceStealDodge) and assert the dodge fired
-    // (steals_dodged rises) without crashing the run. Forcing keeps it deterministic — juking a
-    // wandering rival isn't reliable headless. Runs at 3x time_scale like npc_steal so the autopilot's
-    // proximity catch grows a chain first.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting steal-dodge (reroute) test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 24.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-    ];
-    // Stage arm+dodge every 0.9s across a wide window. Each attempt is a no-op unless a stealable
-    // chain (>= 2 links) exists that frame, so firing many times makes it near-certain at least one
-    // lands while the seek-catch chain is alive. A clean reroute (like the tool parry) marks the juked
-    // rival for revenge and opens a counter-steal window — a following ForceRevengeCross ~0.45s later
-    // threads that marked rival to close the counter-steal, which guards the new "a dodge opens a
-    // counter window" reward so it can't silently regress.
-    let mut t = 14.0_f32;
-    while t < 46.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::ForceStealDodge,
-        });
-        script.push(BotEvent {
-            at: t + 0.45,
-            action: BotAction::ForceRevengeCross,
-        });
-        t += 0.9;
-    }
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::GameNotOver),
-    });
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::DodgedAtLeast(1)),
-    });
-    // The on-beat dodge must open a counter-steal window the player can cash — assert the revenge
-    // steal-back fired off a dodge-marked rival (mirrors script_revenge's splice-then-revenge guard).
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::RevengeStealAtLeast(1)),
-    });
-    script
+fn personality(label: &str) -> Result<RivalPersonality, String> {
+    RivalPersonality::from_label(label).ok_or_else(|| format!("unknown personality `{label}`"))
 }
 
-pub fn script_revenge() -> Vec<BotEvent> {
-    // Guards the revenge back-and-forth — the "you steal, they steal back" half of the steal fight
-    // (ROADMAP headline "tune so it's fun, not punishing... a tense back-and-forth"). After a rival
-    // splices your tail it's marked for a few seconds; rustling the crabs back off that same rival
-    // inside the window pays a revenge bonus and increments revenge_steals. That loop had no
-    // coverage, so a refactor could silently break the marker or the bonus. Build a real chain with
-    // the seek-catch autopilot, then repeatedly stage "rival splices you (ForceNpcCross), then chase
-    // it and steal back (ForceRevengeCross)" and assert the revenge steal-back fired. Forcing keeps
-    // it deterministic. Runs at 3x time_scale like npc_steal so the autopilot grows a chain first.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting revenge back-and-forth test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-        BotEvent {
-            at: 24.0,
-            action: BotAction::Assert(BotAssert::CaughtAtLeast(1)),
-        },
-    ];
-    // Interleave splice-then-revenge every 0.7s across a wide window. ForceNpcCross marks the nearest
-    // rival and hands it your tail; ~0.5s later ForceRevengeCross threads your head through that same
-    // marked rival so the steal-back fires inside the 6s revenge window. Each pair is a no-op unless a
-    // stealable chain exists that frame, and the splice only completes when the seek-catch chain survives
-    // the ~one-beat steal fuse — which at a high frame rate can whole-run-miss if the attempts are too
-    // sparse. A moderately denser, wider stream than the old 0.9s (but not so dense it out-drains the
-    // seek-catch chain) makes at least one splice complete and one steal-back land near-certain across
-    // frame rates (#170), without changing what's asserted. The revenge cross lags its splice by 0.5s so
-    // the real ~one-beat fuse has fired and set the marker before the cross tries to cash it.
-    let mut t = 13.0_f32;
-    while t < 47.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::ForceNpcCross,
-        });
-        script.push(BotEvent {
-            at: t + 0.5,
-            action: BotAction::ForceRevengeCross,
-        });
-        t += 0.7;
-    }
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::GameNotOver),
-    });
-    script.push(BotEvent {
-        at: 48.0,
-        action: BotAction::Assert(BotAssert::RevengeStealAtLeast(1)),
-    });
-    script
+/// One action, from the words after its timestamp. `line` is the whole (trimmed) line, for `log`,
+/// whose message keeps its own spacing.
+fn parse_action(words: &[&str], line: &str) -> Result<BotAction, String> {
+    let key = |name: &str| key_from_name(name).ok_or_else(|| format!("unknown key `{name}`"));
+    Ok(match words {
+        ["log", ..] => {
+            let message = line.split_once("log").map_or("", |(_, m)| m.trim());
+            BotAction::Log(message.to_string())
+        }
+        ["hold", name] => BotAction::HoldKey(key(name)?),
+        ["release", name] => BotAction::ReleaseKey(key(name)?),
+        ["tap", name] => BotAction::TapKey(key(name)?),
+        ["mouse", x, y] => {
+            let coord = |v: &str| {
+                v.parse::<f32>()
+                    .map_err(|_| format!("bad coordinate `{v}`"))
+            };
+            BotAction::MouseMove(Vec2::new(coord(x)?, coord(y)?))
+        }
+        ["assert", check @ ..] => BotAction::Assert(parse_assert(check)?),
+        ["seek_catch", on] => BotAction::SeekCatch(parse_switch(on)?),
+        ["seek_lasso", on] => BotAction::SeekLasso(parse_switch(on)?),
+        ["seek_delivery", on] => BotAction::SeekDelivery(parse_switch(on)?),
+        ["fire_lasso"] => BotAction::FireLasso,
+        ["force_delivery"] => BotAction::ForceDelivery,
+        ["force_npc_cross"] => BotAction::ForceNpcCross,
+        ["force_player_cross"] => BotAction::ForcePlayerCross,
+        ["force_revenge_cross"] => BotAction::ForceRevengeCross,
+        ["force_steal_defense"] => BotAction::ForceStealDefense,
+        ["force_wave_shove"] => BotAction::ForceWaveShove,
+        ["force_steal_dodge"] => BotAction::ForceStealDodge,
+        ["force_rival_cross"] => BotAction::ForceRivalCross,
+        ["force_rival_hunt"] => BotAction::ForceRivalHunt,
+        ["force_hunt_commit"] => BotAction::ForceHuntCommit,
+        ["force_rival_haul"] => BotAction::ForceRivalHaul,
+        ["force_haul_raid"] => BotAction::ForceHaulRaid,
+        ["force_personality", who] => BotAction::ForcePersonality(personality(who)?),
+        ["force_game_over"] => BotAction::ForceGameOver,
+        ["set_goal", goal @ ..] => BotAction::SetGoal(parse_win(goal)?),
+        [name, ..] => return Err(format!("unknown action `{name}`")),
+        [] => return Err("missing action".into()),
+    })
 }
 
-pub fn script_npc_vs_npc() -> Vec<BotEvent> {
-    // Guards the whole-beach ecology steal — the ★ HEADLINE mechanic (ROADMAP: "rivals steal from
-    // each other, not just you"). When a bigger rival NPC train threads a smaller rival's follower
-    // line it splices the smaller one's back half onto itself (update_npc_trains), so the beach churns
-    // crabs between trains with no player involved — a genuine ecosystem (agar.io + Rain World). That
-    // path had no coverage, so a refactor could silently break it. Unlike the player-facing steal
-    // tests this needs no player chain: we just enter the game, let the three ambient trains wander so
-    // their follower path history fills, then repeatedly force the biggest train onto a smaller rival's
-    // mid-follower (ForceRivalCross) and assert a transfer fired (rival_vs_rival_steals rises) without
-    // crashing the run. Forcing keeps it deterministic — lining two wandering leaders up by chance
-    // isn't reliable headless. Seek-catch keeps the player busy so free crabs don't pile to the
-    // overwhelmed game-over; runs at 3x time_scale like the other steal tests.
-    let mut script = vec![
-        BotEvent {
-            at: 0.1,
-            action: BotAction::Log("Starting rival-vs-rival ecology steal test"),
-        },
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::SeekCatch(true),
-        },
-    ];
-    // Force a rival crossing every 0.9s across a wide window. Each attempt is a no-op until a smaller
-    // rival has wandered far enough that its mid-follower path slot exists, so firing many times across
-    // ~30s makes it near-certain at least one lands. Start at 10s to give the slow elder time to trail
-    // a path history its followers sit on.
-    let mut t = 10.0_f32;
-    while t < 44.0 {
-        script.push(BotEvent {
-            at: t,
-            action: BotAction::ForceRivalCross,
-        });
-        // Interleave a deterministic hunt setup so the anticipatory "predator closing" telegraph arms
-        // on the same frames (both read live positions through the real update path).
-        script.push(BotEvent {
-            at: t + 0.45,
-            action: BotAction::ForceRivalHunt,
-        });
-        t += 0.9;
-    }
-    script.push(BotEvent {
-        at: 46.0,
-        action: BotAction::Assert(BotAssert::GameNotOver),
-    });
-    script.push(BotEvent {
-        at: 46.0,
-        action: BotAction::Assert(BotAssert::RivalStealAtLeast(1)),
-    });
-    // ...and that the collision spilled catchable crumbs into the world (ROADMAP step 3, agar.io
-    // "eat the crumbs"): a fraction of each rival-vs-rival cut of ≥2 breaks loose as free crabs the
-    // player can swoop in and rustle, instead of all transferring to the winner. Forcing ~38 crossings
-    // onto mid-followers of multi-crab rivals makes at least one qualifying cut near-certain, so this
-    // guards the spill path can't silently regress to a clean pickpocket.
-    script.push(BotEvent {
-        at: 46.0,
-        action: BotAction::Assert(BotAssert::RivalSpillAtLeast(1)),
-    });
-    // ...and that the anticipatory "predator closing" telegraph fired (ROADMAP step 3 "make it legible
-    // and swoopable"): a bigger King committing to a smaller rival paints a gold King→King line so the
-    // player reads the impending clash from afar and pre-positions to swoop the spilled crumbs. Repeatedly
-    // forcing the biggest train onto a smaller rival leaves the two leaders adjacent, so the natural
-    // hunt urge arms the telegraph on the following frames — guarding the tell can't silently regress.
-    script.push(BotEvent {
-        at: 46.0,
-        action: BotAction::Assert(BotAssert::RivalHuntTelegraphAtLeast(1)),
-    });
-    script
+/// The next argument of an assert, or the error for its absence.
+fn arg(value: Option<&str>) -> Result<&str, String> {
+    value.ok_or_else(|| "missing value".to_string())
 }
 
-pub fn script_groove_dash() -> Vec<BotEvent> {
-    // Smoke-tests the SPACE beat-tap: a dash on its own, and — added with #165 — a SPACE+tool CHORD.
-    // Holding a tool key (E) and tapping SPACE fires that tool ON the beat-tap instead of dashing, so
-    // it exercises the new chord input path end to end. The chord is a no-op-safe cast (self-guards on
-    // cooldown), so we assert the monotonic chord counter rose rather than any tool side effect.
-    vec![
-        BotEvent {
-            at: 0.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 1.0,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 2.0,
-            action: BotAction::Assert(BotAssert::InGame),
-        },
-        BotEvent {
-            at: 3.0,
-            action: BotAction::HoldKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 4.5,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 5.0,
-            action: BotAction::ReleaseKey(KeyCode::ArrowRight),
-        },
-        BotEvent {
-            at: 5.0,
-            action: BotAction::Assert(BotAssert::GameNotOver),
-        },
-        // #165 chord: hold the whistle key, then tap SPACE on it — fires the whistle as a beat-tap
-        // flavor rather than a dash. HoldKey lands the key in keys_held the frame BEFORE the SPACE
-        // tap so the chord detection (which reads keys_held) sees it held.
-        BotEvent {
-            at: 5.5,
-            action: BotAction::HoldKey(KeyCode::KeyE),
-        },
-        BotEvent {
-            at: 5.8,
-            action: BotAction::TapKey(KeyCode::Space),
-        },
-        BotEvent {
-            at: 6.0,
-            action: BotAction::ReleaseKey(KeyCode::KeyE),
-        },
-        BotEvent {
-            at: 6.2,
-            action: BotAction::Assert(BotAssert::ChordFiredAtLeast(1)),
-        },
-        BotEvent {
-            at: 6.2,
-            action: BotAction::Assert(BotAssert::GameNotOver),
-        },
-    ]
+fn count(value: Option<&str>) -> Result<usize, String> {
+    let v = arg(value)?;
+    v.parse().map_err(|_| format!("bad count `{v}`"))
+}
+
+fn switch(value: Option<&str>) -> Result<bool, String> {
+    parse_switch(arg(value)?)
+}
+
+fn rival(value: Option<&str>) -> Result<RivalPersonality, String> {
+    personality(arg(value)?)
+}
+
+/// Without a count: the game's own per-steal cap.
+fn steal_cap(value: Option<&str>) -> Result<usize, String> {
+    value.map_or(Ok(STEAL_MAX_LINKS), |v| count(Some(v)))
+}
+
+/// Both directions of the assert-name table from one list, like `replay::key_names!`: each entry
+/// is a script name, its [`BotAssert`] variant and the parsers for the variant's values, in order.
+macro_rules! bot_asserts {
+    ($($name:literal => $variant:ident $(($($value:ident),+))?,)*) => {
+        impl BotAssert {
+            /// The assert's name in bot scripts.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(BotAssert::$variant { .. } => $name,)*
+                }
+            }
+        }
+
+        /// An assert, from the words after `assert`.
+        fn parse_assert(words: &[&str]) -> Result<BotAssert, String> {
+            let (name, values) = words.split_first().ok_or("missing assert")?;
+            let mut values = values.iter().copied();
+            let check = match *name {
+                $($name => BotAssert::$variant $(($($value(values.next())?),+))?,)*
+                _ => return Err(format!("unknown assert `{name}`")),
+            };
+            match values.next() {
+                Some(extra) => Err(format!("unexpected `{extra}` after assert `{name}`")),
+                None => Ok(check),
+            }
+        }
+    };
+}
+
+bot_asserts! {
+    "game_not_over" => GameNotOver,
+    "chain_at_least" => ChainAtLeast(count),
+    "caught_at_least" => CaughtAtLeast(count),
+    "chord_fired_at_least" => ChordFiredAtLeast(count),
+    "stolen_at_least" => StolenAtLeast(count),
+    "max_single_steal_at_most" => MaxSingleStealAtMost(steal_cap),
+    "stolen_by_player_at_least" => StolenByPlayerAtLeast(count),
+    "parried_at_least" => ParriedAtLeast(count),
+    "wave_shoved_at_least" => WaveShovedAtLeast(count),
+    "dodged_at_least" => DodgedAtLeast(count),
+    "revenge_steal_at_least" => RevengeStealAtLeast(count),
+    "rival_steal_at_least" => RivalStealAtLeast(count),
+    "rival_spill_at_least" => RivalSpillAtLeast(count),
+    "rival_hunt_telegraph_at_least" => RivalHuntTelegraphAtLeast(count),
+    "hunt_intercept_at_least" => HuntInterceptAtLeast(count),
+    "rival_banked_at_least" => RivalBankedAtLeast(count),
+    "haul_raid_at_least" => HaulRaidAtLeast(count),
+    "signature_at_least" => SignatureAtLeast(rival, count),
+    "nemeses_at_least" => NemesesAtLeast(count),
+    "nemesis_returned_at_least" => NemesisReturnedAtLeast(count),
+    "score_at_least" => ScoreAtLeast(count),
+    "selected_next_unlocked" => SelectedNextUnlocked(switch),
+    "show_world_map" => ShowWorldMap,
+    "main_menu" => MainMenu,
+    "play_recommendation" => PlayRecommendation,
+    "title_menu_ready" => TitleMenuReady,
+    "tutorial_active" => TutorialActive,
+    "tutorial_done" => TutorialDone,
+    "in_game" => InGame,
+    "level_complete" => LevelComplete,
+    "paused" => Paused(switch),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_builtin_scenario_parses() {
+        let scenarios = BotScenario::builtin().unwrap();
+        assert!(scenarios.len() >= 12);
+        for s in &scenarios {
+            assert!(!s.script.is_empty(), "{} has no events", s.name);
+            assert!(s.script.iter().all(|e| e.at < s.time_limit), "{}", s.name);
+        }
+        let steal = BotScenario::find("npc_steal").unwrap();
+        assert_eq!(steal.seed, name_seed("npc_steal"));
+        assert!(matches!(
+            steal.script.last().unwrap().action,
+            BotAction::Assert(BotAssert::MaxSingleStealAtMost(STEAL_MAX_LINKS))
+        ));
+    }
+
+    #[test]
+    fn every_blocks_expand_in_place() {
+        let text = "rustler-bot 1\ntime_limit 10\nseed 7\n1 log  hello  there\n\
+                    every 1 from 2 until 4\n  +0 tap Space\n  +0.5 assert paused off\nend\n\
+                    5 force_game_over\n";
+        let s = BotScenario::parse("t", text).unwrap();
+        assert_eq!(s.seed, 7);
+        assert_eq!(s.time_scale, 1.0);
+        let times: Vec<f32> = s.script.iter().map(|e| e.at).collect();
+        assert_eq!(times, [1.0, 2.0, 2.5, 3.0, 3.5, 5.0]);
+        assert!(matches!(&s.script[0].action, BotAction::Log(m) if m == "hello  there"));
+        assert!(matches!(
+            s.script[2].action,
+            BotAction::Assert(BotAssert::Paused(false))
+        ));
+    }
+
+    #[test]
+    fn assert_names_round_trip() {
+        for check in [
+            BotAssert::GameNotOver,
+            BotAssert::CaughtAtLeast(3),
+            BotAssert::MaxSingleStealAtMost(2),
+            BotAssert::RivalHuntTelegraphAtLeast(1),
+            BotAssert::RivalBankedAtLeast(8),
+            BotAssert::SelectedNextUnlocked(true),
+            BotAssert::TitleMenuReady,
+            BotAssert::Paused(false),
+            BotAssert::LevelComplete,
+        ] {
+            let value = match &check {
+                BotAssert::CaughtAtLeast(n)
+                | BotAssert::MaxSingleStealAtMost(n)
+                | BotAssert::RivalHuntTelegraphAtLeast(n)
+                | BotAssert::RivalBankedAtLeast(n) => Some(n.to_string()),
+                BotAssert::SelectedNextUnlocked(v) | BotAssert::Paused(v) => Some(v.to_string()),
+                _ => None,
+            };
+            let mut words = vec![check.name()];
+            words.extend(value.as_deref());
+            let parsed = parse_assert(&words).unwrap();
+            assert_eq!(format!("{parsed:?}"), format!("{check:?}"));
+        }
+    }
+
+    #[test]
+    fn malformed_scripts_are_rejected() {
+        let parse = |body: &str| BotScenario::parse("t", &format!("rustler-bot 1\n{body}"));
+        assert!(BotScenario::parse("t", "time_limit 5\n").is_err());
+        assert!(parse("1 tap Space\n").is_err(), "missing time_limit");
+        assert!(parse("time_limit 5\n1 tap Spacebar\n").is_err());
+        assert!(parse("time_limit 5\n1 moonwalk\n").is_err());
+        assert!(parse("time_limit 5\n1 set_goal steal_back 0\n").is_err());
+        assert!(parse("time_limit 5\n1 assert caught_at_least lots\n").is_err());
+        assert!(parse("time_limit 5\n1 assert caught_at_least 1 2\n").is_err());
+        assert!(parse("time_limit 5\n1 assert signature_at_least pompous\n").is_err());
+        assert!(parse("time_limit 5\n-1 tap Space\n").is_err());
+        assert!(parse("time_limit 5\nevery 0 from 1 until 2\nend\n").is_err());
+        assert!(parse("time_limit 5\nevery 1 from 1 until 2\n+0 tap Space\n").is_err());
+        let err = parse("time_limit 5\n\n2 seek_catch maybe\n").unwrap_err();
+        assert!(err.starts_with("line 4:"), "{err}");
+    }
 }
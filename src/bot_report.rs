@@ -0,0 +1,388 @@
+//! Machine-readable results of a bot run, for CI dashboards.
+//!
+//! `--bot-report <path>` writes a JSON report: the outcome, every assert the script reached with
+//! what it expected and what the game actually had, the asserts it never reached, the run's final
+//! counters, and how many frames and how much game and wall time it took. `--bot-junit <path>`
+//! writes the same run as a JUnit test suite — one test case per assert plus one for finishing in
+//! the time budget, counters as suite properties — which is what most CI tools chart. Both are
+//! written once the run is decided, windowed or `--headless`.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use ggez::audio::SoundSource;
+
+use crate::MainState;
+use crate::bot::{BotAction, BotAssert};
+
+/// One assert as it was evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckRecord {
+    /// Game time it fired at.
+    pub at: f32,
+    pub check: BotAssert,
+    pub expected: String,
+    pub observed: String,
+    pub passed: bool,
+}
+
+/// Where to write a run's reports; both optional.
+#[derive(Clone, Debug, Default)]
+pub struct ReportPaths {
+    pub json: Option<PathBuf>,
+    pub junit: Option<PathBuf>,
+}
+
+impl ReportPaths {
+    pub fn is_empty(&self) -> bool {
+        self.json.is_none() && self.junit.is_none()
+    }
+
+    pub fn write(&self, report: &BotReport) -> Result<(), String> {
+        let write = |path: &Path, text: String| {
+            std::fs::write(path, text)
+                .map_err(|e| format!("could not write {}: {e}", path.display()))
+        };
+        if let Some(path) = &self.json {
+            write(path, report.to_json())?;
+        }
+        if let Some(path) = &self.junit {
+            write(path, report.to_junit())?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything a finished (or abandoned) bot run reports.
+#[derive(Clone, Debug)]
+pub struct BotReport {
+    pub scenario: String,
+    pub passed: bool,
+    pub failure: Option<String>,
+    pub checks: Vec<CheckRecord>,
+    /// Asserts still queued when the run ended, with the time they were due.
+    pub not_reached: Vec<(f32, BotAssert)>,
+    /// The run's final counters, by name.
+    pub counters: Vec<(&'static str, usize)>,
+    pub frames: u64,
+    /// Game seconds simulated.
+    pub game_time: f32,
+    pub wall_time: f64,
+}
+
+impl MainState {
+    /// Evaluate one assert against the current state: whether it holds, then what it expected and
+    /// what it found, as shown in the reports.
+    pub(crate) fn evaluate_bot_assert(&self, check: &BotAssert) -> (bool, String, String) {
+        let at_least =
+            |observed: usize, n: usize| (observed >= n, format!(">= {n}"), observed.to_string());
+        let holds =
+            |observed: bool, want: bool| (observed == want, want.to_string(), observed.to_string());
+        match check {
+            BotAssert::GameNotOver => holds(!self.game_over, true),
+            BotAssert::ChainAtLeast(n) => at_least(self.chain_count, *n),
+            BotAssert::CaughtAtLeast(n) => at_least(self.total_caught, *n),
+            BotAssert::ChordFiredAtLeast(n) => at_least(self.chord_tools_fired, *n),
+            BotAssert::StolenAtLeast(n) => at_least(self.crabs_stolen_by_npc, *n),
+            BotAssert::MaxSingleStealAtMost(n) => {
+                let observed = self.max_single_steal_by_npc;
+                (observed <= *n, format!("<= {n}"), observed.to_string())
+            }
+            BotAssert::StolenByPlayerAtLeast(n) => at_least(self.crabs_stolen_by_player, *n),
+            BotAssert::ParriedAtLeast(n) => at_least(self.steals_parried, *n),
+            BotAssert::WaveShovedAtLeast(n) => at_least(self.rivals_wave_shoved, *n),
+            BotAssert::DodgedAtLeast(n) => at_least(self.steals_dodged, *n),
+            BotAssert::RevengeStealAtLeast(n) => at_least(self.revenge_steals, *n),
+            BotAssert::RivalStealAtLeast(n) => at_least(self.rival_vs_rival_steals, *n),
+            BotAssert::RivalSpillAtLeast(n) => at_least(self.rival_spill_crabs, *n),
+            BotAssert::RivalHuntTelegraphAtLeast(n) => at_least(self.rival_hunt_telegraphs, *n),
+            BotAssert::HuntInterceptAtLeast(n) => at_least(self.hunt_intercepts, *n),
+            BotAssert::RivalBankedAtLeast(n) => at_least(self.rival_crabs_banked, *n),
+            BotAssert::HaulRaidAtLeast(n) => at_least(self.haul_raids, *n),
+            BotAssert::SignatureAtLeast(who, n) => at_least(self.signatures(*who), *n),
+            BotAssert::NemesesAtLeast(n) => at_least(self.nemeses.rivals().len(), *n),
+            BotAssert::NemesisReturnedAtLeast(n) => at_least(self.nemeses_recalled, *n),
+            BotAssert::ScoreAtLeast(n) => at_least(self.score, *n),
+            BotAssert::SelectedNextUnlocked(want) => holds(
+                self.world_map.as_ref().is_some_and(|m| m.next_unlocked()),
+                *want,
+            ),
+            BotAssert::ShowWorldMap => holds(self.show_world_map, true),
+            BotAssert::MainMenu => holds(self.show_instructions && !self.show_world_map, true),
+            BotAssert::PlayRecommendation => holds(self.show_play_recommendation, true),
+            BotAssert::TitleMenuReady => holds(
+                self.show_instructions
+                    && !self.show_world_map
+                    && !self.sounds.action_music.iter().any(|music| music.playing())
+                    && self.sounds.intro_music.playing(),
+                true,
+            ),
+            BotAssert::TutorialActive => holds(self.tutorial.is_some(), true),
+            BotAssert::TutorialDone => holds(self.tutorial.is_none() && self.show_world_map, true),
+            BotAssert::InGame => holds(
+                !self.show_instructions && !self.game_over && !self.show_world_map,
+                true,
+            ),
+            BotAssert::Paused(want) => holds(self.pause.is_some(), *want),
+            BotAssert::LevelComplete => holds(self.level_complete, true),
+        }
+    }
+
+    /// The report for the installed bot script as it stands. `None` without one.
+    pub(crate) fn bot_report(&self) -> Option<BotReport> {
+        let bot = self.bot.as_ref()?;
+        let not_reached = bot.script[bot.cursor.min(bot.script.len())..]
+            .iter()
+            .filter_map(|e| match &e.action {
+                BotAction::Assert(check) => Some((e.at, check.clone())),
+                _ => None,
+            })
+            .collect();
+        Some(BotReport {
+            scenario: bot.name.clone(),
+            passed: bot.done && bot.failed.is_none(),
+            failure: bot.failed.clone(),
+            checks: bot.checks.clone(),
+            not_reached,
+            counters: vec![
+                ("total_caught", self.total_caught),
+                ("crabs_stolen_by_npc", self.crabs_stolen_by_npc),
+                ("crabs_stolen_by_player", self.crabs_stolen_by_player),
+                ("steals_parried", self.steals_parried),
+                ("steals_dodged", self.steals_dodged),
+                ("revenge_steals", self.revenge_steals),
+                ("rival_vs_rival_steals", self.rival_vs_rival_steals),
+                ("score", self.score),
+                ("banked_crabs", self.banked_crabs_run),
+            ],
+            frames: bot.frames,
+            game_time: self.time_elapsed,
+            wall_time: bot.started.elapsed().as_secs_f64(),
+        })
+    }
+
+    /// Write the reports the bot was asked for (see `BotState::report_paths`), if any.
+    pub(crate) fn write_bot_reports(&self) {
+        let Some(bot) = &self.bot else {
+            return;
+        };
+        if bot.report_paths.is_empty() {
+            return;
+        }
+        if let Some(report) = self.bot_report()
+            && let Err(e) = bot.report_paths.write(&report)
+        {
+            eprintln!("Bot report: {e}");
+        }
+    }
+}
+
+impl BotReport {
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\n");
+        let _ = writeln!(out, "  \"scenario\": {},", json_string(&self.scenario));
+        let _ = writeln!(out, "  \"passed\": {},", self.passed);
+        let failure = self.failure.as_deref().map_or("null".into(), json_string);
+        let _ = writeln!(out, "  \"failure\": {failure},");
+        let _ = writeln!(out, "  \"frames\": {},", self.frames);
+        let _ = writeln!(out, "  \"game_time\": {:.3},", self.game_time);
+        let _ = writeln!(out, "  \"wall_time\": {:.3},", self.wall_time);
+        out.push_str("  \"counters\": {");
+        for (i, (name, value)) in self.counters.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            let _ = write!(out, "{sep}\n    \"{name}\": {value}");
+        }
+        out.push_str("\n  },\n  \"checks\": [");
+        for (i, c) in self.checks.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            let _ = write!(
+                out,
+                "{sep}\n    {{\"at\": {:.3}, \"check\": \"{}\", \"expected\": {}, \"observed\": {}, \"passed\": {}}}",
+                c.at,
+                c.check.name(),
+                json_string(&c.expected),
+                json_string(&c.observed),
+                c.passed
+            );
+        }
+        if !self.checks.is_empty() {
+            out.push_str("\n  ");
+        }
+        out.push_str("],\n  \"not_reached\": [");
+        for (i, (at, check)) in self.not_reached.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            let _ = write!(
+                out,
+                "{sep}\n    {{\"at\": {at:.3}, \"check\": \"{}\"}}",
+                check.name()
+            );
+        }
+        if !self.not_reached.is_empty() {
+            out.push_str("\n  ");
+        }
+        out.push_str("]\n}\n");
+        out
+    }
+
+    pub fn to_junit(&self) -> String {
+        let suite = format!("bot.{}", self.scenario);
+        let failures = self.checks.iter().filter(|c| !c.passed).count()
+            + usize::from(self.failure.is_some() && self.checks.iter().all(|c| c.passed));
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{}\" time=\"{:.3}\">",
+            xml_escape(&suite),
+            self.checks.len() + self.not_reached.len() + 1,
+            self.not_reached.len(),
+            self.wall_time
+        );
+        out.push_str("    <properties>\n");
+        let mut property = |name: &str, value: String| {
+            let _ = writeln!(
+                out,
+                "      <property name=\"{name}\" value=\"{}\"/>",
+                xml_escape(&value)
+            );
+        };
+        property("frames", self.frames.to_string());
+        property("game_time", format!("{:.3}", self.game_time));
+        for (name, value) in &self.counters {
+            property(name, value.to_string());
+        }
+        out.push_str("    </properties>\n");
+        let case = |at: f32, name: &str| {
+            format!(
+                "    <testcase classname=\"{}\" name=\"t={at:.1} {name}\"",
+                xml_escape(&suite)
+            )
+        };
+        for c in &self.checks {
+            out.push_str(&case(c.at, c.check.name()));
+            if c.passed {
+                out.push_str("/>\n");
+            } else {
+                let _ = writeln!(
+                    out,
+                    ">\n      <failure message=\"expected {}, observed {}\"/>\n    </testcase>",
+                    xml_escape(&c.expected),
+                    xml_escape(&c.observed)
+                );
+            }
+        }
+        for (at, check) in &self.not_reached {
+            out.push_str(&case(*at, check.name()));
+            out.push_str(">\n      <skipped/>\n    </testcase>\n");
+        }
+        // The run as a whole: it must finish its script inside the time budget.
+        let _ = write!(
+            out,
+            "    <testcase classname=\"{}\" name=\"completes\" time=\"{:.3}\"",
+            xml_escape(&suite),
+            self.wall_time
+        );
+        match &self.failure {
+            Some(reason) if self.checks.iter().all(|c| c.passed) => {
+                let _ = writeln!(
+                    out,
+                    ">\n      <failure message=\"{}\"/>\n    </testcase>",
+                    xml_escape(reason)
+                );
+            }
+            _ => out.push_str("/>\n"),
+        }
+        out.push_str("  </testsuite>\n</testsuites>\n");
+        out
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> BotReport {
+        BotReport {
+            scenario: "npc_steal".into(),
+            passed: false,
+            failure: Some("ASSERT FAILED at t=48.0: StolenAtLeast(1)".into()),
+            checks: vec![
+                CheckRecord {
+                    at: 24.0,
+                    check: BotAssert::CaughtAtLeast(1),
+                    expected: ">= 1".into(),
+                    observed: "9".into(),
+                    passed: true,
+                },
+                CheckRecord {
+                    at: 48.0,
+                    check: BotAssert::StolenAtLeast(1),
+                    expected: ">= 1".into(),
+                    observed: "0".into(),
+                    passed: false,
+                },
+            ],
+            not_reached: vec![(48.0, BotAssert::HuntInterceptAtLeast(1))],
+            counters: vec![("total_caught", 9), ("score", 120)],
+            frames: 1440,
+            game_time: 48.0,
+            wall_time: 1.25,
+        }
+    }
+
+    #[test]
+    fn json_report_lists_checks_and_counters() {
+        let json = sample().to_json();
+        assert!(json.contains("\"passed\": false"));
+        assert!(json.contains("\"failure\": \"ASSERT FAILED at t=48.0: StolenAtLeast(1)\""));
+        assert!(json.contains(
+            "{\"at\": 48.000, \"check\": \"stolen_at_least\", \"expected\": \">= 1\", \"observed\": \"0\", \"passed\": false}"
+        ));
+        assert!(json.contains("\"total_caught\": 9"));
+        assert!(json.contains("\"check\": \"hunt_intercept_at_least\""));
+        assert_eq!(json_string("a\"b\n"), "\"a\\\"b\\n\"");
+    }
+
+    #[test]
+    fn junit_report_counts_failures_once() {
+        let xml = sample().to_junit();
+        // The failed assert is the failure; the run's own test case doesn't count it twice.
+        assert!(xml.contains("tests=\"4\" failures=\"1\" skipped=\"1\""));
+        assert!(xml.contains("<failure message=\"expected &gt;= 1, observed 0\"/>"));
+        assert!(xml.contains("<property name=\"score\" value=\"120\"/>"));
+        assert!(xml.contains("name=\"completes\" time=\"1.250\"/>"));
+
+        let mut timed_out = sample();
+        timed_out.checks.pop();
+        timed_out.failure = Some("time limit exceeded".into());
+        assert!(
+            timed_out
+                .to_junit()
+                .contains("<failure message=\"time limit exceeded\"/>")
+        );
+    }
+}
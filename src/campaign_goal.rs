@@ -0,0 +1,182 @@
+//! Campaign goal tracking: which [`WinCondition`] the run is chasing, and the run counters it is
+//! measured against.
+//!
+//! Most counters are written where their verb happens (banking, cracking, stealing). The two streak
+//! clocks, HoldTrain's and OutgrowRivals', tick here once a frame, and the DethroneKing target is
+//! given a body here: one of the beach's rival trains takes its name when the level starts.
+
+use crate::MainState;
+use crate::levels::{Branch, GoalProgress, Level, WinCondition};
+
+impl MainState {
+    /// The goal of the world-map node being played. The goal comes from the node the player
+    /// launched, NOT `current_level`, which auto-advances when patterns run out. `None` outside a
+    /// campaign run and in tutorials.
+    pub(crate) fn campaign_goal(&self) -> Option<WinCondition> {
+        self.campaign_level().map(|l| l.win_condition.clone())
+    }
+
+    /// The side path of the world-map node being played; `None` on the main route and outside a
+    /// campaign run.
+    pub(crate) fn campaign_branch(&self) -> Option<Branch> {
+        self.campaign_level().and_then(|l| l.branch)
+    }
+
+    fn campaign_level(&self) -> Option<&Level> {
+        if !self.in_campaign || self.tutorial.is_some() {
+            return None;
+        }
+        self.world_map
+            .as_ref()
+            .and_then(|m| m.selected_level_index())
+            .and_then(|i| self.levels.get(i))
+    }
+
+    /// Name one of the beach's rival trains after a DethroneKing target, so the King the goal
+    /// names is actually out there. Called when a campaign level starts.
+    pub(crate) fn crown_goal_king(&mut self) {
+        if let Some(WinCondition::DethroneKing(name)) = self.campaign_goal()
+            && let Some(train) = self.npc_trains.first_mut()
+        {
+            train.name = name;
+        }
+    }
+
+    /// Followers of the longest rival train on the beach.
+    fn longest_rival(&self) -> usize {
+        self.npc_trains
+            .iter()
+            .map(|train| train.follower_types.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Advance the streak clocks `goal` keeps: accumulate while the train holds the line, reset
+    /// the instant it slips — a single bad moment resets a long streak, by design.
+    pub(crate) fn tick_goal_clocks(&mut self, goal: &WinCondition, dt: f32) {
+        match *goal {
+            WinCondition::HoldTrain { target, .. } => {
+                if self.chain_count >= target {
+                    self.hold_train_timer += dt;
+                } else {
+                    self.hold_train_timer = 0.0;
+                }
+            }
+            WinCondition::OutgrowRivals { .. } => {
+                if self.chain_count > self.longest_rival() {
+                    self.outgrow_timer += dt;
+                } else {
+                    self.outgrow_timer = 0.0;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The live counters `goal` is measured against.
+    pub(crate) fn goal_progress(&self, goal: &WinCondition) -> GoalProgress {
+        let (king_followers, dethroned) = match goal {
+            WinCondition::DethroneKing(name) => (
+                self.npc_trains
+                    .iter()
+                    .find(|train| train.name == *name)
+                    .map(|train| train.follower_types.len()),
+                self.dethroned_kings.contains(name),
+            ),
+            _ => (None, false),
+        };
+        let escorting = match *goal {
+            WinCondition::Escort(kind) => self
+                .crabs
+                .iter()
+                .any(|crab| crab.caught && crab.crab_type == kind),
+            _ => false,
+        };
+        GoalProgress {
+            banked: self.banked_crabs_run,
+            train: self.chain_count,
+            shells: self.shells_cracked_run,
+            hold_secs: self.hold_train_timer,
+            stolen: self.crabs_stolen_by_player,
+            outgrow_secs: self.outgrow_timer,
+            longest_rival: self.longest_rival(),
+            king_followers,
+            dethroned,
+            perfect_streak: self.perfect_streak as usize,
+            escorted: self.escorted_run,
+            escorting,
+            bosses: self.boss_catch_times.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemies::CrabType;
+
+    /// A headless run on the first large campaign node, with its goal swapped for `goal`.
+    fn campaign_with(goal: WinCondition) -> MainState {
+        let mut state = MainState::new_headless();
+        state.enter_world_map();
+        let node = state
+            .levels
+            .iter()
+            .position(|l| l.map_size.spawns_npc_trains());
+        let map = state.world_map.as_mut().unwrap();
+        map.selected = map
+            .nodes
+            .iter()
+            .position(|n| n.level_index() == node)
+            .unwrap();
+        state.levels[node.unwrap()].win_condition = goal;
+        state.enter_campaign_level();
+        state.show_instructions = false;
+        state
+    }
+
+    #[test]
+    fn the_named_king_is_on_the_beach_until_dethroned() {
+        let goal = WinCondition::DethroneKing("Admiral Kevin".into());
+        let mut state = campaign_with(goal.clone());
+        assert_eq!(state.npc_trains[0].name, "Admiral Kevin");
+        state.npc_trains[0].follower_types = vec![CrabType::Normal; 2];
+        assert_eq!(state.goal_progress(&goal).king_followers, Some(2));
+        assert!(!goal.met(&state.goal_progress(&goal)));
+
+        // A rival draining the King by other means doesn't count; the player's steal-back does.
+        state.npc_trains[0].follower_types.clear();
+        assert!(!goal.met(&state.goal_progress(&goal)));
+        state.dethroned_kings.push("Admiral Kevin".into());
+        assert!(goal.met(&state.goal_progress(&goal)));
+    }
+
+    #[test]
+    fn outgrow_clock_runs_only_while_the_train_leads() {
+        let goal = WinCondition::OutgrowRivals { seconds: 1.0 };
+        let mut state = campaign_with(goal.clone());
+        for train in &mut state.npc_trains {
+            train.follower_types = vec![CrabType::Normal; 4];
+        }
+        state.chain_count = 4;
+        state.tick_goal_clocks(&goal, 0.5);
+        assert_eq!(state.outgrow_timer, 0.0);
+        state.chain_count = 5;
+        for _ in 0..4 {
+            state.tick_goal_clocks(&goal, 0.25);
+        }
+        assert!(goal.met(&state.goal_progress(&goal)));
+        state.chain_count = 3;
+        state.tick_goal_clocks(&goal, 0.25);
+        assert_eq!(state.outgrow_timer, 0.0);
+    }
+
+    #[test]
+    fn outside_the_campaign_there_is_no_goal() {
+        let mut state = MainState::new_headless();
+        state.reset_game();
+        assert_eq!(state.campaign_goal(), None);
+        let state = campaign_with(WinCondition::PerfectStreak(3));
+        assert_eq!(state.campaign_goal(), Some(WinCondition::PerfectStreak(3)));
+    }
+}
@@ -5,15 +5,16 @@
 //! Extracted verbatim from `main.rs` as `impl MainState` methods to keep that file
 //! navigable. Pure structural move — no behaviour change.
 
-use ggez::Context;
 use ggez::audio::SoundSource;
 use ggez::glam::Vec2;
 use rand::Rng;
 
+use crate::event_log::{GameEvent, PenWorth};
+use crate::levels::WinCondition;
 use crate::*;
 
 impl MainState {
-    pub(crate) fn try_deliver_train(&mut self, ctx: &mut Context) {
+    pub(crate) fn try_deliver_train(&mut self) {
         if self.chain_count == 0 {
             return;
         }
@@ -69,9 +70,28 @@ impl MainState {
         let bank =
             (base as f32 * streak_mult * perfect_mult * self.beat_gamble_mult).round() as usize;
         self.score += bank;
+        self.log_event(GameEvent::Deliver(PenWorth {
+            crabs: delivered,
+            triangle: (n * (n + 1) / 2) * 3,
+            bonds: bonds * BOND_PAIR_BONUS,
+            sandwiches: sandwiches * SANDWICH_BONUS,
+            run: run_bonus,
+            centerpiece,
+            streak_mult,
+            perfect,
+            gamble_mult: self.beat_gamble_mult,
+            bank,
+        }));
         // Raw crab-count tally for the campaign win conditions (BankCrabs) — score is multiplied
         // points, so the goal needs its own honest headcount of what actually filed into the pen.
         self.banked_crabs_run += delivered;
+        if let Some(WinCondition::Escort(kind)) = self.campaign_goal() {
+            self.escorted_run += self
+                .crabs
+                .iter()
+                .filter(|c| c.caught && c.crab_type == kind)
+                .count();
+        }
         // Attribute the rhythm-driven extra of this bank: the delivery streak is a pace reward that
         // survives without the beat, so the baseline keeps it — but the PERFECT (on-beat) delivery
         // bonus and the Groove Gamble multiplier are pure rhythm, so strip only those for the flat
@@ -106,7 +126,8 @@ impl MainState {
         self.penned_marchers.spawn_train(self.pen_pos, &marching);
 
         // The delivered crabs leave the field for good — they've been penned.
-        self.crabs.retain(|c| !c.caught);
+        self.retain_crabs(|c| !c.caught);
+        self.rebuild_crab_index();
         self.chain_count = 0;
         self.tail_run_len = 0; // whole train banked — the match run at the tail is gone
         self.next_milestone = 5;
@@ -282,7 +303,7 @@ impl MainState {
         // silent until some unrelated mid-field catch popped the screen out of nowhere — the
         // "fires at an odd moment" bug Carl hit in playtest. A bank is a lull, not mid-action, so
         // it's exactly when a menu is least disruptive.
-        self.check_upgrade_unlock(ctx);
+        self.check_upgrade_unlock();
     }
 
     // check_upgrade_unlock and roll_upgrade_offer now live in src/upgrade.rs (impl MainState there).
@@ -9,6 +9,7 @@ use rand::Rng;
 
 use crate::constants::*;
 use crate::enemies::CrabType;
+use crate::event_log::{BossPhase, GameEvent, SnapCause};
 use crate::state::MainState;
 
 impl MainState {
@@ -187,6 +188,7 @@ impl MainState {
         // shortened train and all future catches line up cleanly.
         self.crabs
             .retain(|c| !(c.caught && c.chain_index.map_or(false, |ci| ci >= keep)));
+        self.rebuild_crab_index();
         self.chain_count = keep;
         self.recompute_tail_run(); // the tail changed (the whole back half, incl. any match run, is gone)
 
@@ -353,6 +355,13 @@ impl MainState {
     /// Big celebratory payoff when a worn-down boss is finally snagged. `is_tide` swaps the callout
     /// and shockwave color so the Tide Boss reads as its own catch, not a reskinned King Crab.
     pub(crate) fn on_boss_caught(&mut self, pos: Vec2, crab_type: CrabType) {
+        if let Some(arrived) = self.boss_arrived_at.take() {
+            self.boss_catch_times.push(self.time_elapsed - arrived);
+        }
+        self.log_event(GameEvent::BossPhase {
+            boss: crab_type,
+            phase: BossPhase::Caught,
+        });
         self.apply_king_crab_power(pos, crab_type);
         let mut rng = crate::rng::rng();
         // The Hermit King "counts as 3 chain links" — the big boy pays a triple-size lump sum
@@ -645,6 +654,7 @@ impl MainState {
 
         let keep = self.chain_count.saturating_sub(SNAP_LINKS).max(1);
         let snapped = self.chain_count - keep;
+        self.note_snap(SnapCause::Blast, snapped);
         let mut snapped_positions: Vec<Vec2> = Vec::new();
         for crab in &mut self.crabs {
             let Some(ci) = crab.chain_index else { continue };
@@ -925,6 +935,7 @@ impl MainState {
         if tail_in_blast && self.chain_count >= 5 && self.chain_snap_cooldown <= 0.0 {
             let keep = self.chain_count.saturating_sub(TIDE_SNAP_LINKS).max(1);
             let snapped = self.chain_count - keep;
+            self.note_snap(SnapCause::Tide, snapped);
             let mut snapped_positions = std::mem::take(&mut self.pulse_snapped_positions_buf);
             snapped_positions.clear();
             for crab in &mut self.crabs {
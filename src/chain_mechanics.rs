@@ -10,6 +10,7 @@ use rand::Rng;
 
 use crate::constants::*;
 use crate::enemies::{BossCharge, CrabType};
+use crate::event_log::{GameEvent, SnapCause};
 use crate::levels::TerrainKind;
 use crate::state::MainState;
 
@@ -83,6 +84,7 @@ impl MainState {
 
         let keep = self.chain_count.saturating_sub(SNAG_LINKS).max(1);
         let snapped = self.chain_count - keep;
+        self.note_snap(SnapCause::Kelp, snapped);
         let mut snapped_positions: Vec<Vec2> = Vec::new();
         for crab in &mut self.crabs {
             let Some(ci) = crab.chain_index else { continue };
@@ -128,6 +130,12 @@ impl MainState {
         self.screen_shake = self.screen_shake.max(5.0);
     }
 
+    /// Book a tail snap of `links` links: the run's snap tally and the event log.
+    pub(crate) fn note_snap(&mut self, cause: SnapCause, links: usize) {
+        self.snaps_run += 1;
+        self.log_event(GameEvent::Snap { cause, links });
+    }
+
     /// Chain-as-risk: the trailing end of the conga train is exposed and can be knocked loose.
     /// Once the train is long enough to matter, a panicking wild crab (fleeing the beam or
     /// mid-stampede) that barrels into the tail snaps the last few links free — they revert to the
@@ -172,9 +180,10 @@ impl MainState {
         // bite), always leaving at least the head crab attached.
         let keep = self
             .chain_count
-            .saturating_sub(crate::panic_snap_links(self.chain_count))
+            .saturating_sub(self.panic_snap_count(self.chain_count))
             .max(1);
         let snapped = self.chain_count - keep;
+        self.note_snap(SnapCause::Panic, snapped);
         let mut snapped_positions: Vec<Vec2> = Vec::new();
         for crab in &mut self.crabs {
             let Some(ci) = crab.chain_index else { continue };
@@ -10,6 +10,8 @@ use ggez::glam::Vec2;
 use rand::Rng;
 
 use crate::constants::*;
+use crate::enemies::CrabType;
+use crate::spatial::DRIFT;
 use crate::state::MainState;
 
 impl MainState {
@@ -50,22 +52,14 @@ impl MainState {
         // stronger than the Thief's grip on your tail. If a clamped Thief drifts inside a free
         // Magnet's radius, the Magnet wins the tug-of-war and rips the parasite clean off the
         // train — the crab you were cursing for gathering a blob becomes an accidental savior.
-        // magnet_positions_buf was filled this same frame by update_crabs (runs before us) and
-        // holds only *free* Magnets, so a caught Magnet in your own train never triggers this.
+        // The crab index was rebuilt this same frame just before update_crabs (runs before us),
+        // and only *free* Magnets count, so a caught Magnet in your own train never triggers this.
         const MAGNET_PRY_RADIUS: f32 = 190.0; // a touch shorter than the herd pull — it has to get close to pry
-        const MAGNET_PRY_RADIUS_SQ: f32 = MAGNET_PRY_RADIUS * MAGNET_PRY_RADIUS;
-        // Borrow the free-Magnet positions out of self so the &mut self.crabs loop below can call
-        // the lookup without an overlapping self borrow; restored at the end of the function.
-        let magnet_positions = std::mem::take(&mut self.magnet_positions_buf);
+        let crab_index = &self.crab_index;
         let nearest_magnet_to = |p: Vec2| -> Option<Vec2> {
-            let mut best: Option<(f32, Vec2)> = None;
-            for &mp in magnet_positions.iter() {
-                let d2 = p.distance_squared(mp);
-                if d2 < MAGNET_PRY_RADIUS_SQ && best.map_or(true, |(bd2, _)| d2 < bd2) {
-                    best = Some((d2, mp));
-                }
-            }
-            best.map(|(_, mp)| mp)
+            crab_index
+                .nearest_of_type(CrabType::Magnet, p, MAGNET_PRY_RADIUS, |m| !m.caught)
+                .map(|(_, m)| m.pos)
         };
 
         // Emergent crossover: a fleeing Golden's panic scares a latched Thief clean off your tail.
@@ -107,23 +101,17 @@ impl MainState {
         // flavor of latched-Thief save from the two above — the Magnet pry is a physical drag (hauled
         // in), the Golden-panic spook is fright (flees off), and this is pure *greed* (chases away
         // toward the shine, thief_lured aura and all). Softer than both, so it only fires when neither
-        // a Magnet nor a fleeing Golden's panic already grabbed the Thief this frame. Reuses the
-        // golden_lure_positions_buf snapshot update_crabs already built this frame (free, un-snared
-        // Goldens) — no new scan. Almost always an empty check (a free Golden near a raided train is
-        // rare), so it costs nothing most frames.
+        // a Magnet nor a fleeing Golden's panic already grabbed the Thief this frame. Reads the
+        // crab index's Golden bucket (free, un-beamed Goldens as of this frame's rebuild) — no new
+        // scan. Almost always an empty check (a free Golden near a raided train is rare), so it
+        // costs nothing most frames.
         const GOLDEN_LURE_LATCH_RADIUS: f32 = 220.0;
-        const GOLDEN_LURE_LATCH_RADIUS_SQ: f32 =
-            GOLDEN_LURE_LATCH_RADIUS * GOLDEN_LURE_LATCH_RADIUS;
-        let golden_lure_positions = std::mem::take(&mut self.golden_lure_positions_buf);
         let nearest_golden_lure_to = |p: Vec2| -> Option<Vec2> {
-            let mut best: Option<(f32, Vec2)> = None;
-            for &gp in golden_lure_positions.iter() {
-                let d2 = p.distance_squared(gp);
-                if d2 < GOLDEN_LURE_LATCH_RADIUS_SQ && best.map_or(true, |(bd2, _)| d2 < bd2) {
-                    best = Some((d2, gp));
-                }
-            }
-            best.map(|(_, gp)| gp)
+            crab_index
+                .nearest_of_type(CrabType::Golden, p, GOLDEN_LURE_LATCH_RADIUS, |g| {
+                    !g.caught && !g.in_flashlight
+                })
+                .map(|(_, g)| g.pos)
         };
 
         // Advance every Thief's latch state; collect whether any peel fired this frame, plus any
@@ -227,11 +215,9 @@ impl MainState {
                 c.latch_timer = PEEL_INTERVAL;
             }
         }
-        // The closures (and their borrows of the taken buffers) are done after the loop above, so
-        // hand both buffers back to self for next frame's reuse instead of dropping them.
-        self.magnet_positions_buf = magnet_positions;
+        // The closures (and their borrow of the taken buffer) are done after the loop above, so
+        // hand the buffer back to self for next frame's reuse instead of dropping it.
         self.golden_panic_positions_buf = golden_panic_positions;
-        self.golden_lure_positions_buf = golden_lure_positions;
 
         // Feedback for any Thief a Magnet just pried off your tail — a bright orange-green pop and
         // a callout so the save reads as a moment, not a silent stat change. Orange (the Magnet's
@@ -375,69 +361,46 @@ impl MainState {
         let tail_guard = if self.chain_count >= 5 { 3 } else { 0 };
         let body_max = self.chain_count.saturating_sub(tail_guard); // chain_index < body_max = solid wall
 
-        // Gather the solid body segments once into a reused buffer (no per-frame heap churn).
-        self.deflect_body_buf.clear();
-        for crab in &self.crabs {
-            if let Some(ci) = crab.chain_index {
-                if ci < body_max {
-                    self.deflect_body_buf.push(crab.pos);
+        // Sweep the solid body through the shared index: every free, panicking crab near a body
+        // link is a hit, and each crab keeps only its nearest link. Chain length is uncapped and
+        // fleeing is common (any wild crab near the player but outside the beam panics), so this
+        // walks the train's neighbourhood rather than every fleeing crab against every segment.
+        // The index is this step's snapshot, so it's widened by DRIFT and the live crabs decide.
+        self.deflect_hits_buf.clear();
+        for seg_crab in &self.crabs {
+            let Some(ci) = seg_crab.chain_index else {
+                continue;
+            };
+            if ci >= body_max {
+                continue;
+            }
+            let seg = seg_crab.pos;
+            for candidate in self.crab_index.within(seg, DEFLECT_DIST + DRIFT) {
+                let Some(crab) = self.crabs.get(candidate.index) else {
+                    continue;
+                };
+                if crab.caught || crab.is_boss() || !(crab.fleeing || crab.startle_timer > 0.0) {
+                    continue;
+                }
+                let d = seg.distance(crab.pos);
+                if d < DEFLECT_DIST {
+                    self.deflect_hits_buf.push((candidate.index, d, seg));
                 }
             }
         }
-        if self.deflect_body_buf.is_empty() {
+        if self.deflect_hits_buf.is_empty() {
             return;
         }
-
-        // Bucket body segments into a spatial grid keyed by cell (mirrors catch_by_chain's
-        // grid) so each fleeing crab only tests the handful of segments near it instead of
-        // scanning the whole chain. Chain length is uncapped and fleeing is common (any wild
-        // crab near the player but outside the beam panics), so the old linear scan was an
-        // O(fleeing * chain_length) cost that grew for the rest of a long session.
-        let cell_size = DEFLECT_DIST.max(1.0);
-        let cell_of = |p: Vec2| -> (i32, i32) {
-            (
-                (p.x / cell_size).floor() as i32,
-                (p.y / cell_size).floor() as i32,
-            )
-        };
-        // Same unbounded-key fix as catch_grid_buf: full map clear keeps capacity but bounds
-        // iteration to "cells touched this frame", not "cells ever touched over the session".
-        self.deflect_grid_buf.clear();
-        for (i, &seg) in self.deflect_body_buf.iter().enumerate() {
-            self.deflect_grid_buf
-                .entry(cell_of(seg))
-                .or_default()
-                .push(i);
-        }
+        // Crab order, nearest link first, so the dedup below keeps each crab's nearest hit.
+        self.deflect_hits_buf
+            .sort_unstable_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        self.deflect_hits_buf.dedup_by_key(|hit| hit.0);
 
         self.deflect_bounce_buf.clear();
         self.deflect_ricochet_buf.clear();
         let mut rng = crate::rng::rng();
-        for (idx, crab) in self.crabs.iter_mut().enumerate() {
-            if crab.caught || crab.is_boss() {
-                continue;
-            }
-            if !(crab.fleeing || crab.startle_timer > 0.0) {
-                continue;
-            }
-            // Nearest body segment within collision range, restricted to the 3x3 neighborhood
-            // of grid cells around the crab instead of every segment in the chain.
-            let (cx, cy) = cell_of(crab.pos);
-            let mut hit: Option<(f32, Vec2)> = None;
-            for dx in -1..=1 {
-                for dy in -1..=1 {
-                    if let Some(candidates) = self.deflect_grid_buf.get(&(cx + dx, cy + dy)) {
-                        for &i in candidates {
-                            let seg = self.deflect_body_buf[i];
-                            let d = seg.distance(crab.pos);
-                            if d < DEFLECT_DIST && hit.map_or(true, |(hd, _)| d < hd) {
-                                hit = Some((d, seg));
-                            }
-                        }
-                    }
-                }
-            }
-            let Some((_, seg)) = hit else { continue };
+        for &(idx, _, seg) in &self.deflect_hits_buf {
+            let crab = &mut self.crabs[idx];
             let mut n = (crab.pos - seg).normalize_or_zero();
             if n == Vec2::ZERO {
                 n = Vec2::new(0.0, -1.0);
@@ -471,7 +434,7 @@ impl MainState {
         // overlap ricochet apart and cross-startle, so driving your train into a fleeing herd sets
         // off a self-feeding pinball cascade instead of every crab bouncing off the wall in
         // isolation. Cheap because it only considers crabs deflected *this* frame (usually a
-        // handful), bucketed into a grid so each tests just its neighbors.
+        // handful), each testing just the neighbours the crab index has around it.
         self.ricochet_deflected_crabs();
     }
 
@@ -485,21 +448,18 @@ impl MainState {
         if self.deflect_ricochet_buf.len() < 2 {
             return;
         }
-        let cell_size = COLLIDE_DIST.max(1.0);
-        let cell_of = |p: Vec2| -> (i32, i32) {
-            (
-                (p.x / cell_size).floor() as i32,
-                (p.y / cell_size).floor() as i32,
-            )
-        };
-        // Same unbounded-key fix as the other two grids above.
-        self.deflect_ricochet_grid_buf.clear();
-        for (bi, &(_, pos)) in self.deflect_ricochet_buf.iter().enumerate() {
-            self.deflect_ricochet_grid_buf
-                .entry(cell_of(pos))
-                .or_default()
-                .push(bi);
+        // Slot of each deflected crab in deflect_ricochet_buf, by crab index, so neighbours the
+        // shared index turns up can be checked for "deflected this frame" in O(1).
+        self.deflect_ricochet_slot_buf.clear();
+        self.deflect_ricochet_slot_buf
+            .resize(self.crabs.len(), usize::MAX);
+        for (slot, &(ci, _)) in self.deflect_ricochet_buf.iter().enumerate() {
+            self.deflect_ricochet_slot_buf[ci] = slot;
         }
+        // A deflected crab's indexed position can be off by this step's drift plus the shove
+        // out of the wall.
+        let reach = COLLIDE_DIST + DRIFT + CRAB_SIZE * 0.85;
+        let mut neighbours: Vec<usize> = Vec::new();
 
         self.deflect_collide_buf.clear();
         // Collect the resolved (crab_index, new_pos, new_vel) then apply, so we never hold two
@@ -509,40 +469,36 @@ impl MainState {
         let n = self.deflect_ricochet_buf.len();
         for a in 0..n {
             let (ci_a, pos_a) = self.deflect_ricochet_buf[a];
-            let (cx, cy) = cell_of(pos_a);
-            for dx in -1..=1 {
-                for dy in -1..=1 {
-                    if let Some(candidates) =
-                        self.deflect_ricochet_grid_buf.get(&(cx + dx, cy + dy))
-                    {
-                        for &b in candidates {
-                            if b <= a {
-                                continue; // resolve each unordered pair once
-                            }
-                            let (ci_b, pos_b) = self.deflect_ricochet_buf[b];
-                            let delta = pos_b - pos_a;
-                            let d = delta.length();
-                            if d >= COLLIDE_DIST || d <= 0.0001 {
-                                continue;
-                            }
-                            let axis = delta / d;
-                            let overlap = COLLIDE_DIST - d;
-                            // Read velocities, swap the component along the collision axis (equal-mass
-                            // elastic bounce), and separate the pair so they don't stick.
-                            let va = self.crabs[ci_a].vel;
-                            let vb = self.crabs[ci_b].vel;
-                            let van = va.dot(axis);
-                            let vbn = vb.dot(axis);
-                            let new_va = va + axis * (vbn - van);
-                            let new_vb = vb + axis * (van - vbn);
-                            let push = axis * (overlap * 0.5 + 1.0);
-                            resolutions.push((ci_a, pos_a - push, new_va));
-                            resolutions.push((ci_b, pos_b + push, new_vb));
-                            // Midpoint cold ring marks the crack; throttled by the len cap below.
-                            self.deflect_collide_buf.push(pos_a + axis * (d * 0.5));
-                        }
-                    }
+            neighbours.clear();
+            neighbours.extend(
+                self.crab_index
+                    .within(pos_a, reach)
+                    .filter_map(|c| self.deflect_ricochet_slot_buf.get(c.index).copied())
+                    .filter(|&b| b != usize::MAX && b > a), // resolve each unordered pair once
+            );
+            neighbours.sort_unstable();
+            for &b in &neighbours {
+                let (ci_b, pos_b) = self.deflect_ricochet_buf[b];
+                let delta = pos_b - pos_a;
+                let d = delta.length();
+                if d >= COLLIDE_DIST || d <= 0.0001 {
+                    continue;
                 }
+                let axis = delta / d;
+                let overlap = COLLIDE_DIST - d;
+                // Read velocities, swap the component along the collision axis (equal-mass
+                // elastic bounce), and separate the pair so they don't stick.
+                let va = self.crabs[ci_a].vel;
+                let vb = self.crabs[ci_b].vel;
+                let van = va.dot(axis);
+                let vbn = vb.dot(axis);
+                let new_va = va + axis * (vbn - van);
+                let new_vb = vb + axis * (van - vbn);
+                let push = axis * (overlap * 0.5 + 1.0);
+                resolutions.push((ci_a, pos_a - push, new_va));
+                resolutions.push((ci_b, pos_b + push, new_vb));
+                // Midpoint cold ring marks the crack; throttled by the len cap below.
+                self.deflect_collide_buf.push(pos_a + axis * (d * 0.5));
             }
         }
         for (ci, new_pos, new_vel) in resolutions {
@@ -2,6 +2,14 @@ use ggez::glam::Vec2;
 
 pub const PLAYER_SIZE: f32 = 48.0;
 
+// Fixed simulation timestep (1/60 s). Interactive play accumulates wall-clock time and spends it
+// in whole SIM_DT steps, so gameplay — lasso arcs, steal timings, catch-bloom windows — is
+// identical at 30 fps and 144 Hz; drawing interpolates between steps. MAX_FRAME_TIME caps how much
+// wall-clock a single frame may feed in, so a hitch (window drag, breakpoint) runs a few catch-up
+// steps instead of a burst that spirals.
+pub const SIM_DT: f32 = 1.0 / 60.0;
+pub const MAX_FRAME_TIME: f32 = 0.25;
+
 // Lasso skill-shot tuning. The loop flies out to the (range-clamped) aim point over
 // LASSO_THROW_TIME — a real throw with travel time, so crabs can dodge the path. On landing it
 // pauses briefly to tighten on a catch (LASSO_SNAG_TIME — the squeeze/pop), then reels crabs back
@@ -91,11 +99,16 @@ pub const INTENSITY_STAGES: &[(f32, &str, f32, f32)] = &[
     (260.0, "OVERDRIVE", 2.3, 1.38),
 ];
 
+// Loose crabs on the field at once that end an arcade or endless run: the herd has overrun you.
+pub const OVERRUN_FREE_CRABS: usize = 160;
+
 pub const STAGE_DURATION_SCALE: f32 = 0.92;
 pub const STAGE_DURATION_FLOOR: f32 = 0.6;
 pub const BOSS_MAX_HEALTH: f32 = 3.0;
 pub const BOSS_DRAIN_RATE: f32 = 1.0;
 pub const BOSS_SCORE_INTERVAL: usize = 40;
+// Score between bosses on a boss node, where they come back to back.
+pub const BOSS_GAUNTLET_INTERVAL: usize = 10;
 pub const BOSS_CHARGE_COOLDOWN: f32 = 4.5;
 pub const BOSS_WINDUP_TIME: f32 = 0.85;
 pub const BOSS_CHARGE_TIME: f32 = 0.65;
@@ -147,6 +160,23 @@ pub const STEAL_MAX_LINKS: usize = 5;
 // you know which rival to chase. This is what turns the steal from two disconnected verbs into a
 // back-and-forth duel (ROADMAP steal headline: "you steal, they steal back", not a random tax).
 pub const REVENGE_WINDOW: f32 = 6.0;
+// Rival economy (see rival_economy.rs): a rival whose train reaches RIVAL_HAUL_AT followers hauls
+// it home at RIVAL_HAUL_SPEED of its usual pace, banks once its leader is inside RIVAL_PEN_RADIUS of
+// its pen, and sets out again with RIVAL_RESTART_FOLLOWERS. Threading a hauling train adds
+// HAUL_RAID_BONUS to the steal-back's per-crab score.
+pub const RIVAL_HAUL_AT: usize = 10;
+pub const RIVAL_HAUL_SPEED: f32 = 0.8;
+pub const RIVAL_PEN_RADIUS: f32 = 90.0;
+pub const RIVAL_RESTART_FOLLOWERS: usize = 2;
+pub const HAUL_RAID_BONUS: usize = 2;
+// Rival personalities (see rival_ai.rs). A Pirate lurking in kelp or rock cover springs its strike
+// on a player inside PIRATE_AMBUSH_RANGE; a Serious rival guards SERIOUS_GUARD_RADIUS around its
+// pen; a Silly rival arms a steal from SILLY_STEAL_REACH times the usual range; a Raver holds an
+// armed steal for up to RAVER_HOLD_BEATS waiting for the downbeat.
+pub const PIRATE_AMBUSH_RANGE: f32 = 320.0;
+pub const SERIOUS_GUARD_RADIUS: f32 = 360.0;
+pub const SILLY_STEAL_REACH: f32 = 1.5;
+pub const RAVER_HOLD_BEATS: f32 = 4.0;
 // Defensive parry reach: how close a rival leader must be to a tool cast to be repelled. Stomp is
 // the up-close bodyguard (short, punchy); the Beat Wave is the wide ranged save. This is the tool
 // identity — Stomp defends what's on top of you, the Wave sweeps a threat off from across the lane.
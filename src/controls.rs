@@ -1,20 +1,14 @@
 use crate::MainState;
+use crate::bindings::Action;
+use crate::mutators::{Mutator, PreRun};
 use crate::{
     SPRINT_SPEED_MULT, SPRINT_STAMINA_DRAIN_PER_SEC, SPRINT_STAMINA_MAX,
     SPRINT_STAMINA_REGEN_PER_SEC,
 };
-use ggez::Context;
 use ggez::glam::Vec2;
 use ggez::input::keyboard::KeyCode;
-use ggez::winit::keyboard::PhysicalKey;
 
-pub fn handle_player_movement(
-    state: &mut MainState,
-    ctx: &mut Context,
-    dt: f32,
-    speed: f32,
-    area: (f32, f32),
-) {
+pub fn handle_player_movement(state: &mut MainState, dt: f32, speed: f32, area: (f32, f32)) {
     let (width, height) = area;
 
     // Bot steal-back hold: a Force*Cross helper teleported the head onto a rival's follower slot this
@@ -29,65 +23,31 @@ pub fn handle_player_movement(
         }
     }
 
-    // Overlay bot synthetic key state if a bot script is running.
-    let bot_up = state
-        .bot
-        .as_ref()
-        .map_or(false, |b| b.keys_held.contains(&KeyCode::ArrowUp));
-    let bot_down = state
-        .bot
-        .as_ref()
-        .map_or(false, |b| b.keys_held.contains(&KeyCode::ArrowDown));
-    let bot_left = state
-        .bot
-        .as_ref()
-        .map_or(false, |b| b.keys_held.contains(&KeyCode::ArrowLeft));
-    let bot_right = state
-        .bot
-        .as_ref()
-        .map_or(false, |b| b.keys_held.contains(&KeyCode::ArrowRight));
-
+    // Held movement keys, including a running bot script's synthetic ones (see action_held).
     let mut dir = Vec2::ZERO;
-    if ctx
-        .keyboard
-        .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ArrowUp))
-        || ctx
-            .keyboard
-            .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::KeyW))
-        || bot_up
-    {
+    if state.action_held(Action::MoveUp) {
         dir.y -= 1.0;
     }
-    if ctx
-        .keyboard
-        .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ArrowDown))
-        || ctx
-            .keyboard
-            .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::KeyS))
-        || bot_down
-    {
+    if state.action_held(Action::MoveDown) {
         dir.y += 1.0;
     }
-    if ctx
-        .keyboard
-        .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ArrowLeft))
-        || ctx
-            .keyboard
-            .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::KeyA))
-        || bot_left
-    {
+    if state.action_held(Action::MoveLeft) {
         dir.x -= 1.0;
     }
-    if ctx
-        .keyboard
-        .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ArrowRight))
-        || ctx
-            .keyboard
-            .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::KeyD))
-        || bot_right
-    {
+    if state.action_held(Action::MoveRight) {
         dir.x += 1.0;
     }
+    // A gamepad's left stick steers when no key does; a half push is a walk at half the top speed.
+    let mut throttle = 1.0;
+    if dir == Vec2::ZERO
+        && let Some(stick) = state.input.pad.move_dir()
+    {
+        dir = stick;
+        throttle = stick.length();
+    }
+    if state.mutators.has(Mutator::MirrorBeach) {
+        dir.x = -dir.x;
+    }
 
     // Seek-catch autopilot (see BotAction::SeekCatch): steer straight at the nearest catchable crab,
     // overriding the scripted keys. Paired with the auto-whistle in main.rs, this drives a reliable
@@ -152,19 +112,14 @@ pub fn handle_player_movement(
         }
     }
 
-    let sprint_held = ctx
-        .keyboard
-        .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ShiftLeft))
-        || ctx
-            .keyboard
-            .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ShiftRight));
+    let sprint_held = state.action_held(Action::Sprint);
     let sprinting =
         sprint_held && dir != Vec2::ZERO && state.boost_timer <= 0.0 && state.sprint_stamina > 0.0;
 
     // Increase player speed and speed boost based on score.
     let base_speed = speed * (1.0 + state.score as f32 * 0.1) * state.speed_mult;
     let speed_boost_multiplier = 30.0 + state.score as f32 * 0.2;
-    let mut move_speed = base_speed;
+    let mut move_speed = base_speed * throttle;
 
     // Apply speed boost if available.
     if state.boost_timer > 0.0 {
@@ -321,11 +276,45 @@ pub fn handle_player_movement(
     }
 }
 
-pub fn handle_key_down_event(
-    state: &mut MainState,
-    ctx: &mut Context,
-    keycode: Option<KeyCode>,
-) -> bool {
+/// A key on the Controls list (the title menu's CONTROLS page, or the pause menu's): Up/Down pick
+/// an action, Enter arms a rebind, Backspace restores the default. Returns `true` when Esc leaves
+/// the page.
+pub(crate) fn handle_controls_page_key(state: &mut MainState, key: KeyCode) -> bool {
+    match key {
+        KeyCode::ArrowUp => {
+            let n = Action::ALL.len();
+            state.rebind_selection = (state.rebind_selection + n - 1) % n;
+        }
+        KeyCode::ArrowDown => {
+            state.rebind_selection = (state.rebind_selection + 1) % Action::ALL.len();
+        }
+        KeyCode::Space | KeyCode::Enter => {
+            state.rebind_capture = true;
+            state.rebind_notice = None;
+        }
+        KeyCode::Backspace => {
+            state.bindings.reset(Action::ALL[state.rebind_selection]);
+            state.rebind_notice = None;
+            state.save_career();
+        }
+        KeyCode::Escape => {
+            state.rebind_notice = None;
+            return true;
+        }
+        _ => {}
+    }
+    false
+}
+
+/// The movement bindings as the screen directions they push the world-map cursor.
+const MAP_DIRECTIONS: [(Action, Vec2); 4] = [
+    (Action::MoveLeft, Vec2::NEG_X),
+    (Action::MoveRight, Vec2::X),
+    (Action::MoveUp, Vec2::NEG_Y),
+    (Action::MoveDown, Vec2::Y),
+];
+
+pub fn handle_key_down_event(state: &mut MainState, keycode: Option<KeyCode>) -> bool {
     if let Some(key) = keycode {
         if state.show_instructions && !state.menu_intro_complete {
             if key == KeyCode::Space {
@@ -333,20 +322,26 @@ pub fn handle_key_down_event(
             }
             return true;
         }
+        if state.rebind_capture {
+            state.capture_rebind(key);
+            return true;
+        }
+        if state.pause.is_some() {
+            state.handle_pause_key(key);
+            return true;
+        }
         if state.show_world_map {
-            match key {
-                KeyCode::ArrowLeft | KeyCode::KeyA => {
-                    if let Some(map) = &mut state.world_map {
-                        map.move_selection(-1);
-                    }
-                    return true;
-                }
-                KeyCode::ArrowRight | KeyCode::KeyD => {
-                    if let Some(map) = &mut state.world_map {
-                        map.move_selection(1);
-                    }
-                    return true;
+            // The arrows follow the routes drawn on the chart, toward the way they're pushed.
+            if let Some(&(_, dir)) = MAP_DIRECTIONS
+                .iter()
+                .find(|&&(action, _)| state.bindings.matches(action, key))
+            {
+                if let Some(map) = &mut state.world_map {
+                    map.move_toward(dir);
                 }
+                return true;
+            }
+            match key {
                 KeyCode::Space | KeyCode::Enter => {
                     if let Some(map) = &mut state.world_map {
                         if map.selected_unlocked() {
@@ -356,6 +351,7 @@ pub fn handle_key_down_event(
                         } else if map.skip_pending() {
                             // Second Confirm on a locked node — commit the skip and launch.
                             map.unlock_through_selected();
+                            state.save_map_progress();
                             state.enter_campaign_level();
                         } else {
                             // First Confirm on a locked node — arm the soft warning.
@@ -391,6 +387,30 @@ pub fn handle_key_down_event(
                 }
                 return true;
             }
+            // Mutator screen: Up/Down pick a row, Space/Enter toggles a mutator or starts the run from
+            // the START row, Esc returns Home.
+            if state.pre_run.is_some() {
+                let rows = Mutator::ALL.len() + 1;
+                match key {
+                    KeyCode::ArrowUp => {
+                        state.mutator_selection = (state.mutator_selection + rows - 1) % rows;
+                    }
+                    KeyCode::ArrowDown => {
+                        state.mutator_selection = (state.mutator_selection + 1) % rows;
+                    }
+                    KeyCode::Space | KeyCode::Enter => {
+                        match Mutator::ALL.get(state.mutator_selection) {
+                            Some(&mutator) => state.mutator_picks.toggle(mutator),
+                            None => state.start_pre_run(),
+                        }
+                    }
+                    KeyCode::Escape => {
+                        state.pre_run = None;
+                    }
+                    _ => {}
+                }
+                return true;
+            }
             if state.show_play_recommendation {
                 match key {
                     KeyCode::ArrowLeft | KeyCode::ArrowRight => {
@@ -398,8 +418,7 @@ pub fn handle_key_down_event(
                     }
                     KeyCode::Space | KeyCode::Enter => {
                         if state.continue_button_focused {
-                            state.reset_game();
-                            state.show_instructions = false;
+                            state.open_pre_run(PreRun::Arcade);
                         }
                         state.show_play_recommendation = false;
                     }
@@ -410,6 +429,21 @@ pub fn handle_key_down_event(
                 }
                 return true;
             }
+            // Controls page: Up/Down pick an action, Space/Enter arms capture of its new key,
+            // Backspace puts it back on its default, Esc returns Home.
+            if state.menu_page == 2 {
+                if handle_controls_page_key(state, key) {
+                    state.menu_page = 0;
+                }
+                return true;
+            }
+            // Rivals page: read-only, so any confirm/back key returns Home.
+            if state.menu_page == 3 {
+                if matches!(key, KeyCode::Escape | KeyCode::Space | KeyCode::Enter) {
+                    state.menu_page = 0;
+                }
+                return true;
+            }
             // Escape: from Loadout go back to Home; from Home do nothing (use Quit button).
             if key == KeyCode::Escape {
                 if state.menu_page == 1 {
@@ -430,7 +464,7 @@ pub fn handle_key_down_event(
             }
             // Home page: Up/Down navigate, Space/Enter activates.
             if state.menu_page == 0 {
-                const NUM_BUTTONS: usize = 5;
+                const NUM_BUTTONS: usize = crate::menu::BUTTON_LABELS.len();
                 match key {
                     KeyCode::ArrowUp => {
                         state.menu_selection =
@@ -451,19 +485,36 @@ pub fn handle_key_down_event(
                                 state.continue_button_focused = true;
                             } // Play
                             1 => {
-                                state.enter_world_map(ctx);
+                                state.enter_world_map();
                             } // Campaign
                             2 => {
+                                state.open_pre_run(PreRun::Endless);
+                            } // Endless
+                            3 => {
+                                state.start_daily();
+                                state.show_instructions = false;
+                                state.show_how_to_play_text = false;
+                            } // Daily Challenge
+                            4 => {
                                 state.menu_page = 1;
                                 state.menu_selection = 0;
                                 state.show_how_to_play_text = false;
                             } // Loadout
-                            3 => {
+                            5 => {
+                                state.menu_page = 2;
+                                state.rebind_selection = 0;
+                                state.show_how_to_play_text = false;
+                            } // Controls
+                            6 => {
+                                state.menu_page = 3;
+                                state.show_how_to_play_text = false;
+                            } // Rivals
+                            7 => {
                                 state.show_how_to_play_text = true;
                                 state.menu_page = 0;
                             } // How to Play
-                            4 => {
-                                ctx.request_quit();
+                            8 => {
+                                state.quit_requested = true;
                             } // Quit
                             _ => {}
                         }
@@ -471,7 +522,7 @@ pub fn handle_key_down_event(
                     }
                     // Legacy shortcut: C still opens campaign.
                     KeyCode::KeyC => {
-                        state.enter_world_map(ctx);
+                        state.enter_world_map();
                         return true;
                     }
                     _ => {}
@@ -521,7 +572,7 @@ pub fn handle_key_down_event(
                 return true;
             }
         } else {
-            if key == KeyCode::Space {
+            if state.bindings.matches(Action::Dash, key) {
                 // #165 groove chord — SPACE is the unified beat-tap. Tapped alone it dashes
                 // (unchanged, and Carl's explicit "don't touch the dash"). Tapped while a tool key
                 // is held it fires that tool ON this beat-tap instead of dashing — so the player
@@ -531,17 +582,9 @@ pub fn handle_key_down_event(
                 // own, and SPACE with no tool held still dashes byte-for-byte as before.
                 // A tool held down counts whether it's a real key or a bot's synthetic key (the
                 // groove_dash playtest drives the chord this way), mirroring handle_player_movement.
-                let held = |code: KeyCode| -> bool {
-                    ctx.keyboard
-                        .is_physical_key_pressed(&PhysicalKey::Code(code))
-                        || state
-                            .bot
-                            .as_ref()
-                            .map_or(false, |b| b.keys_held.contains(&code))
-                };
-                let whistle_chord = held(KeyCode::KeyE);
-                let stomp_chord = held(KeyCode::KeyR);
-                let wave_chord = held(KeyCode::KeyQ);
+                let whistle_chord = state.action_held(Action::Whistle);
+                let stomp_chord = state.action_held(Action::Stomp);
+                let wave_chord = state.action_held(Action::Wave);
                 if whistle_chord || stomp_chord || wave_chord {
                     // Flavor this beat with the held tool(s) — a chord may layer more than one.
                     if whistle_chord {
@@ -591,43 +634,43 @@ pub fn handle_key_down_event(
                     }
                 }
             }
-            if key == KeyCode::KeyQ {
+            if state.bindings.matches(Action::Wave, key) {
                 // Wave: an on-beat space-clearing shockwave — shoves nearby rival leaders back and
                 // stuns them (and still cancels a rival mid-steal as a save). Distinct from the
                 // Stomp's precise close parry. Same cast as the SPACE+Q chord.
                 state.fire_wave();
             }
-            if key == KeyCode::KeyE {
+            if state.bindings.matches(Action::Whistle, key) {
                 // Whistle: yank nearby crabs toward the player. Same cast as the SPACE+E chord.
                 state.fire_whistle();
             }
-            if key == KeyCode::KeyR {
+            if state.bindings.matches(Action::Stomp, key) {
                 // Stomp: cracks armored shells / up-close parry. Same cast as the SPACE+R chord.
                 state.fire_stomp();
             }
-            if key == KeyCode::KeyT {
+            if state.bindings.matches(Action::Call, key) {
                 // Call (T): a rhythm summon. On the beat, nearby Dancer crabs answer and hop toward
-                // you. Lives on T because F is the flashlight toggle (handled in main.rs, which
+                // you. Defaults to T because F is the flashlight toggle (handled in on_key_down, which
                 // returns before this runs) — so this was previously dead-keyed on F and unreachable.
                 state.issue_call();
             }
-            if key == KeyCode::KeyX {
+            if state.bindings.matches(Action::Cycle, key) {
                 // Cycle: the reposition verb. On the beat it rotates the whole train one slot,
                 // arranging the coveted head/tail ends (mouse-free; the old interior-bubble mode was
                 // removed with the mouse dependency).
                 state.cycle_train();
             }
-            if key == KeyCode::KeyV {
+            if state.bindings.matches(Action::GrooveCall, key) {
                 // Groove Call: a field-wide beat lure. Call on the beat and the WHOLE herd streams
                 // toward you over the next couple bars, surging on each downbeat.
                 state.issue_groove_call();
             }
-            if key == KeyCode::KeyG {
+            if state.bindings.matches(Action::Slam, key) {
                 // Downbeat Slam: the Groove-meter ultimate. Only fires with a full meter on the beat;
                 // yanks every nearby free crab into the train at once for a spectacle payoff.
-                state.downbeat_slam(ctx);
+                state.downbeat_slam();
             }
-            if key == KeyCode::KeyB {
+            if state.bindings.matches(Action::Bank, key) {
                 // Bank: cash out the live Groove Gamble streak into a safe multiplier floor. On the
                 // beat it locks the whole stack; off-beat takes a haircut. Turns the gamble into an
                 // active "when do I bank?" call instead of a passive streak.
@@ -637,8 +680,15 @@ pub fn handle_key_down_event(
                 use ggez::audio::SoundSource;
                 let _ = state.sounds.hihat.play();
             }
+            if state.bindings.matches(Action::Lasso, key) && !state.lasso_key_down {
+                // Keyboard lasso: holding the key winds up exactly like holding the mouse button;
+                // the release is polled in update (there is no key-up event) and throws it.
+                if state.begin_lasso_windup() {
+                    state.lasso_key_down = true;
+                }
+            }
             if key == KeyCode::Escape {
-                state.return_to_main_menu();
+                state.open_pause();
             }
             if key == KeyCode::F2 {
                 state.debug_mode = !state.debug_mode;
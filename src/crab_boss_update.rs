@@ -9,6 +9,7 @@
 use ggez::glam::Vec2;
 use rand::Rng;
 
+use crate::spatial::CrabIndex;
 use crate::*;
 
 /// The per-frame inputs and event-collection buffers the boss AI reads and writes. Scalar
@@ -29,13 +30,13 @@ pub(crate) struct BossUpdateCtx<'a> {
     pub reef_hot_now: bool,
     pub chain_count: usize,
     pub time_elapsed: f32,
-    pub armored_positions: &'a [Vec2],
+    pub crab_index: &'a CrabIndex,
     // --- outputs written back to the loop ---
     pub reef_on_field: &'a mut bool,
     pub reef_boss_pos: &'a mut Vec2,
     pub reef_hit_landed: &'a mut bool,
     pub boss_broke: &'a mut Vec<Vec2>,
-    pub boss_enrages: &'a mut Vec<(Vec2, bool)>,
+    pub boss_enrages: &'a mut Vec<(Vec2, CrabType)>,
     pub tide_fires: &'a mut Vec<Vec2>,
     pub tide_swells: &'a mut Vec<Vec2>,
     pub boss_windups: &'a mut Vec<Vec2>,
@@ -125,7 +126,7 @@ pub(crate) fn update_boss_crab(
     {
         crab.enraged = true;
         crab.charge_cooldown = crab.charge_cooldown.min(1.0); // snap toward its next move — no lull into the finale
-        ctx.boss_enrages.push((crab.pos, crab.is_tide_boss()));
+        ctx.boss_enrages.push((crab.pos, crab.crab_type));
     }
 
     // The Tide Boss doesn't charge — it drifts and pulses. Distinct threat, distinct
@@ -406,6 +407,7 @@ pub(crate) fn update_boss_crab(
         }
         BossCharge::Charging(t) => {
             let nt = t - dt;
+            let lunge_from = crab.pos;
             crab.pos += crab.vel * dt; // vel stays locked to the launch heading
             ctx.boss_charge_dust.push((crab.pos, crab.vel));
             // Emergent crossover: did the lunge just plow into a free Armored crab's
@@ -414,13 +416,20 @@ pub(crate) fn update_boss_crab(
             // itself. The Armored crab is knocked back but keeps its shell (it's not
             // caught — it just took the hit). Uses the boss's bulk-widened reach so a
             // near-miss still counts as a block, matching how the tail-snap gives the
-            // charge a wide hitbox.
+            // charge a wide hitbox. The whole stretch of lane covered this frame is swept, so
+            // a fast lunge can't skip over a shell between two frames.
             const BLOCK_REACH: f32 = CRAB_SIZE * 1.1;
             let block_hit = ctx
-                .armored_positions
-                .iter()
-                .find(|&&ap| crab.pos.distance(ap) < BLOCK_REACH + crab.scale * CRAB_SIZE * 0.5);
-            if let Some(&shell_pos) = block_hit {
+                .crab_index
+                .along(
+                    lunge_from,
+                    crab.pos,
+                    BLOCK_REACH + crab.scale * CRAB_SIZE * 0.5,
+                )
+                .filter(|c| c.crab_type == CrabType::Armored && !c.caught)
+                .min_by_key(|c| c.index)
+                .map(|c| c.pos);
+            if let Some(shell_pos) = block_hit {
                 crab.charge_cooldown = if crab.enraged {
                     BOSS_CHARGE_COOLDOWN * BOSS_ENRAGE_COOLDOWN_SCALE
                 } else {
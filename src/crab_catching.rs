@@ -4,13 +4,13 @@
 //! Split out of `catch_deliver.rs` so the banking side (`try_deliver_train`) and the
 //! catching side each live in their own module. Pure structural move — no behaviour change.
 
-use ggez::Context;
 use ggez::glam::Vec2;
 
+use crate::event_log::{GameEvent, beat_offset};
 use crate::*;
 
 impl MainState {
-    pub(crate) fn handle_crab_catching(&mut self, ctx: &mut Context) {
+    pub(crate) fn handle_crab_catching(&mut self) {
         let mult = self.combo_multiplier();
         let mut any_caught = false;
         // Reused scratch buffers instead of fresh Vec::new() every frame — this function runs
@@ -158,6 +158,14 @@ impl MainState {
                 prev_tail_pos = crab.pos;
                 self.chain_count += 1;
                 self.total_caught += 1;
+                if let Some(log) = &mut self.event_log {
+                    let event = GameEvent::Catch {
+                        archetype: crab.crab_type,
+                        beat_offset: beat_offset(self.beat_timer, self.beat_interval),
+                        train: self.chain_count,
+                    };
+                    log.write(self.time_elapsed, self.beat_count, &event);
+                }
                 let on_beat = self.beat_timer < BEAT_WINDOW
                     || self.beat_timer > self.beat_interval - BEAT_WINDOW;
                 // PERFECT: the catch landed inside the tight sub-window at the very center of the
@@ -445,7 +453,7 @@ impl MainState {
                 self.hitstop_timer = self.hitstop_timer.max(if on_beat { 0.08 } else { 0.05 });
                 // Snap the camera in a hair on every catch, harder on the beat, for extra impact.
                 self.zoom_punch = self.zoom_punch.max(if on_beat { 0.055 } else { 0.035 });
-                play_catch_sound(&mut self.sounds, ctx, &mut rng, self.beat_streak);
+                play_catch_sound(&mut self.sounds, &mut rng, self.beat_streak);
                 // A PERFECT (tight-window) catch also fires the bright sparkle on top, so nailing
                 // the precise window is audible, not just a screen flash — the "satisfying drum
                 // hit" the rhythm loop wants. perfect_streak was just bumped above, so the pitch
@@ -458,7 +466,7 @@ impl MainState {
         // Deferred out of the `&mut self.crabs` loop above: check_upgrade_unlock borrows all of
         // self, which conflicts with the live crab iterator. Score only rises inside the loop, so
         // running the threshold check once afterward is equivalent.
-        self.check_upgrade_unlock(ctx);
+        self.check_upgrade_unlock();
         for &origin in &startle_origins {
             self.emit_catch_startle(origin);
         }
@@ -487,6 +495,14 @@ impl MainState {
                         crab.chain_index = Some(self.chain_count);
                         self.chain_count += 1;
                         self.total_caught += 1;
+                        if let Some(log) = &mut self.event_log {
+                            let event = GameEvent::Catch {
+                                archetype: crab.crab_type,
+                                beat_offset: beat_offset(self.beat_timer, self.beat_interval),
+                                train: self.chain_count,
+                            };
+                            log.write(self.time_elapsed, self.beat_count, &event);
+                        }
                         crab.join_pulse = 1.0;
                         if self.catch_shockwaves.len() < 48 {
                             self.catch_shockwaves
@@ -34,7 +34,9 @@ impl MainState {
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> GameResult {
-        let mut rng = crate::rng::rng();
+        // Cosmetic shake only: draw from the entropy RNG so drawing never advances a seeded
+        // gameplay stream (the draw rate isn't the fixed simulation rate).
+        let mut rng = rand::rng();
         // Level-of-detail hint for draw_crab: the more crabs on the beach (wild herd + conga train
         // + NPC trains drawn in this same pass), the cheaper each crab renders, so a big train stays
         // smooth. Full articulation is reserved for calm fields and hero-sized crabs; tiny/distant
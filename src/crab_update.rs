@@ -8,6 +8,7 @@
 use ggez::glam::Vec2;
 use rand::Rng;
 
+use crate::event_log::{BossPhase, GameEvent};
 use crate::*;
 
 impl MainState {
@@ -103,16 +104,9 @@ impl MainState {
         // Where the King Crab aims: the exposed tail of the conga train if there is one, else the
         // player — "whoever currently holds the highest chain_index". Folded into the single
         // snapshot pass below (tracked via a running best-chain_index candidate) instead of its own
-        // full scan, alongside the Magnet/Golden/Armored position snapshots that used to each walk
-        // self.crabs separately: 4 full passes over a struct with 20+ fields collapsed into 1. Same
-        // results, same order-independent picks (positions just need membership, tail just needs the
-        // max chain_index), a quarter of the cache traffic before the real per-crab loop even starts.
-        let mut magnet_positions = std::mem::take(&mut self.magnet_positions_buf);
-        magnet_positions.clear();
-        let mut golden_lure_positions = std::mem::take(&mut self.golden_lure_positions_buf);
-        golden_lure_positions.clear();
-        let mut armored_positions = std::mem::take(&mut self.armored_positions_buf);
-        armored_positions.clear();
+        // full scan. The Magnet/Golden/Armored positions the passes below need come from the
+        // shared crab index, rebuilt just before this call, so they're the same start-of-frame
+        // snapshot without a per-type list of their own.
         let mut best_chain: Option<(usize, Vec2, CrabType)> = None;
         let mut free_splitter = false;
         // Splice targeting: when the chain is long enough (>= 4 links), the King Crab aims at a
@@ -144,18 +138,10 @@ impl MainState {
                         }
                     }
                 }
-                continue; // caught crabs can't be a Magnet/Golden/Armored source below
+                continue;
             }
             if c.is_splitter() {
                 free_splitter = true;
-            } else if c.is_magnet() {
-                magnet_positions.push(c.pos);
-            } else if c.is_golden() {
-                if !c.in_flashlight {
-                    golden_lure_positions.push(c.pos);
-                }
-            } else if c.is_armored() {
-                armored_positions.push(c.pos);
             }
         }
         let chain_tail_pos = best_chain.map(|(_, pos, _)| pos);
@@ -182,11 +168,11 @@ impl MainState {
         self.free_splitter_present = free_splitter;
 
         // Magnet-crab pull: free-roaming Magnet crabs each tug nearby uncaught crabs toward
-        // themselves, so the herd clumps up around them. Snapshotted above so each ordinary crab
-        // can pull toward the nearest one without a nested borrow. Almost always a tiny list
-        // (Magnets are ~8% of the herd and rare), so a flat per-crab nearest-magnet scan is cheap.
+        // themselves, so the herd clumps up around them. Each ordinary crab finds the nearest one
+        // through the crab index's per-type buckets, so the lookup only touches Magnets near it —
+        // a flat scan of every Magnet per crab went quadratic as the herd grew.
         const MAGNET_RADIUS: f32 = 240.0; // how far a Magnet's pull reaches
-        const MAGNET_RADIUS_SQ: f32 = MAGNET_RADIUS * MAGNET_RADIUS; // avoids a sqrt per candidate below
+        const MAGNET_RADIUS_SQ: f32 = MAGNET_RADIUS * MAGNET_RADIUS;
 
         // Emergent crossover — a snared Golden supercharges its captor Magnet. The Magnet-snares-
         // Golden pass already traps a straying shiny in a lodestone's field; here that trapped prize
@@ -197,24 +183,20 @@ impl MainState {
         // — trap the Golden in a wandering Magnet and it also balls up the nearby loose crabs into a
         // tight cluster you can then sweep with one beam pass. Snapshot which Magnets are charged
         // this frame: a Magnet is charged if a snared Golden sits inside its normal pull radius.
-        // Cheap — Magnets and snared Goldens are both rare, so this double loop is almost always over
-        // near-empty lists. Reuses a scratch Vec to avoid per-frame churn.
+        // Cheap — snared Goldens are rare and each asks the crab index for its nearest Magnet.
+        // Reuses a scratch Vec to avoid per-frame churn.
         let mut charged_magnet_positions = std::mem::take(&mut self.charged_magnet_positions_buf);
         charged_magnet_positions.clear();
         for c in &self.crabs {
             if c.is_golden() && !c.caught && c.magnet_snared > 0.0 {
                 // Attribute this snared Golden to its nearest Magnet (the one that trapped it).
-                let mut nearest: Option<(f32, Vec2)> = None;
-                for &mp in magnet_positions.iter() {
-                    let d2 = c.pos.distance_squared(mp);
-                    if d2 < MAGNET_RADIUS_SQ && nearest.map_or(true, |(bd2, _)| d2 < bd2) {
-                        nearest = Some((d2, mp));
-                    }
-                }
-                if let Some((_, mp)) = nearest {
-                    if !charged_magnet_positions.contains(&mp) {
-                        charged_magnet_positions.push(mp);
-                    }
+                let nearest =
+                    self.crab_index
+                        .nearest_of_type(CrabType::Magnet, c.pos, MAGNET_RADIUS, |m| !m.caught);
+                if let Some((_, magnet)) = nearest
+                    && !charged_magnet_positions.contains(&magnet.pos)
+                {
+                    charged_magnet_positions.push(magnet.pos);
                 }
             }
         }
@@ -242,52 +224,44 @@ impl MainState {
         // Magnet cluster detection: on-beat only (rhythmic flash), check each free Magnet
         // for ≥3 nearby free crabs — the "pied-piper vacuum" tell. Fires on the beat so it
         // pulses with the music rather than strobing every frame.
-        // Single pass over crabs tallying into a per-magnet counter, instead of the old
-        // one-full-crab-scan-per-magnet (O(magnets * crabs) with magnets separate closures
-        // re-walking the whole herd each time) — same per-magnet-independent counting
-        // semantics (a crab in range of two overlapping magnet fields still counts for both),
-        // just one cache-friendly walk of self.crabs instead of magnet_positions.len() of them.
+        // Each free Magnet counts the free crabs the crab index has inside its field — a crab in
+        // range of two overlapping magnet fields still counts for both.
         let cluster_on_beat =
             self.beat_timer < BEAT_WINDOW || self.beat_timer > self.beat_interval - BEAT_WINDOW;
-        if cluster_on_beat && !magnet_positions.is_empty() {
-            let mut cluster_counts = std::mem::take(&mut self.magnet_cluster_counts_buf);
-            cluster_counts.clear();
-            cluster_counts.resize(magnet_positions.len(), 0);
-            for c in &self.crabs {
-                if c.caught || c.is_magnet() || c.is_boss() {
-                    continue;
-                }
-                for (mi, &mp) in magnet_positions.iter().enumerate() {
-                    if c.pos.distance_squared(mp) < MAGNET_RADIUS_SQ {
-                        cluster_counts[mi] += 1;
-                    }
+        if cluster_on_beat {
+            for magnet in self.crabs.iter().filter(|c| c.is_magnet() && !c.caught) {
+                if self.magnet_cluster_hits_buf.len() >= 8 {
+                    break;
                 }
-            }
-            for (mi, &mp) in magnet_positions.iter().enumerate() {
-                if cluster_counts[mi] >= 3 && self.magnet_cluster_hits_buf.len() < 8 {
-                    self.magnet_cluster_hits_buf.push(mp);
+                let clustered = self
+                    .crab_index
+                    .within(magnet.pos, MAGNET_RADIUS)
+                    .filter(|c| {
+                        !c.caught && c.crab_type != CrabType::Magnet && !c.crab_type.is_boss()
+                    })
+                    .count();
+                if clustered >= 3 {
+                    self.magnet_cluster_hits_buf.push(magnet.pos);
                 }
             }
-            self.magnet_cluster_counts_buf = cluster_counts;
         }
 
         // A charged Magnet's field reaches ~40% farther and tugs harder while it holds a prize.
         const CHARGED_MAGNET_RADIUS: f32 = MAGNET_RADIUS * 1.4;
         const CHARGED_MAGNET_RADIUS_SQ: f32 = CHARGED_MAGNET_RADIUS * CHARGED_MAGNET_RADIUS;
 
-        // Emergent crossover — the Golden lures the Magnet. `golden_lure_positions` (every free,
-        // un-beamed Golden's position) was snapshotted in the single pass above, so a roaming
+        // Emergent crossover — the Golden lures the Magnet. Every free, un-beamed Golden is in the
+        // crab index's Golden bucket as of the start of the frame, so a roaming
         // Magnet can be drawn *off its cluster* toward the shiny prize: the mirror of the
         // Magnet-snares-Golden interaction (there the Magnet traps the Golden; here the Golden's
         // shine pulls the Magnet away from tending its herd).
         const MAGNET_LURE_RADIUS: f32 = 300.0; // a Magnet notices a Golden from a bit farther than its own pull reaches
-        const MAGNET_LURE_RADIUS_SQ: f32 = MAGNET_LURE_RADIUS * MAGNET_LURE_RADIUS;
 
         // Emergent crossover — a free Armored crab body-blocks a charging King Crab. The Armored
         // crab is already established as a wall (its calm-anchor shell shelters the herd from panic
-        // ripples); here that same stubborn shell also stops a boss lunge cold. `armored_positions`
-        // (every free Armored crab's position) was snapshotted in the single pass above so the King
-        // Crab's charge arm below can test whether its lane plows through one — if it does, the
+        // ripples); here that same stubborn shell also stops a boss lunge cold. The crab index's
+        // Armored bucket (every free Armored crab as of the start of the frame) lets the King
+        // Crab's charge arm below test whether its lane plows through one — if it does, the
         // shell clangs, the boss skids to a halt on cooldown, and the tail it was aiming for is
         // spared. Parking or leaving an Armored crab between the boss and your train becomes a real
         // defensive routing play — the mirror of a Magnet between your train and an incoming Thief.
@@ -398,7 +372,7 @@ impl MainState {
                     reef_hot_now,
                     chain_count: self.chain_count,
                     time_elapsed: self.time_elapsed,
-                    armored_positions: &armored_positions,
+                    crab_index: &self.crab_index,
                     reef_on_field: &mut reef_on_field,
                     reef_boss_pos: &mut reef_boss_pos,
                     reef_hit_landed: &mut reef_hit_landed,
@@ -811,25 +785,18 @@ impl MainState {
                 // it — the flashlight still wins (a crab in the beam is heading to the player), and a
                 // fleeing crab still bolts, just curving a little toward the cluster. This is what
                 // turns "catch the Magnet" into a two-for-one: the crabs it gathered come with it.
-                // Squared-distance compare so the per-magnet scan (up to ~8% of the herd, times
-                // every ordinary crab) does zero sqrt work until we've already found the winner
-                // — a sqrt per pair here was the hottest unnecessary cost in this per-crab,
-                // per-frame loop. Computed once per crab and shared below by both the ordinary
-                // herd-nudge/Golden-snare check and the Thief-intercept check (a Thief is never
-                // a Magnet or a boss, so this covers it too) instead of scanning
-                // magnet_positions a second time for Thieves.
+                // Computed once per crab from the crab index's Magnet bucket and shared below by
+                // both the ordinary herd-nudge/Golden-snare check and the Thief-intercept check (a
+                // Thief is never a Magnet or a boss, so this covers it too) instead of looking the
+                // Magnets up a second time for Thieves.
                 let nearest_magnet: Option<(f32, Vec2)> =
                     if !crab_in_light && !crab.is_magnet() && !crab.is_boss() {
-                        let mut nearest: Option<(f32, Vec2)> = None;
-                        for &mp in magnet_positions.iter() {
-                            let d2 = crab.pos.distance_squared(mp);
-                            if d2 < MAGNET_RADIUS_SQ && d2 > 1.0 {
-                                if nearest.map_or(true, |(bd2, _)| d2 < bd2) {
-                                    nearest = Some((d2, mp));
-                                }
-                            }
-                        }
-                        nearest
+                        let pos = crab.pos;
+                        self.crab_index
+                            .nearest_of_type(CrabType::Magnet, pos, MAGNET_RADIUS, |m| {
+                                !m.caught && m.pos.distance_squared(pos) > 1.0
+                            })
+                            .map(|(d2, m)| (d2, m.pos))
                     } else {
                         None
                     };
@@ -974,19 +941,22 @@ impl MainState {
                 // abandoning the cluster you were building (bad). Skipped once the Golden is deep in
                 // the Magnet's own field, since the snare pass then takes over and pins it. Uses the
                 // Goldens snapshotted before the loop, so no nested borrow.
-                if crab.is_magnet() && !crab_in_light && !golden_lure_positions.is_empty() {
-                    let mut nearest: Option<(f32, Vec2)> = None;
-                    for &gp in golden_lure_positions.iter() {
-                        let d2 = crab.pos.distance_squared(gp);
-                        // Only chase Goldens that are within lure range but not already inside the
-                        // Magnet's own pull radius — once it's that close the snare handles it.
-                        if d2 < MAGNET_LURE_RADIUS_SQ && d2 > MAGNET_RADIUS_SQ * 0.36 {
-                            if nearest.map_or(true, |(bd2, _)| d2 < bd2) {
-                                nearest = Some((d2, gp));
-                            }
-                        }
-                    }
-                    if let Some((d2, gp)) = nearest {
+                if crab.is_magnet() && !crab_in_light {
+                    let pos = crab.pos;
+                    // Only chase Goldens that are within lure range but not already inside the
+                    // Magnet's own pull radius — once it's that close the snare handles it.
+                    let nearest = self.crab_index.nearest_of_type(
+                        CrabType::Golden,
+                        pos,
+                        MAGNET_LURE_RADIUS,
+                        |g| {
+                            !g.caught
+                                && !g.in_flashlight
+                                && g.pos.distance_squared(pos) > MAGNET_RADIUS_SQ * 0.36
+                        },
+                    );
+                    if let Some((d2, golden)) = nearest {
+                        let gp = golden.pos;
                         let d = d2.sqrt();
                         // Stronger tug the closer the prize, fading out at the edge of lure range.
                         let prox = 1.0 - d / MAGNET_LURE_RADIUS; // 0 at edge, ~1 up close
@@ -1065,25 +1035,23 @@ impl MainState {
                     // raid — but if the Thief catches the shine it just parks a threat right on the
                     // prize you were chasing. Magnet interception still wins (that's a physical drag,
                     // this is only attention), so it only runs when not intercepted. Reuses the
-                    // golden_lure_positions snapshot already built for the Magnet lure — no new scan.
+                    // same Golden bucket of the crab index the Magnet lure reads — no new scan.
                     let mut lured = false;
-                    if !intercepted && !golden_lure_positions.is_empty() {
+                    if !intercepted {
                         const THIEF_LURE_RADIUS: f32 = 260.0;
-                        const THIEF_LURE_RADIUS_SQ: f32 = THIEF_LURE_RADIUS * THIEF_LURE_RADIUS;
                         // Only divert to a Golden that's genuinely closer than the tail it's homing
                         // for — a shine across the arena shouldn't pull it off a tail right beside it.
                         let tail_d2 = thief_tail_pos
                             .map_or(f32::INFINITY, |tp| crab.pos.distance_squared(tp));
-                        let mut nearest: Option<(f32, Vec2)> = None;
-                        for &gp in golden_lure_positions.iter() {
-                            let d2 = crab.pos.distance_squared(gp);
-                            if d2 < THIEF_LURE_RADIUS_SQ
-                                && d2 < tail_d2
-                                && nearest.map_or(true, |(bd2, _)| d2 < bd2)
-                            {
-                                nearest = Some((d2, gp));
-                            }
-                        }
+                        let pos = crab.pos;
+                        let nearest = self
+                            .crab_index
+                            .nearest_of_type(CrabType::Golden, pos, THIEF_LURE_RADIUS, |g| {
+                                !g.caught
+                                    && !g.in_flashlight
+                                    && g.pos.distance_squared(pos) < tail_d2
+                            })
+                            .map(|(d2, g)| (d2, g.pos));
                         if let Some((d2, gp)) = nearest {
                             let d = d2.sqrt();
                             // Stronger tug the closer the prize; leans hard so the divert reads as
@@ -1262,6 +1230,10 @@ impl MainState {
         // Hermit King escape: it won the race to the world edge and dragged a fresh shell-house
         // stack back in — announce the reset so the player knows the crack progress is gone.
         for &pos in hermit_king_reshells.iter() {
+            self.log_event(GameEvent::BossPhase {
+                boss: CrabType::HermitKing,
+                phase: BossPhase::Escaped,
+            });
             // Banner text anchors to the player so the message is readable on screen (the escape
             // happens at the world edge, often off-camera); the shockwave fires at the actual spot.
             self.floating_texts.spawn(
@@ -1313,7 +1285,12 @@ impl MainState {
         // A boss just crossed into its enrage phase — the fight's final act. A hard jolt, a big
         // menacing shockwave in the boss's own color, and an "ENRAGED!" shout mark the turn so the
         // ramp in aggression reads as a deliberate escalation, not random difficulty.
-        for &(pos, is_tide) in boss_enrages.iter() {
+        for &(pos, boss) in boss_enrages.iter() {
+            self.log_event(GameEvent::BossPhase {
+                boss,
+                phase: BossPhase::Enraged,
+            });
+            let is_tide = boss == CrabType::TideBoss;
             let (ring_col, txt_col): ([f32; 3], [f32; 4]) = if is_tide {
                 ([0.3, 0.75, 1.0], [0.5, 0.9, 1.0, 1.0])
             } else {
@@ -1586,10 +1563,7 @@ impl MainState {
         self.boss_enrages_buf = boss_enrages;
         self.tide_fires_buf = tide_fires;
         self.tide_swells_buf = tide_swells;
-        self.magnet_positions_buf = magnet_positions;
-        self.golden_lure_positions_buf = golden_lure_positions;
         self.charged_magnet_positions_buf = charged_magnet_positions;
-        self.armored_positions_buf = armored_positions;
         self.boss_blocks_buf = boss_blocks;
         self.boss_stuns_buf = boss_stuns;
 
@@ -0,0 +1,230 @@
+//! Daily challenge: one beach a day, the same for everybody.
+//!
+//! The UTC date hashes to a seed (see [`daily_seed`]), and the seed deals the day's beach the way
+//! an endless stage is dealt: a campaign level's biome, a terrain kind, an emphasized archetype and
+//! a boss, plus [`DAILY_MUTATORS`] run mutators (see mutators.rs). The run then plays like endless
+//! on that beach, every stage rolling fresh waves, until the herd overruns it or [`DAILY_SECONDS`]
+//! run out. The gameplay RNG stays seeded with the day's seed for the whole run and the simulation
+//! already steps on a fixed timestep, so the same inputs play out the same on every machine. Bought
+//! perks and remembered rivals (see nemesis.rs) stay at home for the same reason.
+//!
+//! The first attempt of the day is the scored one and goes on the daily table (see leaderboard.rs).
+//! The save remembers its date, so every rerun that day is practice.
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::MainState;
+use crate::endless::{endless_stage, stage_waves};
+use crate::leaderboard::today;
+use crate::levels::Level;
+use crate::mutators::Mutators;
+
+/// Length of a daily run, unless the herd overruns it first.
+pub(crate) const DAILY_SECONDS: f32 = 180.0;
+
+/// Mutators dealt with each day's beach.
+const DAILY_MUTATORS: usize = 2;
+
+/// The seed for `date` (`YYYY-MM-DD`): FNV-1a, so every build and platform agrees on it.
+pub(crate) fn daily_seed(date: &str) -> u64 {
+    format!("rustler-daily {date}")
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// The daily challenge in play.
+#[derive(Clone, Debug)]
+pub(crate) struct DailyChallenge {
+    pub date: String,
+    /// False on a practice rerun: the day's scored attempt is already spent.
+    pub scored: bool,
+    /// The level whose biome the beach borrows.
+    base: usize,
+    /// The dealt beach. Each stage is a copy with its own waves.
+    beach: Level,
+    pub mutators: Mutators,
+    /// Whether leaving the run should hand the RNG back to entropy. False when the whole process
+    /// was seeded at startup (bot, record and replay runs).
+    unseed_on_exit: bool,
+}
+
+impl DailyChallenge {
+    /// Deal `date`'s beach from `levels`.
+    pub(crate) fn deal(levels: &[Level], date: &str, scored: bool, unseed_on_exit: bool) -> Self {
+        // Stage 2 is the first endless stage that rolls a terrain and an emphasis.
+        let mut rng = StdRng::seed_from_u64(daily_seed(date));
+        let (base, beach) = endless_stage(levels, 2, &mut rng);
+        Self {
+            date: date.to_string(),
+            scored,
+            base,
+            beach,
+            mutators: Mutators::deal(DAILY_MUTATORS, &mut rng),
+            unseed_on_exit,
+        }
+    }
+
+    /// Stage `stage` (1-based) on the day's beach.
+    pub(crate) fn stage(&self, stage: usize, rng: &mut impl rand::Rng) -> Level {
+        let mut level = self.beach.clone();
+        level.patterns = stage_waves(stage, rng);
+        level.difficulty = 1 + stage / 2;
+        level
+    }
+
+    pub(crate) fn title(&self, stage: usize) -> String {
+        format!(
+            "Daily {} · stage {stage} — {}{}",
+            self.date,
+            self.beach.title,
+            if self.scored { "" } else { " (practice)" }
+        )
+    }
+}
+
+impl MainState {
+    /// Start today's daily challenge: scored if it's the first attempt today, practice otherwise.
+    /// A recorded or replayed session's today is the date it was recorded on.
+    pub(crate) fn start_daily(&mut self) {
+        let date = self.session_date.clone().unwrap_or_else(today);
+        self.start_daily_on(&date);
+    }
+
+    pub(crate) fn start_daily_on(&mut self, date: &str) {
+        let scored = self.daily_played.as_deref() != Some(date);
+        // A restart keeps what the first start knew, since by then the RNG is seeded either way.
+        let unseed_on_exit = self
+            .daily
+            .as_ref()
+            .map_or(!crate::rng::is_seeded(), |daily| daily.unseed_on_exit);
+        let daily = DailyChallenge::deal(&self.levels, date, scored, unseed_on_exit);
+        crate::rng::seed(daily_seed(date));
+        let level = daily.stage(1, &mut crate::rng::rng());
+        let (base, map_size) = (daily.base, level.map_size);
+        self.endless = Some(level);
+        self.mutators = daily.mutators;
+        self.daily = Some(daily);
+        self.reset_game_at(base, map_size);
+        // Everybody starts from the same loadout.
+        self.beam_rank = 0;
+        self.lasso_rank = 0;
+        self.whistle_rank = 0;
+        self.stomp_rank = 0;
+        self.level_title = self.endless_title();
+        if scored {
+            // Spent on start, so quitting a bad run doesn't buy a second try.
+            self.daily_played = Some(date.to_string());
+            self.save_career();
+        }
+    }
+
+    /// Leave the daily challenge, if one is in play.
+    pub(crate) fn end_daily(&mut self) {
+        if self.daily.take().is_some_and(|daily| daily.unseed_on_exit) {
+            crate::rng::unseed();
+        }
+    }
+
+    /// Seconds left in the daily run; `None` outside one.
+    pub(crate) fn daily_time_left(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .map(|_| (DAILY_SECONDS - self.time_elapsed).max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leaderboard::DAILY;
+    use crate::levels::load_levels;
+
+    #[test]
+    fn the_date_deals_the_beach() {
+        let levels = load_levels().unwrap();
+        let a = DailyChallenge::deal(&levels, "2026-10-18", true, false);
+        let b = DailyChallenge::deal(&levels, "2026-10-18", false, true);
+        assert_eq!((a.base, &a.beach.title), (b.base, &b.beach.title));
+        assert_eq!(a.beach.description, b.beach.description);
+        assert_eq!(a.mutators, b.mutators);
+        assert_eq!(a.mutators.iter().count(), DAILY_MUTATORS);
+        assert_ne!(daily_seed("2026-10-18"), daily_seed("2026-10-19"));
+        let beaches: Vec<_> = (1..=28)
+            .map(|day| DailyChallenge::deal(&levels, &format!("2026-02-{day:02}"), true, false))
+            .map(|daily| (daily.beach.title, daily.beach.description, daily.mutators))
+            .collect();
+        assert!(beaches.iter().any(|beach| *beach != beaches[0]));
+
+        let stage = a.stage(4, &mut StdRng::seed_from_u64(1));
+        assert_eq!(stage.biome.name, a.beach.biome.name);
+        assert_eq!(stage.boss_sequence, a.beach.boss_sequence);
+        assert_eq!(stage.difficulty, 3);
+    }
+
+    #[test]
+    fn one_scored_attempt_per_day() {
+        let mut state = MainState::new_headless();
+        state.start_daily_on("2026-10-18");
+        assert!(crate::rng::is_seeded());
+        assert!(state.daily.as_ref().unwrap().scored);
+        assert_eq!(state.daily_played.as_deref(), Some("2026-10-18"));
+        assert_eq!(state.beam_rank, 0);
+        state.score = 120;
+        state.record_run();
+        assert_eq!(state.last_rank_on(DAILY), Some(0));
+
+        // The restart is practice: shown the table, kept off it.
+        state.reset_game();
+        assert!(!state.daily.as_ref().unwrap().scored);
+        assert!(state.level_title.ends_with("(practice)"));
+        state.score = 900;
+        state.record_run();
+        assert_eq!(state.last_rank_on(DAILY), None);
+        assert_eq!(state.leaderboards.table(DAILY).len(), 1);
+        assert_eq!(state.leaderboards.table(DAILY)[0].date, "2026-10-18");
+
+        state.return_to_main_menu();
+        assert!(state.daily.is_none());
+        assert!(!crate::rng::is_seeded());
+        state.start_daily_on("2026-10-19");
+        assert!(state.daily.as_ref().unwrap().scored);
+    }
+
+    #[test]
+    fn daily_runs_replay_the_same_and_end_on_time() {
+        let fingerprint = |state: &mut MainState| {
+            state.show_instructions = false;
+            for _ in 0..900 {
+                state.step(crate::constants::SIM_DT).unwrap();
+            }
+            let crabs: f32 = state.crabs.iter().map(|crab| crab.pos.x + crab.pos.y).sum();
+            let rivals: f32 = state
+                .npc_trains
+                .iter()
+                .map(|train| train.leader_pos.x + train.leader_pos.y)
+                .sum();
+            (state.crabs.len(), crabs, rivals, state.pen_pos)
+        };
+        let mut first = MainState::new_headless();
+        first.start_daily_on("2026-10-18");
+        let first = fingerprint(&mut first);
+
+        // A second player who warmed up on an endless run first gets the same beach.
+        let mut second = MainState::new_headless();
+        second.start_endless();
+        second.show_instructions = false;
+        for _ in 0..300 {
+            second.step(crate::constants::SIM_DT).unwrap();
+        }
+        second.start_daily_on("2026-10-18");
+        assert_eq!(fingerprint(&mut second), first);
+
+        second.time_elapsed = DAILY_SECONDS - 0.01;
+        second.step(crate::constants::SIM_DT).unwrap();
+        assert!(second.game_over);
+        assert_eq!(second.daily_time_left(), Some(0.0));
+    }
+}
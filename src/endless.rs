@@ -0,0 +1,211 @@
+//! Endless mode: keep going until the herd overruns you.
+//!
+//! Arcade tours the authored campaign levels. Endless instead assembles every stage on the spot
+//! from the campaign's parts: one level's biome (its look and music), a terrain kind, an emphasized
+//! archetype, a handful of random spawn waves and one boss from the pool. Each stage is denser and
+//! faster than the one before with no ceiling, on top of the usual intensity ramp, so the run
+//! always ends the same way: [`OVERRUN_FREE_CRABS`] loose crabs at once. The score goes on the
+//! endless table (see leaderboard.rs).
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use crate::MainState;
+use crate::constants::OVERRUN_FREE_CRABS;
+use crate::enemies::CrabType;
+use crate::levels::{
+    BOSSES, EMPHASES, Level, LevelPattern, MapSize, SHAPES, TerrainKind, boss_label, emphasis_label,
+};
+
+/// Terrain kinds a stage can roll. The Desktop stays the campaign's one-off finale.
+const TERRAINS: [(TerrainKind, &str); 4] = [
+    (TerrainKind::Open, "Open"),
+    (TerrainKind::Water, "Flooded"),
+    (TerrainKind::Rock, "Rocky"),
+    (TerrainKind::Kelp, "Tangled"),
+];
+
+/// Herd density of stage `stage` (1-based), relative to the first.
+pub(crate) fn count_mul(stage: usize) -> f32 {
+    1.0 + stage.saturating_sub(1) as f32 * 0.12
+}
+
+/// Clear window of stage `stage`, relative to the first. Floored so a wave is never a blink.
+pub(crate) fn duration_mul(stage: usize) -> f32 {
+    0.97_f32.powi(stage.saturating_sub(1) as i32).max(0.45)
+}
+
+/// Random spawn waves for stage `stage` (1-based): three, growing to five by stage 6.
+pub(crate) fn stage_waves(stage: usize, rng: &mut impl Rng) -> Vec<LevelPattern> {
+    let waves = 3 + (stage / 3).min(2);
+    (0..waves)
+        .map(|_| LevelPattern {
+            pattern: SHAPES.choose(rng).unwrap().clone(),
+            count: rng.random_range(10..=24),
+            duration: rng.random_range(10.0..17.0),
+            centroid: (rng.random_range(0.2..0.8), rng.random_range(0.2..0.8)),
+        })
+        .collect()
+}
+
+/// Assemble stage `stage` (1-based) from the campaign's parts. Returns the index of the level whose
+/// biome it borrows, which is what the music and ground textures are keyed by, with the stage.
+pub(crate) fn endless_stage(levels: &[Level], stage: usize, rng: &mut impl Rng) -> (usize, Level) {
+    let base = rng.random_range(0..levels.len());
+    let mut level = levels[base].clone();
+    // Stage 1 is a plain warm-up: open ground and an unflavoured herd.
+    let (terrain, adjective) = if stage == 1 {
+        TERRAINS[0]
+    } else {
+        *TERRAINS.choose(rng).unwrap()
+    };
+    let emphasis = if stage == 1 {
+        None
+    } else {
+        EMPHASES
+            .iter()
+            .copied()
+            .filter(|&t| t != CrabType::Normal)
+            .collect::<Vec<_>>()
+            .choose(rng)
+            .copied()
+    };
+    let boss = *BOSSES.choose(rng).unwrap();
+    level.patterns = stage_waves(stage, rng);
+    level.title = format!("{adjective} {}", level.biome.name);
+    level.description = format!(
+        "{} with the {} waiting",
+        emphasis_label(emphasis).unwrap_or("MIXED HERD"),
+        boss_label(boss)
+    );
+    level.difficulty = 1 + stage / 2;
+    level.map_size = MapSize::Medium;
+    level.biome.terrain = terrain;
+    level.emphasis = emphasis;
+    level.boss_sequence = vec![boss];
+    (base, level)
+}
+
+impl MainState {
+    /// The level in play: the endless stage during an endless run, otherwise the current campaign
+    /// level. Clamped so a finished run doesn't index past the last level.
+    pub(crate) fn level(&self) -> &Level {
+        self.endless
+            .as_ref()
+            .unwrap_or(&self.levels[self.current_level.min(self.levels.len() - 1)])
+    }
+
+    /// Start a fresh endless run at stage 1.
+    pub(crate) fn start_endless(&mut self) {
+        let (base, level) = endless_stage(&self.levels, 1, &mut crate::rng::rng());
+        let map_size = level.map_size;
+        self.endless = Some(level);
+        self.reset_game_at(base, map_size);
+        self.recall_nemeses();
+        self.level_title = self.endless_title();
+    }
+
+    /// Swap in the next endless stage. `arcade_stage` has already been bumped. A daily challenge
+    /// stays on the day's beach and only rolls new waves.
+    pub(crate) fn next_endless_stage(&mut self) {
+        if let Some(daily) = &self.daily {
+            let level = daily.stage(self.arcade_stage, &mut crate::rng::rng());
+            self.endless = Some(level);
+        } else {
+            let (base, level) =
+                endless_stage(&self.levels, self.arcade_stage, &mut crate::rng::rng());
+            self.current_level = base;
+            self.endless = Some(level);
+        }
+        self.level_title = self.endless_title();
+    }
+
+    pub(crate) fn endless_title(&self) -> String {
+        match &self.daily {
+            Some(daily) => daily.title(self.arcade_stage),
+            None => format!("Endless {} — {}", self.arcade_stage, self.level().title),
+        }
+    }
+
+    /// The loose crabs left before the herd overruns the run, for the endless HUD meter.
+    pub(crate) fn overrun_margin(&self) -> usize {
+        OVERRUN_FREE_CRABS.saturating_sub(self.free_crab_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leaderboard::{ARCADE, ENDLESS};
+    use crate::levels::load_levels;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn stages_are_assembled_from_campaign_parts() {
+        let levels = load_levels().unwrap();
+        let mut rng = StdRng::seed_from_u64(17);
+        let (base, first) = endless_stage(&levels, 1, &mut rng);
+        assert_eq!(first.biome.music, levels[base].biome.music);
+        assert_eq!(first.biome.terrain, TerrainKind::Open);
+        assert_eq!(first.emphasis, None);
+        for stage in 2..40 {
+            let (base, level) = endless_stage(&levels, stage, &mut rng);
+            assert_eq!(level.biome.name, levels[base].biome.name);
+            assert_ne!(level.biome.terrain, TerrainKind::Desktop);
+            assert_ne!(level.emphasis, Some(CrabType::Normal));
+            assert_eq!(level.boss_sequence.len(), 1);
+            assert!(BOSSES.contains(&level.boss_sequence[0]));
+            assert!((3..=5).contains(&level.patterns.len()));
+            assert!(level.title.ends_with(&level.biome.name));
+        }
+
+        let again = endless_stage(&levels, 6, &mut StdRng::seed_from_u64(3));
+        let (base, level) = endless_stage(&levels, 6, &mut StdRng::seed_from_u64(3));
+        assert_eq!((again.0, again.1.title), (base, level.title));
+    }
+
+    #[test]
+    fn endless_runs_climb_stages_and_land_on_the_board() {
+        let mut state = MainState::new_headless();
+        state.start_endless();
+        assert_eq!(state.arcade_stage, 1);
+        assert!(state.level_title.starts_with("Endless 1 — "));
+        for _ in 0..state.level().patterns.len() {
+            state.advance_pattern();
+        }
+        assert_eq!(state.arcade_stage, 2);
+        assert!(state.level_title.starts_with("Endless 2 — "));
+        assert_eq!(
+            state.level().biome.name,
+            state.levels[state.current_level].biome.name
+        );
+
+        state.score = 77;
+        state.longest_train_run = 19;
+        state.record_run();
+        assert_eq!(state.last_rank_on(ENDLESS), Some(0));
+        let entry = &state.leaderboards.table(ENDLESS)[0];
+        assert_eq!((entry.stage, entry.longest_train), (Some(2), 19));
+        assert!(state.leaderboards.table(ARCADE).is_empty());
+
+        // Restarting stays in endless; leaving for the menu ends it.
+        state.reset_game();
+        assert_eq!((state.arcade_stage, state.last_entry.clone()), (1, None));
+        assert!(state.endless.is_some());
+        state.return_to_main_menu();
+        assert!(state.endless.is_none());
+    }
+
+    #[test]
+    fn stages_keep_escalating_past_overdrive() {
+        assert_eq!(count_mul(1), 1.0);
+        assert_eq!(duration_mul(1), 1.0);
+        for stage in 1..60 {
+            assert!(count_mul(stage + 1) > count_mul(stage));
+            assert!(duration_mul(stage + 1) <= duration_mul(stage));
+        }
+        assert!(count_mul(30) > 4.0);
+        assert_eq!(duration_mul(200), 0.45);
+    }
+}
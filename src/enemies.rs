@@ -160,6 +160,17 @@ impl CrabType {
             _ => 0.0,
         }
     }
+    /// The oversized bosses — spawned explicitly, never by the herd roll (see `EnemyCrab::is_boss`).
+    pub fn is_boss(&self) -> bool {
+        matches!(
+            self,
+            CrabType::Boss
+                | CrabType::TideBoss
+                | CrabType::RhythmBoss
+                | CrabType::HermitKing
+                | CrabType::DancerKing
+        )
+    }
     /// How strongly the Whistle ability yanks this crab toward the player — a soft counter, not a
     /// hard requirement. Every archetype still moves at least a little (nothing is whistle-immune
     /// except the boss), but the whistle is *the* tool for gathering skittish Sneaky crabs, while
@@ -286,14 +297,7 @@ impl EnemyCrab {
     /// both the charging King Crab and the pulsing Tide Boss, so all the shared boss plumbing
     /// (health ring, catchable-only-when-drained, unshakeable, non-fleeing) applies to both.
     pub fn is_boss(&self) -> bool {
-        matches!(
-            self.crab_type,
-            CrabType::Boss
-                | CrabType::TideBoss
-                | CrabType::RhythmBoss
-                | CrabType::HermitKing
-                | CrabType::DancerKing
-        )
+        self.crab_type.is_boss()
     }
 
     /// The "Hermit King" specifically — the shell-house tank whose stack of shells the beam can't
@@ -2,11 +2,14 @@
 //! callbacks and the keyboard/mouse input entry points (name entry, upgrade-card picks,
 //! flashlight toggle, and the charge-and-release lasso throw).
 //!
-//! `update` delegates to `tick` (game_update.rs) and `draw` runs the three-pass render
-//! (scene → conga trail → post-process). Extracted verbatim from `main.rs` to keep that
-//! file focused on setup and `main()`. Pure structural move, no behaviour change.
+//! `update` delegates to `tick` (game_update.rs) and `draw` runs the three-pass render (scene →
+//! conga trail → post-process), timing each pass for the F3 overlay (profiler.rs). The input
+//! callbacks only translate ggez events into `replay::InputEvent`s; the `Context`-free handlers
+//! below them do the work, so a recorded session replays through the same code. Extracted verbatim
+//! from `main.rs` to keep that file focused on setup and `main()`. Pure structural move, no
+//! behaviour change.
 
-use ggez::event::EventHandler;
+use ggez::event::{Axis, Button, EventHandler, GamepadId};
 use ggez::glam::Vec2;
 use ggez::graphics::{BlendMode, Canvas, Color, DrawParam, Sampler};
 use ggez::input::keyboard::{KeyCode, KeyInput};
@@ -14,7 +17,11 @@ use ggez::input::mouse::MouseButton;
 use ggez::winit::keyboard::PhysicalKey;
 use ggez::{Context, GameResult};
 
+use crate::bindings::Action;
 use crate::controls::handle_key_down_event;
+use crate::profiler::Section;
+use crate::replay::InputEvent;
+use crate::simulation::InputFrame;
 use crate::*;
 
 impl EventHandler for MainState {
@@ -32,8 +39,118 @@ impl EventHandler for MainState {
             return Ok(());
         }
 
+        // The sim runs at a fixed rate that needn't match the display's, so draw every moving
+        // body where it would be between the last two steps rather than where the last step left
+        // it (see interpolation.rs). The true positions go back before the next step.
+        let stepped = self.begin_interpolated_draw();
+        let result = self.render(ctx);
+        self.end_interpolated_draw(stepped);
+        result
+    }
+
+    fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, _repeat: bool) -> GameResult {
+        // ggez 0.10 (winit 0.30) no longer exposes `KeyInput::keycode`; derive the physical
+        // key code ourselves so the rest of the handling reads exactly as before.
+        let code = match input.event.physical_key {
+            PhysicalKey::Code(code) => Some(code),
+            _ => None,
+        };
+        // F3/F4 are developer keys for the performance overlay, not game input: they work during
+        // a replay and are never recorded.
+        match code {
+            Some(KeyCode::F3) => self.profiler.toggle(),
+            Some(KeyCode::F4) => self.dump_profile(),
+            _ => {
+                let text = input.event.text.map(|t| t.to_string());
+                self.live_input(InputEvent::Key { code, text });
+            }
+        }
+        Ok(())
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _xrel: f32,
+        _yrel: f32,
+    ) -> GameResult {
+        let p = self.view_point(ctx, x, y);
+        self.live_input(InputEvent::MouseMove(p));
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        let p = self.view_point(ctx, x, y);
+        self.live_input(InputEvent::MouseDown(button, p));
+        Ok(())
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        let p = self.view_point(ctx, x, y);
+        self.live_input(InputEvent::MouseUp(button, p));
+        Ok(())
+    }
+
+    // Gamepads: sticks and held buttons are state, sampled into each step's input by `tick`; a
+    // press is also an event, handled like a key press (see gamepad.rs). Every connected pad
+    // drives the one player.
+    fn gamepad_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        _id: GamepadId,
+    ) -> GameResult {
+        self.live_pad.held.insert(btn);
+        self.live_input(InputEvent::PadButton(btn));
+        Ok(())
+    }
+
+    fn gamepad_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        _id: GamepadId,
+    ) -> GameResult {
+        self.live_pad.held.remove(&btn);
+        Ok(())
+    }
+
+    fn gamepad_axis_event(
+        &mut self,
+        _ctx: &mut Context,
+        axis: Axis,
+        value: f32,
+        _id: GamepadId,
+    ) -> GameResult {
+        self.live_pad.set_axis(axis, value);
+        Ok(())
+    }
+}
+
+// The input handling proper. Needs no `Context`: the ggez callbacks above translate into an
+// `InputEvent` in view space, and replay playback (`apply_input`) feeds recorded events through
+// exactly the same paths.
+impl MainState {
+    /// The three-pass render: scene → conga trail → post-process.
+    fn render(&mut self, ctx: &mut Context) -> GameResult {
         // --- Pass 1: render the game scene to an offscreen crisp image ---
+        let started = self.profiler.start();
         self.draw_scene(ctx)?;
+        self.profiler.stop(Section::Scene, started);
 
         // --- Pass 1.5: conga trail / echo-afterimage accumulation (ping-pong) ---
         // A single fixed extra full-screen pass: composite the crisp scene as an opaque base,
@@ -58,39 +175,46 @@ impl EventHandler for MainState {
         // full extra full-screen pass here is a real per-frame win. The trail ping-pong buffers
         // are simply left untouched; when groove climbs back past 0.2, trail_strength ramps up
         // from ~0 so any staleness in the buffers contributes a negligible first-frame blend.
+        let gfx = self
+            .gfx
+            .as_mut()
+            .expect("drawing requires render resources");
         let write_img = if trail_strength > 0.0 {
+            let started = self.profiler.start();
             // Ping-pong: read last frame's accumulation, write this frame's. Both images are
             // allocated once (state.rs) and reused — no per-frame image allocation.
-            let (read_img, write_img) = if self.trail_swap {
-                (self.trail_image_a.clone(), self.trail_image_b.clone())
+            let (read_img, write_img) = if gfx.trail_swap {
+                (gfx.trail_image_a.clone(), gfx.trail_image_b.clone())
             } else {
-                (self.trail_image_b.clone(), self.trail_image_a.clone())
+                (gfx.trail_image_b.clone(), gfx.trail_image_a.clone())
             };
-            let scene = self.scene_image.clone();
+            let scene = gfx.scene_image.clone();
             let tu = TrailUniform {
                 strength: trail_strength,
             };
-            self.trail_params.set_uniforms(ctx, &tu);
+            gfx.trail_params.set_uniforms(ctx, &tu);
             {
                 let mut acc = Canvas::from_image(ctx, write_img.clone(), Color::BLACK);
                 acc.set_sampler(Sampler::nearest_clamp());
                 acc.draw(&scene, DrawParam::default().dest(Vec2::ZERO));
-                acc.set_shader(&self.trail_shader);
-                acc.set_shader_params(&self.trail_params);
+                acc.set_shader(&gfx.trail_shader);
+                acc.set_shader_params(&gfx.trail_params);
                 acc.set_blend_mode(BlendMode::ADD);
                 acc.draw(&read_img, DrawParam::default().dest(Vec2::ZERO));
                 acc.set_blend_mode(BlendMode::ALPHA);
                 acc.set_default_shader();
                 acc.finish(ctx)?;
             }
-            self.trail_swap = !self.trail_swap;
+            gfx.trail_swap = !gfx.trail_swap;
+            self.profiler.stop(Section::Trail, started);
             write_img
         } else {
-            self.scene_image.clone()
+            gfx.scene_image.clone()
         };
 
         // --- Pass 2: blit the accumulated scene to screen with post-processing ---
         {
+            let started = self.profiler.start();
             let (draw_w, draw_h) = ctx.gfx.drawable_size();
             let _scale_x = draw_w / self.width;
             let _scale_y = draw_h / self.height;
@@ -118,25 +242,75 @@ impl EventHandler for MainState {
                 menu_moon_y: 0.2,
             };
             // Reuse cached shader params, just update uniforms (avoids per-frame GPU buffer alloc)
-            self.postprocess_params.set_uniforms(ctx, &uniform);
+            gfx.postprocess_params.set_uniforms(ctx, &uniform);
             let mut screen_canvas = Canvas::from_frame(ctx, Color::BLACK);
-            screen_canvas.set_shader(&self.postprocess_shader);
-            screen_canvas.set_shader_params(&self.postprocess_params);
+            screen_canvas.set_shader(&gfx.postprocess_shader);
+            screen_canvas.set_shader_params(&gfx.postprocess_params);
             screen_canvas.draw(&write_img, DrawParam::default().dest(Vec2::ZERO));
             screen_canvas.set_default_shader();
+            if self.profiler.visible {
+                // The overlay rides on the final canvas, in the same logical space as the HUD. Its
+                // own cost lands in the post-process figure (it is mostly the shared `finish`).
+                screen_canvas.set_screen_coordinates(ggez::graphics::Rect::new(
+                    0.0,
+                    0.0,
+                    self.width,
+                    self.height,
+                ));
+                graphics::draw_perf_overlay(ctx, &mut screen_canvas, &self.profiler, self.width)?;
+            }
             screen_canvas.finish(ctx)?;
+            self.profiler.stop(Section::PostProcess, started);
         }
+        self.profiler.end_frame();
 
         Ok(())
     }
 
-    fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, _repeat: bool) -> GameResult {
-        // ggez 0.10 (winit 0.30) no longer exposes `KeyInput::keycode`; derive the physical
-        // key code ourselves so the rest of the handling reads exactly as before.
-        let keycode = match input.event.physical_key {
-            PhysicalKey::Code(code) => Some(code),
-            _ => None,
-        };
+    /// Window pixels → view space (the fixed logical canvas the game lays out in).
+    fn view_point(&self, ctx: &Context, x: f32, y: f32) -> Vec2 {
+        let window_size = ctx.gfx.window().inner_size();
+        let scale_x = window_size.width as f32 / self.width;
+        let scale_y = window_size.height as f32 / self.height;
+        Vec2::new(x / scale_x, y / scale_y)
+    }
+
+    /// An event from the real keyboard/mouse/gamepad: recorded if a recording is running, ignored
+    /// while a replay is driving the game, otherwise handled.
+    fn live_input(&mut self, event: InputEvent) {
+        if self.replay.is_some() {
+            return;
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.push_event(event.clone());
+        }
+        self.apply_input_event(&event);
+    }
+
+    /// Deliver one frame of recorded input: its events in order, then its held keys and pad.
+    pub(crate) fn apply_input(&mut self, frame: &InputFrame) {
+        for event in &frame.events {
+            self.apply_input_event(event);
+        }
+        self.input.held.clone_from(&frame.held);
+        self.input.pad.clone_from(&frame.pad);
+    }
+
+    pub(crate) fn apply_input_event(&mut self, event: &InputEvent) {
+        match event {
+            InputEvent::Key { code, text } => self.on_key_down(*code, text.as_deref()),
+            InputEvent::MouseMove(p) => {
+                // mouse_pos is used against player/crab positions (world space) for flashlight aim
+                // and crab picking, so store it in world space: view point offset by the camera.
+                self.mouse_pos = self.camera_origin + *p;
+            }
+            InputEvent::MouseDown(button, p) => self.on_mouse_down(*button, *p),
+            InputEvent::MouseUp(button, _) => self.on_mouse_up(*button),
+            InputEvent::PadButton(button) => self.on_pad_down(*button),
+        }
+    }
+
+    pub(crate) fn on_key_down(&mut self, code: Option<KeyCode>, text: Option<&str>) {
         // Player-name text entry. ggez 0.10 removed the separate `text_input_event` callback and
         // delivers typed text on the key event itself (`input.event.text`). Handled first and
         // unconditionally (like 0.9's independent text callback) so a name character still lands
@@ -147,7 +321,7 @@ impl EventHandler for MainState {
             && !self.pending_upgrade
             && self.menu_page == 1
         {
-            if let Some(text) = &input.event.text {
+            if let Some(text) = text {
                 for character in text.chars() {
                     if !character.is_control() && self.player_name.chars().count() < 24 {
                         self.push_player_name_char(character);
@@ -155,78 +329,56 @@ impl EventHandler for MainState {
                 }
             }
         }
+        if self.pause.is_some() {
+            // The pause menu takes every key; none may reach the frozen run or the upgrade cards.
+            handle_key_down_event(self, code);
+            return;
+        }
         if self.pending_upgrade {
             // The choice is a live overlay now, not a freeze: 1/2/3 pick a card, but every other key
             // falls through to normal in-game handling so the player can keep steering and using
             // tools while they decide (and a rival can steal from them mid-decision — the intended
             // pressure to pick fast). 1/2/3 aren't bound to anything in-game (they're loadout-screen
             // only), so consuming them here can't shadow a gameplay action.
-            if let Some(key) = keycode {
+            if let Some(key) = code {
                 match key {
                     KeyCode::Digit1 => {
                         self.apply_upgrade(1);
-                        return Ok(());
+                        return;
                     }
                     KeyCode::Digit2 => {
                         self.apply_upgrade(2);
-                        return Ok(());
+                        return;
                     }
                     KeyCode::Digit3 => {
                         self.apply_upgrade(3);
-                        return Ok(());
+                        return;
                     }
                     _ => {}
                 }
             }
         }
-        if let Some(key) = keycode {
-            if key == KeyCode::KeyF {
+        if let Some(key) = code {
+            if !self.rebind_capture && self.bindings.matches(Action::Flashlight, key) {
                 self.flashlight.on = !self.flashlight.on;
                 use ggez::audio::SoundSource;
                 // Slightly higher pitch on, lower on off, so the toggle direction is audible.
                 let pitch = if self.flashlight.on { 1.15 } else { 0.85 };
                 self.sounds.flashlight_toggle.set_pitch(pitch);
                 let _ = self.sounds.flashlight_toggle.play();
-                return Ok(());
+                return;
             }
         }
-        if handle_key_down_event(self, ctx, keycode) {
-            return Ok(());
-        }
-        Ok(())
+        handle_key_down_event(self, code);
     }
 
-    fn mouse_motion_event(
-        &mut self,
-        ctx: &mut Context,
-        x: f32,
-        y: f32,
-        _xrel: f32,
-        _yrel: f32,
-    ) -> GameResult {
-        let window_size = ctx.gfx.window().inner_size();
-        let scale_x = window_size.width as f32 / self.width;
-        let scale_y = window_size.height as f32 / self.height;
-        // mouse_pos is used against player/crab positions (world space) for flashlight aim and crab
-        // picking, so store it in world space: screen point offset by the camera origin.
-        self.mouse_pos = self.camera_origin + Vec2::new(x / scale_x, y / scale_y);
-        Ok(())
-    }
-
-    fn mouse_button_down_event(
-        &mut self,
-        ctx: &mut Context,
-        button: MouseButton,
-        x: f32,
-        y: f32,
-    ) -> GameResult {
+    fn on_mouse_down(&mut self, button: MouseButton, p: Vec2) {
+        if self.pause.is_some() {
+            return;
+        }
         // Upgrade screen: let the player click a card as an alternative to the number keys.
         if self.pending_upgrade {
             if button == MouseButton::Left {
-                let window_size = ctx.gfx.window().inner_size();
-                let scale_x = window_size.width as f32 / self.width;
-                let scale_y = window_size.height as f32 / self.height;
-                let p = Vec2::new(x / scale_x, y / scale_y);
                 let rects = self.upgrade_card_rects();
                 for (i, r) in rects.iter().enumerate() {
                     if p.x >= r.x && p.x <= r.x + r.w && p.y >= r.y && p.y <= r.y + r.h {
@@ -235,32 +387,41 @@ impl EventHandler for MainState {
                     }
                 }
             }
-            return Ok(());
+            return;
         }
         if self.game_over || self.show_instructions {
-            return Ok(());
+            return;
         }
         // Left click: BEGIN winding up the lasso. The throw fires on mouse_button_up.
-        if button == MouseButton::Left && self.lasso_phase == LassoPhase::Idle {
+        if button == MouseButton::Left && self.begin_lasso_windup() {
             self.lasso_mouse_down = true;
-            self.lasso_charge = 0.0;
-            self.lasso_spin = 0.0;
-            self.lasso_phase = LassoPhase::Winding;
-            // Capture player center for the windup origin; target is updated every frame from mouse_pos.
-            self.lasso_origin = self.player_pos + Vec2::new(PLAYER_SIZE / 2.0, PLAYER_SIZE / 2.0);
         }
-        Ok(())
     }
 
-    fn mouse_button_up_event(
-        &mut self,
-        _ctx: &mut Context,
-        button: MouseButton,
-        _x: f32,
-        _y: f32,
-    ) -> GameResult {
-        if button == MouseButton::Left && self.lasso_phase == LassoPhase::Winding {
+    fn on_mouse_up(&mut self, button: MouseButton) {
+        if button == MouseButton::Left {
             self.lasso_mouse_down = false;
+            self.release_lasso();
+        }
+    }
+
+    /// Start winding up the lasso, if it's idle. Shared by the mouse button and the Lasso key;
+    /// returns whether a wind-up started.
+    pub(crate) fn begin_lasso_windup(&mut self) -> bool {
+        if self.lasso_phase != LassoPhase::Idle {
+            return false;
+        }
+        self.lasso_charge = 0.0;
+        self.lasso_spin = 0.0;
+        self.lasso_phase = LassoPhase::Winding;
+        // Capture player center for the windup origin; target is updated every frame from mouse_pos.
+        self.lasso_origin = self.player_pos + Vec2::new(PLAYER_SIZE / 2.0, PLAYER_SIZE / 2.0);
+        true
+    }
+
+    /// Throw a winding lasso, with range scaled by how long it was charged.
+    pub(crate) fn release_lasso(&mut self) {
+        if self.lasso_phase == LassoPhase::Winding {
             {
                 use ggez::audio::SoundSource;
                 let _ = self.sounds.lasso_sfx.play();
@@ -306,6 +467,5 @@ impl EventHandler for MainState {
             self.lasso_pos = Some(origin);
             self.lasso_charge = 0.0;
         }
-        Ok(())
     }
 }
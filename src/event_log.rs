@@ -0,0 +1,426 @@
+//! Opt-in gameplay event log, for working out where runs are won and lost.
+//!
+//! `--event-log <path>` streams a typed [`GameEvent`] per line as JSON (JSONL): every catch with
+//! the crab's archetype and how far off the beat it landed, every delivery with its pen-worth
+//! breakdown, every snap and its cause, the steal exchange with each rival by name, each rival's
+//! bank, boss phases, upgrade picks, the win condition landing, and how each run ended — including
+//! the player restarting or quitting out of it. Each line carries the run's game time and beat
+//! index:
+//!
+//! ```text
+//! {"t":0.000,"beat":0,"event":"run_start","mode":"campaign","level":2,"unix":1760000000}
+//! {"t":4.183,"beat":8,"event":"catch","archetype":"golden","beat_offset":-0.021,"train":3}
+//! {"t":9.950,"beat":19,"event":"snap","cause":"kelp","links":2}
+//! {"t":31.402,"beat":61,"event":"run_end","reason":"quit_to_map","score":515,"base_score":412}
+//! ```
+//!
+//! Lines are flushed as they are written, like a replay recording, so the log is complete up to
+//! the last event even if the game is killed. Writes work the same windowed and headless (a
+//! `--replay <file> --headless` run logs the recorded session's events).
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::MainState;
+use crate::bot_report::json_string;
+use crate::enemies::CrabType;
+use crate::levels::WinCondition;
+use crate::upgrade::UpgradeId;
+
+/// What tore links off the tail of the train.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SnapCause {
+    /// Dragged through a kelp bed.
+    Kelp,
+    /// A panicking crab or a charging King Crab hit the tail.
+    Panic,
+    /// Caught in a Tide Boss pulse.
+    Tide,
+    /// Swallowed by an erupting King Crab fissure.
+    Blast,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BossPhase {
+    Arrived,
+    Enraged,
+    /// A Hermit King reached the world edge and came back with a fresh shell.
+    Escaped,
+    Caught,
+}
+
+/// Why a run stopped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RunEnd {
+    GameOver,
+    Won,
+    Restart,
+    QuitToMap,
+    QuitToTitle,
+    /// The game was shut down mid-run (window closed, or a headless run finished).
+    Closed,
+}
+
+/// How a delivery's payout was built: the points each term added to the base, then the
+/// multipliers it rode through to `bank`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PenWorth {
+    pub crabs: usize,
+    pub triangle: usize,
+    pub bonds: usize,
+    pub sandwiches: usize,
+    pub run: usize,
+    pub centerpiece: usize,
+    pub streak_mult: f32,
+    pub perfect: bool,
+    pub gamble_mult: f32,
+    pub bank: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum GameEvent {
+    /// `level` is the level being played; tutorials, endless stages and daily beaches have none.
+    RunStart {
+        mode: &'static str,
+        level: Option<usize>,
+    },
+    /// `beat_offset` is seconds from the nearest beat: negative is early, positive late.
+    Catch {
+        archetype: CrabType,
+        beat_offset: f32,
+        train: usize,
+    },
+    Deliver(PenWorth),
+    Snap {
+        cause: SnapCause,
+        links: usize,
+    },
+    /// A rival spliced the player's tail.
+    Steal {
+        rival: String,
+        links: usize,
+    },
+    /// The player threaded a rival's line and took its tail. `raid` is a steal off a train
+    /// hauling home.
+    StealBack {
+        rival: String,
+        links: usize,
+        revenge: bool,
+        raid: bool,
+        on_beat: bool,
+    },
+    /// A rival hauled its train home and banked `crabs` of it.
+    RivalBank {
+        rival: String,
+        crabs: usize,
+    },
+    Parry {
+        rival: String,
+        downbeat: bool,
+    },
+    Dodge {
+        rival: String,
+        downbeat: bool,
+    },
+    BossPhase {
+        boss: CrabType,
+        phase: BossPhase,
+    },
+    UpgradeChosen {
+        upgrade: UpgradeId,
+    },
+    WinConditionMet {
+        goal: WinCondition,
+    },
+    /// `score` is the final one the career and the tables get (mutators applied); `base_score`
+    /// is what was banked before the multiplier.
+    RunEnd {
+        reason: RunEnd,
+        score: usize,
+        base_score: usize,
+    },
+}
+
+impl GameEvent {
+    /// The event's JSON line (without the newline), stamped with game time `t` and beat `beat`.
+    pub(crate) fn to_json(&self, t: f32, beat: u32) -> String {
+        let mut fields: Vec<(&'static str, String)> = Vec::new();
+        let mut field = |name: &'static str, value: String| fields.push((name, value));
+        let kind = match self {
+            GameEvent::RunStart { mode, level } => {
+                field("mode", json_string(mode));
+                field("level", level.map_or("null".to_string(), |l| l.to_string()));
+                let unix = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs());
+                field("unix", unix.to_string());
+                "run_start"
+            }
+            GameEvent::Catch {
+                archetype,
+                beat_offset,
+                train,
+            } => {
+                field("archetype", json_string(&snake_name(archetype)));
+                field("beat_offset", format!("{beat_offset:.3}"));
+                field("train", train.to_string());
+                "catch"
+            }
+            GameEvent::Deliver(worth) => {
+                field("crabs", worth.crabs.to_string());
+                field("triangle", worth.triangle.to_string());
+                field("bonds", worth.bonds.to_string());
+                field("sandwiches", worth.sandwiches.to_string());
+                field("run", worth.run.to_string());
+                field("centerpiece", worth.centerpiece.to_string());
+                field("streak_mult", format!("{:.2}", worth.streak_mult));
+                field("perfect", worth.perfect.to_string());
+                field("gamble_mult", format!("{:.2}", worth.gamble_mult));
+                field("bank", worth.bank.to_string());
+                "deliver"
+            }
+            GameEvent::Snap { cause, links } => {
+                field("cause", json_string(&snake_name(cause)));
+                field("links", links.to_string());
+                "snap"
+            }
+            GameEvent::Steal { rival, links } => {
+                field("rival", json_string(rival));
+                field("links", links.to_string());
+                "steal"
+            }
+            GameEvent::StealBack {
+                rival,
+                links,
+                revenge,
+                raid,
+                on_beat,
+            } => {
+                field("rival", json_string(rival));
+                field("links", links.to_string());
+                field("revenge", revenge.to_string());
+                field("raid", raid.to_string());
+                field("on_beat", on_beat.to_string());
+                "steal_back"
+            }
+            GameEvent::RivalBank { rival, crabs } => {
+                field("rival", json_string(rival));
+                field("crabs", crabs.to_string());
+                "rival_bank"
+            }
+            GameEvent::Parry { rival, downbeat } => {
+                field("rival", json_string(rival));
+                field("downbeat", downbeat.to_string());
+                "parry"
+            }
+            GameEvent::Dodge { rival, downbeat } => {
+                field("rival", json_string(rival));
+                field("downbeat", downbeat.to_string());
+                "dodge"
+            }
+            GameEvent::BossPhase { boss, phase } => {
+                field("boss", json_string(&snake_name(boss)));
+                field("phase", json_string(&snake_name(phase)));
+                "boss_phase"
+            }
+            GameEvent::UpgradeChosen { upgrade } => {
+                field("upgrade", json_string(&snake_name(upgrade)));
+                "upgrade_chosen"
+            }
+            GameEvent::WinConditionMet { goal } => {
+                let (name, target) = match *goal {
+                    WinCondition::BankCrabs(n) => ("bank_crabs", n),
+                    WinCondition::BuildTrain(n) => ("build_train", n),
+                    WinCondition::CrackAndHold { shells, .. } => ("crack_and_hold", shells),
+                    WinCondition::HoldTrain { target, .. } => ("hold_train", target),
+                    WinCondition::StealBack(n) => ("steal_back", n),
+                    WinCondition::OutgrowRivals { seconds } => ("outgrow_rivals", seconds as usize),
+                    WinCondition::DethroneKing(_) => ("dethrone_king", 1),
+                    WinCondition::PerfectStreak(n) => ("perfect_streak", n),
+                    WinCondition::Escort(_) => ("escort", 1),
+                    WinCondition::CatchBosses(n) => ("catch_bosses", n),
+                };
+                field("goal", json_string(name));
+                field("target", target.to_string());
+                match goal {
+                    WinCondition::DethroneKing(rival) => field("rival", json_string(rival)),
+                    WinCondition::Escort(kind) => {
+                        field("archetype", json_string(&snake_name(kind)))
+                    }
+                    _ => {}
+                }
+                "win_condition_met"
+            }
+            GameEvent::RunEnd {
+                reason,
+                score,
+                base_score,
+            } => {
+                field("reason", json_string(&snake_name(reason)));
+                field("score", score.to_string());
+                field("base_score", base_score.to_string());
+                "run_end"
+            }
+        };
+        let mut line = format!("{{\"t\":{t:.3},\"beat\":{beat},\"event\":\"{kind}\"");
+        for (name, value) in fields {
+            line.push_str(&format!(",\"{name}\":{value}"));
+        }
+        line.push('}');
+        line
+    }
+}
+
+/// A unit variant's name in snake_case (`TideBoss` → `tide_boss`), from its `Debug` form.
+pub(crate) fn snake_name(value: &impl std::fmt::Debug) -> String {
+    let mut out = String::new();
+    for (i, c) in format!("{value:?}").chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Signed seconds from `beat_timer`'s nearest beat. The timer counts down to the next beat, so a
+/// small value is just early and one close to `beat_interval` is just late.
+pub(crate) fn beat_offset(beat_timer: f32, beat_interval: f32) -> f32 {
+    if beat_timer < beat_interval * 0.5 {
+        -beat_timer
+    } else {
+        beat_interval - beat_timer
+    }
+}
+
+/// The open `--event-log` file.
+pub struct EventLog {
+    out: BufWriter<File>,
+    /// Whether a `run_start` is waiting for its `run_end`.
+    run_open: bool,
+}
+
+impl EventLog {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            out: BufWriter::new(File::create(path)?),
+            run_open: false,
+        })
+    }
+
+    /// Write one event. Most callers want [`MainState::log_event`]; this is for sites that hold a
+    /// borrow into the herd and can only reach the log's field.
+    pub(crate) fn write(&mut self, t: f32, beat: u32, event: &GameEvent) {
+        let line = event.to_json(t, beat);
+        if let Err(e) = writeln!(self.out, "{line}").and_then(|()| self.out.flush()) {
+            eprintln!("Failed to write event log: {e}");
+        }
+    }
+}
+
+impl MainState {
+    /// Append `event` to the event log, if one is open.
+    pub(crate) fn log_event(&mut self, event: GameEvent) {
+        if let Some(log) = &mut self.event_log {
+            log.write(self.time_elapsed, self.beat_count, &event);
+        }
+    }
+
+    /// Whether events are being logged — for call sites that would otherwise build one (cloning a
+    /// rival's name, say) only to throw it away.
+    pub(crate) fn logging_events(&self) -> bool {
+        self.event_log.is_some()
+    }
+
+    /// Open a run in the log the first time a live run is stepped. No-op while one is open.
+    pub(crate) fn log_run_start(&mut self) {
+        if self.event_log.as_ref().is_none_or(|log| log.run_open) {
+            return;
+        }
+        let (mode, level) = if self.tutorial.is_some() {
+            ("tutorial", None)
+        } else if self.in_campaign {
+            let node = self
+                .world_map
+                .as_ref()
+                .and_then(|m| m.selected_level_index());
+            ("campaign", Some(node.unwrap_or(self.current_level)))
+        } else if self.daily.is_some() {
+            ("daily", None)
+        } else if self.endless.is_some() {
+            ("endless", None)
+        } else {
+            ("arcade", Some(self.current_level))
+        };
+        self.log_event(GameEvent::RunStart { mode, level });
+        if let Some(log) = &mut self.event_log {
+            log.run_open = true;
+        }
+    }
+
+    /// Close the open run in the log. No-op if none is open (a run that already ended in a game
+    /// over isn't ended again by leaving its game-over screen).
+    pub(crate) fn log_run_end(&mut self, reason: RunEnd) {
+        if !self.event_log.as_ref().is_some_and(|log| log.run_open) {
+            return;
+        }
+        self.log_event(GameEvent::RunEnd {
+            reason,
+            score: self.final_score(),
+            base_score: self.score,
+        });
+        if let Some(log) = &mut self.event_log {
+            log.run_open = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_serialise_as_one_json_object() {
+        let catch = GameEvent::Catch {
+            archetype: CrabType::TideBoss,
+            beat_offset: -0.0214,
+            train: 3,
+        };
+        assert_eq!(
+            catch.to_json(4.1834, 8),
+            "{\"t\":4.183,\"beat\":8,\"event\":\"catch\",\"archetype\":\"tide_boss\",\
+             \"beat_offset\":-0.021,\"train\":3}"
+        );
+        let steal = GameEvent::Steal {
+            rival: "Big \"Claw\" Bob".to_string(),
+            links: 4,
+        };
+        assert!(
+            steal
+                .to_json(1.0, 2)
+                .ends_with(",\"event\":\"steal\",\"rival\":\"Big \\\"Claw\\\" Bob\",\"links\":4}")
+        );
+        let end = GameEvent::RunEnd {
+            reason: RunEnd::QuitToMap,
+            score: 515,
+            base_score: 412,
+        };
+        assert!(
+            end.to_json(0.0, 0)
+                .ends_with("\"reason\":\"quit_to_map\",\"score\":515,\"base_score\":412}")
+        );
+    }
+
+    #[test]
+    fn beat_offset_is_signed_towards_the_nearest_beat() {
+        // Counting down to the next beat: 0.05s left is early, 0.05s past the last one is late.
+        assert!((beat_offset(0.05, 0.5) + 0.05).abs() < 1e-6);
+        assert!((beat_offset(0.45, 0.5) - 0.05).abs() < 1e-6);
+        assert_eq!(beat_offset(0.0, 0.5), 0.0);
+    }
+}
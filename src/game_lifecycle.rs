@@ -6,13 +6,14 @@
 //! menu/world-map/campaign/tutorial transitions. Pure structural move: behaviour
 //! is unchanged.
 
-use ggez::Context;
 use ggez::audio::SoundSource;
 use ggez::glam::Vec2;
 use rand::Rng;
 
 use crate::constants::*;
-use crate::levels::MapSize;
+use crate::event_log::RunEnd;
+use crate::levels::{Branch, MapSize};
+use crate::mutators::Mutators;
 use crate::npc_conga_train::NpcCongaTrain;
 use crate::spawnings::spawn_tutorial_crabs;
 use crate::state::{LassoPhase, MainState, WeatherState};
@@ -23,10 +24,22 @@ use crate::{pick_pen_pos, pick_tide_pools};
 
 impl MainState {
     pub(crate) fn reset_game(&mut self) {
-        // `get_levels()` always supplies campaign content, but retain this guard so a malformed
-        // future level source cannot cause a reset-path panic.
+        // Restarting a daily challenge replays the same beach, as practice. Restarting an endless
+        // run starts a new one, not an arcade tour.
+        if let Some(daily) = &self.daily {
+            let date = daily.date.clone();
+            self.start_daily_on(&date);
+            return;
+        }
+        if self.endless.is_some() {
+            self.start_endless();
+            return;
+        }
+        // Loading refuses an empty `resources/levels/`, but retain this guard so a reset can never
+        // panic on a missing level.
         if let Some(level) = self.levels.first() {
             self.reset_game_at(0, level.map_size);
+            self.recall_nemeses();
         }
     }
 
@@ -69,7 +82,7 @@ impl MainState {
         self.world_height = new_height;
     }
 
-    fn reset_game_at(&mut self, level_index: usize, map_size: MapSize) {
+    pub(crate) fn reset_game_at(&mut self, level_index: usize, map_size: MapSize) {
         self.current_level = level_index;
         self.level_title = self
             .levels
@@ -97,6 +110,8 @@ impl MainState {
             height / 2.0 - PLAYER_SIZE / 2.0,
         );
         self.crabs = Vec::default();
+        self.forget_crab_positions();
+        self.rebuild_crab_index();
         self.chain_snap_cooldown = 0.0;
         self.position_history.clear();
         let center = Vec2::new(
@@ -115,8 +130,15 @@ impl MainState {
         self.banked_crabs_run = 0;
         self.shells_cracked_run = 0;
         self.hold_train_timer = 0.0;
+        self.outgrow_timer = 0.0;
+        self.dethroned_kings.clear();
+        self.escorted_run = 0;
         self.level_complete = false;
         self.level_complete_timer = 0.0;
+        self.longest_train_run = 0;
+        self.snaps_run = 0;
+        self.boss_arrived_at = None;
+        self.boss_catch_times.clear();
         self.crabs_stolen_by_npc = 0;
         self.max_single_steal_by_npc = 0;
         self.crabs_stolen_by_player = 0;
@@ -201,6 +223,8 @@ impl MainState {
         self.lasso_origin = Vec2::ZERO;
         self.lasso_charge = 0.0;
         self.lasso_mouse_down = false;
+        self.lasso_key_down = false;
+        self.pause = None;
         self.lasso_spin = 0.0;
         self.lasso_on_beat_bonus = 1.0;
         self.whistle_active = 0.0;
@@ -293,6 +317,7 @@ impl MainState {
         self.game_over = false;
         self.run_recorded = false;
         self.run_is_new_best = false;
+        self.last_entry = None;
         self.boost_timer = 0.0;
         self.boost_cooldown = 0.0;
         self.sprint_stamina = SPRINT_STAMINA_MAX;
@@ -300,14 +325,17 @@ impl MainState {
         if !self.in_campaign {
             self.arcade_stage = 1;
         }
+        self.apply_mutators();
         self.start_current_pattern((width, height));
     }
 
     /// Open the campaign world map. Creates it on first visit; subsequent visits reuse the same
     /// instance so node completion persists across runs.
-    pub(crate) fn enter_world_map(&mut self, _ctx: &mut Context) {
+    pub(crate) fn enter_world_map(&mut self) {
         if self.world_map.is_none() {
-            self.world_map = Some(WorldMap::new());
+            let mut map = WorldMap::new(&self.levels);
+            map.apply_progress(&self.map_progress);
+            self.world_map = Some(map);
         }
         self.stop_level_audio();
         self.show_instructions = false;
@@ -315,12 +343,15 @@ impl MainState {
         self.show_world_map = true;
         self.game_over = false;
         self.in_campaign = false;
+        self.end_daily();
+        self.endless = None;
+        self.mutators = Mutators::default();
         // A calm ambient pad for the campaign map — a breather moment between levels.
         let _ = self.sounds.world_map_pad.play();
     }
 
     /// Stop sounds that belong to a campaign level before returning to the world map.
-    fn stop_level_audio(&mut self) {
+    pub(crate) fn stop_level_audio(&mut self) {
         for music in &self.sounds.action_music {
             music.pause();
         }
@@ -357,11 +388,15 @@ impl MainState {
 
     /// Return to the title menu without terminating the application.
     pub(crate) fn return_to_main_menu(&mut self) {
+        self.log_run_end(RunEnd::QuitToTitle);
         if let Some(map) = &mut self.world_map {
             map.cancel_skip();
         }
         self.stop_level_audio();
         self.sounds.world_map_pad.pause();
+        self.end_daily();
+        self.endless = None;
+        self.mutators = Mutators::default();
         self.reset_game();
         self.show_world_map = false;
         self.show_instructions = true;
@@ -403,6 +438,11 @@ impl MainState {
         self.reset_game_at_level(level_index);
         self.show_world_map = false;
         self.in_campaign = true;
+        self.crown_goal_king();
+        self.recall_nemeses();
+        if self.campaign_branch() == Some(Branch::Boss) {
+            self.next_boss_score = 0;
+        }
     }
 
     /// Called when a campaign run ends — returns to the world map screen. `won` gates progression:
@@ -412,10 +452,15 @@ impl MainState {
     /// it. Without this gate a loss also unlocked the next level, defeating the point of #182.
     /// Career stats are NOT updated here (that path stays in `record_run`).
     pub(crate) fn return_to_world_map(&mut self, won: bool) {
+        self.log_run_end(if won { RunEnd::Won } else { RunEnd::QuitToMap });
         if won {
+            self.record_on_board();
             if let Some(map) = &mut self.world_map {
-                map.complete_selected();
+                // Tutorials have no meaningful score; only their completion time is kept.
+                let score = map.selected_level_index().map(|_| self.score);
+                map.complete_selected(score, self.win_time, self.longest_train_run);
             }
+            self.save_map_progress();
         }
         self.game_over = false;
         self.show_world_map = true;
@@ -424,6 +469,14 @@ impl MainState {
         let _ = self.sounds.world_map_pad.play();
     }
 
+    /// Copy the world map's progress into the save and write it.
+    pub(crate) fn save_map_progress(&mut self) {
+        if let Some(map) = &self.world_map {
+            self.map_progress = map.progress();
+            self.save_career();
+        }
+    }
+
     /// Enter a scripted "How to Play" tutorial session. Starts from a clean run state (so no
     /// leftover herd/boss), then constrains it into a tiny sandbox: leave the spawn patterns alone
     /// (the tutorial gates them off in update) and drop in just a handful of plain crabs to catch.
@@ -437,6 +490,7 @@ impl MainState {
         self.crabs.clear();
         self.crabs =
             spawn_tutorial_crabs(kind, 6, (self.width, self.height), &mut crate::rng::rng());
+        self.rebuild_crab_index();
         // Tutorial worlds are exactly one viewport, so the player and scripted crab ring start
         // together in the centre without any camera travel.
         let tut_center = Vec2::new(
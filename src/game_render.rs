@@ -9,8 +9,6 @@
 use ggez::audio::SoundSource;
 use ggez::glam::Vec2;
 use ggez::graphics::{BlendMode, Canvas, Color, DrawParam, Rect, Sampler, Text};
-use ggez::input::keyboard::KeyCode;
-use ggez::winit::keyboard::PhysicalKey;
 use ggez::{Context, GameResult};
 
 use crate::constants::*;
@@ -36,7 +34,7 @@ use crate::graphics::{
 };
 use crate::hud_cache::*;
 use crate::state::*;
-use crate::{how_to_play_body_text, menu, panic_snap_links};
+use crate::{how_to_play_body_text, menu};
 
 impl MainState {
     fn draw_startup_logo(
@@ -239,7 +237,7 @@ impl MainState {
                     .color(Color::from_rgb(245, 238, 210)),
             );
 
-            let body = how_to_play_body_text();
+            let body = how_to_play_body_text(&self.bindings);
             let mut text = Text::new(body);
             text.set_scale(27.0);
             canvas.draw(
@@ -306,12 +304,12 @@ impl MainState {
 
         // Select texture for current level.
         let _texture = match self.level_textures[self.current_level] {
-            LevelTexture::Grass => &self.textures.grass,
-            LevelTexture::Sand => &self.textures.sand,
+            LevelTexture::Grass => &self.gfx().textures.grass,
+            LevelTexture::Sand => &self.gfx().textures.sand,
         };
 
         // Biome for the current zone (clamped so a finished run doesn't index past the end).
-        let biome = self.levels[self.current_level.min(self.levels.len() - 1)].biome;
+        let biome = &self.level().biome;
         let (tr, tg, tb) = biome.tint;
 
         // Fold the day/night grade into the ground tint so the whole world shifts together with the
@@ -641,7 +639,7 @@ impl MainState {
                 // Use the SAME severity function the panic snap uses, so the readout can't lie:
                 // a longer train shows a bigger at-risk number precisely because a snap tears more
                 // (and pricier, since tri() is triangular) tail links off it.
-                let keep = n.saturating_sub(panic_snap_links(n)).max(1);
+                let keep = n.saturating_sub(self.panic_snap_count(n)).max(1);
                 // Marginal loss folds in the arrangement bonus too: a snap tears off tail links,
                 // which destroys every same-type bond in the torn region (and the one straddling the
                 // cut), so the pricier a train's tail arrangement, the more a snap costs — mirroring
@@ -998,7 +996,7 @@ impl MainState {
             ctx,
             canvas,
             self.player_pos + jam_shimmy,
-            &self.textures.player,
+            &self.gfx().textures.player,
             self.player_vel,
             self.beat_intensity,
             self.time_elapsed,
@@ -1041,12 +1039,7 @@ impl MainState {
             }
         });
 
-        let sprinting = (ctx
-            .keyboard
-            .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ShiftLeft))
-            || ctx
-                .keyboard
-                .is_physical_key_pressed(&PhysicalKey::Code(KeyCode::ShiftRight)))
+        let sprinting = self.action_held(crate::bindings::Action::Sprint)
             && self.sprint_stamina > 0.0
             && self.boost_timer <= 0.0;
 
@@ -1449,8 +1442,11 @@ impl MainState {
     pub(crate) fn draw_scene(&mut self, ctx: &mut Context) -> GameResult {
         let width = self.width;
         let height = self.height;
-        let mut canvas =
-            Canvas::from_image(ctx, self.scene_image.clone(), Color::from_rgb(0, 0, 0));
+        let mut canvas = Canvas::from_image(
+            ctx,
+            self.gfx().scene_image.clone(),
+            Color::from_rgb(0, 0, 0),
+        );
         let shake_ox = self.screen_shake_offset.x;
         let shake_oy = self.screen_shake_offset.y;
         // Zoom punch: shrink the visible world rect (magnify) around the player so they stay
@@ -1486,7 +1482,17 @@ impl MainState {
                 if self.sounds.intro_music.playing() {
                     self.sounds.intro_music.pause();
                 }
-                draw_world_map(ctx, &mut canvas, map, width, height, self.menu_time)?;
+                let node = map.selected_id();
+                let high_scores = (self.leaderboards.table(&node), self.last_rank_on(&node));
+                draw_world_map(
+                    ctx,
+                    &mut canvas,
+                    map,
+                    high_scores,
+                    width,
+                    height,
+                    self.menu_time,
+                )?;
                 canvas.finish(ctx)?;
                 return Ok(());
             }
@@ -1526,7 +1532,7 @@ impl MainState {
             if self.sounds.intro_music.playing() {
                 self.sounds.intro_music.pause();
             }
-            if self.hitstop_timer <= 0.0 {
+            if self.hitstop_timer <= 0.0 && self.pause.is_none() {
                 let active_music = self.action_music_index();
                 let music = &mut self.sounds.action_music[active_music];
                 if music.stopped() {
@@ -1546,6 +1552,10 @@ impl MainState {
                 canvas.set_screen_coordinates(Rect::new(0.0, 0.0, width, height));
                 self.draw_upgrade_screen(ctx, &mut canvas)?;
             }
+            if self.pause.is_some() {
+                canvas.set_screen_coordinates(Rect::new(0.0, 0.0, width, height));
+                self.draw_pause_overlay(ctx, &mut canvas)?;
+            }
         }
         canvas.finish(ctx)?;
         Ok(())
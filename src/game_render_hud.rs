@@ -11,11 +11,12 @@ use ggez::{Context, GameResult};
 
 use crate::constants::*;
 use crate::graphics::{
-    cached_stroke_rect, draw_beat_indicator, draw_crab_radar, draw_flashlight,
-    draw_groove_vignette, draw_reef_phrase, draw_wave_telegraph, draw_weather, unit_square,
+    ToolRoster, draw_day_weather_hud, draw_king_loadout, draw_minimap, draw_tool_roster,
+    minimap_dimensions,
 };
 use crate::graphics::{
-    draw_day_weather_hud, draw_king_loadout, draw_minimap, draw_tool_roster, minimap_dimensions,
+    cached_stroke_rect, draw_beat_indicator, draw_crab_radar, draw_flashlight,
+    draw_groove_vignette, draw_reef_phrase, draw_wave_telegraph, draw_weather, unit_square,
 };
 use crate::hud_cache::*;
 use crate::spawnings::SpawnPattern;
@@ -183,24 +184,27 @@ impl MainState {
                 canvas,
                 width,
                 height,
-                self.whistle_cooldown,
-                crate::WHISTLE_COOLDOWN,
-                self.stomp_cooldown,
-                crate::STOMP_COOLDOWN,
-                self.beat_wave_active,
-                self.call_cooldown,
-                crate::CALL_COOLDOWN,
-                self.boost_cooldown,
-                !matches!(self.lasso_phase, LassoPhase::Idle),
-                lasso_useful,
-                whistle_useful,
-                stomp_useful,
-                wave_useful,
-                call_useful,
-                self.groove,
-                self.time_elapsed,
-                1.0 - (self.beat_timer / self.beat_interval).clamp(0.0, 1.0),
-                self.on_beat_action(),
+                &ToolRoster {
+                    bindings: &self.bindings,
+                    whistle_cd: self.whistle_cooldown,
+                    whistle_max: crate::WHISTLE_COOLDOWN,
+                    stomp_cd: self.stomp_cooldown,
+                    stomp_max: crate::STOMP_COOLDOWN,
+                    wave_busy: self.beat_wave_active,
+                    call_cd: self.call_cooldown,
+                    call_max: crate::CALL_COOLDOWN,
+                    boost_cd: self.boost_cooldown,
+                    lasso_busy: !matches!(self.lasso_phase, LassoPhase::Idle),
+                    lasso_useful,
+                    whistle_useful,
+                    stomp_useful,
+                    wave_useful,
+                    call_useful,
+                    groove: self.groove,
+                    time: self.time_elapsed,
+                    beat_progress: 1.0 - (self.beat_timer / self.beat_interval).clamp(0.0, 1.0),
+                    on_beat: self.on_beat_action(),
+                },
             )?;
         }
 
@@ -236,24 +240,67 @@ impl MainState {
         } else {
             0
         };
+        // The stats box stacks its lines 20 px apart: score, the rhythm bonus once there is one,
+        // the campaign goal or endless overrun meter, then the run's mutators.
+        let goal_line = (self.in_campaign && self.tutorial.is_none()) || self.endless.is_some();
+        let goal_y = if self.rhythm_bonus_score > 0 {
+            50.0
+        } else {
+            30.0
+        };
+        // A long mutator line widens the box, so measure it first.
+        let mutators_width = if self.mutators.is_empty() {
+            0.0
+        } else {
+            MUTATORS_HUD_CACHE.with(|c| -> GameResult<f32> {
+                let mut cache = c.borrow_mut();
+                if !matches!(&*cache, Some((k, ..)) if *k == self.mutators) {
+                    let txt = Text::new(format!("MUTATORS  {}", self.mutators.summary()));
+                    let w = txt.measure(ctx)?.x;
+                    *cache = Some((self.mutators, txt, w));
+                }
+                Ok(cache.as_ref().unwrap().2)
+            })?
+        };
+        // The rivals' banked scores, once one of them has banked (see rival_economy.rs).
+        let rivals_line = self.npc_trains.iter().any(|train| train.rival_score > 0);
+        let rivals_width = if rivals_line {
+            RIVALS_HUD_CACHE.with(|c| -> GameResult<f32> {
+                let mut cache = c.borrow_mut();
+                if !matches!(&*cache, Some((k, ..)) if *k == self.rival_crabs_banked) {
+                    let standings: Vec<_> = self
+                        .rival_standings()
+                        .iter()
+                        .map(|(name, score)| format!("{name} {score}"))
+                        .collect();
+                    let txt = Text::new(format!("RIVALS  {}", standings.join(" · ")));
+                    let w = txt.measure(ctx)?.x;
+                    *cache = Some((self.rival_crabs_banked, txt, w));
+                }
+                Ok(cache.as_ref().unwrap().2)
+            })?
+        } else {
+            0.0
+        };
+        let stats_width = 370.0_f32
+            .max(mutators_width + 12.0)
+            .max(rivals_width + 12.0);
         let stats_height =
             30.0 + if self.rhythm_bonus_score > 0 {
                 20.0
             } else {
                 0.0
-            } + if self.in_campaign && self.tutorial.is_none() {
-                20.0
-            } else {
-                0.0
-            };
+            } + if goal_line { 20.0 } else { 0.0 }
+                + if self.mutators.is_empty() { 0.0 } else { 20.0 }
+                + if rivals_line { 20.0 } else { 0.0 };
         canvas.draw(
             unit_square(ctx)?,
             DrawParam::default()
                 .dest(Vec2::new(5.0, 5.0))
-                .scale(Vec2::new(370.0, stats_height))
+                .scale(Vec2::new(stats_width, stats_height))
                 .color(Color::from_rgba(8, 14, 30, 175)),
         );
-        let stats_border = cached_stroke_rect(ctx, 370.0, stats_height, 1.0)?;
+        let stats_border = cached_stroke_rect(ctx, stats_width, stats_height, 1.0)?;
         canvas.draw(
             &stats_border,
             DrawParam::default()
@@ -333,56 +380,106 @@ impl MainState {
         // Campaign goal counter, under the score/rhythm lines: the live progress toward the
         // level's win condition, so the player always knows where they stand against the goal.
         // Only shows during a campaign run (goal read from the launched world-map node).
-        if self.in_campaign && self.tutorial.is_none() {
-            if let Some(cond) = self
-                .world_map
-                .as_ref()
-                .and_then(|m| m.selected_level_index())
-                .and_then(|i| self.levels.get(i))
-                .map(|l| l.win_condition)
-            {
-                // Bucketed to whole seconds (matches the `{:.0}s` display) so the key only moves
-                // as often as the rendered text actually would.
-                let hold_key = self.hold_train_timer.round() as i32;
-                let key = (
-                    self.level_complete,
-                    cond,
-                    self.banked_crabs_run,
-                    self.chain_count,
-                    self.shells_cracked_run,
-                    hold_key,
-                );
-                CAMPAIGN_GOAL_CACHE.with(|c| {
-                    let mut cache = c.borrow_mut();
-                    let needs_rebuild = match &*cache {
-                        Some((k, _)) => *k != key,
-                        None => true,
-                    };
-                    if needs_rebuild {
-                        let goal = if self.level_complete {
-                            "LEVEL COMPLETE!".to_string()
-                        } else {
-                            cond.progress_text(
-                                self.banked_crabs_run,
-                                self.chain_count,
-                                self.shells_cracked_run,
-                                self.hold_train_timer,
-                            )
-                        };
-                        let txt = Text::new(goal);
-                        *cache = Some((key, txt));
-                    }
-                    let col = if self.level_complete {
-                        Color::from_rgb(255, 230, 80) // celebratory gold once the goal lands
+        if let Some(cond) = self.campaign_goal() {
+            // The streak clocks are bucketed to whole seconds (matches the `{:.0}s` display)
+            // so the key only moves as often as the rendered text actually would.
+            let progress = self.goal_progress(&cond);
+            let mut key_progress = progress.clone();
+            key_progress.hold_secs = key_progress.hold_secs.round();
+            key_progress.outgrow_secs = key_progress.outgrow_secs.round();
+            let key = (self.level_complete, cond, key_progress);
+            CAMPAIGN_GOAL_CACHE.with(|c| {
+                let mut cache = c.borrow_mut();
+                let needs_rebuild = match &*cache {
+                    Some((k, _)) => *k != key,
+                    None => true,
+                };
+                if needs_rebuild {
+                    let goal = if self.level_complete {
+                        "LEVEL COMPLETE!".to_string()
                     } else {
-                        Color::from_rgb(140, 235, 255) // cool goal-teal while it's in progress
+                        key.1.progress_text(&progress)
                     };
-                    canvas.draw(
-                        &cache.as_ref().unwrap().1,
-                        DrawParam::default().dest(Vec2::new(10.0, 50.0)).color(col),
-                    );
-                });
-            }
+                    let txt = Text::new(goal);
+                    *cache = Some((key, txt));
+                }
+                let col = if self.level_complete {
+                    Color::from_rgb(255, 230, 80) // celebratory gold once the goal lands
+                } else {
+                    Color::from_rgb(140, 235, 255) // cool goal-teal while it's in progress
+                };
+                canvas.draw(
+                    &cache.as_ref().unwrap().1,
+                    DrawParam::default()
+                        .dest(Vec2::new(10.0, goal_y))
+                        .color(col),
+                );
+            });
+        }
+
+        // Endless has no goal to chase, only the death line: how close the loose herd is to
+        // overrunning the run. Same slot as the campaign goal, turning red in the last quarter. A
+        // daily challenge adds its clock.
+        if self.endless.is_some() {
+            ENDLESS_OVERRUN_CACHE.with(|c| {
+                let mut cache = c.borrow_mut();
+                let secs_left = self.daily_time_left().map(|left| left.ceil() as u32);
+                let key = (self.free_crab_count, secs_left);
+                if !matches!(&*cache, Some((k, _)) if *k == key) {
+                    let clock = secs_left.map_or(String::new(), |secs| {
+                        format!("   ·   {}:{:02} left", secs / 60, secs % 60)
+                    });
+                    let txt = Text::new(format!(
+                        "OVERRUN  Loose crabs: {} / {}{clock}",
+                        self.free_crab_count, OVERRUN_FREE_CRABS
+                    ));
+                    *cache = Some((key, txt));
+                }
+                let col = if self.overrun_margin() <= OVERRUN_FREE_CRABS / 4
+                    || secs_left.is_some_and(|secs| secs <= 15)
+                {
+                    Color::from_rgb(255, 110, 90)
+                } else {
+                    Color::from_rgb(140, 235, 255)
+                };
+                canvas.draw(
+                    &cache.as_ref().unwrap().1,
+                    DrawParam::default()
+                        .dest(Vec2::new(10.0, goal_y))
+                        .color(col),
+                );
+            });
+        }
+
+        // The run's mutators and what they multiply the score by, then the rival standings, last
+        // in the stats box.
+        let mutators_y = if goal_line { goal_y + 20.0 } else { goal_y };
+        if !self.mutators.is_empty() {
+            MUTATORS_HUD_CACHE.with(|c| {
+                let cache = c.borrow();
+                canvas.draw(
+                    &cache.as_ref().unwrap().1,
+                    DrawParam::default()
+                        .dest(Vec2::new(10.0, mutators_y))
+                        .color(Color::from_rgb(255, 170, 90)),
+                );
+            });
+        }
+        if rivals_line {
+            RIVALS_HUD_CACHE.with(|c| {
+                let cache = c.borrow();
+                let y = if self.mutators.is_empty() {
+                    mutators_y
+                } else {
+                    mutators_y + 20.0
+                };
+                canvas.draw(
+                    &cache.as_ref().unwrap().1,
+                    DrawParam::default()
+                        .dest(Vec2::new(10.0, y))
+                        .color(Color::from_rgb(255, 200, 80)),
+                );
+            });
         }
 
         // Debug-only perf overlay, top-right: avg/worst frame time + fps over the last ~2s
@@ -733,9 +830,9 @@ impl MainState {
                     let mut label = Text::new(format!(
                         "Stage {}: {} | {} | Difficulty: {}",
                         self.arcade_stage,
-                        self.levels[self.current_level].title,
-                        self.levels[self.current_level].description,
-                        self.levels[self.current_level].difficulty
+                        self.level().title,
+                        self.level().description,
+                        self.level().difficulty
                     ));
                     label.set_scale(13.0);
                     let dims = label.measure(ctx)?;
@@ -776,7 +873,7 @@ impl MainState {
         }
 
         if self.debug_mode {
-            let level = &self.levels[self.current_level];
+            let level = self.level();
             let pat = &level.patterns[self.current_pattern];
             let pattern_name = match &pat.pattern {
                 SpawnPattern::UniformRandom => "UniformRandom",
@@ -1123,8 +1220,8 @@ impl MainState {
                 self.flashlight.aim_dir,
                 self.time_since_catch,
                 &self.flashlight,
-                &self.flashlight_shader,
-                &self.flashlight_cone_image,
+                &self.gfx().flashlight_shader,
+                &self.gfx().flashlight_cone_image,
                 self.width,
                 self.height,
                 self.camera_origin,
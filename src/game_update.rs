@@ -8,9 +8,13 @@
 
 use ggez::conf::FullscreenType;
 use ggez::glam::Vec2;
-use ggez::winit::keyboard::PhysicalKey;
 use rand::Rng;
 
+use crate::bindings::Action;
+use crate::event_log::{BossPhase, GameEvent, RunEnd};
+use crate::levels::Branch;
+use crate::profiler::Section;
+use crate::simulation::InputFrame;
 use crate::*;
 
 pub(crate) fn treasure_groove_level(current: f32, on_beat: bool) -> f32 {
@@ -32,17 +36,102 @@ impl MainState {
             }
         }
 
+        // Debug builds pick up edited level files (see level_reload.rs); a bot run keeps the
+        // levels it started with.
+        #[cfg(debug_assertions)]
+        if self.bot.is_none() {
+            self.poll_level_files();
+        }
+
+        // Everything past this point is window-agnostic: gather input, advance the simulation,
+        // then act on whatever it asked of the window (quit, bot exit code).
+        if self.bot.is_some() {
+            // Bot runs keep one step per rendered frame: headless they run as fast as the machine
+            // allows, and `frame_dt` already pins their timestep.
+            self.prev_positions = None;
+            self.next_input(ctx);
+            let raw_dt = self.frame_dt(ctx);
+            self.step(raw_dt)?;
+        } else {
+            // Interactive play: spend the frame's wall-clock time in whole fixed steps so the
+            // game plays the same at any frame rate; the remainder carries over and sets how far
+            // `draw` interpolates toward the next step. A replay steps at its recorded `dt`.
+            let step_dt = self.bot_fixed_dt.unwrap_or(SIM_DT);
+            let frame_time = ctx.time.delta().as_secs_f32().min(MAX_FRAME_TIME);
+            self.sim_accumulator += frame_time;
+            while self.sim_accumulator >= step_dt {
+                self.sim_accumulator -= step_dt;
+                self.capture_prev_positions();
+                self.next_input(ctx);
+                self.step(step_dt)?;
+                if self.quit_requested {
+                    break;
+                }
+            }
+            self.interp_alpha = self.sim_accumulator / step_dt;
+        }
+        if self.quit_requested {
+            ctx.request_quit();
+        }
+        if let Some(failed) = self
+            .bot
+            .as_ref()
+            .filter(|b| b.done)
+            .map(|b| b.failed.is_some())
+        {
+            self.write_bot_reports();
+            self.log_run_end(RunEnd::Closed);
+            std::process::exit(if failed { 1 } else { 0 });
+        }
+        Ok(())
+    }
+
+    /// Load `self.input` for the coming step — the next replay frame while one is playing, the
+    /// live keyboard and gamepad otherwise — and append it to the recording if there is one. Live
+    /// key and mouse events were already handled as they arrived; a replay frame delivers its own.
+    fn next_input(&mut self, ctx: &Context) {
+        match self.replay.as_mut().map(|frames| frames.pop_front()) {
+            Some(Some(frame)) => self.apply_input(&frame),
+            Some(None) => {
+                println!("Replay finished; the keyboard has control.");
+                self.replay = None;
+                self.input = self.live_input_frame(ctx);
+            }
+            None => self.input = self.live_input_frame(ctx),
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.finish_frame(&self.input);
+        }
+    }
+
+    fn live_input_frame(&self, ctx: &Context) -> InputFrame {
+        let mut pad = self.live_pad.clone();
+        pad.read_trigger(&ctx.gamepad);
+        InputFrame {
+            pad,
+            ..InputFrame::from_keyboard(&ctx.keyboard)
+        }
+    }
+
+    /// One simulation step of `raw_dt` seconds (unscaled, unclamped) against the current
+    /// `self.input`. Needs no `Context`, so the headless `Simulation` drives exactly this.
+    pub(crate) fn step(&mut self, raw_dt: f32) -> GameResult {
+        if let Some(bot) = &mut self.bot {
+            bot.frames += 1;
+        }
+        self.profiler.count_step();
         if self.show_instructions || self.show_world_map || self.game_over {
             // The run just ended — bank its result into the persistent career exactly once.
             // Every game_over set-site funnels through here on the next tick, so one guarded
             // call covers them all.
             if self.game_over {
                 self.record_run();
+                self.log_run_end(RunEnd::GameOver);
             }
             // Keep a lightweight clock ticking so the title/menu screen can animate its
             // background, marching crabs, and pulsing prompt even though the main simulation
             // is paused here.
-            let mdt = self.frame_dt(ctx);
+            let mdt = raw_dt;
             self.menu_time += mdt;
             if self.show_instructions
                 && !self.show_how_to_play_text
@@ -69,7 +158,7 @@ impl MainState {
             // which hung campaign_tutorial the instant its tutorial returned to the world map.
             if self.bot.is_some() {
                 self.time_elapsed += mdt.min(0.1) * self.time_scale;
-                self.bot_fire_events(ctx);
+                self.bot_fire_events();
                 self.bot_check_done();
             }
             // Decay the perk-shop buy/deny flashes so they're a brief pop, not a stuck glow.
@@ -82,11 +171,18 @@ impl MainState {
             return Ok(());
         }
 
+        // Paused mid-run (see pause.rs): nothing below runs, the beat clock included, until the
+        // resume count-in lands on a downbeat.
+        if self.step_pause(raw_dt) {
+            return Ok(());
+        }
+        self.log_run_start();
+
         // Clamp raw delta before scaling to prevent a large first-frame hitch (shader compile,
         // audio decode, BPM detection) from collapsing the bot script's timed hold/release
         // sequence — and to guard against the general "spiral of death" when the game falls behind.
         // update_weather uses its own raw delta below and is deliberately left unclamped.
-        let mut dt = self.frame_dt(ctx).min(0.1) * self.time_scale;
+        let mut dt = raw_dt.min(0.1) * self.time_scale;
 
         // Clear strong-match hit buffers so draw_game sees only THIS frame's events.
         self.beam_hermit_hits_buf.clear();
@@ -159,7 +255,7 @@ impl MainState {
 
         // Advance the master groove before cinematic slow-motion dilates `dt`. World motion can
         // stretch for drama, but the backing loop, live percussion and tool windows stay locked.
-        self.update_master_beat(ctx, dt);
+        self.update_master_beat(dt);
 
         // Cinematic slow-motion on the biggest climax moments (boss catch, Downbeat Slam). The
         // timer decays on REAL time so the effect is always the same wall-clock length, but the
@@ -177,10 +273,11 @@ impl MainState {
 
         self.time_elapsed += dt;
         self.time_since_catch += dt;
+        self.longest_train_run = self.longest_train_run.max(self.chain_count);
 
         // Bot playtest harness tick: fire scripted events, check assertions, exit on completion.
         if self.bot.is_some() {
-            self.bot_fire_events(ctx);
+            self.bot_fire_events();
 
             // Seek-catch autopilot (see BotAction::SeekCatch): steering toward the nearest target is
             // handled in handle_player_movement; here we fire the tools. The whistle charms a
@@ -201,7 +298,10 @@ impl MainState {
                 if self.whistle_cooldown <= 0.0 {
                     if let Some(target) = self.nearest_catchable_crab_pos() {
                         if center.distance(target) < 260.0 {
-                            controls::handle_key_down_event(self, ctx, Some(KeyCode::KeyE));
+                            controls::handle_key_down_event(
+                                self,
+                                Some(self.bindings.key(Action::Whistle)),
+                            );
                         }
                     }
                 }
@@ -211,18 +311,24 @@ impl MainState {
                 if self.stomp_cooldown <= 0.0 {
                     if let Some(target) = self.nearest_seek_target_pos() {
                         if center.distance(target) < STOMP_MAX_RADIUS {
-                            controls::handle_key_down_event(self, ctx, Some(KeyCode::KeyR));
+                            controls::handle_key_down_event(
+                                self,
+                                Some(self.bindings.key(Action::Stomp)),
+                            );
                         }
                     }
                 }
             }
 
             self.bot_check_done();
+            if self.bot.as_ref().is_some_and(|b| b.done) {
+                return Ok(());
+            }
         }
 
         // Weather + day/night ambience. Runs on REAL delta (not the slowmo-dilated dt) so the
         // world clock and weather evolve at a steady wall-clock pace regardless of bullet-time.
-        self.update_weather(self.frame_dt(ctx));
+        self.update_weather(raw_dt);
 
         // Tutorial session bookkeeping: keep the sandbox stocked, detect the pass condition, and
         // run a short celebratory hold before handing control back to the title screen. Kept here
@@ -230,7 +336,7 @@ impl MainState {
         if self.tutorial.is_some() {
             // Real (undilated) time for the exit hold so the celebration is a fixed wall-clock
             // length regardless of any slow-mo the catch triggered.
-            let real_dt = self.frame_dt(ctx);
+            let real_dt = raw_dt;
             // If the learner clears the whole sandbox before passing, quietly restock so they can
             // keep practising instead of standing in an empty field. The "cleared" test differs by
             // scenario: BeatTiming crabs stay on the field once caught (nothing removes them), so
@@ -253,6 +359,7 @@ impl MainState {
                 TutorialKind::LassoGrab => self.crabs.iter().all(|c| c.caught),
             };
             if !completed && needs_restock {
+                self.forget_crab_positions();
                 self.crabs = spawn_tutorial_crabs(
                     tut_kind,
                     6,
@@ -269,6 +376,7 @@ impl MainState {
                     // map so the player can pick the next node. Otherwise go back to the title
                     // screen. Either way we never touch game_over, so the career is untouched.
                     self.tutorial = None;
+                    self.log_run_end(RunEnd::Won);
                     if self.in_campaign {
                         // Reached only when the tutorial was PASSED (tutorials have no game-over),
                         // so this is a genuine win — complete the node and unlock the next.
@@ -283,6 +391,7 @@ impl MainState {
                 t.completed = true;
                 t.pass_glow = 0.0;
                 t.exit_timer = 2.2;
+                self.win_time = self.time_elapsed;
                 let center = self.player_pos + Vec2::new(PLAYER_SIZE / 2.0, PLAYER_SIZE / 2.0);
                 self.floating_texts.spawn(
                     "TUTORIAL PASSED!".to_string(),
@@ -566,7 +675,8 @@ impl MainState {
         // The playfield (world) is larger than the viewport; movement, spawning and clamping all
         // happen in world space. The camera (computed below and in draw) maps it back to the screen.
         let area = (self.world_width, self.world_height);
-        handle_player_movement(self, ctx, dt, SPEED, area);
+        self.apply_pad_aim();
+        handle_player_movement(self, dt, SPEED, area);
 
         // Pirate treasure is a rare detour: it appears far enough away to route toward, then grades
         // the pickup with the same tight window as catches. A late grab still protects half a meter,
@@ -641,16 +751,19 @@ impl MainState {
             }
         }
 
-        // Drum Roll (hold T): poll the held key here rather than off the key-down event, since the
-        // event fires unreliably on key-repeat and we need a clean "held across beats" charge. The
-        // per-beat hit counting lives in the beat handler; here we only edge-detect press/release
-        // and drive the timers. Releasing after landing at least one on-beat roll hit FIRES a
-        // focused beam blast; releasing with nothing charged just cancels quietly.
-        let t_held = !self.show_instructions
-            && !self.game_over
-            && ctx
-                .keyboard
-                .is_physical_key_pressed(&PhysicalKey::Code(ggez::input::keyboard::KeyCode::KeyT));
+        // Keyboard lasso: key-down starts the wind-up (see handle_key_down_event); letting go of
+        // the key is the throw, polled here the same way as the drum roll below.
+        if self.lasso_key_down && !self.action_held(Action::Lasso) {
+            self.lasso_key_down = false;
+            self.release_lasso();
+        }
+
+        // Drum Roll (hold Call): poll the held key here rather than off the key-down event, since
+        // the event fires unreliably on key-repeat and we need a clean "held across beats" charge.
+        // The per-beat hit counting lives in the beat handler; here we only edge-detect
+        // press/release and drive the timers. Releasing after landing at least one on-beat roll hit
+        // FIRES a focused beam blast; releasing with nothing charged just cancels quietly.
+        let t_held = !self.show_instructions && !self.game_over && self.action_held(Action::Call);
         if !t_held && self.drum_roll_held {
             // Release edge: fire if we banked any roll hits, otherwise drop the (empty) charge.
             if self.drum_roll_hits > 0 {
@@ -745,8 +858,14 @@ impl MainState {
             }
         }
 
-        self.handle_crab_catching(ctx);
+        let started = self.profiler.start();
+        self.handle_crab_catching();
+        self.profiler.stop(Section::CrabCatching, started);
+        // Every proximity query from here to the end of the step reads this one snapshot.
+        self.rebuild_crab_index();
+        let started = self.profiler.start();
         self.update_crabs(dt, area);
+        self.profiler.stop(Section::UpdateCrabs, started);
 
         // Emergent herding: the conga body walls off panicking crabs, bouncing them back toward
         // the beam. Runs before the snap check so a crab deflected by the body never reaches the
@@ -788,7 +907,7 @@ impl MainState {
         self.damage_tail_in_fissures(dt);
 
         // Cash in the train: drive the conga head into the delivery pen to bank it for score.
-        self.try_deliver_train(ctx);
+        self.try_deliver_train();
         if self.deliver_flash > 0.0 {
             self.deliver_flash = (self.deliver_flash - dt * 1.6).max(0.0);
         }
@@ -986,10 +1105,12 @@ impl MainState {
         // Advance every active player-tool effect (Whistle pull, Groove Call lure, Stomp shockwave,
         // Lasso state machine) — see tool_resolve.rs. Runs here, after the visual-effect decay pass
         // and before the chain-tail catch, exactly where these blocks used to sit inline.
-        self.resolve_active_tools(ctx, dt);
+        let started = self.profiler.start();
+        self.resolve_active_tools(dt);
+        self.profiler.stop(Section::ActiveTools, started);
 
         // Chain tail can catch nearby free crabs
-        self.catch_by_chain(ctx);
+        self.catch_by_chain();
 
         // Fire join-pulse ripple through the conga train on every new catch
         if self.chain_join_ripple {
@@ -1020,7 +1141,14 @@ impl MainState {
         // King Crab boss: once the player is rolling, send in a rare oversized crab that must be
         // worn down under the flashlight before it can be caught. Only one at a time.
         if self.score >= self.next_boss_score && !boss_active {
-            self.next_boss_score = self.score + BOSS_SCORE_INTERVAL;
+            // A boss node's bosses come back to back.
+            self.next_boss_score = self.score
+                + if self.campaign_branch() == Some(Branch::Boss) {
+                    BOSS_GAUNTLET_INTERVAL
+                } else {
+                    BOSS_SCORE_INTERVAL
+                };
+            self.boss_arrived_at = Some(self.time_elapsed);
             // Rotate the boss archetypes so every run cycles through all five climax beats:
             // the King Crab (charge — route the train out of the lane), the Tide Boss (pulse — pull
             // the train back out of range), the Reef DJ (rhythm — its shell only drops when you
@@ -1028,14 +1156,7 @@ impl MainState {
             // stack one pound at a time before it escapes), and the Dancer King (chase — pin down
             // the beat-teleporting evader and bank its entranced court with an on-beat catch).
             // Cycling guarantees variety instead of RNG streaks.
-            // `current_level` can briefly point past the final pattern while the run is ending;
-            // use the last zone's boss metadata rather than letting a transition panic.
-            let boss_kind = self
-                .levels
-                .get(self.current_level)
-                .or_else(|| self.levels.last())
-                .map(|level| level.boss_for_encounter(self.next_boss_kind))
-                .unwrap_or(CrabType::Boss);
+            let boss_kind = self.level().boss_for_encounter(self.next_boss_kind);
             let (boss, title, hint, title_color) = match boss_kind {
                 CrabType::TideBoss => (
                     spawn_tide_boss(
@@ -1089,6 +1210,10 @@ impl MainState {
             };
             self.next_boss_kind = (self.next_boss_kind + 1) % 5;
             let bpos = boss.pos;
+            self.log_event(GameEvent::BossPhase {
+                boss: boss.crab_type,
+                phase: BossPhase::Arrived,
+            });
             self.crabs.push(boss);
             boss_active = true;
             free_crab_count += 1;
@@ -1113,11 +1238,17 @@ impl MainState {
         }
 
         // Spatial King Crab boss rumble + intensity-scaled music layers.
-        self.update_boss_and_music_audio(ctx, dt);
+        self.update_boss_and_music_audio(dt);
 
         // Game over if too many free crabs accumulate (overwhelmed). Reuses the single-pass tally
         // from above (plus the +1 for a boss spawned this frame) instead of a fresh linear scan.
-        if free_crab_count >= 160 {
+        self.free_crab_count = free_crab_count;
+        if free_crab_count >= OVERRUN_FREE_CRABS {
+            self.game_over = true;
+            return Ok(());
+        }
+        // A daily challenge also ends when its clock runs out.
+        if self.daily_time_left() == Some(0.0) {
             self.game_over = true;
             return Ok(());
         }
@@ -1133,30 +1264,14 @@ impl MainState {
                     // The WinCondition was met — complete the node and unlock the next level.
                     self.return_to_world_map(true);
                 }
-            } else if let Some(cond) = self
-                .world_map
-                .as_ref()
-                .and_then(|m| m.selected_level_index())
-                .and_then(|i| self.levels.get(i))
-                .map(|l| l.win_condition)
-            {
-                // HoldTrain streak clock: accumulate while the train holds the target, reset the
-                // instant it dips below — a single bad moment resets a long streak, by design.
-                if let crate::levels::WinCondition::HoldTrain { target, .. } = cond {
-                    if self.chain_count >= target {
-                        self.hold_train_timer += dt;
-                    } else {
-                        self.hold_train_timer = 0.0;
-                    }
-                }
-                if cond.met(
-                    self.banked_crabs_run,
-                    self.chain_count,
-                    self.shells_cracked_run,
-                    self.hold_train_timer,
-                ) {
+            } else if let Some(cond) = self.campaign_goal() {
+                // HoldTrain and OutgrowRivals keep streak clocks (see campaign_goal.rs).
+                self.tick_goal_clocks(&cond, dt);
+                if cond.met(&self.goal_progress(&cond)) {
                     self.level_complete = true;
                     self.level_complete_timer = 2.5;
+                    self.win_time = self.time_elapsed;
+                    self.log_event(GameEvent::WinConditionMet { goal: cond });
                     let center = self.player_pos + Vec2::splat(PLAYER_SIZE / 2.0);
                     self.floating_texts.spawn(
                         "LEVEL COMPLETE!".to_string(),
@@ -1208,10 +1323,14 @@ impl MainState {
         }
 
         // Advance the ambient NPC conga train.
+        let started = self.profiler.start();
         self.update_npc_trains(dt);
+        self.profiler.stop(Section::NpcTrains, started);
 
         // Ambient field audio: steal stings, NPC-train rumble/motifs, crab-theme loops.
-        self.update_ambient_audio(ctx, dt);
+        let started = self.profiler.start();
+        self.update_ambient_audio(dt);
+        self.profiler.stop(Section::AmbientAudio, started);
 
         // Recompute the camera every frame so both draw() and the mouse handlers (which run outside
         // draw) agree on the screen<->world mapping this frame.
@@ -0,0 +1,302 @@
+//! Gamepad input: analog movement and aim, tools on the face and shoulder buttons, D-pad menus.
+//!
+//! A pad feeds the same paths the keyboard does rather than growing its own. Its live state (both
+//! sticks, the right trigger's travel and the buttons held) is a [`PadState`] that `tick` snapshots
+//! into each step's `InputFrame` next to the held keys, so replays carry it and
+//! `handle_player_movement` reads the left stick in the same velocity code as WASD. A button
+//! *press* is an `InputEvent` like a key press, and is translated into one:
+//!
+//! - during a run, into the key currently bound to the button's [`Action`] (see [`pad_action`]),
+//!   so tool chords, the on-beat checks and the lasso's hold-and-release all work unchanged — hold
+//!   X and tap A and that's a Whistle chord;
+//! - on the title menu, pause menu, world map and game-over screen, into the menu keys (D-pad =
+//!   arrows, A = confirm, B = back, X = next loadout column).
+//!
+//! The right stick aims: pushed past the dead zone it places the aim point (the same world-space
+//! cursor the mouse drives) a lasso's reach out from the player, so a lasso wound up on the right
+//! trigger flies where the stick points.
+//!
+//! The triggers press and release as buttons, but the right one's travel is read too: a lasso
+//! wound up on it charges with how hard it's squeezed, from [`LIGHT_SQUEEZE_RATE`] of a held key's
+//! rate just past the click to the key's own rate fully down (see
+//! [`MainState::lasso_charge_rate`]). ggez passes the triggers on only as presses, so the travel is
+//! polled from gilrs each step ([`PadState::read_trigger`]) and recorded with the rest of the pad.
+//!
+//! Button names below follow gilrs' positional layout: South is A on an Xbox pad and Cross on a
+//! PlayStation one.
+
+use std::collections::HashSet;
+
+use ggez::event::{Axis, Button};
+use ggez::glam::Vec2;
+use ggez::input::gamepad::GamepadContext;
+use ggez::input::keyboard::KeyCode;
+
+use crate::MainState;
+use crate::bindings::Action;
+
+/// Stick deflection below this reads as centred; pads rarely rest at exactly zero.
+const STICK_DEADZONE: f32 = 0.2;
+
+/// How far from the player the right stick puts the aim point, in world pixels.
+const AIM_REACH: f32 = 260.0;
+
+/// Trigger travel below which gilrs lets go of the trigger as a button (its default release
+/// threshold), so the least squeeze that still holds a lasso.
+const TRIGGER_RELEASE: f32 = 0.65;
+
+/// How fast a lasso held on a barely squeezed right trigger charges, relative to a held key.
+pub(crate) const LIGHT_SQUEEZE_RATE: f32 = 0.4;
+
+/// Everything a pad is holding at one instant.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PadState {
+    /// Left stick, screen-oriented (+y is down), each axis in -1..=1.
+    pub left: Vec2,
+    /// Right stick, screen-oriented.
+    pub right: Vec2,
+    /// How far the right trigger is squeezed, 0..=1; 0 whenever it isn't held.
+    pub right_trigger: f32,
+    pub held: HashSet<Button>,
+}
+
+impl PadState {
+    /// Record a stick moving. Other axes (and the D-pad, which also arrives as buttons) are
+    /// ignored.
+    pub fn set_axis(&mut self, axis: Axis, value: f32) {
+        match axis {
+            Axis::LeftStickX => self.left.x = value,
+            Axis::LeftStickY => self.left.y = -value,
+            Axis::RightStickX => self.right.x = value,
+            Axis::RightStickY => self.right.y = -value,
+            _ => {}
+        }
+    }
+
+    /// The left stick as a movement direction, its length the throttle (at most 1). `None` while
+    /// it sits in the dead zone.
+    pub fn move_dir(&self) -> Option<Vec2> {
+        outside_deadzone(self.left)
+    }
+
+    /// The right stick's aim direction, normalised. `None` while it sits in the dead zone.
+    pub fn aim_dir(&self) -> Option<Vec2> {
+        outside_deadzone(self.right).map(Vec2::normalize)
+    }
+
+    /// Poll the right trigger's travel off every connected pad, the deepest squeeze winning. A
+    /// trigger that only clicks reports fully down.
+    pub(crate) fn read_trigger(&mut self, gamepads: &GamepadContext) {
+        self.right_trigger = if self.held.contains(&Button::RightTrigger2) {
+            gamepads
+                .gamepads()
+                .filter_map(|(_, pad)| Some(pad.button_data(Button::RightTrigger2)?.value()))
+                .reduce(f32::max)
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+    }
+
+    /// The charge rate the right trigger's squeeze is worth, relative to a held key. `None`
+    /// while the trigger isn't held.
+    pub fn trigger_charge_rate(&self) -> Option<f32> {
+        self.held.contains(&Button::RightTrigger2).then(|| {
+            let squeeze =
+                ((self.right_trigger - TRIGGER_RELEASE) / (1.0 - TRIGGER_RELEASE)).clamp(0.0, 1.0);
+            LIGHT_SQUEEZE_RATE + (1.0 - LIGHT_SQUEEZE_RATE) * squeeze
+        })
+    }
+
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn outside_deadzone(stick: Vec2) -> Option<Vec2> {
+    (stick.length() > STICK_DEADZONE).then(|| stick.clamp_length_max(1.0))
+}
+
+/// The action a button performs during a run.
+pub(crate) fn pad_action(button: Button) -> Option<Action> {
+    Some(match button {
+        Button::South => Action::Dash,
+        Button::West => Action::Whistle,
+        Button::East => Action::Stomp,
+        Button::North => Action::Wave,
+        Button::LeftTrigger => Action::Call,
+        Button::RightTrigger => Action::Cycle,
+        Button::LeftTrigger2 => Action::Sprint,
+        // Held like the key, but the wind-up charges faster the harder it's squeezed.
+        Button::RightTrigger2 => Action::Lasso,
+        Button::LeftThumb => Action::GrooveCall,
+        Button::RightThumb => Action::Slam,
+        Button::DPadUp => Action::Flashlight,
+        Button::DPadDown => Action::Bank,
+        _ => return None,
+    })
+}
+
+/// The key a button stands in for on the menus.
+fn menu_key(button: Button) -> Option<KeyCode> {
+    Some(match button {
+        Button::DPadUp => KeyCode::ArrowUp,
+        Button::DPadDown => KeyCode::ArrowDown,
+        Button::DPadLeft => KeyCode::ArrowLeft,
+        Button::DPadRight => KeyCode::ArrowRight,
+        // Space rather than Enter: it is also what skips the title intro.
+        Button::South | Button::Start => KeyCode::Space,
+        Button::East | Button::Select => KeyCode::Escape,
+        Button::West => KeyCode::Tab,
+        _ => return None,
+    })
+}
+
+/// Both directions of the button-name table for replay files, like `replay::key_names!`.
+macro_rules! button_names {
+    ($($button:ident),* $(,)?) => {
+        pub(crate) fn button_name(button: Button) -> &'static str {
+            match button {
+                $(Button::$button => stringify!($button),)*
+            }
+        }
+
+        pub(crate) fn button_from_name(name: &str) -> Option<Button> {
+            match name {
+                $(stringify!($button) => Some(Button::$button),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+button_names!(
+    South,
+    East,
+    North,
+    West,
+    C,
+    Z,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Unknown,
+);
+
+impl MainState {
+    /// A pad button went down: handled as the key it stands for in the current context.
+    pub(crate) fn on_pad_down(&mut self, button: Button) {
+        if self.show_instructions || self.show_world_map || self.game_over || self.pause.is_some() {
+            // Capturing a rebind listens for a keyboard key; the pad can only back out of it.
+            if self.rebind_capture {
+                self.rebind_capture = button != Button::East;
+                return;
+            }
+            if let Some(key) = menu_key(button) {
+                self.on_key_down(Some(key), None);
+            }
+            return;
+        }
+        if self.pending_upgrade {
+            // The upgrade cards have no keyboard focus (they're picked by number), so the pad gets
+            // its own: D-pad left/right highlights a card, A takes the highlighted one.
+            let focus = self.upgrade_focus;
+            match button {
+                Button::DPadLeft => {
+                    self.upgrade_focus = Some(focus.map_or(0, |f| (f + 2) % 3));
+                    return;
+                }
+                Button::DPadRight => {
+                    self.upgrade_focus = Some(focus.map_or(0, |f| (f + 1) % 3));
+                    return;
+                }
+                Button::South => {
+                    match focus {
+                        Some(f) => self.apply_upgrade(f as u8 + 1),
+                        None => self.upgrade_focus = Some(0),
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+        if button == Button::Start {
+            self.on_key_down(Some(KeyCode::Escape), None);
+        } else if let Some(action) = pad_action(button) {
+            self.on_key_down(Some(self.bindings.key(action)), None);
+        }
+    }
+
+    /// How fast a winding lasso charges, relative to a held key. Held on the right trigger alone it
+    /// follows the squeeze; the mouse or the key charge at their own rate.
+    pub(crate) fn lasso_charge_rate(&self) -> f32 {
+        let by_hand = self.lasso_mouse_down || self.input.is_held(self.bindings.key(Action::Lasso));
+        match self.input.pad.trigger_charge_rate() {
+            Some(rate) if !by_hand => rate,
+            _ => 1.0,
+        }
+    }
+
+    /// Point the aim cursor where the right stick is pushed, if it is.
+    pub(crate) fn apply_pad_aim(&mut self) {
+        if let Some(aim) = self.input.pad.aim_dir() {
+            let center = self.player_pos + Vec2::splat(crate::PLAYER_SIZE / 2.0);
+            self.mouse_pos = center + aim * AIM_REACH;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sticks_respect_the_deadzone() {
+        let mut pad = PadState::default();
+        pad.set_axis(Axis::LeftStickX, 0.1);
+        assert_eq!(pad.move_dir(), None);
+        pad.set_axis(Axis::LeftStickY, 1.0);
+        pad.set_axis(Axis::LeftStickX, 1.0);
+        // Up on the stick is up on screen, and a diagonal never outruns a straight push.
+        let dir = pad.move_dir().unwrap();
+        assert!(dir.y < 0.0 && dir.x > 0.0);
+        assert!((dir.length() - 1.0).abs() < 1e-5);
+        pad.set_axis(Axis::RightStickX, -0.5);
+        assert_eq!(pad.aim_dir(), Some(Vec2::new(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn a_harder_squeeze_charges_the_lasso_faster() {
+        let mut pad = PadState {
+            right_trigger: 0.7,
+            ..PadState::default()
+        };
+        assert_eq!(pad.trigger_charge_rate(), None);
+        pad.held.insert(Button::RightTrigger2);
+        let light = pad.trigger_charge_rate().unwrap();
+        pad.right_trigger = 1.0;
+        assert_eq!(pad.trigger_charge_rate(), Some(1.0));
+        assert!(light > LIGHT_SQUEEZE_RATE && light < 1.0);
+        pad.right_trigger = TRIGGER_RELEASE;
+        assert_eq!(pad.trigger_charge_rate(), Some(LIGHT_SQUEEZE_RATE));
+    }
+
+    #[test]
+    fn button_names_round_trip() {
+        for button in [Button::South, Button::RightTrigger2, Button::DPadLeft] {
+            assert_eq!(button_from_name(button_name(button)), Some(button));
+        }
+        assert_eq!(button_from_name("Triangle"), None);
+    }
+}
@@ -6,7 +6,8 @@
 //! instance caches that only they use.
 
 use super::*;
-use ggez::graphics::MeshBuilder;
+use crate::levels::Branch;
+use ggez::graphics::{MeshBuilder, TextFragment};
 
 const MINIMAP_PX_PER_VIEWPORT: f32 = 90.0;
 const MINIMAP_MIN_WIDTH: f32 = 140.0;
@@ -49,48 +50,60 @@ thread_local! {
     // Cache for draw_tool_roster's 15 labels (key/name/hint x 5 slots). Every one of those was a
     // fresh Text::new() + set_scale() call every single frame the roster was visible — i.e. all of
     // active gameplay, the same per-frame glyph-shaping cost COMBO_LABEL_CACHE above already fixed
-    // for the combo meter. 14 of the 15 strings are truly static per slot; only the GROOVE slot's
-    // hint toggles between "SLAM ready!" and "need groove" as the meter fills, so each cache entry
-    // stores the source &'static str alongside its shaped Text and rebuilds only on a content
-    // mismatch — a rare event for 14 of 15 slots, and just a two-way flip for the 15th.
-    static TOOL_ROSTER_TEXT_CACHE: RefCell<[Option<(&'static str, Text)>; 21]> =
+    // for the combo meter. Almost every string is static for a whole run; only the GROOVE slot's
+    // hint toggles between "SLAM ready!" and "need groove" as the meter fills, and the key labels
+    // change when the player rebinds, so each cache entry stores the source string alongside its
+    // shaped Text and rebuilds only on a content mismatch.
+    static TOOL_ROSTER_TEXT_CACHE: RefCell<[Option<(String, Text)>; 21]> =
         RefCell::new([const { None }; 21]);
+    // The bindings the roster's key labels (every third TOOL_ROSTER_TEXT_CACHE entry) were built
+    // from; a rebind clears those entries.
+    static TOOL_ROSTER_BINDINGS: RefCell<Option<crate::bindings::Bindings>> =
+        const { RefCell::new(None) };
     static KING_LOADOUT_TEXT_CACHE: RefCell<Option<([u32; 5], [u32; 4], Vec<Text>)>> =
         RefCell::new(None);
 
     // Cache for the world-map screen's Text objects. draw_world_map rebuilt a fresh Text +
     // measure() for every node label, the title, and the controls hint on every frame the map
     // screen was visible — the same unbounded-idle-time pattern every other menu screen already
-    // fixed. Node labels: keyed per-node by (completed, unlocked); those are the only two booleans
-    // that change label text (suffix " ✓" and lock " [locked]"). Selection changes fill color only,
-    // never the label text, so it's not part of the key. Title and hint are static literals →
+    // fixed. Node labels: keyed per-node by their text (a stop number, or a side path's letter).
+    // Selection and progress change fill color only, never the label text, so they're not part of
+    // the key. Title and hint are static literals →
     // cached unconditionally. A path-line segment cache is skipped: there are only N-1 ≤ 3 path
     // segments and they're connection-only (two endpoint positions, no text/glyphs), so the per-
     // frame cost of their static geometry is negligible compared to glyph-shaping.
-    static WORLD_MAP_NODE_LABELS: RefCell<Vec<Option<((bool, bool), Text, f32)>>> = RefCell::new(Vec::new());
+    static WORLD_MAP_NODE_LABELS: RefCell<Vec<Option<(String, Text, f32)>>> = RefCell::new(Vec::new());
     static WORLD_MAP_TITLE_CACHE: RefCell<Option<(Text, f32)>> = RefCell::new(None);
     static WORLD_MAP_HINT_CACHE: RefCell<Option<(Text, f32)>> = RefCell::new(None);
     static WORLD_MAP_SKIP_CACHE: RefCell<Option<(Text, f32)>> = RefCell::new(None);
-    static WORLD_MAP_SELECTED_CACHE: RefCell<Option<(usize, bool, bool, Text, f32)>> = RefCell::new(None);
+    static WORLD_MAP_SELECTED_CACHE: RefCell<Option<(SelectedKey, Text, f32)>> = RefCell::new(None);
+    static WORLD_MAP_SCORES_CACHE: RefCell<Option<(String, Text, f32)>> = RefCell::new(None);
     // The illustrated map only changes on resize; cache its water and island geometry rather than
     // re-tessellating decorative meshes every frame while the menu is open.
     static WORLD_MAP_SCENERY_CACHE: RefCell<Option<((i32, i32), Mesh)>> = RefCell::new(None);
-    // Route geometry changes only when a node unlocks. Cache its dotted treasure trail separately
-    // so cursor movement and animation don't create dozens of line meshes per frame.
-    static WORLD_MAP_ROUTE_CACHE: RefCell<Option<((i32, i32, Vec<bool>), Mesh)>> = RefCell::new(None);
-    // Per-node biome tint for the world map, built once (the node list is stable for the session).
-    // Campaign nodes take their level's `biome.tint`; tutorial nodes get a warm amber on-ramp colour.
-    // Cached so we never rebuild the (String-allocating) `get_levels()` list per frame.
-    static WORLD_MAP_NODE_TINTS: RefCell<Option<Vec<Color>>> = RefCell::new(None);
+    // Route geometry changes only when a node unlocks (or an edited level file moves, recolours or
+    // reroutes one). Cache its dotted treasure trail separately so cursor movement and animation
+    // don't create dozens of line meshes per frame.
+    static WORLD_MAP_ROUTE_CACHE: RefCell<Option<(RouteKey, Mesh)>> = RefCell::new(None);
 }
 
+/// What the cached route depends on per node: unlocked, map position (as bits) and tint.
+type RouteNodeKey = (bool, [u32; 2], Option<(u8, u8, u8)>);
+/// The cached route's whole key: screen size, every node, and every edge's ends.
+type RouteKey = (i32, i32, Vec<RouteNodeKey>, Vec<(usize, usize)>);
+/// What the selected node's caption depends on: the selection, its completed and unlocked state,
+/// and its best score and time.
+type SelectedKey = (usize, bool, bool, Option<usize>, Option<f32>);
+
 /// Campaign world map screen. Draws an illustrated tropical-island treasure chart.
 /// Nodes are colored by state: locked=dim gray, unlocked=white, completed=teal, selected=gold ring.
-/// Call this instead of the game/title draw when `show_world_map` is true.
+/// Call this instead of the game/title draw when `show_world_map` is true. `high_scores` is the
+/// selected node's table, with the rank the run just finished took on it, if any.
 pub fn draw_world_map(
     ctx: &mut Context,
     canvas: &mut Canvas,
     map: &crate::world_map::WorldMap,
+    high_scores: (&[crate::leaderboard::LeaderboardEntry], Option<usize>),
     width: f32,
     height: f32,
     menu_time: f32,
@@ -367,51 +380,48 @@ pub fn draw_world_map(
         }
     }
 
-    // Biome tint per node — built once (see the cache note above). Cloned out cheaply (≤ a handful
-    // of Copy `Color`s) so the draw loops below can read tints without holding the RefCell borrow.
-    let node_tints: Vec<Color> = WORLD_MAP_NODE_TINTS.with(|c| {
-        let mut cache = c.borrow_mut();
-        if cache.is_none() {
-            use crate::world_map::NodeKind;
-            let levels = crate::levels::get_levels();
-            let tints = map
-                .nodes
-                .iter()
-                .map(|n| match &n.kind {
-                    // Tutorials are the welcoming on-ramp — a warm amber, distinct from any biome.
-                    NodeKind::Tutorial(_) => Color::new(0.90, 0.70, 0.35, 1.0),
-                    NodeKind::Level(i) => {
-                        let (r, g, b) = levels
-                            .get(*i)
-                            .map(|l| l.biome.tint)
-                            .unwrap_or((200, 200, 200));
-                        Color::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0)
-                    }
-                })
-                .collect::<Vec<_>>();
-            *cache = Some(tints);
-        }
-        cache.clone().unwrap()
-    });
+    // Campaign nodes take their level's biome tint; tutorials are the welcoming on-ramp — a warm
+    // amber, distinct from any biome.
+    let node_tints: Vec<Color> = map
+        .nodes
+        .iter()
+        .map(|n| match n.tint {
+            Some((r, g, b)) => Color::from_rgb(r, g, b),
+            None => Color::new(0.90, 0.70, 0.35, 1.0),
+        })
+        .collect();
 
-    // Connecting trails between consecutive nodes. Curved trails follow the island's coast and
-    // river rather than cutting arbitrary straight lines across it. The dotted, warm route reads
-    // as a pirate's treasure trail while biome tint previews each destination.
+    // Trails along the map's edges. Curved trails follow the island's coast and river rather than
+    // cutting arbitrary straight lines across it. The dotted, warm route reads as a pirate's
+    // treasure trail while biome tint previews each destination. A secret's trail stays off the
+    // chart until it opens.
     WORLD_MAP_ROUTE_CACHE.with(|c| -> ggez::GameResult {
         let mut cache = c.borrow_mut();
-        let unlocked: Vec<bool> = map.nodes.iter().map(|node| node.unlocked).collect();
-        let key = (sx.round() as i32, sy.round() as i32, unlocked);
+        let nodes: Vec<RouteNodeKey> = map
+            .nodes
+            .iter()
+            .map(|node| {
+                let (x, y) = node.position;
+                (node.unlocked, [x.to_bits(), y.to_bits()], node.tint)
+            })
+            .collect();
+        let edges = map.edges.iter().map(|e| (e.from, e.to)).collect();
+        let key = (sx.round() as i32, sy.round() as i32, nodes, edges);
         if cache
             .as_ref()
             .map_or(true, |(cached_key, _)| *cached_key != key)
         {
             let mut builder = MeshBuilder::new();
-            for i in 0..map.nodes.len().saturating_sub(1) {
-                let a = node_to_screen(map.nodes[i].position);
-                let b = node_to_screen(map.nodes[i + 1].position);
+            for (i, edge) in map.edges.iter().enumerate() {
+                let (from, to) = (&map.nodes[edge.from], &map.nodes[edge.to]);
+                if to.hidden() {
+                    continue;
+                }
+                let a = node_to_screen(from.position);
+                let b = node_to_screen(to.position);
                 let bend = if i % 2 == 0 { 1.0 } else { -1.0 };
-                let control = if i + 2 == map.nodes.len() {
-                    // The Desktop finale is reached through an impossible chart-edge jump, not a
+                let control = if to.branch == Some(Branch::Secret) {
+                    // The Desktop secret is reached through an impossible chart-edge jump, not a
                     // road cutting diagonally across every biome.
                     Vec2::new(sx * 0.97, sy * 0.39)
                 } else {
@@ -427,8 +437,8 @@ pub fn draw_world_map(
                     .map(|step| curve(step as f32 / 18.0))
                     .collect::<Vec<_>>();
                 builder.line(&outline, 7.0, Color::new(0.025, 0.08, 0.09, 0.88))?;
-                if map.nodes[i + 1].unlocked {
-                    let (ca, cb) = (node_tints[i], node_tints[i + 1]);
+                if to.unlocked {
+                    let (ca, cb) = (node_tints[edge.from], node_tints[edge.to]);
                     const SEGS: usize = 14;
                     for s in (0..SEGS).step_by(2) {
                         let t0 = s as f32 / SEGS as f32;
@@ -477,7 +487,7 @@ pub fn draw_world_map(
                         mid - tangent * 5.0 + normal * 4.5,
                         mid - tangent * 5.0 - normal * 4.5,
                     ],
-                    if map.nodes[i + 1].unlocked {
+                    if to.unlocked {
                         Color::new(1.0, 0.82, 0.26, 0.95)
                     } else {
                         Color::new(0.77, 0.68, 0.43, 0.88)
@@ -490,7 +500,14 @@ pub fn draw_world_map(
         Ok(())
     })?;
 
+    let mut stop_number = 0;
     for (i, node) in map.nodes.iter().enumerate() {
+        if node.branch.is_none() {
+            stop_number += 1;
+        }
+        if node.hidden() {
+            continue;
+        }
         let pos = node_to_screen(node.position);
         let is_selected = i == map.selected;
 
@@ -568,10 +585,16 @@ pub fn draw_world_map(
                 }),
         );
 
-        // Compact itinerary numbers keep the dense island circuit readable; the selected stop's
-        // full name lives in the fixed caption above the map. Cache entries rebuild only when a
-        // stop changes state, matching the other menu/HUD text caches.
-        let label_key = (node.completed, node.unlocked);
+        // Compact itinerary numbers keep the dense island circuit readable, and side paths take a
+        // letter instead so the main route's count stays unbroken; the selected stop's full name
+        // lives in the fixed caption above the map. Cache entries rebuild only when a label's
+        // text changes, matching the other menu/HUD text caches.
+        let label_text = match node.branch {
+            None => stop_number.to_string(),
+            Some(Branch::Challenge) => "C".to_string(),
+            Some(Branch::Boss) => "B".to_string(),
+            Some(Branch::Secret) => "?".to_string(),
+        };
         WORLD_MAP_NODE_LABELS.with(|c| -> ggez::GameResult {
             let mut labels = c.borrow_mut();
             // Grow the Vec to cover this node index if needed (the map never shrinks mid-session).
@@ -579,13 +602,11 @@ pub fn draw_world_map(
                 labels.resize_with(i + 1, || None);
             }
             let entry = &mut labels[i];
-            // Rebuild only when the node's (completed, unlocked) state actually changes.
-            if entry.as_ref().map(|(k, _, _)| *k) != Some(label_key) {
-                let stop_number = format!("{}", i + 1);
-                let mut label = Text::new(stop_number);
+            if entry.as_ref().map(|(k, _, _)| k) != Some(&label_text) {
+                let mut label = Text::new(label_text.as_str());
                 label.set_scale(18.0);
                 let w = label.measure(ctx)?.x;
-                *entry = Some((label_key, label, w));
+                *entry = Some((label_text, label, w));
             }
             if let Some((_, label, w)) = entry.as_ref() {
                 canvas.draw(
@@ -613,14 +634,16 @@ pub fn draw_world_map(
         );
         WORLD_MAP_SELECTED_CACHE.with(|c| -> ggez::GameResult {
             let mut cache = c.borrow_mut();
-            let key = (map.selected, selected.completed, selected.unlocked);
-            // Only selection and progression state affect the caption; cached text and width are the
-            // derived payload and therefore intentionally excluded from the validity key.
-            if cache
-                .as_ref()
-                .map(|(i, completed, unlocked, _, _)| (*i, *completed, *unlocked))
-                != Some(key)
-            {
+            let key = (
+                map.selected,
+                selected.completed,
+                selected.unlocked,
+                selected.best_score,
+                selected.best_time,
+            );
+            // The caption is built only when one of those changes; the unlock hint is fixed by the
+            // routes into the node, so it needs no place in the key.
+            if cache.as_ref().map(|(k, _, _)| k) != Some(&key) {
                 let status = if selected.completed {
                     "COMPLETE"
                 } else if selected.unlocked {
@@ -628,12 +651,17 @@ pub fn draw_world_map(
                 } else {
                     "UNCHARTED"
                 };
-                let mut text = Text::new(format!("{status}  •  {}", selected.name));
+                // A locked stop says what opens it instead.
+                let caption = match selected.best_text().or_else(|| map.unlock_hint()) {
+                    Some(detail) => format!("{status}  •  {}  •  {detail}", selected.name),
+                    None => format!("{status}  •  {}", selected.name),
+                };
+                let mut text = Text::new(caption);
                 text.set_scale(20.0);
                 let w = text.measure(ctx)?.x;
-                *cache = Some((key.0, key.1, key.2, text, w));
+                *cache = Some((key, text, w));
             }
-            if let Some((_, _, _, text, w)) = cache.as_ref() {
+            if let Some((_, text, w)) = cache.as_ref() {
                 canvas.draw(
                     text,
                     DrawParam::default()
@@ -645,6 +673,44 @@ pub fn draw_world_map(
         })?;
     }
 
+    // The selected node's podium, under the controls hint: its best three runs, with the one the
+    // player just set in gold. Keyed on the rows themselves.
+    let (table, highlight) = high_scores;
+    if !table.is_empty() {
+        let ranking = crate::leaderboard::Ranking::of(&map.selected_id());
+        let rows: Vec<String> = table
+            .iter()
+            .take(3)
+            .enumerate()
+            .map(|(i, entry)| entry.row(i, ranking))
+            .collect();
+        let key = format!("{highlight:?} {}", rows.join("\n"));
+        WORLD_MAP_SCORES_CACHE.with(|c| -> ggez::GameResult {
+            let mut cache = c.borrow_mut();
+            if cache.as_ref().map(|(k, _, _)| k) != Some(&key) {
+                let mut text = Text::default();
+                for (i, row) in rows.into_iter().enumerate() {
+                    let color = if highlight == Some(i) {
+                        Color::new(1.0, 0.84, 0.24, 1.0)
+                    } else {
+                        Color::new(0.78, 0.86, 0.92, 1.0)
+                    };
+                    text.add(TextFragment::new(row + "\n").color(color));
+                }
+                text.set_scale(14.0);
+                let w = text.measure(ctx)?.x;
+                *cache = Some((key, text, w));
+            }
+            if let Some((_, text, w)) = cache.as_ref() {
+                canvas.draw(
+                    text,
+                    DrawParam::default().dest(Vec2::new((sx - w) * 0.5, sy * 0.915)),
+                );
+            }
+            Ok(())
+        })?;
+    }
+
     // Title — static literal, built once and reused forever. Same pattern as MENU_PROMPT_CACHE.
     WORLD_MAP_TITLE_CACHE.with(|c| -> ggez::GameResult {
         let mut cache = c.borrow_mut();
@@ -670,7 +736,7 @@ pub fn draw_world_map(
         let mut cache = c.borrow_mut();
         if cache.is_none() {
             let mut hint =
-                Text::new("Left / Right: Navigate     Space / Enter: Play     Esc: Back");
+                Text::new("Arrows: Follow the routes     Space / Enter: Play     Esc: Back");
             hint.set_scale(15.0);
             let w = hint.measure(ctx)?.x;
             *cache = Some((hint, w));
@@ -1138,44 +1204,75 @@ pub fn draw_king_loadout(
     })
 }
 
-/// Compact tool roster at the bottom centre — shows each tool's key, name, matchup hint, and
-/// cooldown bar so the player always knows what's ready and what each key does.
-pub fn draw_tool_roster(
-    ctx: &mut Context,
-    canvas: &mut Canvas,
-    width: f32,
-    height: f32,
+/// Everything the tool roster reads off the run, gathered by the HUD pass each frame.
+pub struct ToolRoster<'a> {
+    pub bindings: &'a crate::bindings::Bindings,
     // Cooldowns (0 = ready, >0 = on cooldown)
-    whistle_cd: f32,
-    whistle_max: f32,
-    stomp_cd: f32,
-    stomp_max: f32,
-    wave_busy: bool, // true while the Wave shockwave ring is flying
-    call_cd: f32,
-    call_max: f32,
-    boost_cd: f32,    // dash cooldown
-    lasso_busy: bool, // true when lasso is in flight/dragging
+    pub whistle_cd: f32,
+    pub whistle_max: f32,
+    pub stomp_cd: f32,
+    pub stomp_max: f32,
+    pub wave_busy: bool, // true while the Wave shockwave ring is flying
+    pub call_cd: f32,
+    pub call_max: f32,
+    pub boost_cd: f32,    // dash cooldown
+    pub lasso_busy: bool, // true when lasso is in flight/dragging
     // Contextual usefulness: true when firing this tool RIGHT NOW would actually do something
     // (a target is in range). A ready+useful pad lights up bright so the player reads *which* tool
     // the moment calls for, not just which are off cooldown.
-    lasso_useful: bool,
-    whistle_useful: bool,
-    stomp_useful: bool,
-    wave_useful: bool,
-    call_useful: bool,
+    pub lasso_useful: bool,
+    pub whistle_useful: bool,
+    pub stomp_useful: bool,
+    pub wave_useful: bool,
+    pub call_useful: bool,
     // Groove/G state
-    groove: f32, // 0..1 groove meter level (for V/G readiness hint)
-    time: f32,
+    pub groove: f32, // 0..1 groove meter level (for V/G readiness hint)
+    pub time: f32,
     // Rhythm sync: progress toward the next beat (0 = just landed, 1 = about to land) and whether
     // the current instant is inside the on-beat cast window. A READY pad breathes with the beat
     // instead of a free-running sine — it swells as the beat approaches and flashes brightest right
     // in the on-beat window, so the roster reads as a row of drum pads telling you *when* to hit for
     // the on-beat bonus (#164 legibility; the ROADMAP "each tool key is a drum pad" vision).
-    beat_progress: f32,
-    on_beat: bool,
+    pub beat_progress: f32,
+    pub on_beat: bool,
+}
+
+/// Compact tool roster at the bottom centre — shows each tool's key (as currently bound), name,
+/// matchup hint, and cooldown bar so the player always knows what's ready and what each key does.
+pub fn draw_tool_roster(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    width: f32,
+    height: f32,
+    roster: &ToolRoster,
 ) -> ggez::GameResult {
+    use crate::bindings::Action;
+
+    let &ToolRoster {
+        bindings,
+        whistle_cd,
+        whistle_max,
+        stomp_cd,
+        stomp_max,
+        wave_busy,
+        call_cd,
+        call_max,
+        boost_cd,
+        lasso_busy,
+        lasso_useful,
+        whistle_useful,
+        stomp_useful,
+        wave_useful,
+        call_useful,
+        groove,
+        time,
+        beat_progress,
+        on_beat,
+    } = roster;
+
     struct ToolSlot {
-        key: &'static str,
+        /// The actions whose keys the slot's label lists; the lasso's starts with the mouse click.
+        keys: &'static [Action],
         name: &'static str,
         hint: &'static str,
         color: [f32; 3],
@@ -1196,7 +1293,7 @@ pub fn draw_tool_roster(
 
     let slots = [
         ToolSlot {
-            key: "click",
+            keys: &[Action::Lasso],
             name: "LASSO",
             hint: "snags Thieves",
             color: [0.3, 0.85, 0.45],
@@ -1204,7 +1301,7 @@ pub fn draw_tool_roster(
             useful: lasso_useful,
         },
         ToolSlot {
-            key: "E",
+            keys: &[Action::Whistle],
             name: "WHISTLE",
             hint: "pulls Dancers",
             color: [0.4, 0.85, 1.0],
@@ -1212,7 +1309,7 @@ pub fn draw_tool_roster(
             useful: whistle_useful,
         },
         ToolSlot {
-            key: "R",
+            keys: &[Action::Stomp],
             name: "STOMP",
             hint: "cracks shells",
             color: [0.6, 0.7, 1.0],
@@ -1220,7 +1317,7 @@ pub fn draw_tool_roster(
             useful: stomp_useful,
         },
         ToolSlot {
-            key: "Q",
+            keys: &[Action::Wave],
             name: "WAVE",
             hint: "shoves rivals",
             color: [0.45, 0.9, 1.0],
@@ -1228,7 +1325,7 @@ pub fn draw_tool_roster(
             useful: wave_useful,
         },
         ToolSlot {
-            key: "T",
+            keys: &[Action::Call],
             name: "CALL",
             hint: "calls Dancers",
             color: [1.0, 0.55, 0.9],
@@ -1236,7 +1333,7 @@ pub fn draw_tool_roster(
             useful: call_useful,
         },
         ToolSlot {
-            key: "Space",
+            keys: &[Action::Dash],
             name: "DASH",
             hint: "on beat = +",
             color: [1.0, 0.9, 0.5],
@@ -1244,7 +1341,7 @@ pub fn draw_tool_roster(
             useful: true,
         },
         ToolSlot {
-            key: "V · G",
+            keys: &[Action::GrooveCall, Action::Slam],
             name: "GROOVE",
             hint: groove_hint,
             color: [0.45, 1.0, 0.85],
@@ -1263,6 +1360,20 @@ pub fn draw_tool_roster(
 
     let sq = unit_square(ctx)?;
 
+    // The key labels only change when the player rebinds, so they are built once per binding set
+    // rather than formatted every frame.
+    TOOL_ROSTER_BINDINGS.with(|seen| {
+        let mut seen = seen.borrow_mut();
+        if seen.as_ref() != Some(bindings) {
+            *seen = Some(bindings.clone());
+            TOOL_ROSTER_TEXT_CACHE.with(|cache| {
+                for entry in cache.borrow_mut().iter_mut().step_by(3) {
+                    *entry = None;
+                }
+            });
+        }
+    });
+
     // Beat-synced pad glow (0..1): eases up as the next beat approaches and snaps to full inside
     // the on-beat window, so a ready pad pulses ON the beat rather than to a free-running clock.
     // This is the timing cue for on-beat tool casts — the pads light up when it pays to hit them.
@@ -1348,10 +1459,16 @@ pub fn draw_tool_roster(
         TOOL_ROSTER_TEXT_CACHE.with(|cache_cell| -> ggez::GameResult {
             let mut cache = cache_cell.borrow_mut();
             let entry = &mut cache[i * 3];
-            if entry.as_ref().map_or(true, |(s, _)| *s != slot.key) {
-                let mut t = Text::new(slot.key);
+            if entry.is_none() {
+                let click = (slot.keys[0] == Action::Lasso).then_some("click");
+                let keys: Vec<&str> = click
+                    .into_iter()
+                    .chain(slot.keys.iter().map(|&action| bindings.label(action)))
+                    .collect();
+                let label = keys.join(" · ");
+                let mut t = Text::new(label.as_str());
                 t.set_scale(12.0);
-                *entry = Some((slot.key, t));
+                *entry = Some((label, t));
             }
             canvas.draw(
                 &entry.as_ref().unwrap().1,
@@ -1381,7 +1498,7 @@ pub fn draw_tool_roster(
             if entry.as_ref().map_or(true, |(s, _)| *s != slot.name) {
                 let mut t = Text::new(slot.name);
                 t.set_scale(14.0);
-                *entry = Some((slot.name, t));
+                *entry = Some((slot.name.to_string(), t));
             }
             canvas.draw(
                 &entry.as_ref().unwrap().1,
@@ -1402,7 +1519,7 @@ pub fn draw_tool_roster(
             if entry.as_ref().map_or(true, |(s, _)| *s != slot.hint) {
                 let mut t = Text::new(slot.hint);
                 t.set_scale(11.0);
-                *entry = Some((slot.hint, t));
+                *entry = Some((slot.hint.to_string(), t));
             }
             canvas.draw(
                 &entry.as_ref().unwrap().1,
@@ -48,6 +48,7 @@ impl InstancedMeshExt for Canvas {
         if instances.instances().is_empty() {
             return;
         }
+        crate::profiler::count_batch(instances.instances().len());
         self.draw_instanced_mesh(mesh, instances, param);
     }
 }
@@ -125,6 +126,11 @@ pub use lasso::*;
 mod player_render;
 pub use player_render::*;
 
+// The F3 performance overlay (per-subsystem frame-time graph and batch counts) lives in its own
+// file. Re-exported so the `graphics::draw_perf_overlay` call-site path matches the rest.
+mod perf_overlay;
+pub(crate) use perf_overlay::*;
+
 // A single unit-radius circle mesh, built once and reused for every particle by
 // scaling it via `DrawParam` instead of baking each particle's radius into fresh
 // mesh geometry. Milestone fireworks alone can push 200+ live particles, each
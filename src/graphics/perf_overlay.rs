@@ -0,0 +1,138 @@
+//! The F3 performance overlay: a rolling stacked-bar graph of the profiler's per-subsystem frame
+//! timings, a 60 fps budget line, and the window's mean/worst per section plus instanced batch and
+//! instance counts. Drawn last, straight onto the screen canvas, so it costs nothing in the scene
+//! it is measuring. The numbers come from `profiler::Profiler`.
+
+use super::*;
+use crate::profiler::{HISTORY, Profiler, Section};
+use ggez::graphics::MeshBuilder;
+
+const PANEL_W: f32 = 420.0;
+const GRAPH_H: f32 = 110.0;
+/// Milliseconds the graph's full height stands for (two 60 fps frames).
+const GRAPH_MS: f32 = 33.3;
+const BUDGET_MS: f32 = 1000.0 / 60.0;
+const LINE_H: f32 = 16.0;
+
+fn section_color(section: Section) -> Color {
+    match section {
+        Section::UpdateCrabs => Color::from_rgb(255, 140, 90),
+        Section::NpcTrains => Color::from_rgb(230, 90, 200),
+        Section::CrabCatching => Color::from_rgb(250, 220, 90),
+        Section::ActiveTools => Color::from_rgb(120, 230, 120),
+        Section::AmbientAudio => Color::from_rgb(150, 150, 160),
+        Section::Scene => Color::from_rgb(90, 170, 255),
+        Section::Trail => Color::from_rgb(90, 230, 230),
+        Section::PostProcess => Color::from_rgb(170, 130, 255),
+    }
+}
+
+/// Draw the overlay in the top-right corner of a `width`-wide screen-space canvas.
+pub(crate) fn draw_perf_overlay(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    profiler: &Profiler,
+    width: f32,
+) -> ggez::GameResult {
+    let text_rows = Section::ALL.len() + 3;
+    let panel = Rect::new(
+        width - PANEL_W - 10.0,
+        30.0,
+        PANEL_W,
+        GRAPH_H + 20.0 + text_rows as f32 * LINE_H,
+    );
+    let graph = Rect::new(panel.x + 10.0, panel.y + 10.0, PANEL_W - 20.0, GRAPH_H);
+    let px_per_ms = GRAPH_H / GRAPH_MS;
+    let bar_w = graph.w / HISTORY as f32;
+
+    let mut builder = MeshBuilder::new();
+    builder.rectangle(DrawMode::fill(), panel, Color::new(0.02, 0.03, 0.06, 0.82))?;
+    // Newest frame on the right edge, so the graph scrolls left as frames arrive.
+    let history = profiler.history();
+    let first_x = graph.right() - history.len() as f32 * bar_w;
+    for (i, sample) in history.iter().enumerate() {
+        let x = first_x + i as f32 * bar_w;
+        let mut y = graph.bottom();
+        for section in Section::ALL {
+            let h = (sample.sections[section as usize] * px_per_ms).min(y - graph.y);
+            if h > 0.0 {
+                y -= h;
+                builder.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(x, y, bar_w, h),
+                    section_color(section),
+                )?;
+            }
+        }
+        // Whole-frame time as a faint tick above the stack: the gap is time nobody measured.
+        let frame_y = (graph.bottom() - sample.frame * px_per_ms).max(graph.y);
+        builder.rectangle(
+            DrawMode::fill(),
+            Rect::new(x, frame_y, bar_w, 1.0),
+            Color::new(1.0, 1.0, 1.0, 0.35),
+        )?;
+    }
+    let budget_y = graph.bottom() - BUDGET_MS * px_per_ms;
+    builder.line(
+        &[
+            Vec2::new(graph.x, budget_y),
+            Vec2::new(graph.right(), budget_y),
+        ],
+        1.0,
+        Color::new(1.0, 0.3, 0.3, 0.8),
+    )?;
+    let mesh = Mesh::from_data(ctx, builder.build());
+    canvas.draw(&mesh, DrawParam::default());
+
+    let (mean, worst) = profiler.summary();
+    let mut y = graph.bottom() + 10.0;
+    let mut line = |canvas: &mut Canvas, msg: String, color: Color| {
+        canvas.draw(
+            &Text::new(msg),
+            DrawParam::default()
+                .dest(Vec2::new(graph.x, y))
+                .color(color),
+        );
+        y += LINE_H;
+    };
+    line(
+        canvas,
+        format!(
+            "frame {:6.2} avg {:6.2} max ms  ({:.1} steps/frame)",
+            mean.frame,
+            worst.frame,
+            history.iter().map(|s| s.steps).sum::<u32>() as f32 / history.len().max(1) as f32,
+        ),
+        Color::WHITE,
+    );
+    for section in Section::ALL {
+        let i = section as usize;
+        line(
+            canvas,
+            format!(
+                "{:<21}{:6.2} avg {:6.2} max ms",
+                section.label(),
+                mean.sections[i],
+                worst.sections[i],
+            ),
+            section_color(section),
+        );
+    }
+    line(
+        canvas,
+        format!(
+            "{} batches / {} instances (max {} / {})",
+            mean.batches, mean.instances, worst.batches, worst.instances,
+        ),
+        Color::WHITE,
+    );
+    line(
+        canvas,
+        profiler
+            .last_dump
+            .clone()
+            .unwrap_or_else(|| "F4 saves this window as CSV".to_string()),
+        Color::new(0.7, 0.7, 0.75, 1.0),
+    );
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::enemies::CrabType;
-use crate::levels::WinCondition;
+use crate::levels::{GoalProgress, WinCondition};
+use crate::mutators::Mutators;
 use ggez::glam::Vec2;
 use ggez::graphics::{Mesh, Text};
 use std::{cell::RefCell, collections::HashMap};
@@ -21,13 +22,26 @@ thread_local! {
     pub static RHYTHM_BONUS_CACHE: RefCell<Option<(usize, Text)>> = RefCell::new(None);
 
     /// Cache for the campaign goal progress line — keyed by the raw counters that feed
-    /// `progress_text` (bucketed to the same whole-second resolution it's displayed at) instead
-    /// of the rendered string itself, so formatting the line only happens on an actual rebuild
-    /// rather than every frame just to build a comparison key (mirrors PERF_OVERLAY_CACHE below).
+    /// `progress_text` (streak clocks bucketed to the same whole-second resolution they're
+    /// displayed at) instead of the rendered string itself, so formatting the line only happens on
+    /// an actual rebuild rather than every frame just to build a comparison key (mirrors
+    /// PERF_OVERLAY_CACHE below).
     #[allow(clippy::type_complexity)]
-    pub static CAMPAIGN_GOAL_CACHE: RefCell<Option<((bool, WinCondition, usize, usize, usize, i32), Text)>> =
+    pub static CAMPAIGN_GOAL_CACHE: RefCell<Option<((bool, WinCondition, GoalProgress), Text)>> =
         RefCell::new(None);
 
+    /// Endless overrun meter — keyed by the loose-crab count it prints and, in a daily challenge,
+    /// the whole seconds left.
+    pub static ENDLESS_OVERRUN_CACHE: RefCell<Option<((usize, Option<u32>), Text)>> =
+        RefCell::new(None);
+
+    /// Mutator line in the stats box — keyed by the set it names, with its measured width.
+    pub static MUTATORS_HUD_CACHE: RefCell<Option<(Mutators, Text, f32)>> = RefCell::new(None);
+
+    /// Rival standings line in the stats box — keyed by `rival_crabs_banked`, which moves on every
+    /// rival bank and never repeats, with its measured width.
+    pub static RIVALS_HUD_CACHE: RefCell<Option<(usize, Text, f32)>> = RefCell::new(None);
+
     #[cfg(debug_assertions)]
     pub static PERF_OVERLAY_CACHE: RefCell<Option<(i32, i32, i32, Text, f32)>> = RefCell::new(None);
 
@@ -96,7 +110,7 @@ thread_local! {
     pub static CHAIN_ORDER_CACHE: RefCell<Option<(usize, Vec<(usize, Option<[f32; 3]>)>)>> = RefCell::new(None);
 
     #[allow(clippy::type_complexity)]
-    pub static LEVEL_TITLE_OVERLAY_CACHE: RefCell<Option<(String, &'static str, Text, Mesh, Mesh, Text, f32, f32, f32, f32, f32, Option<(Text, f32)>)>> = RefCell::new(None);
+    pub static LEVEL_TITLE_OVERLAY_CACHE: RefCell<Option<(String, String, Text, Mesh, Mesh, Text, f32, f32, f32, f32, f32, Option<(Text, f32)>)>> = RefCell::new(None);
 
     #[allow(clippy::type_complexity)]
     pub static UPGRADE_SCREEN_CACHE: RefCell<Option<(
@@ -121,10 +135,11 @@ thread_local! {
 
     #[allow(clippy::type_complexity)]
     pub static GAME_OVER_CACHE: RefCell<Option<(
-        (usize, u32, u32, usize, usize, usize, bool),
+        (usize, u32, u32, usize, usize, usize, bool, (usize, Option<String>, Option<(String, usize)>)),
         Mesh,
         Text,
         Option<(Text, f32)>,
+        Option<(Text, f32)>,
     )>> = RefCell::new(None);
 
     #[allow(clippy::type_complexity)]
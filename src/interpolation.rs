@@ -0,0 +1,158 @@
+//! Render interpolation for the fixed-timestep loop.
+//!
+//! Interactive play advances the simulation in whole `SIM_DT` steps (see `MainState::tick`), so a
+//! 144 Hz display draws several frames per step and a 30 fps laptop runs two steps per frame.
+//! Drawing the raw post-step positions would make motion stutter on the former. Instead `tick`
+//! snapshots the moving bodies just before each step, and `draw` blends from that snapshot toward
+//! the current state by the fraction of a step still sitting in the accumulator.
+//!
+//! Only positions that visibly glide are blended: the player, the camera that follows them, every
+//! crab (free or in the conga line) and each rival train's leader. Rival followers trail the leader
+//! along its distance-sampled path history, which already moves in discrete hops. The blend is
+//! applied by swapping the interpolated values into `MainState` for the duration of `draw` and
+//! swapping the true ones back afterwards, so none of the draw code needs to know about it.
+//!
+//! Crabs are matched to their snapshot by index. A step that removes crabs from the middle of the
+//! list does it through [`MainState::retain_crabs`], which drops the same entries from the
+//! snapshot, and a step that replaces the herd wholesale calls
+//! [`MainState::forget_crab_positions`]; otherwise the crabs behind a gap would blend from their
+//! neighbours' old positions.
+
+use ggez::glam::Vec2;
+
+use crate::MainState;
+use crate::enemies::EnemyCrab;
+
+/// Anything that moved further than this in one step teleported (respawn, level load) and is drawn
+/// where it is.
+const MAX_BLEND_DISTANCE: f32 = 96.0;
+
+/// The positions the renderer blends, captured at one instant. Kept between frames so capturing
+/// reuses its buffers.
+#[derive(Default)]
+pub(crate) struct RenderPositions {
+    player: Vec2,
+    camera: Vec2,
+    crabs: Vec<Vec2>,
+    trains: Vec<Vec2>,
+}
+
+impl RenderPositions {
+    fn capture_from(&mut self, state: &MainState) {
+        self.player = state.player_pos;
+        self.camera = state.camera_origin;
+        self.crabs.clear();
+        self.crabs.extend(state.crabs.iter().map(|c| c.pos));
+        self.trains.clear();
+        self.trains
+            .extend(state.npc_trains.iter().map(|t| t.leader_pos));
+    }
+
+    fn restore(&self, state: &mut MainState) {
+        state.player_pos = self.player;
+        state.camera_origin = self.camera;
+        for (crab, &pos) in state.crabs.iter_mut().zip(&self.crabs) {
+            crab.pos = pos;
+        }
+        for (train, &pos) in state.npc_trains.iter_mut().zip(&self.trains) {
+            train.leader_pos = pos;
+        }
+    }
+}
+
+fn blend(prev: Vec2, cur: Vec2, alpha: f32) -> Vec2 {
+    if prev.distance_squared(cur) > MAX_BLEND_DISTANCE * MAX_BLEND_DISTANCE {
+        cur
+    } else {
+        prev.lerp(cur, alpha)
+    }
+}
+
+impl MainState {
+    /// Snapshot the moving bodies just before a step, into the previous snapshot's buffers.
+    pub(crate) fn capture_prev_positions(&mut self) {
+        let mut prev = self.prev_positions.take().unwrap_or_default();
+        prev.capture_from(self);
+        self.prev_positions = Some(prev);
+    }
+
+    /// Remove the crabs `keep` turns down, from the field and from the pre-step snapshot alike.
+    pub(crate) fn retain_crabs(&mut self, keep: impl Fn(&EnemyCrab) -> bool) {
+        if let Some(prev) = &mut self.prev_positions {
+            // Entries past the field's end can't be matched to a crab any more; drop them too.
+            let mut crabs = self.crabs.iter();
+            prev.crabs.retain(|_| crabs.next().is_some_and(&keep));
+        }
+        self.crabs.retain(keep);
+    }
+
+    /// The herd was replaced outright: draw this step's crabs where they are.
+    pub(crate) fn forget_crab_positions(&mut self) {
+        if let Some(prev) = &mut self.prev_positions {
+            prev.crabs.clear();
+        }
+    }
+
+    /// Swap the blended positions in for drawing. Returns the true post-step positions, to be put
+    /// back with [`MainState::end_interpolated_draw`]; `None` when there is nothing to blend.
+    pub(crate) fn begin_interpolated_draw(&mut self) -> Option<RenderPositions> {
+        let prev = self.prev_positions.as_ref()?;
+        let mut stepped = std::mem::take(&mut self.stepped_positions);
+        stepped.capture_from(self);
+        let alpha = self.interp_alpha;
+        self.player_pos = blend(prev.player, stepped.player, alpha);
+        self.camera_origin = blend(prev.camera, stepped.camera, alpha);
+        // Indices past the snapshot are crabs/trains spawned by the latest step: drawn as-is.
+        for (crab, &p) in self.crabs.iter_mut().zip(&prev.crabs) {
+            crab.pos = blend(p, crab.pos, alpha);
+        }
+        for (train, &p) in self.npc_trains.iter_mut().zip(&prev.trains) {
+            train.leader_pos = blend(p, train.leader_pos, alpha);
+        }
+        Some(stepped)
+    }
+
+    pub(crate) fn end_interpolated_draw(&mut self, stepped: Option<RenderPositions>) {
+        if let Some(stepped) = stepped {
+            stepped.restore(self);
+            self.stepped_positions = stepped;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemies::CrabType;
+    use crate::spawnings::spawn_scattered_crab;
+
+    #[test]
+    fn blend_lerps_small_moves_and_snaps_teleports() {
+        let a = Vec2::new(10.0, 10.0);
+        assert_eq!(blend(a, Vec2::new(20.0, 10.0), 0.25), Vec2::new(12.5, 10.0));
+        let far = Vec2::new(1000.0, 10.0);
+        assert_eq!(blend(a, far, 0.25), far);
+    }
+
+    #[test]
+    fn removing_crabs_keeps_the_snapshot_lined_up() {
+        let mut state = MainState::new_headless();
+        let mut rng = crate::rng::rng();
+        state.crabs = (0..3)
+            .map(|i| {
+                let pos = Vec2::new(100.0 + 10.0 * i as f32, 100.0);
+                spawn_scattered_crab(pos, Vec2::ZERO, CrabType::Normal, &mut rng)
+            })
+            .collect();
+        state.capture_prev_positions();
+        let before: Vec<Vec2> = state.crabs.iter().map(|c| c.pos).collect();
+        state.crabs[1].caught = true;
+        state.retain_crabs(|c| !c.caught);
+        let prev = &state.prev_positions.as_ref().unwrap().crabs;
+        assert_eq!(prev.len(), state.crabs.len());
+        assert_eq!(prev[1], before[2]);
+
+        state.forget_crab_positions();
+        assert!(state.prev_positions.as_ref().unwrap().crabs.is_empty());
+    }
+}
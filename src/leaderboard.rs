@@ -0,0 +1,397 @@
+//! Local high-score tables: one per mode (arcade, endless, daily) and one per world-map node, kept
+//! in the save (see save.rs).
+//!
+//! A table is named by its board id: [`ARCADE`], [`ENDLESS`], [`DAILY`], or the node's
+//! `WorldMapNode::id`. Arcade and endless record every run, since every run ends in an overrun. The
+//! daily table records each day's one scored attempt; practice reruns stay off it. A campaign stage records
+//! the runs that cleared it, and a tutorial records its clear times. A run makes a table when it
+//! beats one of the top [`LEADERBOARD_SIZE`]. A run that only ties an old one queues behind it, so
+//! a table never reshuffles on a tie.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::MainState;
+use crate::skins::PlayerSkin;
+
+/// How many runs each table keeps.
+pub(crate) const LEADERBOARD_SIZE: usize = 10;
+
+/// Board id of the arcade table.
+pub(crate) const ARCADE: &str = "arcade";
+/// Board id of the endless table.
+pub(crate) const ENDLESS: &str = "endless";
+/// Board id of the daily challenge table.
+pub(crate) const DAILY: &str = "daily";
+
+/// What a table ranks by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Ranking {
+    /// Most crabs banked, then the later stage, then the faster time.
+    Score,
+    /// Fastest clear. Tutorials have no score.
+    Time,
+}
+
+impl Ranking {
+    pub fn of(board: &str) -> Self {
+        if board.starts_with("tutorial-") {
+            Ranking::Time
+        } else {
+            Ranking::Score
+        }
+    }
+
+    /// How `a` ranks against `b`: `Less` when `a` is the better run.
+    fn compare(self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        let by_time = a.time.total_cmp(&b.time);
+        match self {
+            Ranking::Score => b
+                .score
+                .cmp(&a.score)
+                .then_with(|| b.stage.cmp(&a.stage))
+                .then(by_time),
+            Ranking::Time => by_time,
+        }
+    }
+}
+
+/// One finished run on a table.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LeaderboardEntry {
+    pub name: String,
+    pub score: usize,
+    /// The stage an arcade or endless run reached; `None` on a node's table.
+    pub stage: Option<usize>,
+    pub longest_train: usize,
+    /// Seconds the run lasted, or took to clear its node.
+    pub time: f32,
+    /// The day the run ended, as `YYYY-MM-DD`.
+    pub date: String,
+    pub skin: PlayerSkin,
+}
+
+impl LeaderboardEntry {
+    /// The save line's value after the board id:
+    /// `<score> <stage|-> <longest train> <time> <date> <hat> <facial hair> <accessory> <name>`.
+    /// The name goes last because it can contain spaces.
+    fn to_save_value(&self) -> String {
+        let skin = self.skin.to_save_line();
+        format!(
+            "{} {} {} {} {} {} {}",
+            self.score,
+            self.stage.map_or("-".to_string(), |s| s.to_string()),
+            self.longest_train,
+            self.time,
+            self.date,
+            skin.trim_start_matches("skin "),
+            self.name
+        )
+    }
+
+    fn from_save_value(value: &str) -> Option<Self> {
+        let mut fields = value.splitn(9, ' ');
+        let score = fields.next()?.parse().ok()?;
+        let stage = match fields.next()? {
+            "-" => None,
+            stage => Some(stage.parse().ok()?),
+        };
+        let longest_train = fields.next()?.parse().ok()?;
+        let time = fields.next()?.parse().ok()?;
+        let date = fields.next()?.to_string();
+        let skin = [fields.next()?, fields.next()?, fields.next()?].join(" ");
+        Some(Self {
+            name: crate::normalize_player_name(fields.next().unwrap_or("")),
+            score,
+            stage,
+            longest_train,
+            time,
+            date,
+            skin: PlayerSkin::from_save_line(&format!("skin {skin}")),
+        })
+    }
+
+    /// One line of a printed table, e.g. `3. Crabby (Crown) — 412 crabs · stage 9 · train 31 ·
+    /// 2026-10-18`.
+    pub fn row(&self, rank: usize, ranking: Ranking) -> String {
+        let who = match self.skin.hat {
+            crate::skins::Hat::None => self.name.clone(),
+            hat => format!("{} ({})", self.name, hat.name()),
+        };
+        let mut parts = Vec::new();
+        if ranking == Ranking::Score {
+            parts.push(format!("{} crabs", self.score));
+        }
+        match self.stage {
+            Some(stage) => parts.push(format!("stage {stage}")),
+            None => parts.push(clock(self.time)),
+        }
+        if ranking == Ranking::Score {
+            parts.push(format!("train {}", self.longest_train));
+        }
+        parts.push(self.date.clone());
+        format!("{:>2}. {who}  —  {}", rank + 1, parts.join("  ·  "))
+    }
+}
+
+/// Every table, by board id.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Leaderboards {
+    tables: BTreeMap<String, Vec<LeaderboardEntry>>,
+}
+
+impl Leaderboards {
+    /// The table `board`, best first; empty if nothing has made it yet.
+    pub fn table(&self, board: &str) -> &[LeaderboardEntry] {
+        self.tables.get(board).map_or(&[], Vec::as_slice)
+    }
+
+    /// Put `entry` on the table `board` if it makes the cut. Returns the 0-based rank it landed
+    /// at, or `None` if it fell off the bottom.
+    pub fn insert(&mut self, board: &str, entry: LeaderboardEntry) -> Option<usize> {
+        let ranking = Ranking::of(board);
+        let table = self.tables.entry(board.to_string()).or_default();
+        let rank = table
+            .iter()
+            .position(|e| ranking.compare(&entry, e) == Ordering::Less)
+            .unwrap_or(table.len());
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        table.insert(rank, entry);
+        table.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+
+    /// The save's `board` lines, one per entry, table by table.
+    pub fn save_lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.tables.iter().flat_map(|(board, table)| {
+            table
+                .iter()
+                .map(move |e| format!("board {board} {}", e.to_save_value()))
+        })
+    }
+
+    /// Read one `board` line's value: the board id, then the entry. Lines past a table's size
+    /// (a hand-edited save) are dropped.
+    pub fn parse_save_value(&mut self, value: &str) -> Option<()> {
+        let (board, entry) = value.split_once(' ')?;
+        let entry = LeaderboardEntry::from_save_value(entry)?;
+        let table = self.tables.entry(board.to_string()).or_default();
+        if table.len() < LEADERBOARD_SIZE {
+            table.push(entry);
+        }
+        Some(())
+    }
+
+    /// Read an `endless` line from a save written before the per-mode tables:
+    /// `<score> <stage> <longest train> <date> <name>`.
+    pub fn parse_legacy_endless(&mut self, value: &str) -> Option<()> {
+        let mut fields = value.splitn(5, ' ');
+        let score = fields.next()?;
+        let stage = fields.next()?;
+        let train = fields.next()?;
+        let date = fields.next()?;
+        let name = fields.next().unwrap_or("");
+        let skin = PlayerSkin::default().to_save_line();
+        let skin = skin.trim_start_matches("skin ");
+        self.parse_save_value(&format!(
+            "{ENDLESS} {score} {stage} {train} 0 {date} {skin} {name}"
+        ))
+    }
+}
+
+impl MainState {
+    /// The table the run in play belongs on: its mode's, or its world-map node's.
+    pub(crate) fn run_board(&self) -> Option<String> {
+        if self.daily.is_some() {
+            Some(DAILY.to_string())
+        } else if self.endless.is_some() {
+            Some(ENDLESS.to_string())
+        } else if self.in_campaign {
+            self.world_map.as_ref().map(|map| map.selected_id())
+        } else {
+            Some(ARCADE.to_string())
+        }
+    }
+
+    /// Put the run that just ended on its table. Arcade and endless runs call this when they are
+    /// overrun, campaign stages and tutorials when they are cleared. A daily practice run is only
+    /// shown the table.
+    pub(crate) fn record_on_board(&mut self) {
+        let Some(board) = self.run_board() else {
+            return;
+        };
+        if self.daily.as_ref().is_some_and(|daily| !daily.scored) {
+            return;
+        }
+        let entry = LeaderboardEntry {
+            name: crate::normalize_player_name(&self.player_name),
+            score: self.final_score(),
+            stage: (!self.in_campaign).then_some(self.arcade_stage),
+            longest_train: self.longest_train_run,
+            time: if self.in_campaign {
+                self.win_time
+            } else {
+                self.time_elapsed
+            },
+            // A daily attempt that runs past midnight still belongs to the day it was dealt.
+            date: self
+                .daily
+                .as_ref()
+                .map_or_else(today, |daily| daily.date.clone()),
+            skin: self.player_skin,
+        };
+        self.last_entry = self
+            .leaderboards
+            .insert(&board, entry)
+            .map(|rank| (board, rank));
+    }
+
+    /// The rank the last recorded run took on `board`, if it went there.
+    pub(crate) fn last_rank_on(&self, board: &str) -> Option<usize> {
+        self.last_entry
+            .as_ref()
+            .filter(|(b, _)| b == board)
+            .map(|&(_, rank)| rank)
+    }
+}
+
+/// `m:ss.s`, the way the world map prints a time.
+pub(crate) fn clock(secs: f32) -> String {
+    format!("{}:{:04.1}", (secs / 60.0) as u32, secs % 60.0)
+}
+
+/// Today's date (UTC) as `YYYY-MM-DD`.
+pub(crate) fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    date_from_unix(secs)
+}
+
+/// The calendar date of a unix timestamp, as `YYYY-MM-DD`. Howard Hinnant's days-to-civil.
+fn date_from_unix(secs: u64) -> String {
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skins::Hat;
+
+    fn run(name: &str, score: usize, stage: usize) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: name.to_string(),
+            score,
+            stage: Some(stage),
+            longest_train: 12,
+            time: 95.5,
+            date: "2026-10-18".to_string(),
+            skin: PlayerSkin::default(),
+        }
+    }
+
+    fn names<'a>(boards: &'a Leaderboards, board: &str) -> Vec<&'a str> {
+        boards
+            .table(board)
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn tables_keep_the_best_runs_in_order() {
+        let mut boards = Leaderboards::default();
+        assert_eq!(boards.insert(ENDLESS, run("a", 50, 3)), Some(0));
+        assert_eq!(boards.insert(ENDLESS, run("b", 80, 4)), Some(0));
+        // Same score, later stage wins; an exact tie queues behind the older run.
+        assert_eq!(boards.insert(ENDLESS, run("c", 50, 5)), Some(1));
+        assert_eq!(boards.insert(ENDLESS, run("d", 50, 5)), Some(2));
+        assert_eq!(names(&boards, ENDLESS), ["b", "c", "d", "a"]);
+        assert!(boards.table(ARCADE).is_empty());
+
+        for i in 0..LEADERBOARD_SIZE {
+            boards.insert(ENDLESS, run("filler", 100 + i, 6));
+        }
+        assert_eq!(boards.table(ENDLESS).len(), LEADERBOARD_SIZE);
+        assert_eq!(boards.insert(ENDLESS, run("late", 1, 1)), None);
+        assert_eq!(boards.insert(ENDLESS, run("top", 500, 9)), Some(0));
+        assert_eq!(boards.table(ENDLESS).len(), LEADERBOARD_SIZE);
+    }
+
+    #[test]
+    fn tutorials_rank_by_clear_time() {
+        let mut boards = Leaderboards::default();
+        let clear = |name: &str, time: f32| LeaderboardEntry {
+            stage: None,
+            time,
+            ..run(name, 0, 0)
+        };
+        boards.insert("tutorial-beat", clear("slow", 60.0));
+        boards.insert("tutorial-beat", clear("fast", 21.5));
+        boards.insert("tide-pools", clear("slow", 60.0));
+        boards.insert("tide-pools", clear("fast", 21.5));
+        assert_eq!(names(&boards, "tutorial-beat"), ["fast", "slow"]);
+        // A stage ranks by score first; on a tie, the faster clear.
+        assert_eq!(names(&boards, "tide-pools"), ["fast", "slow"]);
+        assert_eq!(
+            boards.table("tutorial-beat")[0].row(0, Ranking::Time),
+            " 1. fast  —  0:21.5  ·  2026-10-18"
+        );
+    }
+
+    #[test]
+    fn tables_round_trip_through_the_save_lines() {
+        let mut boards = Leaderboards::default();
+        let mut dressed = run("Crabby McCrabface", 412, 7);
+        dressed.skin.hat = Hat::Crown;
+        boards.insert(ARCADE, dressed);
+        boards.insert(
+            "rocky-shore",
+            LeaderboardEntry {
+                stage: None,
+                ..run("Old Timer", 90, 0)
+            },
+        );
+        let mut read = Leaderboards::default();
+        for line in boards.save_lines() {
+            let value = line.strip_prefix("board ").unwrap();
+            assert_eq!(read.parse_save_value(value), Some(()));
+        }
+        assert_eq!(read, boards);
+        assert_eq!(
+            read.table(ARCADE)[0].row(0, Ranking::Score),
+            " 1. Crabby McCrabface (Crown)  —  412 crabs  ·  stage 7  ·  train 12  ·  2026-10-18"
+        );
+        assert_eq!(
+            read.parse_save_value("arcade 12 three 4 1.0 2026-01-01 x"),
+            None
+        );
+
+        read.parse_legacy_endless("1840 9 31 2026-10-18 Crabby")
+            .unwrap();
+        let legacy = &read.table(ENDLESS)[0];
+        assert_eq!(
+            (legacy.score, legacy.stage, legacy.longest_train),
+            (1840, Some(9), 31)
+        );
+    }
+
+    #[test]
+    fn dates_follow_the_calendar() {
+        assert_eq!(date_from_unix(0), "1970-01-01");
+        assert_eq!(date_from_unix(951_782_400), "2000-02-29");
+        assert_eq!(date_from_unix(1_792_281_600), "2026-10-18");
+    }
+}
@@ -0,0 +1,141 @@
+//! Debug-build hot reload for `resources/levels/`. Save a level file while the game runs and the
+//! edit is picked up within a second. The world map shows it at once, and play uses it from the
+//! next wave.
+//!
+//! The files are polled, not watched: their modification times are compared once a second, so
+//! there is nothing platform-specific to set up. An edit that fails validation is not applied and
+//! its error is printed. Adding or removing a level, or changing a level's music, is also refused
+//! with a message, because each level's music and rival calls are baked when the game starts. So
+//! is changing a level's `id`, which its saved progress and high scores are filed under.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::MainState;
+use crate::levels::{Level, level_paths, levels_dir, load_levels_from};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The level files as last seen: each path with its modification time.
+#[derive(Debug, Default)]
+pub(crate) struct LevelWatch {
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+    next_poll: Option<Instant>,
+}
+
+impl LevelWatch {
+    fn stamps() -> Vec<(PathBuf, Option<SystemTime>)> {
+        level_paths(&levels_dir())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+
+    /// True when a level file was saved, added or removed since the last poll. The first poll
+    /// only takes the baseline.
+    fn changed(&mut self) -> bool {
+        let now = Instant::now();
+        if self.next_poll.is_some_and(|at| now < at) {
+            return false;
+        }
+        let baseline = self.next_poll.is_none();
+        self.next_poll = Some(now + POLL_INTERVAL);
+        let stamps = Self::stamps();
+        let changed = !baseline && stamps != self.stamps;
+        self.stamps = stamps;
+        changed
+    }
+}
+
+impl MainState {
+    /// Reload the level files if any changed on disk since the last poll.
+    pub(crate) fn poll_level_files(&mut self) {
+        if !self.level_watch.changed() {
+            return;
+        }
+        match load_levels_from(&levels_dir()).and_then(|levels| self.swap_levels(levels)) {
+            Ok(()) => println!("[levels] reloaded {} levels", self.levels.len()),
+            Err(e) => eprintln!("[levels] kept the running levels: {e}"),
+        }
+    }
+
+    /// Put edited `levels` in place of the running ones, or explain why they need a restart.
+    pub(crate) fn swap_levels(&mut self, levels: Vec<Level>) -> Result<(), String> {
+        if levels.len() != self.levels.len() {
+            return Err(format!(
+                "the campaign went from {} to {} levels; restart to add or remove one",
+                self.levels.len(),
+                levels.len()
+            ));
+        }
+        // Progress and high scores are saved under the id the session started with.
+        if let Some((old, level)) = self
+            .levels
+            .iter()
+            .zip(&levels)
+            .find(|(old, new)| old.id != new.id)
+        {
+            return Err(format!(
+                "`{}` changed its id from `{}` to `{}`; restart to play it under the new one",
+                level.title, old.id, level.id
+            ));
+        }
+        if let Some((_, level)) = self
+            .levels
+            .iter()
+            .zip(&levels)
+            .find(|(old, new)| old.biome.music != new.biome.music)
+        {
+            return Err(format!(
+                "`{}` changed its music; restart to hear it",
+                level.title
+            ));
+        }
+        self.levels = levels;
+        // The wave in progress may be past the end of a level that lost patterns.
+        let level = &self.levels[self.current_level.min(self.levels.len() - 1)];
+        self.current_pattern = self.current_pattern.min(level.patterns.len() - 1);
+        if let Some(map) = &mut self.world_map {
+            map.refresh_levels(&self.levels);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::BiomeMusic;
+
+    #[test]
+    fn edited_levels_swap_in_unless_they_need_a_restart() {
+        let mut state = MainState::new_headless();
+        state.enter_world_map();
+        state.current_level = 1;
+        state.current_pattern = 4;
+
+        let mut edited = state.levels.clone();
+        edited[1].biome.name = "Riptide Flats".to_string();
+        edited[1].map_position = (0.6, 0.3);
+        edited[1].patterns.truncate(2);
+        state.swap_levels(edited.clone()).unwrap();
+        assert_eq!(state.current_pattern, 1);
+        let node = &state.world_map.as_ref().unwrap().nodes[5];
+        assert_eq!(node.name, "Stage 2 — Riptide Flats");
+        assert_eq!(node.position, (0.6, 0.3));
+
+        let mut retuned = edited.clone();
+        retuned[1].biome.music = BiomeMusic::SunnyGroove;
+        assert!(state.swap_levels(retuned).unwrap_err().contains("music"));
+        let mut renamed = edited.clone();
+        renamed[1].id = "riptide-flats".to_string();
+        assert!(state.swap_levels(renamed).unwrap_err().contains("restart"));
+        edited.pop();
+        assert!(state.swap_levels(edited).unwrap_err().contains("restart"));
+        assert_eq!(state.levels[1].biome.music, BiomeMusic::TidalDorian);
+    }
+}
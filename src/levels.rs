@@ -1,4 +1,67 @@
+//! Campaign levels: what a stage is made of, and the loader for the data files that describe them.
+//!
+//! Each stage is one file in `resources/levels/`. The campaign plays them in file-name order (a
+//! number prefix sets the running order), and each file also places its stage on the world map,
+//! so adding or tweaking a stage needs no rebuild. The format is line-based text, like a bot
+//! script:
+//!
+//! ```text
+//! rustler-level 1
+//! # Comments and blank lines are ignored.
+//! id tide-pools
+//! title Undertow Shuffle
+//! description Route a growing train through dragging tidal pools.
+//! difficulty 2
+//! map_size tutorial
+//! map_position 0.63 0.29
+//! biome Tide Pools
+//! tint 150 215 255
+//! pulse 90 200 255
+//! terrain water
+//! layout coast
+//! music tidal_dorian
+//! emphasis magnet
+//! bosses tide_boss
+//! win build_train 15
+//! pattern uniform_random 10 11.2 0.7 0.3
+//! pattern circle 15 16.8 0.5 0.5
+//! ```
+//!
+//! Every key except `emphasis` is required, and each appears once, except `pattern`. `id` names the
+//! level's world-map node for saves, high-score tables and other levels' `route` lines, so it
+//! stays put when files are added or renumbered: lowercase letters, digits and dashes, unique
+//! across the campaign. Each
+//! `pattern` line adds one spawn wave: its shape, crab count, seconds to clear it, and centroid as
+//! fractions of the map. Names are the Rust variants in snake_case (`hermit_king`, `sine_wave`).
+//! `bosses` is the cycle the level's boss encounters walk through. `win` is one of:
+//! - `bank_crabs <n>`
+//! - `build_train <n>`
+//! - `crack_and_hold shells <n> min_train <n>`
+//! - `hold_train target <n> seconds <s>`
+//! - `steal_back <n>`
+//! - `outgrow_rivals seconds <s>`
+//! - `dethrone_king <name>`
+//! - `perfect_streak <n>`
+//! - `escort <archetype>`
+//! - `catch_bosses <n>`
+//!
+//! `steal_back`, `outgrow_rivals` and `dethrone_king` are played against rival trains, which a
+//! `map_size tutorial` level doesn't have, so that pairing is refused.
+//!
+//! Two optional keys place the level on the world map's route graph (see world_map.rs). Each
+//! `route <node> [train <n> | score <n> | under <s>]` line opens the level from another node, a
+//! level's `id` or a tutorial id, once that node is won by a run that also reached the train length,
+//! score or finishing time given. A level without `route` lines follows the main-route level before
+//! it. `branch challenge`, `branch boss` or `branch secret` puts it on a side path instead of the
+//! main route; a boss node sends its bosses in back to back from the first second.
+//!
+//! Mistakes are reported with the file and line. A debug build also picks up edits while the game
+//! is running (see `level_reload.rs`).
+
+use std::path::{Path, PathBuf};
+
 use crate::enemies::CrabType;
+use crate::event_log::snake_name;
 use crate::spawnings::SpawnPattern;
 
 /// Playfield size relative to the fixed game viewport. Keeping this on `Level` makes the campaign's
@@ -31,7 +94,7 @@ impl MapSize {
 /// built for — not just "get X score" — so crossing into the next biome feels like a gear change.
 /// Evaluated every frame during a campaign run (see the win-check block in `game_update`); when
 /// met, the world-map node completes and the next one unlocks.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum WinCondition {
     /// Total crabs banked at the pen reaches this count.
     BankCrabs(usize),
@@ -43,54 +106,107 @@ pub enum WinCondition {
     /// Train stays at or above `target` for `seconds` consecutive seconds (the timer resets the
     /// moment the train dips below the target).
     HoldTrain { target: usize, seconds: f32 },
+    /// Crabs rustled off rival trains (the player's steal-back) reaches this count.
+    StealBack(usize),
+    /// The train stays strictly the longest on the beach for `seconds` consecutive seconds (the
+    /// timer resets the moment a rival draws level).
+    OutgrowRivals { seconds: f32 },
+    /// The rival King with this name loses its last follower to a steal-back. The level crowns
+    /// one of its rival trains with the name (see `MainState::crown_goal_king`).
+    DethroneKing(String),
+    /// PERFECT-window catches in a row reaches this count.
+    PerfectStreak(usize),
+    /// A crab of this archetype rides the train into the pen.
+    Escort(CrabType),
+    /// Bosses caught this run reaches this count.
+    CatchBosses(usize),
+}
+
+/// The live run counters a [`WinCondition`] is measured against, gathered by
+/// `MainState::goal_progress`. Each condition reads only the fields it needs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GoalProgress {
+    pub banked: usize,
+    pub train: usize,
+    pub shells: usize,
+    /// Seconds the train has continuously been at or above the HoldTrain target.
+    pub hold_secs: f32,
+    /// Crabs rustled off rival trains.
+    pub stolen: usize,
+    /// Seconds the train has continuously been the longest on the beach.
+    pub outgrow_secs: f32,
+    /// Followers of the longest rival train.
+    pub longest_rival: usize,
+    /// Followers the DethroneKing target has left; `None` while it isn't on the beach.
+    pub king_followers: Option<usize>,
+    /// Whether the DethroneKing target has been stripped bare this run.
+    pub dethroned: bool,
+    pub perfect_streak: usize,
+    /// Crabs of the Escort archetype banked this run, and whether one is riding the train now.
+    pub escorted: usize,
+    pub escorting: bool,
+    pub bosses: usize,
 }
 
 impl WinCondition {
+    /// Whether the goal is played against rival trains, so a map without them could never be won
+    /// fairly (see `MapSize::spawns_npc_trains`).
+    pub fn needs_rivals(&self) -> bool {
+        matches!(
+            self,
+            WinCondition::StealBack(_)
+                | WinCondition::OutgrowRivals { .. }
+                | WinCondition::DethroneKing(_)
+        )
+    }
+
     /// Pure evaluation against the live run counters, so the same predicate is reachable from a
-    /// headless test as from the frame loop. `hold_secs` is how long the train has continuously
-    /// been at or above the HoldTrain target (maintained by the caller).
-    pub fn met(&self, banked: usize, train: usize, shells: usize, hold_secs: f32) -> bool {
+    /// headless test as from the frame loop. The streak clocks in `progress` are maintained by the
+    /// caller.
+    pub fn met(&self, progress: &GoalProgress) -> bool {
+        let p = progress;
         match *self {
-            WinCondition::BankCrabs(n) => banked >= n,
-            WinCondition::BuildTrain(n) => train >= n,
+            WinCondition::BankCrabs(n) => p.banked >= n,
+            WinCondition::BuildTrain(n) => p.train >= n,
             WinCondition::CrackAndHold {
                 shells: s,
                 min_train,
-            } => shells >= s && train >= min_train,
-            WinCondition::HoldTrain { seconds, .. } => hold_secs >= seconds,
+            } => p.shells >= s && p.train >= min_train,
+            WinCondition::HoldTrain { seconds, .. } => p.hold_secs >= seconds,
+            WinCondition::StealBack(n) => p.stolen >= n,
+            WinCondition::OutgrowRivals { seconds } => p.outgrow_secs >= seconds,
+            WinCondition::DethroneKing(_) => p.dethroned,
+            WinCondition::PerfectStreak(n) => p.perfect_streak >= n,
+            WinCondition::Escort(_) => p.escorted > 0,
+            WinCondition::CatchBosses(n) => p.bosses >= n,
         }
     }
 
     /// Short live-progress line for the HUD corner counter, so the player always knows where they
     /// stand against the goal.
-    pub fn progress_text(
-        &self,
-        banked: usize,
-        train: usize,
-        shells: usize,
-        hold_secs: f32,
-    ) -> String {
+    pub fn progress_text(&self, progress: &GoalProgress) -> String {
+        let p = progress;
         match *self {
-            WinCondition::BankCrabs(n) => format!("GOAL  Bank crabs: {} / {}", banked.min(n), n),
+            WinCondition::BankCrabs(n) => format!("GOAL  Bank crabs: {} / {}", p.banked.min(n), n),
             WinCondition::BuildTrain(n) => {
-                format!("GOAL  Train of {} at once: {} / {}", n, train.min(n), n)
+                format!("GOAL  Train of {} at once: {} / {}", n, p.train.min(n), n)
             }
             WinCondition::CrackAndHold {
                 shells: s,
                 min_train,
             } => format!(
                 "GOAL  Shells cracked: {} / {}  |  Train: {} (keep \u{2265} {})",
-                shells.min(s),
+                p.shells.min(s),
                 s,
-                train,
+                p.train,
                 min_train
             ),
             WinCondition::HoldTrain { target, seconds } => {
-                if train >= target {
+                if p.train >= target {
                     format!(
                         "GOAL  Hold train \u{2265} {}: {:.0}s / {:.0}s",
                         target,
-                        hold_secs.min(seconds),
+                        p.hold_secs.min(seconds),
                         seconds
                     )
                 } else {
@@ -100,10 +216,106 @@ impl WinCondition {
                     )
                 }
             }
+            WinCondition::StealBack(n) => {
+                format!("GOAL  Rustle crabs off rivals: {} / {}", p.stolen.min(n), n)
+            }
+            WinCondition::OutgrowRivals { seconds } => {
+                if p.train > p.longest_rival {
+                    format!(
+                        "GOAL  Longest train on the beach: {:.0}s / {:.0}s",
+                        p.outgrow_secs.min(seconds),
+                        seconds
+                    )
+                } else {
+                    format!(
+                        "GOAL  Outgrow every rival ({} long) and stay ahead {:.0}s",
+                        p.longest_rival, seconds
+                    )
+                }
+            }
+            WinCondition::DethroneKing(ref name) => match p.king_followers {
+                Some(n) if !p.dethroned => {
+                    format!("GOAL  Dethrone {name}: {n} followers left")
+                }
+                _ => format!("GOAL  Dethrone {name}"),
+            },
+            WinCondition::PerfectStreak(n) => format!(
+                "GOAL  PERFECT catches in a row: {} / {}",
+                p.perfect_streak.min(n),
+                n
+            ),
+            WinCondition::Escort(kind) => {
+                let kind = snake_name(&kind).replace('_', " ");
+                if p.escorting {
+                    format!("GOAL  Get the {kind} crab to the pen!")
+                } else {
+                    format!("GOAL  Catch a {kind} crab and bank it")
+                }
+            }
+            WinCondition::CatchBosses(n) => {
+                format!("GOAL  Bosses caught: {} / {}", p.bosses.min(n), n)
+            }
+        }
+    }
+}
+
+/// Which side path off the world map's main route a level sits on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Branch {
+    /// An optional detour with a harder goal.
+    Challenge,
+    /// Bosses only, back to back.
+    Boss,
+    /// Hidden on the map until its route opens.
+    Secret,
+}
+
+/// What the winning run on a route's source node must also have done to open the route.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnlockRule {
+    /// Any win.
+    Win,
+    /// The run's longest train reached this length.
+    Train(usize),
+    /// The run scored at least this much.
+    Score(usize),
+    /// The goal was met within this many seconds.
+    Under(f32),
+}
+
+impl UnlockRule {
+    /// Whether a winning run with this `score`, goal `time` and longest `train` opens the route.
+    pub fn met(self, score: usize, time: f32, train: usize) -> bool {
+        match self {
+            UnlockRule::Win => true,
+            UnlockRule::Train(n) => train >= n,
+            UnlockRule::Score(n) => score >= n,
+            UnlockRule::Under(seconds) => time < seconds,
+        }
+    }
+
+    /// The requirement for the world-map caption: "win Stage 6 with a train ≥ 20".
+    pub fn describe(self, from: &str) -> String {
+        match self {
+            UnlockRule::Win => format!("win {from}"),
+            UnlockRule::Train(n) => format!("win {from} with a train \u{2265} {n}"),
+            UnlockRule::Score(n) => format!("win {from} scoring \u{2265} {n}"),
+            UnlockRule::Under(seconds) => {
+                format!("win {from} in under {}", crate::leaderboard::clock(seconds))
+            }
         }
     }
 }
 
+/// A `route` line: the level opens once the node `from` is won under `rule`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route {
+    /// The source node's save id (see `WorldMapNode::id`).
+    pub from: String,
+    pub rule: UnlockRule,
+}
+
+#[derive(Clone, Debug)]
 pub struct LevelPattern {
     pub pattern: SpawnPattern,
     pub count: usize,
@@ -114,7 +326,7 @@ pub struct LevelPattern {
 /// What the terrain patches in a biome physically *do* — the mechanical wrinkle that makes each
 /// zone route differently, not just look different. The same patch geometry (see `pick_tide_pools`)
 /// is reused for all of them; the kind decides how the player and train interact with a patch.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TerrainKind {
     /// No terrain hazard — an open, gentle field. The beginner zone.
     Open,
@@ -153,9 +365,9 @@ pub enum MapLayout {
 /// they travel somewhere instead of one continuous space. `tint` is a multiply grade laid over
 /// the whole ground; `pulse` recolors the on-beat flash to match the zone's mood; `terrain` is the
 /// mechanical wrinkle its patches carry; `layout` gives the ground a distinct broad composition.
-#[derive(Clone, Copy)]
+#[derive(Clone, Debug)]
 pub struct Biome {
-    pub name: &'static str,
+    pub name: String,
     pub tint: (u8, u8, u8),
     pub pulse: (u8, u8, u8),
     pub terrain: TerrainKind,
@@ -178,11 +390,20 @@ pub enum BiomeMusic {
     DesktopChip,
 }
 
+#[derive(Clone, Debug)]
 pub struct Level {
+    /// The level's world-map node id (see the module docs); never its position in the file order.
+    pub id: String,
     pub title: String,
     pub description: String,
     pub difficulty: usize,
     pub map_size: MapSize,
+    /// Where the level's node sits on the world map, as fractions (0..1, 0..1) of the map canvas.
+    pub map_position: (f32, f32),
+    /// The side path the level sits on; `None` on the main route.
+    pub branch: Option<Branch>,
+    /// The routes that open the level. Empty means it follows the main-route level before it.
+    pub routes: Vec<Route>,
     pub biome: Biome,
     /// The herd archetype this zone leans on — its "second half" of the gear-change. Terrain
     /// (above) changes how the ground routes; `emphasis` changes *what you're catching* so
@@ -195,7 +416,7 @@ pub struct Level {
     /// while the Desktop deliberately cycles through every boss as its meme finale.
     pub boss_sequence: Vec<CrabType>,
     /// The completion goal for this level during a campaign run. Meeting it completes the
-    /// world-map node and unlocks the next one.
+    /// world-map node and opens the routes out of it.
     pub win_condition: WinCondition,
     pub patterns: Vec<LevelPattern>,
 }
@@ -238,400 +459,447 @@ pub fn boss_label(boss: CrabType) -> &'static str {
     }
 }
 
-pub fn get_levels() -> Vec<Level> {
-    vec![
-        Level {
-            title: "Tutorial".to_string(),
-            description: "Learn the full catch, train, and bank loop on open sand.".to_string(),
-            difficulty: 0,
-            map_size: MapSize::Tutorial,
-            biome: Biome {
-                name: "Sunny Meadow",
-                tint: (255, 248, 214),
-                pulse: (120, 255, 120),
-                terrain: TerrainKind::Open,
-                layout: MapLayout::Meadow,
-                music: BiomeMusic::SunnyGroove,
-            },
-            emphasis: None,
-            boss_sequence: vec![CrabType::Boss],
-            // Clean intro: teaches the full catch -> train -> bank loop with no hazards.
-            win_condition: WinCondition::BankCrabs(25),
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::SingleRandom,
-                    count: 6,
-                    duration: 14.0,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::SingleRandom,
-                    count: 4,
-                    duration: 11.2,
-                    centroid: (0.2, 0.8),
-                },
-            ],
-        },
-        Level {
-            title: "Undertow Shuffle".to_string(),
-            description: "Route a growing train through dragging tidal pools.".to_string(),
-            difficulty: 2,
-            map_size: MapSize::Tutorial,
-            biome: Biome {
-                name: "Tide Pools",
-                tint: (150, 215, 255),
-                pulse: (90, 200, 255),
-                terrain: TerrainKind::Water,
-                layout: MapLayout::Coast,
-                music: BiomeMusic::TidalDorian,
-            },
-            // Water routes the herd; the Magnet reroutes it again by clustering free crabs — the
-            // zone becomes a routing puzzle where you catch a Magnet to net the blob it gathered.
-            emphasis: Some(CrabType::Magnet),
-            boss_sequence: vec![CrabType::TideBoss],
-            // One gross catching move: a well-timed Magnet catch scoops the clustered herd, so the
-            // win fires mid-wave the instant the train hits 15 — no banking, no patience required.
-            win_condition: WinCondition::BuildTrain(15),
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::UniformRandom,
-                    count: 10,
-                    duration: 11.2,
-                    centroid: (0.7, 0.3),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::SineWave,
-                    count: 13,
-                    duration: 14.0,
-                    centroid: (0.3, 0.7),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Circle,
-                    count: 15,
-                    duration: 16.8,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 18,
-                    duration: 14.0,
-                    centroid: (0.8, 0.8),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 11,
-                    duration: 8.4,
-                    centroid: (0.2, 0.2),
-                },
-            ],
-        },
-        Level {
-            title: "Breaker's Passage".to_string(),
-            description: "Crack shells while threading the rocky chokepoints.".to_string(),
-            difficulty: 3,
-            map_size: MapSize::Tutorial,
-            biome: Biome {
-                name: "Rocky Shore",
-                tint: (178, 192, 208),
-                pulse: (205, 222, 235),
-                terrain: TerrainKind::Rock,
-                layout: MapLayout::Coast,
-                music: BiomeMusic::RockShanty,
-            },
-            // Rocky chokepoints already make you thread the train; the Armored emphasis makes you
-            // reach for the Stomp constantly — a zone of shells to crack while dodging the rocks.
-            emphasis: Some(CrabType::Armored),
-            boss_sequence: vec![CrabType::HermitKing],
-            // Two gates force both verbs: stomp shells open in the rock chokepoints AND hold a
-            // real train — no cheesing shells from a safe corner while ignoring the herd.
-            win_condition: WinCondition::CrackAndHold {
-                shells: 8,
-                min_train: 15,
-            },
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 22,
-                    duration: 14.0,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::SineWave,
-                    count: 18,
-                    duration: 16.8,
-                    centroid: (0.8, 0.2),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Circle,
-                    count: 26,
-                    duration: 19.6,
-                    centroid: (0.2, 0.8),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 15,
-                    duration: 11.2,
-                    centroid: (0.8, 0.8),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::SineWave,
-                    count: 11,
-                    duration: 8.4,
-                    centroid: (0.2, 0.2),
-                },
-            ],
-        },
-        Level {
-            title: "Kelp After Dark".to_string(),
-            description: "Defend a packed conga line on a snagging neon dance floor.".to_string(),
-            difficulty: 4,
-            map_size: MapSize::Tutorial,
-            biome: Biome {
-                name: "Neon Kelp Forest",
-                tint: (120, 185, 150),
-                pulse: (255, 90, 220),
-                terrain: TerrainKind::Kelp,
-                layout: MapLayout::River,
-                music: BiomeMusic::KelpDisco,
-            },
-            // Kelp already snags your tail loose; a Thief infestation gnaws at it too — the whole
-            // zone is one long fight to defend the train you've built. Tail pressure squared.
-            emphasis: Some(CrabType::Thief),
-            boss_sequence: vec![CrabType::Boss],
-            // Pure defense: getting to 20 is easy, keeping them against kelp snags and Thieves is
-            // the whole game. The 30s timer resets the moment the train dips below 20.
-            win_condition: WinCondition::HoldTrain {
-                target: 20,
-                seconds: 30.0,
-            },
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::BeatGrid,
-                    count: 16,
-                    duration: 16.8,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Spiral,
-                    count: 22,
-                    duration: 19.6,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::BeatGrid,
-                    count: 30,
-                    duration: 19.6,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Spiral,
-                    count: 38,
-                    duration: 22.4,
-                    centroid: (0.5, 0.5),
-                },
-            ],
-        },
-        Level {
-            title: "Lunar Waltz".to_string(),
-            description: "Follow the beat through a moonlit dance floor.".to_string(),
-            difficulty: 5,
-            map_size: MapSize::Large,
-            biome: Biome {
-                name: "Moonlit Ballroom",
-                tint: (126, 118, 190),
-                pulse: (255, 170, 245),
-                terrain: TerrainKind::Open,
-                layout: MapLayout::Beach,
-                music: BiomeMusic::MoonlitWaltz,
-            },
-            emphasis: Some(CrabType::Dancer),
-            boss_sequence: vec![CrabType::RhythmBoss],
-            win_condition: WinCondition::BuildTrain(24),
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::BeatGrid,
-                    count: 22,
-                    duration: 16.8,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Spiral,
-                    count: 28,
-                    duration: 19.6,
-                    centroid: (0.3, 0.7),
-                },
-            ],
-        },
-        Level {
-            title: "Hermit's March".to_string(),
-            description: "Crack the borrowed shells before the Warren closes in.".to_string(),
-            difficulty: 6,
-            map_size: MapSize::Large,
-            biome: Biome {
-                name: "Shellgrave Warren",
-                tint: (184, 146, 112),
-                pulse: (255, 205, 125),
-                terrain: TerrainKind::Rock,
-                layout: MapLayout::Beach,
-                music: BiomeMusic::WarrenMarch,
-            },
-            emphasis: Some(CrabType::Hermit),
-            boss_sequence: vec![CrabType::HermitKing],
-            win_condition: WinCondition::CrackAndHold {
-                shells: 12,
-                min_train: 18,
-            },
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 24,
-                    duration: 16.8,
-                    centroid: (0.4, 0.4),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Circle,
-                    count: 30,
-                    duration: 19.6,
-                    centroid: (0.7, 0.6),
-                },
-            ],
-        },
-        Level {
-            title: "Gilded Current".to_string(),
-            description: "Chase the shine before the tide hides the prize.".to_string(),
-            difficulty: 7,
-            map_size: MapSize::Large,
-            biome: Biome {
-                name: "Sunken Treasury",
-                tint: (214, 180, 106),
-                pulse: (255, 245, 130),
-                terrain: TerrainKind::Water,
-                layout: MapLayout::Underwater,
-                music: BiomeMusic::TreasuryRave,
+/// Where the campaign's level files live: `resources/levels`, next to the game's other resources.
+pub fn levels_dir() -> PathBuf {
+    crate::resource_dir().join("levels")
+}
+
+/// Load and validate the campaign from `resources/levels/`.
+pub fn load_levels() -> Result<Vec<Level>, String> {
+    load_levels_from(&levels_dir())
+}
+
+/// Every `.level` file in `dir`, in running order.
+pub fn level_paths(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("could not read {}: {e}", dir.display()))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "level"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Load every level file in `dir`, checking them against each other as well as one by one.
+pub fn load_levels_from(dir: &Path) -> Result<Vec<Level>, String> {
+    let paths = level_paths(dir)?;
+    if paths.is_empty() {
+        return Err(format!("no .level files in {}", dir.display()));
+    }
+    let levels = paths
+        .iter()
+        .map(|p| Level::load(p))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (i, level) in levels.iter().enumerate() {
+        if crate::world_map::is_tutorial_id(&level.id) {
+            return Err(format!(
+                "{}: id `{}` is a tutorial's",
+                paths[i].display(),
+                level.id
+            ));
+        }
+        if let Some(j) = levels[..i].iter().position(|other| other.id == level.id) {
+            return Err(format!(
+                "{}: id `{}` is already taken by {}",
+                paths[i].display(),
+                level.id,
+                paths[j].display()
+            ));
+        }
+        for route in &level.routes {
+            if route.from == level.id {
+                return Err(format!(
+                    "{}: a level can't route from itself",
+                    paths[i].display()
+                ));
+            }
+            if !crate::world_map::is_node_id(&route.from, &levels) {
+                return Err(format!(
+                    "{}: route from unknown node `{}`",
+                    paths[i].display(),
+                    route.from
+                ));
+            }
+        }
+        if let Some(j) = levels[..i]
+            .iter()
+            .position(|other| other.map_position == level.map_position)
+        {
+            return Err(format!(
+                "{}: map_position is already taken by {}",
+                paths[i].display(),
+                paths[j].display()
+            ));
+        }
+    }
+    Ok(levels)
+}
+
+impl Level {
+    /// A level file anywhere on disk.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, line)) if line.trim() == HEADER => {}
+            _ => return Err(format!("missing `{HEADER}` header")),
+        }
+        let mut fields = LevelFields::default();
+        for (index, line) in lines {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, rest) = line
+                .split_once(char::is_whitespace)
+                .map_or((line, ""), |(key, rest)| (key, rest.trim()));
+            fields
+                .set(key, rest)
+                .map_err(|e| format!("line {}: {e}", index + 1))?;
+        }
+        fields.finish()
+    }
+}
+
+const HEADER: &str = "rustler-level 1";
+
+/// Every key a level file understands, with how its line is written.
+const KEYS: [(&str, &str); 18] = [
+    ("id", "id <slug>"),
+    ("title", "title <text>"),
+    ("description", "description <text>"),
+    ("difficulty", "difficulty <n>"),
+    ("map_size", "map_size <size>"),
+    ("map_position", "map_position <x> <y>"),
+    ("branch", "branch <side path>"),
+    ("route", "route <node> [train <n> | score <n> | under <s>]"),
+    ("biome", "biome <name>"),
+    ("tint", "tint <r> <g> <b>"),
+    ("pulse", "pulse <r> <g> <b>"),
+    ("terrain", "terrain <kind>"),
+    ("layout", "layout <layout>"),
+    ("music", "music <theme>"),
+    ("emphasis", "emphasis <archetype>"),
+    ("bosses", "bosses <boss> [<boss> ...]"),
+    ("win", "win <condition> ..."),
+    ("pattern", "pattern <shape> <count> <seconds> <x> <y>"),
+];
+
+const MAP_SIZES: [MapSize; 3] = [MapSize::Tutorial, MapSize::Medium, MapSize::Large];
+const BRANCHES: [Branch; 3] = [Branch::Challenge, Branch::Boss, Branch::Secret];
+const TERRAINS: [TerrainKind; 5] = [
+    TerrainKind::Open,
+    TerrainKind::Water,
+    TerrainKind::Rock,
+    TerrainKind::Kelp,
+    TerrainKind::Desktop,
+];
+const LAYOUTS: [MapLayout; 5] = [
+    MapLayout::Meadow,
+    MapLayout::Beach,
+    MapLayout::Underwater,
+    MapLayout::Coast,
+    MapLayout::River,
+];
+const THEMES: [BiomeMusic; 9] = [
+    BiomeMusic::SunnyGroove,
+    BiomeMusic::TidalDorian,
+    BiomeMusic::RockShanty,
+    BiomeMusic::KelpDisco,
+    BiomeMusic::MoonlitWaltz,
+    BiomeMusic::WarrenMarch,
+    BiomeMusic::TreasuryRave,
+    BiomeMusic::SplitterShanty,
+    BiomeMusic::DesktopChip,
+];
+/// The herd archetypes a level can lean on.
+pub(crate) const EMPHASES: [CrabType; 11] = [
+    CrabType::Normal,
+    CrabType::Fast,
+    CrabType::Big,
+    CrabType::Sneaky,
+    CrabType::Armored,
+    CrabType::Dancer,
+    CrabType::Magnet,
+    CrabType::Thief,
+    CrabType::Hermit,
+    CrabType::Golden,
+    CrabType::Splitter,
+];
+pub(crate) const BOSSES: [CrabType; 5] = [
+    CrabType::Boss,
+    CrabType::TideBoss,
+    CrabType::RhythmBoss,
+    CrabType::HermitKing,
+    CrabType::DancerKing,
+];
+pub(crate) const SHAPES: [SpawnPattern; 7] = [
+    SpawnPattern::UniformRandom,
+    SpawnPattern::SineWave,
+    SpawnPattern::Circle,
+    SpawnPattern::Cluster,
+    SpawnPattern::SingleRandom,
+    SpawnPattern::BeatGrid,
+    SpawnPattern::Spiral,
+];
+
+/// A level file's keys as they are read, before the required ones are checked for.
+#[derive(Default)]
+struct LevelFields {
+    id: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    difficulty: Option<usize>,
+    map_size: Option<MapSize>,
+    map_position: Option<(f32, f32)>,
+    branch: Option<Branch>,
+    routes: Vec<Route>,
+    biome: Option<String>,
+    tint: Option<(u8, u8, u8)>,
+    pulse: Option<(u8, u8, u8)>,
+    terrain: Option<TerrainKind>,
+    layout: Option<MapLayout>,
+    music: Option<BiomeMusic>,
+    emphasis: Option<CrabType>,
+    bosses: Option<Vec<CrabType>>,
+    win: Option<WinCondition>,
+    patterns: Vec<LevelPattern>,
+}
+
+impl LevelFields {
+    /// Read one `key rest` line into its slot.
+    fn set(&mut self, key: &str, rest: &str) -> Result<(), String> {
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        match (key, words.as_slice()) {
+            ("id", [id]) => once(&mut self.id, key, slug(id)?),
+            ("title", [_, ..]) => once(&mut self.title, key, rest.to_string()),
+            ("description", [_, ..]) => once(&mut self.description, key, rest.to_string()),
+            ("difficulty", [n]) => once(&mut self.difficulty, key, count(n)?),
+            ("map_size", [name]) => once(
+                &mut self.map_size,
+                key,
+                named(&MAP_SIZES, name, "map size")?,
+            ),
+            ("map_position", [x, y]) => {
+                once(&mut self.map_position, key, (fraction(x)?, fraction(y)?))
+            }
+            ("branch", [name]) => once(&mut self.branch, key, named(&BRANCHES, name, "branch")?),
+            ("route", [from, rule @ ..]) => {
+                self.routes.push(Route {
+                    from: from.to_string(),
+                    rule: parse_rule(rule)?,
+                });
+                Ok(())
+            }
+            ("biome", [_, ..]) => once(&mut self.biome, key, rest.to_string()),
+            ("tint", [r, g, b]) => once(&mut self.tint, key, rgb(r, g, b)?),
+            ("pulse", [r, g, b]) => once(&mut self.pulse, key, rgb(r, g, b)?),
+            ("terrain", [name]) => once(&mut self.terrain, key, named(&TERRAINS, name, "terrain")?),
+            ("layout", [name]) => once(&mut self.layout, key, named(&LAYOUTS, name, "layout")?),
+            ("music", [name]) => once(&mut self.music, key, named(&THEMES, name, "music theme")?),
+            ("emphasis", [name]) => once(
+                &mut self.emphasis,
+                key,
+                named(&EMPHASES, name, "archetype")?,
+            ),
+            ("bosses", [_, ..]) => {
+                let bosses = words
+                    .iter()
+                    .map(|name| named(&BOSSES, name, "boss"))
+                    .collect::<Result<_, _>>()?;
+                once(&mut self.bosses, key, bosses)
+            }
+            ("win", _) => once(&mut self.win, key, parse_win(&words)?),
+            ("pattern", [shape, n, secs, x, y]) => {
+                let count = count(n)?;
+                if count == 0 {
+                    return Err("a pattern needs at least one crab".into());
+                }
+                self.patterns.push(LevelPattern {
+                    pattern: named(&SHAPES, shape, "pattern shape")?,
+                    count,
+                    duration: seconds(secs)?,
+                    centroid: (fraction(x)?, fraction(y)?),
+                });
+                Ok(())
+            }
+            _ => match KEYS.iter().find(|(known, _)| *known == key) {
+                Some((_, usage)) => Err(format!("expected `{usage}`")),
+                None => Err(format!("unknown key `{key}`")),
             },
-            emphasis: Some(CrabType::Golden),
-            boss_sequence: vec![CrabType::Boss],
-            win_condition: WinCondition::BankCrabs(55),
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::UniformRandom,
-                    count: 26,
-                    duration: 16.8,
-                    centroid: (0.6, 0.3),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 34,
-                    duration: 22.4,
-                    centroid: (0.3, 0.7),
-                },
-            ],
-        },
-        Level {
-            title: "Cutlass Causeway".to_string(),
-            description: "Shape the train carefully: every catch can cut it in two.".to_string(),
-            difficulty: 8,
-            map_size: MapSize::Large,
+        }
+    }
+
+    /// The finished level, or the first required key the file left out.
+    fn finish(self) -> Result<Level, String> {
+        fn required<T>(slot: Option<T>, key: &str) -> Result<T, String> {
+            slot.ok_or_else(|| format!("missing `{key}`"))
+        }
+        if self.patterns.is_empty() {
+            return Err("missing `pattern` (a level needs at least one wave)".into());
+        }
+        if let (Some(win), Some(map_size)) = (&self.win, self.map_size)
+            && win.needs_rivals()
+            && !map_size.spawns_npc_trains()
+        {
+            return Err(format!(
+                "`win` needs rival trains, and a `map_size {}` level has none",
+                snake_name(&map_size)
+            ));
+        }
+        Ok(Level {
+            id: required(self.id, "id")?,
+            title: required(self.title, "title")?,
+            description: required(self.description, "description")?,
+            difficulty: required(self.difficulty, "difficulty")?,
+            map_size: required(self.map_size, "map_size")?,
+            map_position: required(self.map_position, "map_position")?,
+            branch: self.branch,
+            routes: self.routes,
             biome: Biome {
-                name: "The Splitter's Causeway",
-                tint: (190, 132, 156),
-                pulse: (255, 125, 180),
-                terrain: TerrainKind::Kelp,
-                layout: MapLayout::River,
-                music: BiomeMusic::SplitterShanty,
-            },
-            emphasis: Some(CrabType::Splitter),
-            boss_sequence: vec![CrabType::Boss],
-            win_condition: WinCondition::HoldTrain {
-                target: 24,
-                seconds: 36.0,
+                name: required(self.biome, "biome")?,
+                tint: required(self.tint, "tint")?,
+                pulse: required(self.pulse, "pulse")?,
+                terrain: required(self.terrain, "terrain")?,
+                layout: required(self.layout, "layout")?,
+                music: required(self.music, "music")?,
             },
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::SineWave,
-                    count: 28,
-                    duration: 19.6,
-                    centroid: (0.5, 0.3),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Spiral,
-                    count: 36,
-                    duration: 22.4,
-                    centroid: (0.5, 0.7),
-                },
-            ],
+            emphasis: self.emphasis,
+            boss_sequence: required(self.bosses, "bosses")?,
+            win_condition: required(self.win, "win")?,
+            patterns: self.patterns,
+        })
+    }
+}
+
+/// Fill a key's slot, refusing a second line for the same key.
+fn once<T>(slot: &mut Option<T>, key: &str, value: T) -> Result<(), String> {
+    match slot.replace(value) {
+        Some(_) => Err(format!("`{key}` given twice")),
+        None => Ok(()),
+    }
+}
+
+/// The option called `name` (its variant in snake_case), or an error listing the choices.
+fn named<T: Clone + std::fmt::Debug>(options: &[T], name: &str, what: &str) -> Result<T, String> {
+    options
+        .iter()
+        .find(|option| snake_name(*option) == name)
+        .cloned()
+        .ok_or_else(|| {
+            let names: Vec<String> = options.iter().map(snake_name).collect();
+            format!("unknown {what} `{name}` (expected {})", names.join(", "))
+        })
+}
+
+fn slug(value: &str) -> Result<String, String> {
+    if value
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "bad id `{value}` (lowercase letters, digits and dashes only)"
+        ))
+    }
+}
+
+fn count(value: &str) -> Result<usize, String> {
+    value.parse().map_err(|_| format!("bad count `{value}`"))
+}
+
+/// A goal count: at least one, or the level would be won on arrival.
+fn goal(value: &str) -> Result<usize, String> {
+    match count(value)? {
+        0 => Err("a goal of 0 is met before the level starts".into()),
+        n => Ok(n),
+    }
+}
+
+/// A positive, finite number of seconds.
+fn seconds(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|s| s.is_finite() && *s > 0.0)
+        .ok_or_else(|| format!("bad duration `{value}` (expected seconds above 0)"))
+}
+
+/// A position as a fraction of the map, 0 to 1.
+fn fraction(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|f| (0.0..=1.0).contains(f))
+        .ok_or_else(|| format!("bad position `{value}` (expected 0 to 1)"))
+}
+
+fn rgb(r: &str, g: &str, b: &str) -> Result<(u8, u8, u8), String> {
+    let channel = |v: &str| {
+        v.parse::<u8>()
+            .map_err(|_| format!("bad colour channel `{v}` (expected 0 to 255)"))
+    };
+    Ok((channel(r)?, channel(g)?, channel(b)?))
+}
+
+pub(crate) fn parse_win(words: &[&str]) -> Result<WinCondition, String> {
+    Ok(match words {
+        ["bank_crabs", n] => WinCondition::BankCrabs(goal(n)?),
+        ["build_train", n] => WinCondition::BuildTrain(goal(n)?),
+        ["crack_and_hold", "shells", shells, "min_train", min_train] => {
+            WinCondition::CrackAndHold {
+                shells: goal(shells)?,
+                min_train: count(min_train)?,
+            }
+        }
+        ["hold_train", "target", target, "seconds", secs] => WinCondition::HoldTrain {
+            target: goal(target)?,
+            seconds: seconds(secs)?,
         },
-        // The fourth-wall surprise (Inscryption / old Windows PowerToys): a special level that
-        // "shouldn't be in the game." The playfield becomes a flat OS wallpaper and the terrain
-        // patches render as rectangular application windows you route the conga train around. It
-        // sits last so it's *discovered* by getting this far, per INSPIRATION — the big Control-style
-        // title card does the wink. For this first slice the windows are solid walls (reusing the
-        // Rock push-out collision); the real transparent-window hookup is deferred to ggez 0.10.
-        Level {
-            title: "Unauthorized Encore".to_string(),
-            description: "Wait — this isn't part of the game. Route the train around the windows."
-                .to_string(),
-            difficulty: 9,
-            map_size: MapSize::Large,
-            biome: Biome {
-                name: "You Shouldn't Be Here",
-                // Flat neutral desktop wallpaper (classic teal). main.rs paints this opaque over the
-                // ground so the beach texture reads as a plain screen — the transparency seam.
-                tint: (58, 110, 128),
-                // Cool window-highlight blue for the on-beat pulse / accents.
-                pulse: (150, 190, 235),
-                terrain: TerrainKind::Desktop,
-                layout: MapLayout::Meadow,
-                music: BiomeMusic::DesktopChip,
-            },
-            // No archetype emphasis — the wink is the whole hook; keep the herd plain so the terrain
-            // (the windows) is what reads as different, not the crabs.
-            emphasis: None,
-            boss_sequence: vec![
-                CrabType::Boss,
-                CrabType::TideBoss,
-                CrabType::RhythmBoss,
-                CrabType::HermitKing,
-                CrabType::DancerKing,
-            ],
-            // The hardest banking challenge: the window panels force long, risky routes to the pen.
-            // (The issue's BankUnderPressure escape-tracking variant is deferred — there's no
-            // "escaped off-world" concept in the sim yet — so this takes its sanctioned fallback.)
-            win_condition: WinCondition::BankCrabs(40),
-            patterns: vec![
-                LevelPattern {
-                    pattern: SpawnPattern::UniformRandom,
-                    count: 16,
-                    duration: 16.8,
-                    centroid: (0.5, 0.5),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Cluster,
-                    count: 22,
-                    duration: 19.6,
-                    centroid: (0.3, 0.4),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::BeatGrid,
-                    count: 28,
-                    duration: 19.6,
-                    centroid: (0.7, 0.6),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::SineWave,
-                    count: 34,
-                    duration: 22.4,
-                    centroid: (0.4, 0.7),
-                },
-                LevelPattern {
-                    pattern: SpawnPattern::Circle,
-                    count: 40,
-                    duration: 25.2,
-                    centroid: (0.6, 0.3),
-                },
-            ],
+        ["steal_back", n] => WinCondition::StealBack(goal(n)?),
+        ["outgrow_rivals", "seconds", secs] => WinCondition::OutgrowRivals {
+            seconds: seconds(secs)?,
         },
-    ]
+        ["dethrone_king", name @ ..] if !name.is_empty() => {
+            WinCondition::DethroneKing(name.join(" "))
+        }
+        ["perfect_streak", n] => WinCondition::PerfectStreak(goal(n)?),
+        ["escort", name] => WinCondition::Escort(named(&EMPHASES, name, "archetype")?),
+        ["catch_bosses", n] => WinCondition::CatchBosses(goal(n)?),
+        [name, ..] => {
+            return Err(format!(
+                "unknown or malformed win condition `{name}` (expected `bank_crabs <n>`, \
+                 `build_train <n>`, `crack_and_hold shells <n> min_train <n>`, \
+                 `hold_train target <n> seconds <s>`, `steal_back <n>`, \
+                 `outgrow_rivals seconds <s>`, `dethrone_king <name>`, `perfect_streak <n>`, \
+                 `escort <archetype>` or `catch_bosses <n>`)"
+            ));
+        }
+        [] => return Err("missing win condition".into()),
+    })
+}
+
+/// A route's rule: nothing for any win, or the one extra the winning run needs.
+fn parse_rule(words: &[&str]) -> Result<UnlockRule, String> {
+    Ok(match words {
+        [] => UnlockRule::Win,
+        ["train", n] => UnlockRule::Train(goal(n)?),
+        ["score", n] => UnlockRule::Score(goal(n)?),
+        ["under", secs] => UnlockRule::Under(seconds(secs)?),
+        _ => {
+            return Err(format!(
+                "bad route rule `{}` (expected `train <n>`, `score <n>` or `under <s>`)",
+                words.join(" ")
+            ));
+        }
+    })
 }
 
 #[cfg(test)]
@@ -640,7 +908,7 @@ mod tests {
 
     #[test]
     fn test_first_four_campaign_levels_are_tutorial_sized() {
-        let levels = get_levels();
+        let levels = load_levels().unwrap();
         assert!(
             levels[..4]
                 .iter()
@@ -655,7 +923,7 @@ mod tests {
 
     #[test]
     fn campaign_biomes_use_distinct_map_layouts() {
-        let levels = get_levels();
+        let levels = load_levels().unwrap();
         assert_eq!(levels[0].biome.layout, MapLayout::Meadow);
         assert_eq!(levels[4].biome.layout, MapLayout::Beach);
         assert_eq!(levels[6].biome.layout, MapLayout::Underwater);
@@ -684,8 +952,8 @@ mod tests {
 
     #[test]
     fn every_campaign_level_has_the_designed_win_condition() {
-        let levels = get_levels();
-        assert_eq!(levels.len(), 9);
+        let levels = load_levels().unwrap();
+        assert_eq!(levels.len(), 11);
         assert_eq!(levels[0].win_condition, WinCondition::BankCrabs(25));
         assert_eq!(levels[1].win_condition, WinCondition::BuildTrain(15));
         assert_eq!(
@@ -719,59 +987,283 @@ mod tests {
             }
         );
         assert_eq!(levels[8].win_condition, WinCondition::BankCrabs(40));
+        assert_eq!(levels[9].win_condition, WinCondition::StealBack(25));
+        assert_eq!(levels[10].win_condition, WinCondition::CatchBosses(3));
     }
 
     #[test]
     fn bosses_follow_their_biome_families() {
-        let levels = get_levels();
+        let levels = load_levels().unwrap();
         assert_eq!(levels[0].boss_for_encounter(0), CrabType::Boss);
         assert_eq!(levels[1].boss_for_encounter(0), CrabType::TideBoss);
         assert_eq!(levels[4].emphasis, Some(CrabType::Dancer));
         assert_eq!(levels[4].boss_for_encounter(0), CrabType::RhythmBoss);
         assert_eq!(levels[5].emphasis, Some(CrabType::Hermit));
         assert_eq!(levels[5].boss_for_encounter(0), CrabType::HermitKing);
-        let desktop = levels.last().unwrap();
+        let desktop = levels
+            .iter()
+            .find(|l| l.biome.terrain == TerrainKind::Desktop)
+            .unwrap();
         assert_eq!(desktop.boss_for_encounter(4), CrabType::DancerKing);
         assert_eq!(desktop.boss_for_encounter(5), CrabType::Boss);
     }
 
     #[test]
-    fn campaign_progression_has_unique_music_and_rising_difficulty() {
+    fn main_route_has_unique_music_and_rising_difficulty() {
         use std::collections::HashSet;
 
-        let levels = get_levels();
-        let themes: HashSet<_> = levels.iter().map(|level| level.biome.music).collect();
-        assert_eq!(themes.len(), levels.len());
+        // Side paths revisit a neighbouring biome's theme; the main route never repeats one.
+        let levels = load_levels().unwrap();
+        let main: Vec<_> = levels.iter().filter(|l| l.branch.is_none()).collect();
+        let themes: HashSet<_> = main.iter().map(|level| level.biome.music).collect();
+        assert_eq!(themes.len(), main.len());
         assert!(
-            levels
-                .windows(2)
+            main.windows(2)
                 .all(|pair| pair[0].difficulty <= pair[1].difficulty)
         );
+        let desktop = levels.iter().find(|l| l.branch == Some(Branch::Secret));
+        assert_eq!(
+            desktop.unwrap().routes,
+            [Route {
+                from: "splitters-causeway".into(),
+                rule: UnlockRule::Train(30)
+            }]
+        );
+    }
+
+    #[test]
+    fn level_files_parse_and_reject_mistakes_by_line() {
+        const BASE: &str = "rustler-level 1\nid test-flats\ntitle Test Flats\ndescription A level.\n\
+                            difficulty 1\nmap_size medium\nmap_position 0.5 0.5\n\
+                            biome Test Biome\ntint 1 2 3\npulse 4 5 6\nterrain rock\n\
+                            layout river\nmusic kelp_disco\nbosses boss tide_boss\n\
+                            win crack_and_hold shells 3 min_train 4\n\
+                            pattern beat_grid 5 2.5 0.25 0.75\n";
+        let level = Level::parse(BASE).unwrap();
+        assert_eq!(level.biome.name, "Test Biome");
+        assert_eq!(level.map_size, MapSize::Medium);
+        assert_eq!(level.emphasis, None);
+        assert_eq!(level.boss_for_encounter(1), CrabType::TideBoss);
+        assert_eq!(level.patterns[0].centroid, (0.25, 0.75));
+        assert!(matches!(level.patterns[0].pattern, SpawnPattern::BeatGrid));
+
+        let with = |line: &str| Level::parse(&format!("{BASE}{line}\n"));
+        assert!(with("emphasis golden").unwrap().emphasis == Some(CrabType::Golden));
+        let err = with("emphasis tide_boss").unwrap_err();
+        assert!(
+            err.starts_with("line 17: unknown archetype `tide_boss`"),
+            "{err}"
+        );
+        assert!(
+            with("terrain open")
+                .unwrap_err()
+                .contains("`terrain` given twice")
+        );
+        assert!(with("pattern spiral 0 2 0.5 0.5").is_err());
+        assert!(with("pattern spiral 4 2 1.5 0.5").is_err());
+        assert!(with("pattern spiral 4 -1 0.5 0.5").is_err());
+        assert!(
+            with("pattern spiral 4 2")
+                .unwrap_err()
+                .contains("expected `pattern <shape>")
+        );
+        assert!(with("weather stormy").unwrap_err().contains("unknown key"));
+        let without_win = BASE.replace("win crack_and_hold shells 3 min_train 4\n", "");
+        assert_eq!(Level::parse(&without_win).unwrap_err(), "missing `win`");
+        let bad_win = BASE.replace("shells 3", "shells 0");
+        assert!(Level::parse(&bad_win).is_err());
+        assert!(Level::parse(&BASE.replace("tint 1 2 3", "tint 1 2 300")).is_err());
+        assert!(Level::parse(&BASE[16..]).is_err(), "missing header");
+
+        let side = with("branch boss\nroute tide-pools\nroute tutorial-lasso under 90").unwrap();
+        assert_eq!(side.branch, Some(Branch::Boss));
+        assert_eq!(side.routes[0].rule, UnlockRule::Win);
+        assert_eq!(side.routes[1].rule, UnlockRule::Under(90.0));
+        assert_eq!(level.branch, None);
+        assert!(level.routes.is_empty());
+        assert!(
+            with("branch detour")
+                .unwrap_err()
+                .contains("unknown branch")
+        );
+        assert!(
+            with("route tide-pools train")
+                .unwrap_err()
+                .contains("bad route rule `train`")
+        );
+        assert!(
+            with("route")
+                .unwrap_err()
+                .contains("expected `route <node>")
+        );
+        assert!(with("id Test_Flats").is_err());
+    }
+
+    #[test]
+    fn every_level_file_sits_at_its_own_map_position() {
+        let levels = load_levels().unwrap();
+        assert!(
+            levels
+                .iter()
+                .all(|l| l.map_position != (0.5, 0.5) && !l.patterns.is_empty())
+        );
+        let dir = std::env::temp_dir().join(format!("rustler-levels-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let first = std::fs::read_to_string(levels_dir().join("01-sunny-meadow.level")).unwrap();
+        std::fs::write(dir.join("01-a.level"), &first).unwrap();
+        std::fs::write(
+            dir.join("02-b.level"),
+            first.replace("title Tutorial", "title Copy"),
+        )
+        .unwrap();
+        let err = load_levels_from(&dir).unwrap_err();
+        assert!(
+            err.contains("02-b.level: id `sunny-meadow` is already taken"),
+            "{err}"
+        );
+        let copy = first.replace("id sunny-meadow", "id copy");
+        std::fs::write(dir.join("02-b.level"), &copy).unwrap();
+        let err = load_levels_from(&dir).unwrap_err();
+        assert!(
+            err.contains("02-b.level: map_position is already taken"),
+            "{err}"
+        );
+        let moved = copy.replace("map_position 0.51 0.43", "map_position 0.2 0.2");
+        std::fs::write(
+            dir.join("02-b.level"),
+            moved.clone() + "route rocky-shore\n",
+        )
+        .unwrap();
+        let err = load_levels_from(&dir).unwrap_err();
+        assert!(
+            err.contains("route from unknown node `rocky-shore`"),
+            "{err}"
+        );
+        std::fs::write(
+            dir.join("02-b.level"),
+            moved + "route tutorial-beat train 5\n",
+        )
+        .unwrap();
+        let loaded = load_levels_from(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(loaded.unwrap()[1].routes[0].rule, UnlockRule::Train(5));
     }
 
     #[test]
     fn win_condition_predicates_gate_correctly() {
+        let run = |banked, train, shells, hold_secs| GoalProgress {
+            banked,
+            train,
+            shells,
+            hold_secs,
+            ..GoalProgress::default()
+        };
         // BankCrabs cares only about the banked total.
-        assert!(WinCondition::BankCrabs(25).met(25, 0, 0, 0.0));
-        assert!(!WinCondition::BankCrabs(25).met(24, 99, 99, 99.0));
+        assert!(WinCondition::BankCrabs(25).met(&run(25, 0, 0, 0.0)));
+        assert!(!WinCondition::BankCrabs(25).met(&run(24, 99, 99, 99.0)));
         // BuildTrain fires the instant the live train hits the target.
-        assert!(WinCondition::BuildTrain(15).met(0, 15, 0, 0.0));
-        assert!(!WinCondition::BuildTrain(15).met(99, 14, 0, 0.0));
+        assert!(WinCondition::BuildTrain(15).met(&run(0, 15, 0, 0.0)));
+        assert!(!WinCondition::BuildTrain(15).met(&run(99, 14, 0, 0.0)));
         // CrackAndHold needs BOTH gates at once.
         let cah = WinCondition::CrackAndHold {
             shells: 8,
             min_train: 15,
         };
-        assert!(cah.met(0, 15, 8, 0.0));
-        assert!(!cah.met(0, 14, 8, 0.0));
-        assert!(!cah.met(0, 15, 7, 0.0));
+        assert!(cah.met(&run(0, 15, 8, 0.0)));
+        assert!(!cah.met(&run(0, 14, 8, 0.0)));
+        assert!(!cah.met(&run(0, 15, 7, 0.0)));
         // HoldTrain is satisfied purely by the accumulated hold time (the caller resets it when
         // the train dips below target).
         let hold = WinCondition::HoldTrain {
             target: 20,
             seconds: 30.0,
         };
-        assert!(hold.met(0, 20, 0, 30.0));
-        assert!(!hold.met(0, 20, 0, 29.9));
+        assert!(hold.met(&run(0, 20, 0, 30.0)));
+        assert!(!hold.met(&run(0, 20, 0, 29.9)));
+    }
+
+    #[test]
+    fn steal_goals_parse_and_gate() {
+        let parse = |line: &str| parse_win(&line.split_whitespace().collect::<Vec<_>>());
+        assert_eq!(parse("steal_back 6"), Ok(WinCondition::StealBack(6)));
+        assert_eq!(
+            parse("outgrow_rivals seconds 20"),
+            Ok(WinCondition::OutgrowRivals { seconds: 20.0 })
+        );
+        assert_eq!(
+            parse("dethrone_king Captain  Kevin"),
+            Ok(WinCondition::DethroneKing("Captain Kevin".into()))
+        );
+        assert_eq!(
+            parse("perfect_streak 4"),
+            Ok(WinCondition::PerfectStreak(4))
+        );
+        assert_eq!(
+            parse("escort golden"),
+            Ok(WinCondition::Escort(CrabType::Golden))
+        );
+        assert!(parse("steal_back 0").is_err());
+        assert!(parse("dethrone_king").is_err());
+        assert!(parse("escort rhythm_boss").is_err());
+        assert!(!WinCondition::BuildTrain(5).needs_rivals());
+
+        let level = std::fs::read_to_string(levels_dir().join("01-sunny-meadow.level")).unwrap();
+        let rival_goal = level.replace("win bank_crabs 25", "win steal_back 4");
+        assert_eq!(
+            Level::parse(&rival_goal).unwrap_err(),
+            "`win` needs rival trains, and a `map_size tutorial` level has none"
+        );
+        let medium = rival_goal.replace("map_size tutorial", "map_size medium");
+        assert!(Level::parse(&medium).is_ok());
+
+        let mut p = GoalProgress {
+            stolen: 5,
+            train: 9,
+            longest_rival: 9,
+            outgrow_secs: 19.9,
+            king_followers: Some(3),
+            perfect_streak: 3,
+            escorting: true,
+            ..GoalProgress::default()
+        };
+        let king = WinCondition::DethroneKing("Captain Kevin".into());
+        let outgrow = WinCondition::OutgrowRivals { seconds: 20.0 };
+        assert!(!WinCondition::StealBack(6).met(&p));
+        assert!(!outgrow.met(&p));
+        assert!(!king.met(&p));
+        assert!(!WinCondition::PerfectStreak(4).met(&p));
+        assert!(!WinCondition::Escort(CrabType::Golden).met(&p));
+        assert_eq!(
+            outgrow.progress_text(&p),
+            "GOAL  Outgrow every rival (9 long) and stay ahead 20s"
+        );
+        assert_eq!(
+            king.progress_text(&p),
+            "GOAL  Dethrone Captain Kevin: 3 followers left"
+        );
+        assert_eq!(
+            WinCondition::Escort(CrabType::Golden).progress_text(&p),
+            "GOAL  Get the golden crab to the pen!"
+        );
+
+        p.stolen = 6;
+        p.train = 10;
+        p.outgrow_secs = 20.0;
+        p.dethroned = true;
+        p.perfect_streak = 4;
+        p.escorted = 1;
+        assert!(WinCondition::StealBack(6).met(&p));
+        assert!(outgrow.met(&p));
+        assert!(king.met(&p));
+        assert!(WinCondition::PerfectStreak(4).met(&p));
+        assert!(WinCondition::Escort(CrabType::Golden).met(&p));
+        assert_eq!(
+            outgrow.progress_text(&p),
+            "GOAL  Longest train on the beach: 20s / 20s"
+        );
+        assert_eq!(
+            WinCondition::StealBack(6).progress_text(&p),
+            "GOAL  Rustle crabs off rivals: 6 / 6"
+        );
     }
 }
@@ -1,6 +1,11 @@
 mod audio_mix;
+mod balance;
 mod beat;
+mod bench;
+mod bindings;
 mod bot;
+mod bot_report;
+mod campaign_goal;
 mod catch_deliver;
 mod catch_effects;
 mod chain_mechanics;
@@ -11,29 +16,47 @@ mod crab_boss_update;
 mod crab_catching;
 mod crab_render;
 mod crab_update;
+mod daily;
+mod endless;
 mod enemies;
 mod event_handler;
+mod event_log;
 mod floating_text;
 mod game_lifecycle;
 mod game_render;
 mod game_render_hud;
 mod game_update;
+mod gamepad;
 mod graphics;
 mod hud_cache;
+mod interpolation;
 mod king_crab_audio;
+mod leaderboard;
+#[cfg(debug_assertions)]
+mod level_reload;
 mod levels;
 mod menu;
 mod menu_intro;
+mod mutators;
+mod nemesis;
 mod npc_conga_train;
 mod npc_scenarios;
 mod npc_trains;
 mod npc_trains_render;
 mod overlays;
+mod pause;
 mod player_tools;
+mod profiler;
+mod replay;
+mod rival_ai;
+mod rival_economy;
 mod rival_taunts;
 mod rng;
+mod save;
+mod simulation;
 mod skins;
 mod sounds;
+mod spatial;
 mod spawnings;
 mod startle;
 mod state;
@@ -48,7 +71,7 @@ pub use constants::*;
 pub use hud_cache::*;
 pub use state::*;
 
-use std::{cell::RefCell, env, fs, path};
+use std::{cell::RefCell, env, path};
 
 // Scratch buffer for count_chain_bonds — reused across calls to avoid a per-call heap alloc
 // every frame. The Vec is grown-but-not-shrunk, so it reaches steady state after the first
@@ -79,29 +102,75 @@ pub(crate) fn sanitize_player_name(name: &str) -> String {
     cleaned.trim().to_string()
 }
 
-/// Returns the instructions shown on the "How to Play" menu card.
-pub(crate) fn how_to_play_body_text() -> String {
+/// Returns the instructions shown on the "How to Play" menu card, naming the keys currently bound
+/// in `bindings`.
+pub(crate) fn how_to_play_body_text(bindings: &bindings::Bindings) -> String {
+    use bindings::Action;
+    let key = |action| bindings.label(action);
+    let tool =
+        |action: Action, blurb: &str| format!("- {}  {}: {blurb}", key(action), action.label());
+    let movement: String = [
+        Action::MoveUp,
+        Action::MoveLeft,
+        Action::MoveDown,
+        Action::MoveRight,
+    ]
+    .map(key)
+    .concat();
     [
-        "Gather wild crabs into a conga train, then bank it at the pen.",
-        "Everything you do pays more ON THE BEAT.",
-        "Move with WASD / arrows  ·  hold Shift to sprint.",
-        "Tap Space on the beat to Dash — or hold a tool (E/R/Q) and tap",
-        "Space to 'chord' that tool onto the beat instead of dashing.",
-        "",
-        "Your tools — each is for a different job:",
-        "- Space  Dash: burst to a crab, or shake off a King Crab",
-        "- E  Whistle: yank skittish crabs toward you",
-        "- R  Stomp: crack armored shells, and guard your tail",
-        "- Q  Wave: on-beat shockwave — shove nearby rivals back to clear space",
-        "- F  Flashlight: toggle it on to auto-melt the nearest King Crab catchable",
-        "- T  Call: charm nearby Dancer crabs to hop over to you on the beat",
-        "- X  Cycle: rotate the train — tuck your best crabs up front",
-        "- V  Groove Call: lure the whole field in over a few bars",
-        "- G  Slam: full-groove finisher — mass-catch everything near",
-        "- B  Bank: lock in your gamble streak (and jam!)",
-        "- Mouse (hold / release): lasso a cluster and reel it in",
-        "",
-        "Press Enter, Space, or Esc to go back.",
+        "Gather wild crabs into a conga train, then bank it at the pen.".to_string(),
+        "Everything you do pays more ON THE BEAT.".to_string(),
+        format!(
+            "Move with {movement} / arrows  ·  hold {} to sprint.",
+            key(Action::Sprint)
+        ),
+        format!(
+            "Tap {} on the beat to Dash — or hold a tool ({}/{}/{}) and tap",
+            key(Action::Dash),
+            key(Action::Whistle),
+            key(Action::Stomp),
+            key(Action::Wave)
+        ),
+        format!(
+            "{} to 'chord' that tool onto the beat instead of dashing.",
+            key(Action::Dash)
+        ),
+        String::new(),
+        "Your tools — each is for a different job:".to_string(),
+        tool(Action::Dash, "burst to a crab, or shake off a King Crab"),
+        tool(Action::Whistle, "yank skittish crabs toward you"),
+        tool(Action::Stomp, "crack armored shells, and guard your tail"),
+        tool(
+            Action::Wave,
+            "on-beat shockwave — shove nearby rivals back to clear space",
+        ),
+        tool(
+            Action::Flashlight,
+            "toggle it on to auto-melt the nearest King Crab catchable",
+        ),
+        tool(
+            Action::Call,
+            "charm nearby Dancer crabs to hop over to you on the beat",
+        ),
+        tool(
+            Action::Cycle,
+            "rotate the train — tuck your best crabs up front",
+        ),
+        tool(
+            Action::GrooveCall,
+            "lure the whole field in over a few bars",
+        ),
+        tool(
+            Action::Slam,
+            "full-groove finisher — mass-catch everything near",
+        ),
+        tool(Action::Bank, "lock in your gamble streak (and jam!)"),
+        format!(
+            "- Mouse or {} (hold / release): lasso a cluster and reel it in",
+            key(Action::Lasso)
+        ),
+        String::new(),
+        "Press Enter, Space, or Esc to go back.".to_string(),
     ]
     .join("\n")
 }
@@ -116,7 +185,7 @@ use ggez::conf::{FullscreenType, WindowMode, WindowSetup};
 use ggez::event;
 use ggez::glam::Vec2;
 use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
-use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::input::keyboard::KeyInput;
 use ggez::input::mouse::MouseButton;
 use ggez::winit::dpi::LogicalSize;
 use ggez::{Context, ContextBuilder, GameResult};
@@ -124,7 +193,7 @@ use rand::Rng;
 
 use crate::controls::handle_player_movement;
 use crate::enemies::{BossCharge, CrabType, EnemyCrab, HermitKingPhase, hermit_king_phase};
-use crate::levels::{TerrainKind, get_levels};
+use crate::levels::TerrainKind;
 use crate::spawnings::{
     spawn_boss, spawn_dancer_king, spawn_enemies, spawn_hermit_king, spawn_hype_dancer,
     spawn_rhythm_boss, spawn_tide_boss, spawn_tutorial_crabs,
@@ -232,12 +301,9 @@ pub(crate) fn pick_tide_pools(
 
 impl MainState {
     /// The terrain wrinkle of the zone currently in play — decides what the terrain patches do
-    /// (open field, wade-drag water, solid rock chokepoints, or crab-snagging kelp). Clamped so a
-    /// finished run doesn't index past the last level.
+    /// (open field, wade-drag water, solid rock chokepoints, or crab-snagging kelp).
     fn current_terrain(&self) -> TerrainKind {
-        self.levels[self.current_level.min(self.levels.len() - 1)]
-            .biome
-            .terrain
+        self.level().biome.terrain
     }
 
     /// Rocky Shore tide: is the native rock patch at `index` a *low rock* the tide can submerge?
@@ -415,7 +481,7 @@ impl MainState {
         }
     }
 
-    fn catch_by_chain(&mut self, ctx: &mut Context) {
+    fn catch_by_chain(&mut self) {
         // On-beat catch bloom: the train's catch window widens on the beat (widest on the downbeat)
         // and settles back before the next hit, so crossing a drifting crab ON the beat scoops it
         // while an off-beat pass just misses. Set in the beat handler, decayed in update_crabs, drawn
@@ -428,62 +494,22 @@ impl MainState {
         if self.chain_positions_buf.is_empty() {
             return;
         }
-        // Bucket uncaught crabs into a spatial grid keyed by cell so each chain link only
-        // tests the handful of crabs near it instead of the whole uncaught set. Without this,
-        // the scan below is O(caught * uncaught) and gets noticeably slower as the conga
-        // train — and the crab count — grow.
-        //
-        // The grid (and its per-cell Vec<usize> buckets) live in a persistent buffer and are
-        // cleared-and-refilled rather than reallocated every frame: the play area is a fixed
-        // size, so distinct cell keys stabilize almost immediately and this stops rebuilding a
-        // fresh HashMap plus dozens of small Vecs on every single tick.
-        let cell_size = catch_radius.max(1.0);
-        let cell_of = |p: Vec2| -> (i32, i32) {
-            (
-                (p.x / cell_size).floor() as i32,
-                (p.y / cell_size).floor() as i32,
-            )
-        };
-        // Clear only the cells touched last frame (via catch_grid_keys_buf) rather than calling
-        // HashMap::clear(), which drops every inner Vec<usize> and forces a fresh heap alloc when
-        // the same cell is re-inserted next frame. Crabs move slowly so they typically stay in the
-        // same cells frame-to-frame; reusing the Vec allocation avoids ~40-50 small allocs/frame.
-        // We still visit only live cells (not "every cell ever"), so the bounded-iteration goal
-        // from the original fix is preserved — this is strictly cheaper than the HashMap::clear() path.
-        for &k in &self.catch_grid_keys_buf {
-            if let Some(v) = self.catch_grid_buf.get_mut(&k) {
-                v.clear();
-            }
-        }
-        self.catch_grid_keys_buf.clear();
-        for (i, c) in self.crabs.iter().enumerate() {
-            if c.is_catchable() {
-                let k = cell_of(c.pos);
-                let bucket = self.catch_grid_buf.entry(k).or_default();
-                if bucket.is_empty() {
-                    // Only record the key the first time we insert into this cell this frame,
-                    // so catch_grid_keys_buf has one entry per cell (not per crab).
-                    self.catch_grid_keys_buf.push(k);
-                }
-                bucket.push(i);
-            }
-        }
+        // Each chain link only tests the free crabs the shared index has near it, instead of the
+        // whole uncaught set — without that the scan is O(caught * uncaught) and gets noticeably
+        // slower as the conga train, and the crab count, grow. The index is this step's snapshot,
+        // so it's widened by DRIFT and the live crab decides.
         let catch_radius_sq = catch_radius * catch_radius;
         self.caught_now_buf.clear();
         self.caught_now_buf.resize(self.crabs.len(), false);
         for &cp in &self.chain_positions_buf {
-            let (cx, cy) = cell_of(cp);
-            for dx in -1..=1 {
-                for dy in -1..=1 {
-                    if let Some(candidates) = self.catch_grid_buf.get(&(cx + dx, cy + dy)) {
-                        for &i in candidates {
-                            if !self.caught_now_buf[i]
-                                && cp.distance_squared(self.crabs[i].pos) < catch_radius_sq
-                            {
-                                self.caught_now_buf[i] = true;
-                            }
-                        }
-                    }
+            for candidate in self.crab_index.within(cp, catch_radius + spatial::DRIFT) {
+                let i = candidate.index;
+                if let Some(crab) = self.crabs.get(i)
+                    && !self.caught_now_buf[i]
+                    && crab.is_catchable()
+                    && cp.distance_squared(crab.pos) < catch_radius_sq
+                {
+                    self.caught_now_buf[i] = true;
                 }
             }
         }
@@ -518,8 +544,8 @@ impl MainState {
             self.hitstop_timer = self.hitstop_timer.max(0.04);
             self.zoom_punch = self.zoom_punch.max(0.03);
             self.time_since_catch = 0.0;
-            play_catch_sound(&mut self.sounds, ctx, &mut rng, self.beat_streak);
-            self.check_upgrade_unlock(ctx);
+            play_catch_sound(&mut self.sounds, &mut rng, self.beat_streak);
+            self.check_upgrade_unlock();
         }
     }
 
@@ -530,19 +556,27 @@ impl MainState {
             self.game_over = true;
             return;
         }
-        let level = &self.levels[self.current_level];
+        let level = self.level();
         let p = &level.patterns[self.current_pattern];
         // Arcade is an endless tour through the biome set. Each completed tour raises the herd
         // density and shortens the clear window, while deterministic centroid transforms keep later
-        // visits from replaying the exact same spatial sequence.
-        let arcade_tour = if self.in_campaign {
-            0
+        // visits from replaying the exact same spatial sequence. Endless stages are fresh every
+        // time, so they skip the transforms and escalate every stage, without a cap.
+        let (arcade_count_mul, arcade_duration_mul) = if self.endless.is_some() {
+            (
+                endless::count_mul(self.arcade_stage),
+                endless::duration_mul(self.arcade_stage),
+            )
+        } else if self.in_campaign {
+            (1.0, 1.0)
         } else {
-            (self.arcade_stage.saturating_sub(1) / self.levels.len().max(1)) as u32
+            let arcade_tour = (self.arcade_stage.saturating_sub(1) / self.levels.len()) as f32;
+            (
+                (1.0 + arcade_tour * 0.12).min(3.0),
+                (1.0 - arcade_tour * 0.035).max(0.58),
+            )
         };
-        let arcade_count_mul = (1.0 + arcade_tour as f32 * 0.12).min(3.0);
-        let arcade_duration_mul = (1.0 - arcade_tour as f32 * 0.035).max(0.58);
-        let centroid = if self.in_campaign {
+        let centroid = if self.in_campaign || self.endless.is_some() {
             p.centroid
         } else {
             match self.arcade_stage % 4 {
@@ -582,7 +616,7 @@ impl MainState {
             count,
             area,
             centroid,
-            level.emphasis,
+            self.mutators.emphasis().or(level.emphasis),
             &mut rng,
         );
         self.crabs.extend(crabs);
@@ -594,25 +628,25 @@ impl MainState {
         // Count every wave the player clears this run — drives the every-4th Frenzy cadence.
         self.waves_cleared = self.waves_cleared.wrapping_add(1);
         self.current_pattern += 1;
-        let level = &self.levels[self.current_level];
-        if self.current_pattern >= level.patterns.len() {
-            let previous_title = level.title.clone();
+        if self.current_pattern >= self.level().patterns.len() {
             self.current_pattern = 0;
             // Campaign nodes are self-contained maps: keep generating waves in this biome until
             // the explicit win condition returns the player to the world map. Arcade instead moves
-            // forever through an escalating, procedurally varied biome sequence.
+            // forever through an escalating, procedurally varied biome sequence, and endless
+            // assembles a brand-new stage each time.
             if !self.in_campaign {
                 self.arcade_stage = self.arcade_stage.saturating_add(1);
-                self.current_level = (self.current_level + 1) % self.levels.len();
-                self.level_title = self
-                    .levels
-                    .get(self.current_level)
-                    .map(|level| format!("Stage {} — {}", self.arcade_stage, level.title))
-                    .unwrap_or(previous_title);
-                self.level_title_timer = 3.1;
-                if let Some(next_level) = self.levels.get(self.current_level) {
-                    self.resize_world(next_level.map_size);
+                if self.endless.is_some() {
+                    self.next_endless_stage();
+                } else {
+                    self.current_level = (self.current_level + 1) % self.levels.len();
+                    self.level_title = format!(
+                        "Stage {} — {}",
+                        self.arcade_stage, self.levels[self.current_level].title
+                    );
                 }
+                self.level_title_timer = 3.1;
+                self.resize_world(self.level().map_size);
                 // Fresh biome, fresh pen and terrain locations.
                 let player_center = self.player_pos + Vec2::splat(PLAYER_SIZE / 2.0);
                 self.pen_pos = pick_pen_pos(
@@ -621,7 +655,7 @@ impl MainState {
                     player_center,
                     &mut crate::rng::rng(),
                 );
-                let difficulty = self.levels[self.current_level].difficulty;
+                let difficulty = self.level().difficulty;
                 self.tide_pools = pick_tide_pools(
                     self.world_width,
                     self.world_height,
@@ -648,12 +682,17 @@ impl MainState {
             return;
         }
         self.run_recorded = true;
-        self.run_is_new_best = self.score > self.career_best_score;
+        let score = self.final_score();
+        self.run_is_new_best = score > self.career_best_score;
         if self.run_is_new_best {
-            self.career_best_score = self.score;
+            self.career_best_score = score;
         }
-        self.career_total_score += self.score;
+        self.career_total_score += score;
         self.career_runs += 1;
+        // Arcade and endless runs only end here; campaign stages make their table by clearing.
+        if !self.in_campaign {
+            self.record_on_board();
+        }
         self.save_career();
     }
 
@@ -672,25 +711,39 @@ impl MainState {
         }
     }
 
-    /// Persist the whole career ledger (best/total/runs + spend side) to disk. Best-effort: a
-    /// failed write never disrupts play.
+    /// Persist the whole career ledger (best/total/runs + spend side), loadout, name and
+    /// world-map progress.
+    /// Best-effort: a failed write is reported but never disrupts play. Skipped when there is no
+    /// save store (headless runs).
     fn save_career(&self) {
-        let _ = fs::write(
-            "career.txt",
-            format!(
-                "{} {} {} {} {} {} {} {}\n{}\nname {}",
-                self.career_best_score,
-                self.career_total_score,
-                self.career_runs,
-                self.career_spent,
-                self.start_beam_rank,
-                self.start_lasso_rank,
-                self.start_whistle_rank,
-                self.start_stomp_rank,
-                self.player_skin.to_save_line(),
-                crate::normalize_player_name(&self.player_name),
-            ),
-        );
+        let Some(store) = &self.save_store else {
+            return;
+        };
+        if let Err(e) = store.save(&self.save_data()) {
+            eprintln!("Failed to save: {e}");
+        }
+    }
+
+    /// Everything [`MainState::save_career`] persists, as it stands.
+    fn save_data(&self) -> save::SaveData {
+        save::SaveData {
+            best_score: self.career_best_score,
+            total_score: self.career_total_score,
+            runs: self.career_runs,
+            spent: self.career_spent,
+            start_beam_rank: self.start_beam_rank,
+            start_lasso_rank: self.start_lasso_rank,
+            start_whistle_rank: self.start_whistle_rank,
+            start_stomp_rank: self.start_stomp_rank,
+            best_time: (self.best_time < f32::MAX).then_some(self.best_time),
+            player_skin: self.player_skin,
+            player_name: self.player_name.clone(),
+            map_progress: self.map_progress.clone(),
+            bindings: self.bindings.clone(),
+            leaderboards: self.leaderboards.clone(),
+            daily_played: self.daily_played.clone(),
+            rivals: self.nemeses.clone(),
+        }
     }
 
     fn push_player_name_char(&mut self, ch: char) {
@@ -710,7 +763,7 @@ impl MainState {
     /// Title-screen skin picker: step the option in the currently focused cosmetic column
     /// (`skin_slot`: 0=Hat, 1=FacialHair, 2=Accessory) by `dir` (+1/-1), wrapping around its
     /// `::ALL` list. The change is applied to `player_skin` immediately (so the live preview
-    /// and flavour text update at once) and persisted to career.txt right away.
+    /// and flavour text update at once) and persisted to the save right away.
     fn cycle_skin_option(&mut self, dir: i32) {
         let step = |len: usize, cur: usize| -> usize {
             ((cur as i32 + dir).rem_euclid(len as i32)) as usize
@@ -785,7 +838,11 @@ impl MainState {
 }
 
 enum AppState {
-    Loading { has_drawn: bool },
+    /// `event_log` waits here for the `MainState` that will write to it.
+    Loading {
+        has_drawn: bool,
+        event_log: Option<event_log::EventLog>,
+    },
     // Holds the freshly-created MainState for one extra frame so that the first
     // state.draw() call happens in a different ggez tick than MainState::new().
     // ggez's ShaderParams::set_uniforms() uses a dynamic-offset uniform buffer
@@ -803,22 +860,31 @@ enum AppState {
 impl event::EventHandler for AppState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
         match self {
-            Self::Loading { has_drawn } if *has_drawn => {
+            Self::Loading {
+                has_drawn,
+                event_log,
+            } if *has_drawn => {
                 // `update` runs outside ggez's render frame, so startup construction must not
                 // invoke the progress renderer here. The loading screen was already presented
                 // by `draw` on the preceding frame.
-                *self = Self::Warming(MainState::new(ctx)?);
+                let mut state = MainState::new(ctx)?;
+                state.event_log = event_log.take();
+                *self = Self::Warming(state);
                 Ok(())
             }
-            Self::Loading { has_drawn } => {
+            Self::Loading { has_drawn, .. } => {
                 *has_drawn = true;
                 Ok(())
             }
             Self::Warming(_) => {
                 // Transition to Ready on the tick after MainState was created.
-                if let Self::Warming(state) =
-                    std::mem::replace(self, Self::Loading { has_drawn: false })
-                {
+                if let Self::Warming(state) = std::mem::replace(
+                    self,
+                    Self::Loading {
+                        has_drawn: false,
+                        event_log: None,
+                    },
+                ) {
                     *self = Self::Ready(state);
                 }
                 Ok(())
@@ -835,6 +901,13 @@ impl event::EventHandler for AppState {
         }
     }
 
+    fn quit_event(&mut self, _ctx: &mut Context) -> Result<bool, ggez::GameError> {
+        if let Self::Ready(state) = self {
+            state.log_run_end(event_log::RunEnd::Closed);
+        }
+        Ok(false)
+    }
+
     fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, repeat: bool) -> GameResult {
         if let Self::Ready(state) = self {
             state.key_down_event(ctx, input, repeat)
@@ -950,34 +1023,221 @@ fn draw_loading_screen(ctx: &mut Context, progress: f32, label: &str) -> GameRes
     canvas.finish(ctx)
 }
 
-fn main() -> GameResult {
-    let resource_dir = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+/// The game's resource directory: the checkout's `resources/` under cargo, else `./resources`.
+pub(crate) fn resource_dir() -> path::PathBuf {
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
         let mut path = path::PathBuf::from(manifest_dir);
         path.push("resources");
         path
     } else {
         path::PathBuf::from("./resources")
-    };
+    }
+}
+
+fn main() -> GameResult {
+    let resource_dir = resource_dir();
 
     let args: Vec<String> = std::env::args().collect();
-    let bot_script: Option<String> = args
-        .windows(2)
-        .find(|w| w[0] == "--bot")
-        .map(|w| w[1].clone());
+    if args.iter().any(|a| a == "--list-bots") {
+        match bot::BotScenario::builtin() {
+            Ok(scenarios) => {
+                for s in scenarios {
+                    println!(
+                        "{:<20} {:>4}s at {}x, {} events",
+                        s.name,
+                        s.time_limit,
+                        s.time_scale,
+                        s.script.len()
+                    );
+                }
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+    }
+    let arg = |flag: &str| args.windows(2).find(|w| w[0] == flag).map(|w| w[1].clone());
+
+    // `--balance <scenario|policy>`: many seeded headless runs and their outcomes (see balance.rs).
+    if let Some(what) = arg("--balance") {
+        let number = |flag: &str| {
+            arg(flag).map(|v| {
+                v.parse::<f64>().unwrap_or_else(|_| {
+                    eprintln!("{flag} expects a number, got `{v}`");
+                    std::process::exit(1);
+                })
+            })
+        };
+        let subject = balance::Subject::resolve(
+            &what,
+            number("--duration").map(|d| d as f32),
+            number("--node").map(|n| n as usize),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("Invalid --balance subject: {e}");
+            std::process::exit(1);
+        });
+        let runs = number("--runs").map_or(20, |n| n as usize);
+        let first_seed = number("--seed").map_or(1, |s| s as u64);
+        let jobs = number("--jobs").map_or_else(
+            || std::thread::available_parallelism().map_or(1, |n| n.get()),
+            |j| j as usize,
+        );
+        let outcomes = balance::run_batch(&subject, first_seed, runs, jobs);
+        if let Some(out) = arg("--out") {
+            let text = if out.ends_with(".json") {
+                balance::to_json(&outcomes)
+            } else {
+                balance::to_csv(&outcomes)
+            };
+            if let Err(e) = std::fs::write(&out, text) {
+                eprintln!("Could not write {out}: {e}");
+                std::process::exit(1);
+            }
+        }
+        print!("{}", balance::summary(&outcomes));
+        std::process::exit(0);
+    }
+
+    // `--bench <preset>`: time the simulation hot paths on a stress-scale world (see bench.rs).
+    if let Some(preset) = arg("--bench") {
+        let fail = |e: String| -> ! {
+            eprintln!("{e}");
+            std::process::exit(1);
+        };
+        let number = |flag: &str| {
+            arg(flag).map(|v| {
+                v.parse::<f64>()
+                    .unwrap_or_else(|_| fail(format!("{flag} expects a number, got `{v}`")))
+            })
+        };
+        let mut config = bench::BenchConfig::preset(&preset)
+            .unwrap_or_else(|e| fail(format!("Invalid --bench preset: {e}")));
+        let counts = [
+            ("--crabs", &mut config.crabs),
+            ("--chained", &mut config.chained),
+            ("--trains", &mut config.trains),
+            ("--train-len", &mut config.train_len),
+            ("--steps", &mut config.steps),
+        ];
+        for (flag, field) in counts {
+            if let Some(n) = number(flag) {
+                *field = n as usize;
+            }
+        }
+        if let Some(mix) = arg("--mix") {
+            config.mix =
+                bench::Mix::parse(&mix).unwrap_or_else(|e| fail(format!("Invalid --mix: {e}")));
+        }
+        if let Some(map) = arg("--map") {
+            config.map = match map.as_str() {
+                "medium" => levels::MapSize::Medium,
+                "large" => levels::MapSize::Large,
+                _ => fail(format!("--map expects medium or large, got `{map}`")),
+            };
+        }
+        if let Some(panicked) = number("--panicked") {
+            config.panicked = panicked as f32;
+        }
+        if let Some(seed) = number("--seed") {
+            config.seed = seed as u64;
+        }
+        let result = bench::run(&config);
+        print!("{}", bench::summary(&config, &result));
+        if let Some(out) = arg("--out")
+            && let Err(e) = std::fs::write(&out, bench::to_csv(&result))
+        {
+            fail(format!("Could not write {out}: {e}"));
+        }
+        if let Some(baseline) = arg("--baseline") {
+            let text = std::fs::read_to_string(&baseline)
+                .unwrap_or_else(|e| fail(format!("Could not read {baseline}: {e}")));
+            let tolerance = number("--tolerance").map_or(25.0, |t| t as f32);
+            match bench::compare(&result, &text, tolerance) {
+                Ok(report) => print!("against {baseline}:\n{report}"),
+                Err(report) => fail(format!(
+                    "against {baseline}:\n{report}slower than the baseline by more than {tolerance}%"
+                )),
+            }
+        }
+        std::process::exit(0);
+    }
 
     // Seed the deterministic bot RNG BEFORE anything (incl. MainState::new's initial king-crab
     // name generation) draws from it, so the ENTIRE bot run — construction included — is
     // reproducible. Bot-only and skipped for RUSTLER_RECORD; see the fuller note at the bot setup
     // below. Real interactive play never reaches this branch, so its RNG stays entropy-seeded.
-    if let Some(ref name) = bot_script {
+    let bot_scenario = match (arg("--bot"), arg("--bot-file")) {
+        (Some(name), _) => Some(bot::BotScenario::find(&name)),
+        (None, Some(file)) => Some(bot::BotScenario::load(path::Path::new(&file))),
+        (None, None) => None,
+    }
+    .map(|loaded| {
+        loaded.unwrap_or_else(|e| {
+            eprintln!("Invalid bot script: {e}");
+            std::process::exit(1);
+        })
+    });
+    // `--bot-report <file>` / `--bot-junit <file>`: structured results for CI (see bot_report.rs).
+    let report_paths = bot_report::ReportPaths {
+        json: arg("--bot-report").map(path::PathBuf::from),
+        junit: arg("--bot-junit").map(path::PathBuf::from),
+    };
+    // `--event-log <file>`: stream gameplay events as JSON lines (see event_log.rs).
+    let mut event_log = arg("--event-log").map(|p| {
+        event_log::EventLog::create(path::Path::new(&p)).unwrap_or_else(|e| {
+            eprintln!("Could not create event log {p}: {e}");
+            std::process::exit(1);
+        })
+    });
+    if let Some(scenario) = &bot_scenario {
+        // `--headless` runs the scenario on the `Simulation` core instead: no window, no audio
+        // device, no display server. Same script, seed, time scale and fixed timestep.
+        if args.iter().any(|a| a == "--headless") {
+            let mut sim = simulation::Simulation::bot(scenario);
+            sim.set_event_log(event_log.take());
+            let passed = sim.run_bot().is_ok();
+            sim.close_event_log();
+            if let Some(report) = sim.bot_report()
+                && let Err(e) = report_paths.write(&report)
+            {
+                eprintln!("Bot report: {e}");
+            }
+            std::process::exit(if passed { 0 } else { 1 });
+        }
         if std::env::var_os("RUSTLER_RECORD").is_none() {
-            // Per-scenario constant seed: distinct streams keep scenarios independent while each
-            // stays reproducible. A hash of the name gives a stable, unique-per-scenario u64.
-            let seed = name.bytes().fold(0xC5AB_1234_5678_9ABC_u64, |h, b| {
-                h.rotate_left(7) ^ (b as u64).wrapping_mul(0x100000001B3)
-            });
-            rng::seed(seed);
+            rng::seed(scenario.seed);
+        }
+    }
+
+    // `--record <file>` streams this session's input to a replay file; `--replay <file>` plays
+    // one back. Interactive play already runs on a fixed timestep, so all a reproducible run
+    // still needs is a known RNG seed — recording picks one at random and writes it down first.
+    let record_path = arg("--record");
+    let replay_path = arg("--replay");
+    if record_path.is_some() && bot_scenario.is_some() {
+        eprintln!("--record captures interactive play; it can't be combined with --bot");
+        std::process::exit(1);
+    }
+    let replay = replay_path.map(|p| {
+        replay::Replay::load(path::Path::new(&p)).unwrap_or_else(|e| {
+            eprintln!("Invalid replay: {e}");
+            std::process::exit(1);
+        })
+    });
+    if let Some(replay) = &replay {
+        if args.iter().any(|a| a == "--headless") {
+            let sim = simulation::Simulation::run_replay(replay, event_log.take());
+            println!("Replayed {} frames: {}", replay.frames.len(), sim.summary());
+            std::process::exit(0);
         }
+        rng::seed(replay.seed);
+    }
+    let record_seed = record_path.as_ref().map(|_| rand::random::<u64>());
+    if let Some(seed) = record_seed {
+        rng::seed(seed);
     }
 
     let (mut ctx, event_loop) = ContextBuilder::new("rustler", "carlthome")
@@ -1005,22 +1265,24 @@ fn main() -> GameResult {
             .window()
             .request_inner_size(LogicalSize::new(logical_w, logical_h));
     }
-    let mut app = if bot_script.is_some() {
-        AppState::Ready(MainState::new(&mut ctx)?)
+    let mut app = if bot_scenario.is_some() || replay.is_some() || record_path.is_some() {
+        let mut state = match &replay {
+            Some(replay) => MainState::new_for_replay(&mut ctx, replay.save.clone())?,
+            None => MainState::new(&mut ctx)?,
+        };
+        state.event_log = event_log;
+        AppState::Ready(state)
     } else {
-        AppState::Loading { has_drawn: false }
+        AppState::Loading {
+            has_drawn: false,
+            event_log,
+        }
     };
 
-    if let Some(ref name) = bot_script {
+    if let Some(scenario) = bot_scenario {
         let AppState::Ready(state) = &mut app else {
             unreachable!("bot startup always initializes the game state");
         };
-        use bot::{
-            BotState, script_campaign_escape, script_campaign_full, script_campaign_loss,
-            script_campaign_tutorial, script_groove_dash, script_menu_to_game, script_npc_steal,
-            script_npc_vs_npc, script_player_steal, script_revenge, script_steal_defense,
-            script_steal_dodge,
-        };
         // ── Determinism, root-cause fix for playtest flakiness ────────────────────────────────
         // The bot asserts on emergent outcomes ("a revenge steal happened"), which are only a
         // stable pass/fail if every run is reproducible. Two things make a run vary:
@@ -1034,10 +1296,11 @@ fn main() -> GameResult {
         //      `crate::rng::rng()`. It's seeded from a per-scenario constant above (before
         //      MainState::new) so the draw sequence is the same every run.
         //
-        // Both are strictly bot-only: interactive play never seeds the RNG and never sets a fixed
-        // dt, so its variable-dt smooth rendering and entropy randomness are completely unchanged.
-        // RUSTLER_RECORD (the shareable-GIF path) is deliberately excluded so the captured clip
-        // still plays at natural wall-clock speed.
+        // Interactive play gets (1) too, from its fixed-step accumulator (see `MainState::tick`),
+        // but it still ticks once per fixed step of *wall-clock* time; a bot takes one step per
+        // rendered frame so it runs as fast as the machine allows. (2) is bot/replay-only —
+        // ordinary play keeps its entropy randomness. RUSTLER_RECORD (the shareable-GIF path) is
+        // deliberately excluded so the captured clip still plays at natural wall-clock speed.
         if std::env::var_os("RUSTLER_RECORD").is_none() {
             // Fixed simulation timestep (default 1/60 s). RUSTLER_BOT_DT overrides it so a run can
             // be replayed at a different effective frame rate to prove the outcome is truly
@@ -1046,66 +1309,62 @@ fn main() -> GameResult {
                 .ok()
                 .and_then(|s| s.parse::<f32>().ok())
                 .filter(|d| *d > 0.0)
-                .unwrap_or(1.0 / 60.0);
+                .unwrap_or(bot::BOT_DT);
             state.bot_fixed_dt = Some(fixed_dt);
         }
-        // menu_to_game and campaign_tutorial run at 3× so the proximity catch check fires frequently
-        // enough for the seek-catch autopilot to register catches (at 8× the player teleports past
-        // crabs between frames, catching nothing). campaign_tutorial's BeatTiming lesson clears on
-        // ON-BEAT catches, which the autopilot lands by volume (a steady stream of whistle catches at
-        // a ~30% on-beat rate); its script leaves a wide time margin so even an unlucky low-rate run
-        // banks 3 on-beat catches and returns to the world map before the final assert.
         state.time_scale = if std::env::var_os("RUSTLER_RECORD").is_some() {
             // Recording a shareable clip: run at real time so the captured gameplay looks
             // natural rather than the sped-up pace the headless playtests use.
             1.0
         } else {
-            match name.as_str() {
-                // The chain-dependent defense scenarios (parry/dodge/revenge) need the seek-catch
-                // autopilot to reliably hold a >=2-link chain for their ForceStealDefense/Dodge/
-                // RevengeCross attempts to have anything to act on. At 3x the *effective* per-frame
-                // step (real_dt * time_scale) grows large on a slow/loaded CI runner, and — as the
-                // 8x comment below notes — the player then teleports past crabs and catches nothing,
-                // so the chain stalls at 1 link and the parry/revenge asserts flake red. 2x keeps the
-                // step small enough that catches register reliably, trading a little wall-clock (still
-                // a parallel matrix leg) for a green that isn't a coin-flip.
-                "steal_defense" | "steal_dodge" | "revenge" => 2.0,
-                "campaign_escape" | "campaign_loss" | "menu_to_game" | "campaign_tutorial"
-                | "campaign_full" | "npc_steal" | "player_steal" | "npc_vs_npc" => 3.0,
-                _ => 8.0,
-            }
+            scenario.time_scale
         };
-        state.bot = Some(match name.as_str() {
-            "menu_to_game" => BotState::new(script_menu_to_game(), 60.0),
-            "campaign_escape" => BotState::new(script_campaign_escape(), 8.0),
-            "campaign_loss" => BotState::new(script_campaign_loss(), 8.0),
-            "campaign_tutorial" => BotState::new(script_campaign_tutorial(), 76.0),
-            "campaign_full" => BotState::new(script_campaign_full(), 322.0),
-            "npc_steal" => BotState::new(script_npc_steal(), 58.0),
-            "player_steal" => BotState::new(script_player_steal(), 58.0),
-            "steal_defense" => BotState::new(script_steal_defense(), 58.0),
-            "steal_dodge" => BotState::new(script_steal_dodge(), 58.0),
-            "revenge" => BotState::new(script_revenge(), 58.0),
-            "npc_vs_npc" => BotState::new(script_npc_vs_npc(), 56.0),
-            "groove_dash" => BotState::new(script_groove_dash(), 10.0),
-            other => {
-                eprintln!("Unknown bot script: {}", other);
-                std::process::exit(1);
-            }
-        });
+        // Scripts press the default keys, whatever the player has rebound them to.
+        state.bindings = bindings::Bindings::default();
+        let mut bot = scenario.bot_state();
+        bot.report_paths = report_paths;
+        state.bot = Some(bot);
         state.skip_menu_intro();
     }
 
+    if let AppState::Ready(state) = &mut app {
+        if let Some(replay) = replay {
+            state.bot_fixed_dt = Some(replay.dt);
+            state.session_date = replay.date;
+            state.replay = Some(replay.frames.into());
+        }
+        if let (Some(path), Some(seed)) = (record_path, record_seed) {
+            // The whole session keeps the day it started on, as its playback will.
+            state.session_date = Some(leaderboard::today());
+            let header = replay::Replay {
+                seed,
+                dt: state.bot_fixed_dt.unwrap_or(SIM_DT),
+                date: state.session_date.clone(),
+                save: state.save_data(),
+                frames: Vec::new(),
+            };
+            match replay::Recorder::create(path::Path::new(&path), &header) {
+                Ok(recorder) => state.recorder = Some(recorder),
+                Err(e) => {
+                    eprintln!("Could not create replay file {path}: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
     event::run(ctx, event_loop, app)
 }
 
 #[cfg(test)]
 mod how_to_play_tests {
     use super::how_to_play_body_text;
+    use crate::bindings::{Action, Bindings};
+    use ggez::input::keyboard::KeyCode;
 
     #[test]
     fn how_to_play_text_matches_current_controls() {
-        let text = how_to_play_body_text();
+        let text = how_to_play_body_text(&Bindings::default());
         for expected in [
             "Shift",
             "Space  Dash",
@@ -1129,6 +1388,18 @@ mod how_to_play_tests {
         assert!(!text.contains("Z: whistle"));
         assert!(!text.contains("C: cycle"));
     }
+
+    #[test]
+    fn how_to_play_text_follows_rebinding() {
+        let mut bindings = Bindings::default();
+        bindings.rebind(Action::MoveUp, KeyCode::KeyZ).unwrap();
+        bindings.rebind(Action::MoveLeft, KeyCode::KeyQ).unwrap();
+        let text = how_to_play_body_text(&bindings);
+        assert!(text.contains("Move with ZQSD / arrows"));
+        // Wave was on Q, so it picked up Move Left's old key.
+        assert!(text.contains("- A  Wave"));
+        assert!(text.contains("hold a tool (E/R/A)"));
+    }
 }
 
 #[cfg(test)]
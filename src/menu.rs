@@ -3,6 +3,7 @@ use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
 use ggez::{Context, GameResult};
 use std::cell::RefCell;
 
+use crate::bindings::Action;
 use crate::enemies::{BossCharge, CrabType, EnemyCrab};
 use crate::graphics::{
     draw_crab, draw_rustler, flush_crab_bodies, flush_crab_legs, unit_circle, unit_square,
@@ -11,9 +12,23 @@ use crate::hud_cache::{
     CAREER_LABEL_CACHE, LOADOUT_PAGE_CACHE, MENU_BUTTONS_CACHE, MENU_SUBTITLE_CACHE,
     MENU_TITLE_CACHE, MENU_TITLE_CHARS_CACHE,
 };
+use crate::mutators::{Mutator, PreRun};
 use crate::skins::PlayerSkin;
 use crate::state::MainState;
 
+/// The home page's buttons, top to bottom. `controls.rs` maps a selection index to its action.
+pub(crate) const BUTTON_LABELS: [&str; 9] = [
+    "PLAY",
+    "CAMPAIGN",
+    "ENDLESS",
+    "DAILY CHALLENGE",
+    "LOADOUT",
+    "CONTROLS",
+    "RIVALS",
+    "HOW TO PLAY",
+    "QUIT",
+];
+
 pub fn draw_menu(
     state: &MainState,
     ctx: &mut Context,
@@ -410,7 +425,7 @@ pub fn draw_menu(
             ctx,
             canvas,
             Vec2::new(preview_x + 26.0, preview_top + 16.0),
-            &state.textures.player,
+            &state.gfx().textures.player,
             Vec2::ZERO,
             0.2 + 0.2 * (t * 3.0).sin().abs(),
             t,
@@ -440,10 +455,9 @@ pub fn draw_menu(
 
     // --- Home page: traditional centered menu buttons ----------------------------------
     if state.menu_page == 0 {
-        const BUTTON_LABELS: [&str; 5] = ["PLAY", "CAMPAIGN", "LOADOUT", "HOW TO PLAY", "QUIT"];
         let btn_w = 320.0_f32;
-        let btn_h = 54.0_f32;
-        let btn_gap = 18.0_f32;
+        let btn_h = 42.0_f32;
+        let btn_gap = 8.0_f32;
         let total_h = BUTTON_LABELS.len() as f32 * (btn_h + btn_gap) - btn_gap;
         let btn_start_y = height * 0.42;
         let btn_x = (width - btn_w) / 2.0;
@@ -740,6 +754,127 @@ pub fn draw_menu(
         }
     }
 
+    // --- Mutator screen before an arcade or endless run -----------------------------------
+    if let Some(mode) = state.pre_run {
+        let dim = unit_square(ctx)?;
+        canvas.draw(
+            dim,
+            DrawParam::default()
+                .scale(Vec2::new(width, height))
+                .color(Color::from_rgba(0, 0, 0, 170)),
+        );
+        let row_h = 44.0;
+        let panel_w = 720.0;
+        let panel_h = 150.0 + (Mutator::ALL.len() + 1) as f32 * row_h;
+        let panel_x = (width - panel_w) * 0.5;
+        let panel_y = (height - panel_h) * 0.5;
+        let panel_rect = Rect::new(panel_x, panel_y, panel_w, panel_h);
+        let panel = Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            panel_rect,
+            18.0,
+            Color::from_rgb(20, 28, 52),
+        )?;
+        let border = Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::stroke(3.0),
+            panel_rect,
+            18.0,
+            Color::from_rgb(116, 230, 209),
+        )?;
+        canvas.draw(&panel, DrawParam::default());
+        canvas.draw(&border, DrawParam::default());
+
+        let title = match mode {
+            PreRun::Arcade => "ARCADE MUTATORS",
+            PreRun::Endless => "ENDLESS MUTATORS",
+        };
+        let mut label = Text::new(title);
+        label.set_scale(32.0);
+        let label_w = label.measure(ctx)?.x;
+        canvas.draw(
+            &label,
+            DrawParam::default()
+                .dest(Vec2::new((width - label_w) * 0.5, panel_y + 24.0))
+                .color(Color::from_rgb(255, 232, 170)),
+        );
+
+        let picks = state.mutator_picks;
+        let rows_y = panel_y + 84.0;
+        let highlight = Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, panel_w - 48.0, row_h - 6.0),
+            8.0,
+            Color::from_rgb(57, 166, 151),
+        )?;
+        for (i, mutator) in Mutator::ALL.iter().enumerate() {
+            let y = rows_y + i as f32 * row_h;
+            let selected = state.mutator_selection == i;
+            if selected {
+                canvas.draw(
+                    &highlight,
+                    DrawParam::default().dest(Vec2::new(panel_x + 24.0, y - 6.0)),
+                );
+            }
+            let on = picks.has(*mutator);
+            let color = if on {
+                Color::from_rgb(255, 255, 225)
+            } else {
+                Color::from_rgb(150, 163, 185)
+            };
+            let check = if on { "[x]" } else { "[ ]" };
+            for (text, scale, x, dy) in [
+                (format!("{check}  {}", mutator.label()), 20.0, 40.0, 0.0),
+                (format!("x{:.2}", mutator.score_mult()), 20.0, 250.0, 0.0),
+                (mutator.blurb().to_string(), 16.0, 330.0, 3.0),
+            ] {
+                let mut cell = Text::new(text);
+                cell.set_scale(scale);
+                canvas.draw(
+                    &cell,
+                    DrawParam::default()
+                        .dest(Vec2::new(panel_x + x, y + dy))
+                        .color(color),
+                );
+            }
+        }
+
+        // START row: names the multiplier the picks add up to.
+        let start_y = rows_y + Mutator::ALL.len() as f32 * row_h + 8.0;
+        if state.mutator_selection == Mutator::ALL.len() {
+            canvas.draw(
+                &highlight,
+                DrawParam::default().dest(Vec2::new(panel_x + 24.0, start_y - 6.0)),
+            );
+        }
+        let start = if picks.is_empty() {
+            "START  (no mutators)".to_string()
+        } else {
+            format!("START  —  score x{:.2}", picks.score_mult())
+        };
+        let mut label = Text::new(start);
+        label.set_scale(22.0);
+        let label_w = label.measure(ctx)?.x;
+        canvas.draw(
+            &label,
+            DrawParam::default()
+                .dest(Vec2::new((width - label_w) * 0.5, start_y))
+                .color(Color::from_rgb(255, 232, 170)),
+        );
+        let mut hint =
+            Text::new("\u{25B2}/\u{25BC} pick    Space/Enter toggle or start    Esc back");
+        hint.set_scale(15.0);
+        let hint_w = hint.measure(ctx)?.x;
+        canvas.draw(
+            &hint,
+            DrawParam::default()
+                .dest(Vec2::new((width - hint_w) * 0.5, panel_y + panel_h - 32.0))
+                .color(Color::from_rgba(160, 170, 200, 160)),
+        );
+    }
+
     // --- Loadout page: skin picker + perk shop -----------------------------------------
     if state.menu_page == 1 {
         let skin = state.player_skin;
@@ -855,7 +990,7 @@ pub fn draw_menu(
             ctx,
             canvas,
             preview_center - Vec2::new(15.0, 15.0) + Vec2::new(0.0, bob),
-            &state.textures.player,
+            &state.gfx().textures.player,
             Vec2::ZERO,
             0.4 + 0.4 * (t * 3.0).sin().abs(),
             t,
@@ -910,5 +1045,182 @@ pub fn draw_menu(
         );
     } // end menu_page == 1 (Loadout)
 
+    // --- Controls page: one row per action with its bound key ---------------------------
+    if state.menu_page == 2 {
+        draw_controls_list(state, ctx, canvas, width, height * 0.3)?;
+    }
+
+    // --- Rivals page: the nemesis roster, most notorious first --------------------------
+    if state.menu_page == 3 {
+        draw_rivals_list(state, ctx, canvas, width, height * 0.3)?;
+    }
+
+    Ok(())
+}
+
+/// The RIVALS page: one row per remembered rival (see nemesis.rs), or a note that nobody holds a
+/// grudge yet, and the way back.
+fn draw_rivals_list(
+    state: &MainState,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    width: f32,
+    list_top: f32,
+) -> GameResult {
+    let row_h = 30.0_f32;
+    let list_w = 680.0_f32;
+    let list_x = (width - list_w) / 2.0;
+    let rivals = state.nemeses.rivals();
+    let rows = rivals.len().max(1);
+    canvas.draw(
+        unit_square(ctx)?,
+        DrawParam::default()
+            .dest(Vec2::new(list_x - 16.0, list_top - 12.0))
+            .scale(Vec2::new(list_w + 32.0, rows as f32 * row_h + 24.0))
+            .color(Color::from_rgba(10, 14, 30, 150)),
+    );
+
+    // The roster only changes between runs, so each row's shaped Text is cached against its
+    // source string like the Controls rows.
+    thread_local! {
+        static RIVALS_ROW_CACHE: RefCell<Vec<Option<(String, Text)>>> = const { RefCell::new(Vec::new()) };
+    }
+    RIVALS_ROW_CACHE.with(|c| {
+        let mut cache = c.borrow_mut();
+        cache.resize_with(crate::nemesis::ROSTER_SIZE.max(rows), || None);
+        let lines: Vec<String> = if rivals.is_empty() {
+            vec!["No rivals yet. Get robbed a little, or rob someone.".to_string()]
+        } else {
+            rivals.iter().map(|nemesis| nemesis.row()).collect()
+        };
+        for (i, line) in lines.iter().enumerate() {
+            let entry = &mut cache[i];
+            if entry.as_ref().is_none_or(|(s, _)| s != line) {
+                let mut t = Text::new(line.as_str());
+                t.set_scale(20.0);
+                *entry = Some((line.clone(), t));
+            }
+            // The worst of them burns hottest.
+            let color = if i == 0 && !rivals.is_empty() {
+                Color::from_rgb(255, 170, 110)
+            } else {
+                Color::from_rgba(200, 210, 230, 220)
+            };
+            canvas.draw(
+                &entry.as_ref().unwrap().1,
+                DrawParam::default()
+                    .dest(Vec2::new(list_x, list_top + i as f32 * row_h))
+                    .color(color),
+            );
+        }
+    });
+
+    let below = list_top + rows as f32 * row_h + 28.0;
+    let mut hint = Text::new("Esc back");
+    hint.set_scale(16.0);
+    let hw = hint.measure(ctx)?.x;
+    canvas.draw(
+        &hint,
+        DrawParam::default()
+            .dest(Vec2::new((width - hw) / 2.0, below))
+            .color(Color::from_rgba(160, 170, 200, 160)),
+    );
+    Ok(())
+}
+
+/// The Controls list: every action and its key, the highlighted row, the capture prompt, a hint
+/// line and any rebind notice. Drawn by the title menu's CONTROLS page and the pause menu's.
+pub(crate) fn draw_controls_list(
+    state: &MainState,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    width: f32,
+    list_top: f32,
+) -> GameResult {
+    let row_h = 30.0_f32;
+    let list_w = 460.0_f32;
+    let list_x = (width - list_w) / 2.0;
+    let square = unit_square(ctx)?;
+    canvas.draw(
+        square,
+        DrawParam::default()
+            .dest(Vec2::new(list_x - 16.0, list_top - 12.0))
+            .scale(Vec2::new(
+                list_w + 32.0,
+                Action::ALL.len() as f32 * row_h + 24.0,
+            ))
+            .color(Color::from_rgba(10, 14, 30, 150)),
+    );
+
+    // Row text only changes on a rebind (or while a capture is armed), so each row's shaped
+    // Text is cached against its source string like the other menu labels.
+    thread_local! {
+        static CONTROLS_ROW_CACHE: RefCell<Vec<Option<(String, Text)>>> = const { RefCell::new(Vec::new()) };
+    }
+    CONTROLS_ROW_CACHE.with(|c| {
+        let mut cache = c.borrow_mut();
+        cache.resize_with(Action::ALL.len() * 2, || None);
+        for (i, action) in Action::ALL.into_iter().enumerate() {
+            let y = list_top + i as f32 * row_h;
+            let selected = state.rebind_selection == i;
+            if selected {
+                canvas.draw(
+                    square,
+                    DrawParam::default()
+                        .dest(Vec2::new(list_x - 8.0, y - 3.0))
+                        .scale(Vec2::new(list_w + 16.0, row_h - 2.0))
+                        .color(Color::from_rgba(60, 180, 160, 150)),
+                );
+            }
+            let key = if selected && state.rebind_capture {
+                "press a key…"
+            } else {
+                state.bindings.label(action)
+            };
+            let color = if selected {
+                Color::from_rgb(255, 255, 225)
+            } else {
+                Color::from_rgba(200, 210, 230, 220)
+            };
+            for (slot, text, x) in [
+                (i * 2, action.label(), list_x),
+                (i * 2 + 1, key, list_x + list_w * 0.62),
+            ] {
+                let entry = &mut cache[slot];
+                if entry.as_ref().is_none_or(|(s, _)| s != text) {
+                    let mut t = Text::new(text);
+                    t.set_scale(21.0);
+                    *entry = Some((text.to_string(), t));
+                }
+                canvas.draw(
+                    &entry.as_ref().unwrap().1,
+                    DrawParam::default().dest(Vec2::new(x, y)).color(color),
+                );
+            }
+        }
+    });
+
+    let below = list_top + Action::ALL.len() as f32 * row_h + 28.0;
+    let mut hint =
+        Text::new("\u{25B2}/\u{25BC} choose    Enter rebind    Backspace default    Esc back");
+    hint.set_scale(16.0);
+    let hw = hint.measure(ctx)?.x;
+    canvas.draw(
+        &hint,
+        DrawParam::default()
+            .dest(Vec2::new((width - hw) / 2.0, below))
+            .color(Color::from_rgba(160, 170, 200, 160)),
+    );
+    if let Some(notice) = &state.rebind_notice {
+        let mut t = Text::new(notice.as_str());
+        t.set_scale(18.0);
+        let nw = t.measure(ctx)?.x;
+        canvas.draw(
+            &t,
+            DrawParam::default()
+                .dest(Vec2::new((width - nw) / 2.0, below + 28.0))
+                .color(Color::from_rgb(255, 140, 120)),
+        );
+    }
     Ok(())
 }
@@ -0,0 +1,287 @@
+//! Run mutators: opt-in rules that bend an arcade or endless run in exchange for a score
+//! multiplier.
+//!
+//! Each mutator only turns a knob the game already has: the herd's emphasized archetype, whether
+//! the whistle fires, the starting `INTENSITY_STAGES` tier, how many links a panic snap tears off,
+//! which way the steering reads, and how many rival trains start on the beach. They're picked on a
+//! screen before the run (see menu.rs), come into force in `reset_game_at`, and stay for every
+//! restart until the player leaves for the menu or the world map. Campaign nodes never carry them.
+//! A daily challenge deals its own (see daily.rs).
+//!
+//! The run's score is multiplied when it ends (see [`MainState::final_score`]), so the numbers the
+//! player watches during the run, and everything keyed off them, stay unchanged.
+
+use rand::Rng;
+
+use crate::MainState;
+use crate::enemies::CrabType;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Mutator {
+    ThiefPlague,
+    NoWhistle,
+    DoubleTempo,
+    GlassTrain,
+    MirrorBeach,
+    GoldenRush,
+    RivalsX2,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 7] = [
+        Mutator::ThiefPlague,
+        Mutator::NoWhistle,
+        Mutator::DoubleTempo,
+        Mutator::GlassTrain,
+        Mutator::MirrorBeach,
+        Mutator::GoldenRush,
+        Mutator::RivalsX2,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mutator::ThiefPlague => "Thief Plague",
+            Mutator::NoWhistle => "No Whistle",
+            Mutator::DoubleTempo => "Double Tempo",
+            Mutator::GlassTrain => "Glass Train",
+            Mutator::MirrorBeach => "Mirror Beach",
+            Mutator::GoldenRush => "Golden Rush",
+            Mutator::RivalsX2 => "Rivals x2",
+        }
+    }
+
+    pub fn blurb(self) -> &'static str {
+        match self {
+            Mutator::ThiefPlague => "Every herd is emphasized toward Thieves",
+            Mutator::NoWhistle => "The whistle never fires",
+            Mutator::DoubleTempo => "The run starts HEATED",
+            Mutator::GlassTrain => "Panic snaps tear off twice the links",
+            Mutator::MirrorBeach => "Left steers right and right steers left",
+            Mutator::GoldenRush => "Every herd is emphasized toward Golden crabs",
+            Mutator::RivalsX2 => "Six rival trains instead of three",
+        }
+    }
+
+    /// What the mutator does to the run's score. Golden Rush makes a run easier, so it costs.
+    pub fn score_mult(self) -> f32 {
+        match self {
+            Mutator::ThiefPlague => 1.25,
+            Mutator::NoWhistle => 1.3,
+            Mutator::DoubleTempo => 1.4,
+            Mutator::GlassTrain => 1.3,
+            Mutator::MirrorBeach => 1.5,
+            Mutator::GoldenRush => 0.75,
+            Mutator::RivalsX2 => 1.35,
+        }
+    }
+
+    /// The herd emphasis the mutator forces, if it forces one.
+    fn emphasis(self) -> Option<CrabType> {
+        match self {
+            Mutator::ThiefPlague => Some(CrabType::Thief),
+            Mutator::GoldenRush => Some(CrabType::Golden),
+            _ => None,
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << Mutator::ALL.iter().position(|&m| m == self).unwrap()
+    }
+}
+
+/// A set of mutators.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Mutators(u8);
+
+impl Mutators {
+    pub fn has(self, mutator: Mutator) -> bool {
+        self.0 & mutator.bit() != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Mutator> {
+        Mutator::ALL.into_iter().filter(move |&m| self.has(m))
+    }
+
+    /// Switch `mutator` on or off. Thief Plague and Golden Rush both pick the emphasis, so
+    /// switching one on switches the other off.
+    pub fn toggle(&mut self, mutator: Mutator) {
+        if !self.has(mutator) && mutator.emphasis().is_some() {
+            for other in self.iter().filter(|m| m.emphasis().is_some()) {
+                self.0 &= !other.bit();
+            }
+        }
+        self.0 ^= mutator.bit();
+    }
+
+    /// `count` random mutators, for a daily challenge.
+    pub fn deal(count: usize, rng: &mut impl Rng) -> Self {
+        let mut set = Mutators::default();
+        while set.iter().count() < count.min(Mutator::ALL.len() - 1) {
+            let mutator = Mutator::ALL[rng.random_range(0..Mutator::ALL.len())];
+            if !set.has(mutator) {
+                set.toggle(mutator);
+            }
+        }
+        set
+    }
+
+    /// The product of every mutator's multiplier.
+    pub fn score_mult(self) -> f32 {
+        self.iter().map(Mutator::score_mult).product()
+    }
+
+    /// The herd emphasis the set forces, if any.
+    pub fn emphasis(self) -> Option<CrabType> {
+        self.iter().find_map(Mutator::emphasis)
+    }
+
+    /// The set for the HUD: "Glass Train · Rivals x2  x1.76".
+    pub fn summary(self) -> String {
+        let labels: Vec<_> = self.iter().map(Mutator::label).collect();
+        format!("{}  x{:.2}", labels.join(" · "), self.score_mult())
+    }
+}
+
+/// The run the mutator screen will start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PreRun {
+    Arcade,
+    Endless,
+}
+
+impl MainState {
+    /// Open the mutator screen before a run in `mode`.
+    pub(crate) fn open_pre_run(&mut self, mode: PreRun) {
+        self.pre_run = Some(mode);
+        self.mutator_selection = Mutator::ALL.len();
+    }
+
+    /// Start the run the mutator screen was opened for, with the picked mutators.
+    pub(crate) fn start_pre_run(&mut self) {
+        let Some(mode) = self.pre_run.take() else {
+            return;
+        };
+        self.mutators = self.mutator_picks;
+        match mode {
+            PreRun::Arcade => self.reset_game(),
+            PreRun::Endless => self.start_endless(),
+        }
+        self.show_instructions = false;
+        self.show_how_to_play_text = false;
+    }
+
+    /// Turn the knobs the active mutators own. Called at the end of `reset_game_at`, before the
+    /// first wave is dealt.
+    pub(crate) fn apply_mutators(&mut self) {
+        if self.mutators.has(Mutator::DoubleTempo) {
+            // The tempo catches up on the first downbeat (see `on_beat`).
+            self.intensity_stage = 2;
+            self.stage_banner_name = crate::constants::INTENSITY_STAGES[2].1;
+            self.stage_banner_timer = 2.0;
+        }
+        if self.mutators.has(Mutator::RivalsX2) {
+            let mirrored: Vec<_> = self
+                .npc_trains
+                .iter()
+                .map(|train| train.mirrored(self.world_width))
+                .collect();
+            self.npc_trains.extend(mirrored);
+        }
+    }
+
+    /// Links a panic snap tears off a train of `n`, Glass Train included.
+    pub(crate) fn panic_snap_count(&self, n: usize) -> usize {
+        let links = crate::panic_snap_links(n);
+        if self.mutators.has(Mutator::GlassTrain) {
+            links * 2
+        } else {
+            links
+        }
+    }
+
+    /// The run's score with the mutators' multiplier applied: what the career and the tables get.
+    pub(crate) fn final_score(&self) -> usize {
+        if self.mutators.is_empty() {
+            self.score
+        } else {
+            (self.score as f32 * self.mutators.score_mult()).round() as usize
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leaderboard::ARCADE;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn emphasis_mutators_exclude_each_other() {
+        let mut set = Mutators::default();
+        set.toggle(Mutator::ThiefPlague);
+        set.toggle(Mutator::GlassTrain);
+        assert_eq!(set.emphasis(), Some(CrabType::Thief));
+        set.toggle(Mutator::GoldenRush);
+        assert!(!set.has(Mutator::ThiefPlague));
+        assert_eq!(set.emphasis(), Some(CrabType::Golden));
+        set.toggle(Mutator::GoldenRush);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [Mutator::GlassTrain]);
+        assert_eq!(set.summary(), "Glass Train  x1.30");
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..50 {
+            let dealt = Mutators::deal(3, &mut rng);
+            assert_eq!(dealt.iter().count(), 3);
+            assert!(
+                !(dealt.has(Mutator::ThiefPlague) && dealt.has(Mutator::GoldenRush)),
+                "{dealt:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn mutators_turn_the_existing_knobs() {
+        let mut state = MainState::new_headless();
+        state.reset_game();
+        let rivals = state.npc_trains.len();
+        assert_eq!(state.panic_snap_count(12), 5);
+
+        for mutator in [
+            Mutator::ThiefPlague,
+            Mutator::NoWhistle,
+            Mutator::DoubleTempo,
+            Mutator::GlassTrain,
+            Mutator::RivalsX2,
+        ] {
+            state.mutator_picks.toggle(mutator);
+        }
+        state.open_pre_run(PreRun::Arcade);
+        state.start_pre_run();
+        assert!(state.pre_run.is_none() && !state.show_instructions);
+        assert_eq!(state.intensity_stage, 2);
+        assert_eq!(state.npc_trains.len(), rivals * 2);
+        assert_eq!(state.panic_snap_count(12), 10);
+        assert_eq!(state.mutators.emphasis(), Some(CrabType::Thief));
+        state.fire_whistle();
+        assert_eq!(state.whistle_cooldown, 0.0);
+
+        // Restarting keeps them; the multiplier lands on the recorded score.
+        state.reset_game();
+        assert_eq!(state.intensity_stage, 2);
+        state.score = 100;
+        state.record_run();
+        let expected = (100.0 * 1.25 * 1.3 * 1.4 * 1.3 * 1.35_f32).round() as usize;
+        assert_eq!(state.leaderboards.table(ARCADE)[0].score, expected);
+        assert_eq!(state.career_best_score, expected);
+
+        // Campaign runs never carry them.
+        state.enter_world_map();
+        assert!(state.mutators.is_empty());
+        assert_eq!(state.mutator_picks.iter().count(), 5);
+    }
+}
@@ -0,0 +1,367 @@
+//! Nemesis roster: the rival King Crabs that remember you, run after run.
+//!
+//! A rival joins the roster the first time it steals from the player, wins a clash against them or
+//! is stripped bare by them, and from then on its history is kept in the save (see save.rs): crabs
+//! it has stolen from you, clashes it has won, times you stripped it bare and runs you have met.
+//! Each clash it wins promotes it a tier, from scout through wanderer to elder. When a beach with
+//! rivals starts, the most notorious nemeses take over its trains at their own tier, and their
+//! clash taunts call back to the history (see `rival_taunts::clash_taunt`). The daily challenge
+//! leaves them at home, since its beach has to play the same for everybody. The title menu's
+//! RIVALS page lists the roster.
+//!
+//! The roster is capped at [`ROSTER_SIZE`]. A newcomer past the cap always gets in, pushing out the
+//! least notorious rival already there, so a rival met late still gets to build a history.
+
+use ggez::glam::Vec2;
+
+use crate::levels::WinCondition;
+use crate::state::MainState;
+
+/// How many rivals the roster remembers.
+pub(crate) const ROSTER_SIZE: usize = 10;
+
+/// The top tier, the elder (see `NpcCongaTrain::new_at`).
+const TOP_TIER: usize = 2;
+
+/// One remembered rival.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Nemesis {
+    pub name: String,
+    /// 0 the scout, 1 the wanderer, 2 the elder: the tier it comes back as.
+    pub tier: usize,
+    /// Crabs it has stolen from the player.
+    pub stolen: usize,
+    /// Clashes it has won against the player.
+    pub wins: usize,
+    /// Times the player stripped it bare.
+    pub defeats: usize,
+    /// Runs it has shown up in, the first included.
+    pub met: usize,
+}
+
+impl Nemesis {
+    fn new(name: &str, tier: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            tier: tier.min(TOP_TIER),
+            stolen: 0,
+            wins: 0,
+            defeats: 0,
+            met: 1,
+        }
+    }
+
+    /// How much of a menace it has been; the roster's order.
+    pub fn notoriety(&self) -> usize {
+        self.stolen + self.wins * 4 + self.defeats * 2 + self.met
+    }
+
+    /// How deep the grudge runs, 0 to 3, for the taunts: shared history past the first clash.
+    pub fn grudge(&self) -> usize {
+        match (self.wins + self.defeats + self.met).saturating_sub(1) {
+            0..=1 => 0,
+            2..=4 => 1,
+            5..=9 => 2,
+            _ => 3,
+        }
+    }
+
+    pub fn tier_name(&self) -> &'static str {
+        match self.tier {
+            0 => "scout",
+            1 => "wanderer",
+            _ => "elder",
+        }
+    }
+
+    /// One line of the RIVALS page, e.g. `Cap'n Clawbeard (elder) — stole 14 · won 3 · stripped 1
+    /// · met 5`.
+    pub fn row(&self) -> String {
+        format!(
+            "{} ({})  —  stole {}  ·  won {}  ·  stripped {}  ·  met {}",
+            self.name,
+            self.tier_name(),
+            self.stolen,
+            self.wins,
+            self.defeats,
+            self.met
+        )
+    }
+
+    /// The save line's value: `<tier> <stolen> <wins> <defeats> <met> <name>`. The name goes last
+    /// because it can contain spaces.
+    fn to_save_value(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.tier, self.stolen, self.wins, self.defeats, self.met, self.name
+        )
+    }
+
+    fn from_save_value(value: &str) -> Option<Self> {
+        let mut fields = value.splitn(6, ' ');
+        let mut count = || fields.next()?.parse::<usize>().ok();
+        let (tier, stolen, wins, defeats, met) = (count()?, count()?, count()?, count()?, count()?);
+        let name = fields.next()?.trim();
+        if name.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            tier: tier.min(TOP_TIER),
+            stolen,
+            wins,
+            defeats,
+            met,
+        })
+    }
+}
+
+/// What a rival just did to the player, or the player to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Encounter {
+    /// It stole this many crabs off the player's train.
+    Stole(usize),
+    /// It won a clash.
+    Won,
+    /// The player stripped it bare.
+    Stripped,
+}
+
+/// Every remembered rival, most notorious first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct NemesisRoster {
+    rivals: Vec<Nemesis>,
+}
+
+impl NemesisRoster {
+    pub fn rivals(&self) -> &[Nemesis] {
+        &self.rivals
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Nemesis> {
+        self.rivals.iter().find(|n| n.name == name)
+    }
+
+    /// Write `encounter` into `name`'s history, adding it at `tier` if it is new.
+    pub fn record(&mut self, name: &str, tier: usize, encounter: Encounter) {
+        let at = match self.rivals.iter().position(|n| n.name == name) {
+            Some(at) => at,
+            None => {
+                // A full roster makes room by forgetting whoever ranks last, so the newcomer gets
+                // in and can build a history however small its start.
+                if self.rivals.len() >= ROSTER_SIZE {
+                    self.rivals.truncate(ROSTER_SIZE - 1);
+                }
+                self.rivals.push(Nemesis::new(name, tier));
+                self.rivals.len() - 1
+            }
+        };
+        let nemesis = &mut self.rivals[at];
+        match encounter {
+            Encounter::Stole(crabs) => nemesis.stolen += crabs,
+            Encounter::Won => {
+                nemesis.wins += 1;
+                nemesis.tier = (nemesis.tier + 1).min(TOP_TIER);
+            }
+            Encounter::Stripped => nemesis.defeats += 1,
+        }
+        self.rank();
+    }
+
+    fn rank(&mut self) {
+        // Stable, so ties keep the order they were met in.
+        self.rivals
+            .sort_by_key(|n| std::cmp::Reverse(n.notoriety()));
+    }
+
+    /// The save's `rival` lines, most notorious first.
+    pub fn save_lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.rivals
+            .iter()
+            .map(|n| format!("rival {}", n.to_save_value()))
+    }
+
+    /// Read one `rival` line's value. Lines past the roster's size (a hand-edited save) are
+    /// dropped.
+    pub fn parse_save_value(&mut self, value: &str) -> Option<()> {
+        let nemesis = Nemesis::from_save_value(value)?;
+        if self.rivals.len() < ROSTER_SIZE && self.get(&nemesis.name).is_none() {
+            self.rivals.push(nemesis);
+            self.rank();
+        }
+        Some(())
+    }
+}
+
+impl MainState {
+    /// Remember what rival `i` just did. Called where the steal, the clash or the strip happens.
+    pub(crate) fn remember_rival(&mut self, i: usize, encounter: Encounter) {
+        let train = &self.npc_trains[i];
+        // Back from the base scale: 1.2 the scout, 1.8 the wanderer, 2.4 the elder.
+        let tier = ((train.base_scale - 1.2) / 0.6).round().max(0.0) as usize;
+        self.nemeses.record(&train.name, tier, encounter);
+    }
+
+    /// Hand the beach's rival trains to the most notorious nemeses, each at its own tier. Called
+    /// where an arcade, endless or campaign run starts, once the trains (mutators included) are
+    /// out and a DethroneKing level has crowned its King, whose train stays the King's.
+    pub(crate) fn recall_nemeses(&mut self) {
+        if self.daily.is_some() {
+            return;
+        }
+        // `crown_goal_king` takes the first train.
+        let crowned = usize::from(matches!(
+            self.campaign_goal(),
+            Some(WinCondition::DethroneKing(_))
+        ));
+        let trains = self.npc_trains.get_mut(crowned..).unwrap_or_default();
+        let returning: Vec<_> = self
+            .nemeses
+            .rivals()
+            .iter()
+            .take(trains.len())
+            .cloned()
+            .collect();
+        for (train, nemesis) in trains.iter_mut().zip(&returning) {
+            train.name = nemesis.name.clone();
+            train.set_tier(nemesis.tier);
+            self.floating_texts.spawn(
+                format!("{} is back!", nemesis.name),
+                train.leader_pos - Vec2::new(70.0, 60.0),
+                22.0,
+                [1.0, 0.55, 0.3, 1.0],
+            );
+        }
+        for nemesis in &returning {
+            if let Some(n) = self
+                .nemeses
+                .rivals
+                .iter_mut()
+                .find(|n| n.name == nemesis.name)
+            {
+                n.met += 1;
+            }
+        }
+        self.nemeses.rank();
+        self.nemeses_recalled += returning.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wins_promote_and_the_roster_keeps_the_worst() {
+        let mut roster = NemesisRoster::default();
+        roster.record("Uncle Snippy", 0, Encounter::Stole(3));
+        roster.record("Uncle Snippy", 0, Encounter::Won);
+        roster.record("Uncle Snippy", 0, Encounter::Won);
+        roster.record("Uncle Snippy", 0, Encounter::Won);
+        let snippy = roster.get("Uncle Snippy").unwrap();
+        assert_eq!((snippy.stolen, snippy.wins, snippy.tier), (3, 3, TOP_TIER));
+
+        for k in 0..ROSTER_SIZE {
+            roster.record(&format!("Kevin {k}"), 1, Encounter::Stripped);
+        }
+        assert_eq!(roster.rivals().len(), ROSTER_SIZE);
+        assert_eq!(roster.rivals()[0].name, "Uncle Snippy");
+        // Someone had to go, and it was a Kevin who was already there, not the newest one.
+        assert!(roster.get(&format!("Kevin {}", ROSTER_SIZE - 1)).is_some());
+        assert!(roster.get(&format!("Kevin {}", ROSTER_SIZE - 2)).is_none());
+        assert_eq!(
+            roster
+                .rivals()
+                .iter()
+                .filter(|n| n.name.starts_with("Kevin"))
+                .count(),
+            ROSTER_SIZE - 1
+        );
+    }
+
+    #[test]
+    fn the_roster_round_trips_through_the_save_lines() {
+        let mut roster = NemesisRoster::default();
+        roster.record("Cap'n Clawbeard", 2, Encounter::Stole(14));
+        roster.record("DJ Bassline", 0, Encounter::Stripped);
+        let mut read = NemesisRoster::default();
+        for line in roster.save_lines() {
+            let value = line.strip_prefix("rival ").unwrap();
+            read.parse_save_value(value).unwrap();
+        }
+        assert_eq!(read, roster);
+        assert!(read.parse_save_value("1 2 3 nope Kevin").is_none());
+        assert!(read.parse_save_value("1 2 3 4 5").is_none());
+    }
+
+    /// Select the first campaign node with rival trains, optionally swap its goal, and play it.
+    fn enter_rival_node(state: &mut MainState, goal: Option<WinCondition>) {
+        state.enter_world_map();
+        let node = state
+            .levels
+            .iter()
+            .position(|l| l.map_size.spawns_npc_trains());
+        let map = state.world_map.as_mut().unwrap();
+        map.selected = map
+            .nodes
+            .iter()
+            .position(|n| n.level_index() == node)
+            .unwrap();
+        if let Some(goal) = goal {
+            state.levels[node.unwrap()].win_condition = goal;
+        }
+        state.enter_campaign_level();
+    }
+
+    #[test]
+    fn nemeses_come_back_at_their_tier() {
+        let mut state = MainState::new_headless();
+        state
+            .nemeses
+            .record("Admiral Ironpincer", 2, Encounter::Won);
+        enter_rival_node(&mut state, None);
+        let train = state
+            .npc_trains
+            .iter()
+            .find(|t| t.name == "Admiral Ironpincer")
+            .unwrap();
+        assert_eq!(train.base_scale, 2.4);
+        assert_eq!(state.nemeses.get("Admiral Ironpincer").unwrap().met, 2);
+        assert_eq!(state.nemeses_recalled, 1);
+
+        // Stripping it bare goes on its record.
+        let i = state
+            .npc_trains
+            .iter()
+            .position(|t| t.name == "Admiral Ironpincer")
+            .unwrap();
+        state.remember_rival(i, Encounter::Stripped);
+        assert_eq!(state.nemeses.get("Admiral Ironpincer").unwrap().defeats, 1);
+
+        // The daily beach stays the same for everybody.
+        state.start_daily_on("2026-10-18");
+        assert!(
+            state
+                .npc_trains
+                .iter()
+                .all(|t| t.name != "Admiral Ironpincer")
+        );
+        state.end_daily();
+    }
+
+    #[test]
+    fn the_goal_king_keeps_its_train() {
+        let mut state = MainState::new_headless();
+        for name in ["Uncle Snippy", "DJ Bassline", "Sir Pinchalot"] {
+            state.nemeses.record(name, 0, Encounter::Stole(1));
+        }
+        let goal = WinCondition::DethroneKing("Admiral Kevin".into());
+        enter_rival_node(&mut state, Some(goal));
+        let names: Vec<_> = state.npc_trains.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Admiral Kevin", "Uncle Snippy", "DJ Bassline"]);
+        assert_eq!(state.nemeses_recalled, 2);
+        // Only the rivals that made it onto the beach have met the player again.
+        assert_eq!(state.nemeses.get("Sir Pinchalot").unwrap().met, 1);
+        assert_eq!(state.nemeses.get("DJ Bassline").unwrap().met, 2);
+    }
+}
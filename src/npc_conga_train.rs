@@ -12,6 +12,7 @@ use crate::enemies::CrabType;
 
 /// Ambient wandering NPC conga train — a King Crab leading a few followers across the world.
 /// Visual-only: it does not steal from or react to the player. It's world life, like weather.
+#[derive(Clone)]
 pub struct NpcCongaTrain {
     pub leader_pos: Vec2,
     pub leader_vel: Vec2,
@@ -99,6 +100,15 @@ pub struct NpcCongaTrain {
     /// telegraph burns at full intensity (a stalking rival shows the same tell faint), and the
     /// commit moment itself is called out by name. Cleared when the hunt ends or the strike resolves.
     pub hunt_committed: bool,
+    /// Latched while a Pompous rival has the player in reach but won't stoop to a bigger train, so
+    /// the snub counts once when it starts rather than once per frame it lasts.
+    pub snubbing: bool,
+    /// Latched while the train is hauling its followers home to bank them at its pen
+    /// (`territory_center`): it stops hunting and lumbers straight home, so it is at its richest and
+    /// slowest exactly when it is easiest to thread. Cleared when it banks or is stripped bare.
+    pub hauling: bool,
+    /// Followers this rival has banked at its pen this run — its score on the HUD.
+    pub rival_score: usize,
 }
 
 /// Generate a King Crab name. Leans hard into pirate swagger and crab-rave energy, with the
@@ -197,37 +207,30 @@ pub fn gen_king_crab_name(rng: &mut impl rand::Rng) -> String {
     format!("{} {}", title, name)
 }
 
-impl NpcCongaTrain {
-    pub fn new(world_width: f32, world_height: f32) -> Self {
-        Self::new_at(world_width, world_height, 0)
-    }
-
-    pub fn new_at(world_width: f32, world_height: f32, index: usize) -> Self {
-        // Three distinct tiers: small scout, medium wanderer, large elder.
-        // Scale, speed, and follower count all differ so they read instantly at a glance.
-        let (sx, sy, tc_x, tc_y, leader_scale, speed_hint) = match index {
-            0 => (0.2, 0.3, 0.25, 0.3, 1.2_f32, 110.0_f32), // small/fast scout, top-left territory
-            1 => (0.8, 0.2, 0.75, 0.25, 1.8_f32, 80.0_f32), // medium wanderer, top-right territory
-            _ => (0.5, 0.8, 0.5, 0.75, 2.4_f32, 55.0_f32),  // large elder, bottom territory
-        };
-        let _ = speed_hint; // stored per-train would need another field; use leader_scale as proxy in update
-        let start = Vec2::new(world_width * sx, world_height * sy);
-        let territory_center = Vec2::new(world_width * tc_x, world_height * tc_y);
-        // Initial target biased toward territory center
-        let target = territory_center + Vec2::new(world_width * 0.1, world_height * 0.05);
-        let follower_types = match index {
-            // Small scout: fast light crabs
-            0 => vec![CrabType::Fast, CrabType::Sneaky, CrabType::Normal],
-            // Medium wanderer: balanced mix
-            1 => vec![
+/// Leader scale and starting followers of a tier-`index` train: 0 the scout, 1 the wanderer, 2 and
+/// up the elder.
+fn tier_retinue(index: usize) -> (f32, Vec<CrabType>) {
+    match index {
+        // Small scout: fast light crabs
+        0 => (
+            1.2,
+            vec![CrabType::Fast, CrabType::Sneaky, CrabType::Normal],
+        ),
+        // Medium wanderer: balanced mix
+        1 => (
+            1.8,
+            vec![
                 CrabType::Armored,
                 CrabType::Normal,
                 CrabType::Fast,
                 CrabType::Magnet,
                 CrabType::Dancer,
             ],
-            // Large elder: heavy diverse retinue
-            _ => vec![
+        ),
+        // Large elder: heavy diverse retinue
+        _ => (
+            2.4,
+            vec![
                 CrabType::Big,
                 CrabType::Dancer,
                 CrabType::Golden,
@@ -236,7 +239,29 @@ impl NpcCongaTrain {
                 CrabType::Hermit,
                 CrabType::Fast,
             ],
+        ),
+    }
+}
+
+impl NpcCongaTrain {
+    pub fn new(world_width: f32, world_height: f32) -> Self {
+        Self::new_at(world_width, world_height, 0)
+    }
+
+    pub fn new_at(world_width: f32, world_height: f32, index: usize) -> Self {
+        // Three distinct tiers: small scout, medium wanderer, large elder.
+        // Scale, speed, and follower count all differ so they read instantly at a glance.
+        let (sx, sy, tc_x, tc_y, speed_hint) = match index {
+            0 => (0.2, 0.3, 0.25, 0.3, 110.0_f32), // small/fast scout, top-left territory
+            1 => (0.8, 0.2, 0.75, 0.25, 80.0_f32), // medium wanderer, top-right territory
+            _ => (0.5, 0.8, 0.5, 0.75, 55.0_f32),  // large elder, bottom territory
         };
+        let _ = speed_hint; // stored per-train would need another field; use leader_scale as proxy in update
+        let start = Vec2::new(world_width * sx, world_height * sy);
+        let territory_center = Vec2::new(world_width * tc_x, world_height * tc_y);
+        // Initial target biased toward territory center
+        let target = territory_center + Vec2::new(world_width * 0.1, world_height * 0.05);
+        let (leader_scale, follower_types) = tier_retinue(index);
         let mut history = VecDeque::new();
         history.push_back(start);
         let name = gen_king_crab_name(&mut crate::rng::rng());
@@ -268,6 +293,32 @@ impl NpcCongaTrain {
             rival_hunt_intensity: 0.0,
             stalk_patience: 0.0,
             hunt_committed: false,
+            snubbing: false,
+            hauling: false,
+            rival_score: 0,
+        }
+    }
+
+    /// Re-tier the train in place: it keeps its territory and name but takes tier `index`'s size
+    /// and retinue (see `new_at`).
+    pub fn set_tier(&mut self, index: usize) {
+        let (scale, followers) = tier_retinue(index);
+        self.leader_scale = scale;
+        self.base_scale = scale;
+        self.follower_types = followers;
+    }
+
+    /// The same train across the beach's vertical midline, for a second rival in each territory.
+    pub fn mirrored(&self, world_width: f32) -> Self {
+        let flip = |p: Vec2| Vec2::new(world_width - p.x, p.y);
+        let mut train = self.clone();
+        train.name = gen_king_crab_name(&mut crate::rng::rng());
+        train.leader_pos = flip(train.leader_pos);
+        train.target = flip(train.target);
+        train.territory_center = flip(train.territory_center);
+        for pos in &mut train.path_history {
+            *pos = flip(*pos);
         }
+        train
     }
 }
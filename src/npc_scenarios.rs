@@ -11,6 +11,7 @@ use ggez::glam::Vec2;
 use crate::constants::*;
 use crate::enemies::CrabType;
 use crate::npc_conga_train::NpcCongaTrain;
+use crate::rival_taunts::RivalPersonality;
 use crate::spawnings::spawn_scattered_crab;
 use crate::state::MainState;
 
@@ -548,43 +549,58 @@ impl MainState {
     /// this same frame (bot_fire_events runs before update_npc_trains). It exercises the REAL arming
     /// path — the hunt block reads these live positions and applies its own closeness/gap gating — and
     /// only shortcuts the RNG wander that would otherwise line two leaders up far from the player by
-    /// chance. No-op with fewer than two trains or no strictly-smaller rival.
+    /// chance. The hunter is the biggest train that hunts at all (a Silly rival never does, see
+    /// rival_ai.rs). With no strictly-smaller rival to hunt it tops a pair up first; no-op with
+    /// fewer than two trains.
     pub fn force_rival_hunt(&mut self) {
         self.ensure_bot_npc_trains();
-        let Some(thief) =
-            (0..self.npc_trains.len()).max_by_key(|&i| self.npc_trains[i].follower_types.len())
+        let Some(thief) = (0..self.npc_trains.len())
+            .filter(|&i| self.npc_trains[i].personality() != RivalPersonality::Silly)
+            .max_by_key(|&i| self.npc_trains[i].follower_types.len())
         else {
             return;
         };
         let thief_len = self.npc_trains[thief].follower_types.len();
-        if thief_len < 2 {
-            return; // need room for a strictly-smaller rival with at least one follower
-        }
-        let Some(victim) = (0..self.npc_trains.len()).find(|&v| {
+        let smaller = (0..self.npc_trains.len()).find(|&v| {
             v != thief && {
                 let l = self.npc_trains[v].follower_types.len();
                 l >= 1 && l < thief_len
             }
-        }) else {
+        });
+        let Some(victim) = smaller.or_else(|| (0..self.npc_trains.len()).find(|&v| v != thief))
+        else {
             return;
         };
-        // Corner diagonally opposite the player — a full world away, so both leaders sit well beyond
-        // PURSUIT_RANGE (550) from the player this frame and the rival hunt (not player pursuit) wins.
+        if smaller.is_none() {
+            // The forced crossings and the player's steal-backs can strip every train bare long
+            // before the wander lines a pair up, so top the pair up (like bot_prime_chain does for
+            // the player) rather than no-op the whole window away.
+            self.npc_trains[victim].follower_types = vec![CrabType::Normal; 1];
+            let hunter = &mut self.npc_trains[thief].follower_types;
+            hunter.resize(hunter.len().max(3), CrabType::Normal);
+            self.ensure_train_path_history(victim, 1);
+            self.ensure_train_path_history(thief, 3);
+        }
+        // Corner diagonally opposite the player — a full world away, so the hunter sits beyond even
+        // the widest pursuit range (PURSUIT_RANGE (550) plus up to 180 for a player off the groove,
+        // see `exposure` in update_npc_trains) and the rival hunt (not player pursuit) wins. The
+        // hunter takes the corner itself and the victim sits inward of it.
         let corner_x = if self.player_pos.x < self.world_width * 0.5 {
-            self.world_width * 0.9
+            self.world_width * 0.95
         } else {
-            self.world_width * 0.1
+            self.world_width * 0.05
         };
         let corner_y = if self.player_pos.y < self.world_height * 0.5 {
-            self.world_height * 0.9
+            self.world_height * 0.95
         } else {
-            self.world_height * 0.1
+            self.world_height * 0.05
         };
-        let victim_pos = Vec2::new(corner_x, corner_y);
-        self.npc_trains[victim].leader_pos = victim_pos;
+        let thief_pos = Vec2::new(corner_x, corner_y);
+        self.npc_trains[thief].leader_pos = thief_pos;
         // 200px apart: inside RIVAL_HUNT_RANGE (620), closeness ≈ 0.68 > the 0.35 arm gate, and >80px
         // so the telegraph line itself draws too.
-        self.npc_trains[thief].leader_pos = victim_pos + Vec2::new(-200.0, 0.0);
+        let inward = (self.world_width * 0.5 - corner_x).signum();
+        self.npc_trains[victim].leader_pos = thief_pos + Vec2::new(inward * 200.0, 0.0);
         self.npc_trains[thief].idle_timer = 0.0;
     }
 
@@ -635,4 +651,122 @@ impl MainState {
         self.npc_trains[ni].stalk_patience = 1.0;
         self.npc_trains[ni].hunt_committed = true;
     }
+
+    /// Bot-test helper (see BotAction::ForceRivalHaul): top the rival farthest from the player up to a
+    /// full haul and park it a short walk from its pen, on the far side from the player so nothing
+    /// the player does can reach it on the way, with a fresh trail behind it. The haul trigger, the
+    /// trip and the bank are left to the real `update_rival_haul`.
+    pub fn force_rival_haul(&mut self) {
+        self.ensure_bot_npc_trains();
+        let player_center = self.player_pos + Vec2::splat(PLAYER_SIZE / 2.0);
+        let ni = (0..self.npc_trains.len()).max_by(|&a, &b| {
+            let da = self.npc_trains[a]
+                .territory_center
+                .distance_squared(player_center);
+            let db = self.npc_trains[b]
+                .territory_center
+                .distance_squared(player_center);
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let Some(ni) = ni else {
+            return;
+        };
+        let train = &mut self.npc_trains[ni];
+        while train.follower_types.len() < RIVAL_HAUL_AT {
+            train.follower_types.push(CrabType::Normal);
+        }
+        let pen = train.territory_center;
+        let out = (pen - player_center).normalize_or(Vec2::X);
+        let margin = 80.0;
+        train.leader_pos = (pen + out * (RIVAL_PEN_RADIUS + 150.0)).clamp(
+            Vec2::splat(margin),
+            Vec2::new(self.world_width - margin, self.world_height - margin),
+        );
+        train.leader_vel = -out * 40.0;
+        train.idle_timer = 0.0;
+        train.path_history.clear();
+        train.path_history.push_back(train.leader_pos);
+        self.ensure_train_path_history(ni, RIVAL_HAUL_AT);
+    }
+
+    /// Bot-test helper (see BotAction::ForceHaulRaid): thread the player's head through the middle of
+    /// a rival hauling home, so the next `update_npc_trains` runs the real steal-back as a raid.
+    pub fn force_haul_raid(&mut self) {
+        const STEPS: usize = 14; // must match update_npc_trains / draw_npc_conga_train spacing
+        let Some(ni) = self.npc_trains.iter().position(|train| train.hauling) else {
+            return;
+        };
+        let mid_fi = self.npc_trains[ni].follower_types.len() / 2;
+        self.ensure_train_path_history(ni, mid_fi + 1);
+        if let Some(&fpos) = self.npc_trains[ni].path_history.get((mid_fi + 1) * STEPS) {
+            self.player_pos = fpos - Vec2::splat(PLAYER_SIZE / 2.0);
+            self.player_steal_cooldown = 0.0;
+        }
+    }
+
+    /// Bot-test helper (see BotAction::ForcePersonality): rename the rival nearest the player to a
+    /// `personality` name and stage the spot its signature move wants, then leave the move to the
+    /// real `update_npc_trains`.
+    pub fn force_personality(&mut self, personality: RivalPersonality) {
+        self.ensure_bot_npc_trains();
+        let player_center = self.player_pos + Vec2::splat(PLAYER_SIZE / 2.0);
+        let ni = (0..self.npc_trains.len()).min_by(|&a, &b| {
+            let da = self.npc_trains[a]
+                .leader_pos
+                .distance_squared(player_center);
+            let db = self.npc_trains[b]
+                .leader_pos
+                .distance_squared(player_center);
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let Some(ni) = ni else {
+            return;
+        };
+        let margin = 80.0;
+        let on_beach = |p: Vec2, w: f32, h: f32| {
+            p.clamp(Vec2::splat(margin), Vec2::new(w - margin, h - margin))
+        };
+        let (w, h) = (self.world_width, self.world_height);
+        // Where the rival waits and where the player stands.
+        let (lair, player_at) = match personality {
+            RivalPersonality::Pirate => match self.nearest_cover(player_center) {
+                Some(hideout) => {
+                    let out = (player_center - hideout).normalize_or(Vec2::X);
+                    (hideout, on_beach(hideout + out * 220.0, w, h))
+                }
+                None => (player_center + Vec2::new(200.0, 0.0), player_center),
+            },
+            RivalPersonality::Serious => {
+                let home = self.npc_trains[ni].territory_center;
+                (home, on_beach(home + Vec2::new(0.0, 160.0), w, h))
+            }
+            _ => (player_center + Vec2::new(200.0, 0.0), player_center),
+        };
+        self.player_pos = player_at - Vec2::splat(PLAYER_SIZE / 2.0);
+        self.player_vel = Vec2::ZERO;
+        self.bot_prime_chain(6);
+        let train = &mut self.npc_trains[ni];
+        train.name = match personality {
+            RivalPersonality::Plain => "Kevin",
+            RivalPersonality::Pirate => "Cap'n Clawbeard",
+            RivalPersonality::Pompous => "Gravelord Brinewraith",
+            RivalPersonality::Raver => "DJ Bassline",
+            RivalPersonality::Serious => "Admiral Ironpincer",
+            RivalPersonality::Silly => "Uncle Snippy",
+        }
+        .to_string();
+        train.follower_types = vec![CrabType::Normal; 4];
+        train.hauling = false;
+        train.leader_pos = on_beach(lair, w, h);
+        train.leader_vel = Vec2::ZERO;
+        // Aim it at the player and hold the timer, so no arrival idle skips the hunt block.
+        train.target = player_at;
+        train.target_timer = 30.0;
+        train.idle_timer = 0.0;
+        train.stalk_patience = 0.0;
+        train.hunt_committed = false;
+        train.path_history.clear();
+        train.path_history.push_back(train.leader_pos);
+        self.ensure_train_path_history(ni, 4);
+    }
 }
@@ -9,6 +9,10 @@ use rand::Rng;
 
 use crate::constants::*;
 use crate::enemies::CrabType;
+use crate::event_log::GameEvent;
+use crate::nemesis::Encounter;
+use crate::rival_taunts::RivalPersonality;
+use crate::spatial::DRIFT;
 use crate::spawnings::{spawn_scattered_crab, spawn_stolen_crab};
 use crate::state::MainState;
 
@@ -25,6 +29,12 @@ impl MainState {
         // on_downbeat_now(). A reroute that lands on the downbeat is the "big save" version.
         let downbeat = on_beat && self.beat_count % 4 == 0;
         for i in 0..self.npc_trains.len() {
+            // A rival carrying a full train heads home to bank it (see rival_economy.rs).
+            self.update_rival_haul(i);
+            let hauling = self.npc_trains[i].hauling;
+            // Who the rival is, beyond its tier (see rival_ai.rs).
+            let personality = self.npc_trains[i].personality();
+
             // --- Idle pause at destination -------------------------------------------------
             // When idle_timer > 0 the train has just arrived at a target and is "surveying"
             // before picking a new one — gives Rain World-style decisiveness, not dumb wandering.
@@ -78,12 +88,19 @@ impl MainState {
                 let angle: f32 = rng.random_range(0.0..std::f32::consts::TAU);
                 let territory_pt = tc + Vec2::new(angle.cos(), angle.sin()) * wander_radius;
                 let next_target = rand_pt.lerp(territory_pt, territory_bias);
+                let next_target = self.personal_wander_target(personality, tc, next_target);
                 self.npc_trains[i].target = next_target.clamp(
                     Vec2::splat(margin),
                     Vec2::new(self.world_width - margin, self.world_height - margin),
                 );
                 // Timer is a fallback; normal flow goes through idle_timer arrival check
                 self.npc_trains[i].target_timer = rng.random_range(18.0_f32..35.0);
+                if personality == RivalPersonality::Silly {
+                    // Silly rivals barely pause and change their minds every couple of seconds.
+                    self.npc_trains[i].idle_timer = idle_secs * 0.25;
+                    self.npc_trains[i].target = rand_pt;
+                    self.npc_trains[i].target_timer = rng.random_range(1.5_f32..3.0);
+                }
             }
 
             // --- Steering ------------------------------------------------------------------
@@ -92,11 +109,17 @@ impl MainState {
                 _ if self.npc_trains[i].leader_scale < 1.5 => 105.0, // small scout
                 _ if self.npc_trains[i].leader_scale < 2.0 => 80.0,  // medium wanderer
                 _ => 52.0,                                           // large elder
-            };
+            } * if hauling { RIVAL_HAUL_SPEED } else { 1.0 };
             // Gentle perpendicular wobble so the path curves naturally instead of beelining.
             let perp = Vec2::new(-to_target.y, to_target.x).normalize_or_zero();
-            let wobble_phase = self.time_elapsed * 0.4 + i as f32 * 2.1;
-            let wobble = perp * wobble_phase.sin() * 18.0;
+            // A Silly rival zigzags wide and fast.
+            let (wobble_rate, wobble_size) = if personality == RivalPersonality::Silly {
+                (1.9, 70.0)
+            } else {
+                (0.4, 18.0)
+            };
+            let wobble_phase = self.time_elapsed * wobble_rate + i as f32 * 2.1;
+            let wobble = perp * wobble_phase.sin() * wobble_size;
 
             if dist > 1.0 {
                 let desired = (to_target / dist + wobble / dist.max(1.0)) * speed;
@@ -181,19 +204,38 @@ impl MainState {
             // hunters at arm's length and lapsing invites the steal (INSPIRATION "steal to win",
             // "keys as drum pads": playing the groove well IS the defense). The wider reach trips
             // the hunt-intent telegraph sooner, so you *see* a rival commit the moment you slip.
-            let exposure = (1.0 - self.groove).clamp(0.0, 1.0); // 0 in the pocket, 1 fully off-beat
+            // A Raver reads it the other way round: the louder the groove, the harder it's drawn in.
+            let exposure = if personality == RivalPersonality::Raver {
+                self.groove.clamp(0.0, 1.0)
+            } else {
+                (1.0 - self.groove).clamp(0.0, 1.0) // 0 in the pocket, 1 fully off-beat
+            };
             let pursuit_range = PURSUIT_RANGE + exposure * 180.0;
             // Boldness by tier: 0 for the skittish scout (base 1.2) up to 1 for the elder (2.4).
             let boldness = ((self.npc_trains[i].base_scale - 1.2) / 1.2).clamp(0.0, 1.0);
             // Hunt intent smooths toward its phase goal while this rival is on a steal route and back
             // toward 0 otherwise, so the early-warning tell fades in/out instead of popping. Updated
             // every non-idle frame (goal 0 when not hunting) so it always relaxes once the chase ends.
-            let hunting = self.chain_count >= 2
+            // A hauling rival has its prize already and only wants to get home with it. Past
+            // that, the personality decides: a Pompous rival won't stoop to a bigger train, a
+            // Serious one only chases intruders and a Silly one never hunts at all.
+            let my_len = self.npc_trains[i].follower_types.len();
+            let in_reach = !hauling
+                && self.chain_count >= 2
                 && dist_to_player < pursuit_range
                 && self
                     .cached_steal_target_pos
                     .or(self.cached_tail_pos)
                     .is_some();
+            let willing = personality.will_hunt(self.chain_count, my_len)
+                && (personality != RivalPersonality::Serious
+                    || self.intruding(self.npc_trains[i].territory_center));
+            let snubbing = in_reach && !willing && personality == RivalPersonality::Pompous;
+            if snubbing && !self.npc_trains[i].snubbing {
+                self.note_signature(personality);
+            }
+            self.npc_trains[i].snubbing = snubbing;
+            let hunting = in_reach && willing;
             if !hunting {
                 // Hunt lost (player banked, escaped range, or the chain snapped): drop any commit
                 // and bleed patience so the next hunt starts from a fresh stalk, not a hair trigger.
@@ -232,15 +274,33 @@ impl MainState {
                         let build = 0.10 + boldness * 0.10 + exposure * 0.35 + length_urge * 0.5;
                         self.npc_trains[i].stalk_patience =
                             (self.npc_trains[i].stalk_patience + build * dt).min(1.0);
-                        if self.npc_trains[i].stalk_patience >= 1.0 {
+                        // Some skip the stalk: a Pirate springs from cover on a player who comes
+                        // too close, and a Serious rival goes straight for an intruder.
+                        let sprung = match personality {
+                            RivalPersonality::Pirate => {
+                                dist_to_player < PIRATE_AMBUSH_RANGE
+                                    && self.in_cover(self.npc_trains[i].leader_pos)
+                            }
+                            RivalPersonality::Serious => true,
+                            _ => false,
+                        };
+                        if sprung {
+                            self.npc_trains[i].stalk_patience = 1.0;
+                        }
+                        // A Raver waits for the downbeat to commit.
+                        let on_cue = personality != RivalPersonality::Raver || downbeat;
+                        if self.npc_trains[i].stalk_patience >= 1.0 && on_cue {
                             // COMMIT — the strike begins. Call the hunter out by name so the scare
                             // is legible from across the field, matching the "on your tail!" arm
                             // warning's threat language (only fires once per stalk cycle, since
                             // patience must rebuild from 0 after every strike).
                             self.npc_trains[i].hunt_committed = true;
+                            if sprung {
+                                self.note_signature(personality);
+                            }
                             let npc_name = self.npc_trains[i].name.clone();
                             self.floating_texts.spawn(
-                                format!("⚠ {} is hunting you!", npc_name),
+                                personality.strike_callout(&npc_name, sprung),
                                 self.npc_trains[i].leader_pos - Vec2::new(90.0, 60.0),
                                 24.0,
                                 [0.95, 0.25, 0.18, 1.0],
@@ -301,59 +361,60 @@ impl MainState {
             // lingers after the chase ends.
             self.npc_trains[i].rival_hunt_target_pos = None;
             self.npc_trains[i].rival_hunt_intensity = 0.0;
-            if !hunting && self.npc_trains[i].idle_timer <= 0.0 {
-                let my_len = self.npc_trains[i].follower_types.len();
-                if my_len >= 1 {
-                    const RIVAL_HUNT_RANGE: f32 = 620.0;
-                    let my_pos = self.npc_trains[i].leader_pos;
-                    // Nearest strictly-smaller rival with followers — the only train this one can
-                    // actually splice, so the urge and the splice rule below agree.
-                    let mut best: Option<(usize, f32)> = None;
-                    for v in 0..self.npc_trains.len() {
-                        if v == i {
-                            continue;
-                        }
-                        let vlen = self.npc_trains[v].follower_types.len();
-                        if vlen == 0 || vlen >= my_len {
-                            continue;
-                        }
-                        let d = my_pos.distance(self.npc_trains[v].leader_pos);
-                        if d < RIVAL_HUNT_RANGE && best.map_or(true, |(_, bd)| d < bd) {
-                            best = Some((v, d));
-                        }
+            if !hunting
+                && !hauling
+                && personality != RivalPersonality::Silly
+                && self.npc_trains[i].idle_timer <= 0.0
+                && my_len >= 1
+            {
+                const RIVAL_HUNT_RANGE: f32 = 620.0;
+                let my_pos = self.npc_trains[i].leader_pos;
+                // Nearest strictly-smaller rival with followers — the only train this one can
+                // actually splice, so the urge and the splice rule below agree.
+                let mut best: Option<(usize, f32)> = None;
+                for v in 0..self.npc_trains.len() {
+                    if v == i {
+                        continue;
                     }
-                    if let Some((v, d)) = best {
-                        // Aim at the victim's back-half thread point (its mid-follower slot on
-                        // path_history, spacing 14 to match the splice pass) so the leader routes to
-                        // slice a meaningful section, exactly like the player-pursuit path does.
-                        let vlen = self.npc_trains[v].follower_types.len();
-                        let thread_fi = vlen.saturating_sub(1) / 2;
-                        let hunt_pos = self.npc_trains[v]
-                            .path_history
-                            .get((thread_fi + 1) * 14)
-                            .copied()
-                            .unwrap_or(self.npc_trains[v].leader_pos);
-                        // Stronger urge the closer the prey and the bigger the size gap, but it stays
-                        // a bias layered onto territory patrol — not a beeline — so trains still read
-                        // as roaming their regions between kills.
-                        let closeness = ((RIVAL_HUNT_RANGE - d) / RIVAL_HUNT_RANGE).clamp(0.0, 1.0);
-                        let gap_urge = ((my_len - vlen) as f32 / 6.0).clamp(0.0, 0.5);
-                        let blend = (closeness * 0.6 + gap_urge).clamp(0.0, 1.0);
-                        self.npc_trains[i].target =
-                            self.npc_trains[i].target.lerp(hunt_pos, blend * dt * 2.2);
-                        // Arm the gold "predator closing" telegraph toward the prey's *leader* (King→King,
-                        // so the read is "that big train is bearing down on that small one"), but only
-                        // once the predator is genuinely closing — a wide, lazy urge shouldn't clutter the
-                        // field. Gate on real closeness so the tell means "clash incoming, get in position."
-                        if closeness > 0.35 {
-                            self.npc_trains[i].rival_hunt_target_pos =
-                                Some(self.npc_trains[v].leader_pos);
-                            self.npc_trains[i].rival_hunt_intensity = blend;
-                            // Monotonic tally for the bot guard — bumped here (the draw pass only holds
-                            // an immutable borrow of npc_trains). Armed ⇒ drawn, so this tracks the tell.
-                            self.rival_hunt_telegraphs =
-                                self.rival_hunt_telegraphs.saturating_add(1);
-                        }
+                    let vlen = self.npc_trains[v].follower_types.len();
+                    if vlen == 0 || vlen >= my_len {
+                        continue;
+                    }
+                    let d = my_pos.distance(self.npc_trains[v].leader_pos);
+                    if d < RIVAL_HUNT_RANGE && best.map_or(true, |(_, bd)| d < bd) {
+                        best = Some((v, d));
+                    }
+                }
+                if let Some((v, d)) = best {
+                    // Aim at the victim's back-half thread point (its mid-follower slot on
+                    // path_history, spacing 14 to match the splice pass) so the leader routes to
+                    // slice a meaningful section, exactly like the player-pursuit path does.
+                    let vlen = self.npc_trains[v].follower_types.len();
+                    let thread_fi = vlen.saturating_sub(1) / 2;
+                    let hunt_pos = self.npc_trains[v]
+                        .path_history
+                        .get((thread_fi + 1) * 14)
+                        .copied()
+                        .unwrap_or(self.npc_trains[v].leader_pos);
+                    // Stronger urge the closer the prey and the bigger the size gap, but it stays
+                    // a bias layered onto territory patrol — not a beeline — so trains still read
+                    // as roaming their regions between kills.
+                    let closeness = ((RIVAL_HUNT_RANGE - d) / RIVAL_HUNT_RANGE).clamp(0.0, 1.0);
+                    let gap_urge = ((my_len - vlen) as f32 / 6.0).clamp(0.0, 0.5);
+                    let blend = (closeness * 0.6 + gap_urge).clamp(0.0, 1.0);
+                    self.npc_trains[i].target =
+                        self.npc_trains[i].target.lerp(hunt_pos, blend * dt * 2.2);
+                    // Arm the gold "predator closing" telegraph toward the prey's *leader* (King→King,
+                    // so the read is "that big train is bearing down on that small one"), but only
+                    // once the predator is genuinely closing — a wide, lazy urge shouldn't clutter the
+                    // field. Gate on real closeness so the tell means "clash incoming, get in position."
+                    if closeness > 0.35 {
+                        self.npc_trains[i].rival_hunt_target_pos =
+                            Some(self.npc_trains[v].leader_pos);
+                        self.npc_trains[i].rival_hunt_intensity = blend;
+                        // Monotonic tally for the bot guard — bumped here (the draw pass only holds
+                        // an immutable borrow of npc_trains). Armed ⇒ drawn, so this tracks the tell.
+                        self.rival_hunt_telegraphs = self.rival_hunt_telegraphs.saturating_add(1);
                     }
                 }
             }
@@ -373,22 +434,32 @@ impl MainState {
             // Revenge marker burns down: once it lapses the "chase me" ring fades and a steal-back
             // off this rival is just a normal rustle, not a revenge bonus.
             self.npc_trains[i].revenge_timer = (self.npc_trains[i].revenge_timer - dt).max(0.0);
-            if self.npc_trains[i].steal_cooldown <= 0.0 && self.chain_count > 1 {
-                const STEAL_RANGE: f32 = 58.0;
-                const STEAL_RANGE_SQ: f32 = STEAL_RANGE * STEAL_RANGE;
+            if self.npc_trains[i].steal_cooldown <= 0.0
+                && self.chain_count > 1
+                && personality.will_steal(self.chain_count, my_len)
+            {
+                // A Silly rival grabs at links from farther off.
+                let steal_range = 58.0 * personality.steal_reach();
+                let steal_range_sq = steal_range * steal_range;
                 // STEAL_FUSE (telegraph window, ~one beat between arming and the snap) lives in
-                // constants.rs so the bot defense test arms with the exact same fuse.
+                // constants.rs so the bot defense test arms with the exact same fuse. A Raver's
+                // fuse runs up to a bar longer, since it holds the snap for the downbeat.
+                let fuse = if personality == RivalPersonality::Raver {
+                    STEAL_FUSE + RAVER_HOLD_BEATS * self.beat_interval
+                } else {
+                    STEAL_FUSE
+                };
                 let npc_pos = self.npc_trains[i].leader_pos;
                 let armed = self.npc_trains[i].steal_threat > 0.0;
                 // Early-out: if the NPC is far from the player and the chain tail — and nothing is
-                // already armed — no chain crab can be within STEAL_RANGE. Use cached_tail_pos (the
+                // already armed — no chain crab can be within steal range. Use cached_tail_pos (the
                 // farthest link, already computed by update_crabs) as a lower-bound proxy to avoid the
                 // O(n_crabs) scan. Once armed we fall through so the fuse still counts down to its snap.
                 let chain_span = self
                     .cached_tail_pos
                     .map_or(0.0_f32, |t| t.distance(self.player_pos));
                 let dist_to_chain = dist_to_player - chain_span;
-                if dist_to_chain > STEAL_RANGE && !armed {
+                if dist_to_chain > steal_range && !armed {
                     continue; // skip inner per-crab scan entirely this frame for this NPC
                 }
                 // Find the earliest (closest-to-head) link the NPC is within range of.
@@ -397,7 +468,7 @@ impl MainState {
                     .crabs
                     .iter()
                     .filter(|c| c.caught && c.chain_index.map_or(false, |idx| idx > 0))
-                    .filter(|c| npc_pos.distance_squared(c.pos) < STEAL_RANGE_SQ)
+                    .filter(|c| npc_pos.distance_squared(c.pos) < steal_range_sq)
                     .map(|c| c.chain_index.unwrap())
                     .min();
 
@@ -405,7 +476,7 @@ impl MainState {
                     // ARM the steal the moment a link comes into range: start the telegraph fuse and
                     // latch the target link so the snap fires from here even if the leader drifts off it.
                     if let Some(splice_idx) = splice_at {
-                        self.npc_trains[i].steal_threat = STEAL_FUSE;
+                        self.npc_trains[i].steal_threat = fuse;
                         self.npc_trains[i].steal_target = splice_idx;
                         let npc_name = self.npc_trains[i].name.clone();
                         let warn_pos = self
@@ -451,7 +522,7 @@ impl MainState {
                         .find(|c| c.caught && c.chain_index == Some(thread_idx))
                         .map(|c| c.pos);
                     if let Some(tp) = thread_pos {
-                        // ~2.5× STEAL_RANGE: a committed run or a sprint-juke, not a hair's breadth.
+                        // ~2.5× the steal range: a committed run or a sprint-juke, not a hair's breadth.
                         const ESCAPE_RANGE: f32 = 145.0;
                         if npc_pos.distance(tp) > ESCAPE_RANGE {
                             // Dodged — the rival lost the thread. Fizzle cleanly and put it on a short
@@ -464,6 +535,10 @@ impl MainState {
                             self.npc_trains[i].hunt_committed = false;
                             self.npc_trains[i].stalk_patience = 0.0;
                             self.steals_dodged += 1;
+                            if self.logging_events() {
+                                let rival = self.npc_trains[i].name.clone();
+                                self.log_event(GameEvent::Dodge { rival, downbeat });
+                            }
                             // Flip the reroute into offense, mirroring the tool parry (try_defend_steal):
                             // a clean juke leaves the rival strung out and exposed, so mark it for revenge
                             // and open a counter-steal window — thread its line inside the window and the
@@ -531,9 +606,13 @@ impl MainState {
                             crab.spooked_timer = crab.spooked_timer.max(0.22); // trembling "AT RISK" tell
                         }
                     }
-                    let telegraph_shown = self.npc_trains[i].steal_threat < STEAL_FUSE - 0.12;
-                    let fire =
-                        self.npc_trains[i].steal_threat <= 0.0 || (on_beat && telegraph_shown);
+                    let telegraph_shown = self.npc_trains[i].steal_threat < fuse - 0.12;
+                    let cue = if personality == RivalPersonality::Raver {
+                        downbeat
+                    } else {
+                        on_beat
+                    };
+                    let fire = self.npc_trains[i].steal_threat <= 0.0 || (cue && telegraph_shown);
                     if fire {
                         self.npc_trains[i].steal_threat = 0.0;
                         // Collect the stolen types before mutating crabs
@@ -564,6 +643,19 @@ impl MainState {
                             self.steal_loss_sfx = true; // play the descending loss sting (has no ctx here)
                             self.npc_trains[i].follower_types.extend(stolen_types);
                             self.npc_trains[i].steal_cooldown = 2.2;
+                            self.remember_rival(i, Encounter::Stole(stolen_count));
+                            // A Raver's steal on the drop, or any Silly steal (it never hunts, so
+                            // every one is opportunistic), is that personality's signature move.
+                            match personality {
+                                RivalPersonality::Raver if downbeat => {
+                                    self.note_signature(personality)
+                                }
+                                RivalPersonality::Silly => {
+                                    self.note_signature(personality);
+                                    self.npc_trains[i].steal_cooldown = 1.2;
+                                }
+                                _ => {}
+                            }
                             // Strike resolved (it won it): sated, the predator falls back to lurking —
                             // patience rebuilds from zero before the next stalk→strike cycle.
                             self.npc_trains[i].hunt_committed = false;
@@ -581,6 +673,10 @@ impl MainState {
                                 30.0,
                                 [0.96, 0.72, 0.16, 1.0],
                             );
+                            self.log_event(GameEvent::Steal {
+                                rival: npc_name,
+                                links: stolen_count,
+                            });
                             // A beat below the loss text: point the player at the counter-play.
                             self.floating_texts.spawn(
                                 "REVENGE — chase them down!".to_string(),
@@ -635,6 +731,8 @@ impl MainState {
                     // closes a duel and pays a bonus (cleared so it only lands once per marker).
                     let revenge = self.npc_trains[i].revenge_timer > 0.0;
                     self.npc_trains[i].revenge_timer = 0.0;
+                    // Raid: a train hauling home is carrying its whole haul and pays extra for it.
+                    let raid = hauling;
                     // split_off(fi) leaves 0..fi on the rival and returns the back section fi..tail.
                     let stolen = self.npc_trains[i].follower_types.split_off(fi);
                     let stolen_count = stolen.len();
@@ -661,6 +759,11 @@ impl MainState {
                     // Monotonic tally so the bot playtest can assert the steal-back fired without
                     // racing the live chain count (which banks/snaps drop back to zero).
                     self.crabs_stolen_by_player += stolen_count;
+                    // Stripping a King bare is what a DethroneKing goal waits for, and it won't forget it.
+                    if self.npc_trains[i].follower_types.is_empty() {
+                        self.dethroned_kings.push(self.npc_trains[i].name.clone());
+                        self.remember_rival(i, Encounter::Stripped);
+                    }
                     self.steal_gain_sfx = true; // play the rising triumphant sting (has no ctx here)
                     // Reward: stealing feeds the groove (harder on the beat) and banks score. A
                     // revenge steal-back (off a rival that just spliced you) pays extra — the payoff
@@ -674,6 +777,10 @@ impl MainState {
                         score_mult += 2; // stack the revenge bonus on top of the on-beat bonus
                         groove_gain += 0.14;
                     }
+                    if raid {
+                        self.haul_raids += 1;
+                        score_mult += HAUL_RAID_BONUS;
+                    }
                     self.score += stolen_count * score_mult;
                     self.groove = (self.groove + groove_gain).min(1.0);
                     if on_beat {
@@ -685,11 +792,20 @@ impl MainState {
                     let npc_name = self.npc_trains[i].name.clone();
                     let label = if revenge {
                         format!("REVENGE! GOT {} BACK!", stolen_count)
+                    } else if raid {
+                        format!("RAIDED {}'s HAUL! +{}", npc_name, stolen_count)
                     } else if on_beat {
                         format!("RUSTLED {} — ON BEAT!", stolen_count)
                     } else {
                         format!("RUSTLED {} from {}!", stolen_count, npc_name)
                     };
+                    self.log_event(GameEvent::StealBack {
+                        rival: npc_name,
+                        links: stolen_count,
+                        revenge,
+                        raid,
+                        on_beat,
+                    });
                     self.floating_texts.spawn(
                         label,
                         player_center - Vec2::new(90.0, 60.0),
@@ -712,13 +828,22 @@ impl MainState {
                 const CATCH_RANGE: f32 = 52.0;
                 const CATCH_RANGE_SQ: f32 = CATCH_RANGE * CATCH_RANGE;
                 let npc_pos = self.npc_trains[i].leader_pos;
-                let caught = self.crabs.iter_mut().find(|c| {
-                    !c.caught
-                        && !c.is_boss()
-                        && c.is_catchable()
-                        && npc_pos.distance_squared(c.pos) < CATCH_RANGE_SQ
-                });
-                if let Some(crab) = caught {
+                // First crab (in herd order) of those the crab index has near the leader.
+                let crabs = &self.crabs;
+                let caught = self
+                    .crab_index
+                    .within(npc_pos, CATCH_RANGE + DRIFT)
+                    .map(|c| c.index)
+                    .filter(|&ci| {
+                        crabs.get(ci).is_some_and(|c| {
+                            !c.caught
+                                && !c.is_boss()
+                                && c.is_catchable()
+                                && npc_pos.distance_squared(c.pos) < CATCH_RANGE_SQ
+                        })
+                    })
+                    .min();
+                if let Some(crab) = caught.map(|ci| &mut self.crabs[ci]) {
                     let ct = crab.crab_type;
                     // Teleport the crab far off-screen rather than marking it caught=true with
                     // no chain_index — that would corrupt rendering InstanceArray capacity checks.
@@ -908,10 +1033,13 @@ impl MainState {
                         }
                     }
                     self.chain_count = self.chain_count.saturating_sub(released);
+                    // It won this one, and it will bring it up next time.
+                    self.remember_rival(ni, Encounter::Won);
                     let taunt = crate::rival_taunts::clash_taunt(
                         &npc_name,
                         released,
                         self.chain_count,
+                        self.nemeses.get(&npc_name),
                         &mut crate::rng::rng(),
                     );
                     self.floating_texts.spawn(
@@ -390,6 +390,21 @@ impl MainState {
                 );
             }
 
+            // --- Haul home marker --------------------------------------------------------------
+            // While a rival hauls its train home (see rival_economy.rs) its pen wears a beat-pulsed
+            // gold ring, so the player can see where the haul is headed and cut it off on the way.
+            if npc.hauling {
+                let beat_phase = (self.beat_timer / self.beat_interval.max(0.0001)).clamp(0.0, 1.0);
+                let pulse = (beat_phase * std::f32::consts::TAU).cos() * 0.5 + 0.5;
+                let ring = cached_stroke_circle(ctx, RIVAL_PEN_RADIUS, 3.0 + pulse * 2.0)?;
+                canvas.draw(
+                    &ring,
+                    DrawParam::default()
+                        .dest(npc.territory_center)
+                        .color(Color::new(1.0, 0.78, 0.3, 0.35 + pulse * 0.3)),
+                );
+            }
+
             // Name banner floating above the King Crab — a distinct, readable-across-the-field
             // label so rivals tell apart at a glance (agar.io: spot the big one creeping in from
             // the edge). Three signals stack:
@@ -1,5 +1,7 @@
+use std::cell::RefCell;
+
 use ggez::glam::Vec2;
-use ggez::graphics::{Canvas, Color, DrawParam, Mesh, Rect, Text};
+use ggez::graphics::{Canvas, Color, DrawParam, Mesh, Rect, Text, TextFragment};
 use ggez::{Context, GameResult};
 
 use crate::MainState;
@@ -8,6 +10,8 @@ use crate::hud_cache::{
     FRENZY_BANNER_CACHE, GAME_OVER_CACHE, LEVEL_TITLE_OVERLAY_CACHE, STAGE_BANNER_CACHE,
     TUTORIAL_OVERLAY_CACHE, UPGRADE_SCREEN_CACHE,
 };
+use crate::leaderboard::{ARCADE, DAILY, ENDLESS, Ranking};
+use crate::pause::{Pause, PausePage};
 use crate::upgrade::{UPGRADE_POOL, UpgradeId};
 
 /// Full-screen overlay and HUD-screen drawing: level title cards, frenzy/stage banners, the
@@ -36,8 +40,8 @@ impl MainState {
         // Slide in from left: during fade-in, title slides right into position.
         let slide_x = (1.0 - alpha) * -80.0;
 
-        let level = &self.levels[self.current_level.min(self.levels.len() - 1)];
-        let biome = level.biome;
+        let level = self.level();
+        let biome = &level.biome;
 
         LEVEL_TITLE_OVERLAY_CACHE.with(|c| -> Result<(), ggez::GameError> {
             let mut cache = c.borrow_mut();
@@ -57,7 +61,7 @@ impl MainState {
                 subtitle.set_scale(22.0);
                 let sub_dims = subtitle.measure(ctx)?;
 
-                let emphasis = self.levels[self.current_level.min(self.levels.len() - 1)].emphasis;
+                let emphasis = level.emphasis;
                 let boss = level.boss_for_encounter(self.next_boss_kind);
                 // This string and Text are built only when the title-card cache changes, not per
                 // animation frame.
@@ -74,7 +78,7 @@ impl MainState {
 
                 *cache = Some((
                     self.level_title.clone(),
-                    biome.name,
+                    biome.name.clone(),
                     title,
                     // bg_rect slot — unused now, store a dummy
                     Mesh::new_rectangle(
@@ -471,15 +475,17 @@ impl MainState {
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> GameResult {
-        const BOX_WIDTH: f32 = 600.0;
-        const BOX_HEIGHT: f32 = 260.0;
-        const BOX_X: f32 = 340.0;
-        const BOX_Y: f32 = 360.0;
+        // Wide and tall enough for the run's high-score table under the summary.
+        const BOX_WIDTH: f32 = 760.0;
+        const BOX_HEIGHT: f32 = 540.0;
+        const BOX_X: f32 = 260.0;
+        const BOX_Y: f32 = 220.0;
 
         // All inputs that drive the text are frozen once game_over is set (update() returns
         // early, record_run() fires once) — so build the Mesh and Text objects once and reuse
         // them every subsequent frame rather than paying a GPU buffer upload + glyph-shaping
         // pass ~60 times/second for however long the player sits on the results screen.
+        let board = self.run_board();
         let cache_key = (
             self.score,
             self.time_elapsed.to_bits(),
@@ -488,10 +494,11 @@ impl MainState {
             self.career_total_score,
             self.career_runs,
             self.run_is_new_best,
+            (self.arcade_stage, board.clone(), self.last_entry.clone()),
         );
         GAME_OVER_CACHE.with(|c| -> GameResult {
             let mut cache = c.borrow_mut();
-            let stale = cache.as_ref().map_or(true, |(k, _, _, _)| *k != cache_key);
+            let stale = cache.as_ref().map_or(true, |(k, ..)| *k != cache_key);
             if stale {
                 let bg_box = Mesh::new_rectangle(
                     ctx,
@@ -499,11 +506,46 @@ impl MainState {
                     Rect::new(BOX_X, BOX_Y, BOX_WIDTH, BOX_HEIGHT),
                     Color::from_rgba(40, 0, 80, 180),
                 )?;
+                let headline = if let Some(daily) = &self.daily {
+                    let ending = if self.daily_time_left() == Some(0.0) {
+                        "Time's up"
+                    } else {
+                        "Overrun"
+                    };
+                    format!(
+                        "Daily {}: {ending} on stage {}!\nLongest train: {}",
+                        daily.date, self.arcade_stage, self.longest_train_run
+                    )
+                } else if self.endless.is_some() {
+                    format!(
+                        "Overrun on stage {}!\nLongest train: {}",
+                        self.arcade_stage, self.longest_train_run
+                    )
+                } else {
+                    "Game Over!".to_string()
+                };
+                let banked = if self.mutators.is_empty() {
+                    format!("{} crabs banked", self.score)
+                } else {
+                    format!(
+                        "{} crabs banked x{:.2} mutators = {}",
+                        self.score,
+                        self.mutators.score_mult(),
+                        self.final_score()
+                    )
+                };
                 let text = Text::new(format!(
-                    "Game Over!\nThis run: {} crabs banked\nTime: {:.2}s   Best time: {:.2}s\n\nCareer best: {}\nCareer total: {} over {} runs\n\nPress Space or Enter to try again.  Esc for menu.",
-                    self.score, self.time_elapsed, self.best_time,
+                    "{headline}\nThis run: {banked}\nTime: {:.2}s   Best time: {:.2}s\n\nCareer best: {}\nCareer total: {} over {} runs\n\nPress Space or Enter to try again.  Esc for menu.",
+                    self.time_elapsed, self.best_time,
                     self.career_best_score, self.career_total_score, self.career_runs,
                 ));
+                let table = match &board {
+                    Some(board) => {
+                        let top = text.measure(ctx)?.y + 24.0;
+                        Some((self.high_score_text(board), top))
+                    }
+                    None => None,
+                };
                 let banner = if self.run_is_new_best && self.score > 0 {
                     let mut b = Text::new("★ NEW CAREER BEST! ★");
                     b.set_scale(34.0);
@@ -512,16 +554,18 @@ impl MainState {
                 } else {
                     None
                 };
-                *cache = Some((cache_key, bg_box, text, banner));
+                *cache = Some((cache_key, bg_box, text, table, banner));
             }
-            let (_, bg_box, text, banner) = cache.as_ref().unwrap();
+            let (_, bg_box, text, table, banner) = cache.as_ref().unwrap();
             canvas.draw(bg_box, DrawParam::default());
-            canvas.draw(
-                text,
-                DrawParam::default()
-                    .dest(Vec2::new(370.0, 380.0))
-                    .color(Color::WHITE),
-            );
+            let text_pos = Vec2::new(BOX_X + 30.0, BOX_Y + 20.0);
+            canvas.draw(text, DrawParam::default().dest(text_pos).color(Color::WHITE));
+            if let Some((table_text, top)) = table {
+                canvas.draw(
+                    table_text,
+                    DrawParam::default().dest(text_pos + Vec2::new(0.0, *top)),
+                );
+            }
             // Celebrate a fresh career best with a pulsing banner so beating your record lands.
             // The Text and its width are cached; only the per-frame alpha pulse is computed fresh.
             if let Some((banner_text, bw)) = banner {
@@ -537,6 +581,45 @@ impl MainState {
         })
     }
 
+    /// The high-score table `board` as one Text, one run per line, with the run that just ended
+    /// in gold. A run that missed the table gets a closing line saying why.
+    fn high_score_text(&self, board: &str) -> Text {
+        let name = match board {
+            ARCADE => "ARCADE".to_string(),
+            ENDLESS => "ENDLESS".to_string(),
+            DAILY => "DAILY CHALLENGE".to_string(),
+            _ => self.world_map.as_ref().map_or(String::new(), |map| {
+                map.nodes[map.selected].name.to_uppercase()
+            }),
+        };
+        let mut text = Text::new(
+            TextFragment::new(format!("{name}  HIGH SCORES\n"))
+                .color(Color::from_rgb(140, 235, 255)),
+        );
+        let ranking = Ranking::of(board);
+        let rank = self.last_rank_on(board);
+        for (i, entry) in self.leaderboards.table(board).iter().enumerate() {
+            let color = if rank == Some(i) {
+                Color::from_rgb(255, 215, 60)
+            } else {
+                Color::from_rgb(220, 220, 235)
+            };
+            text.add(TextFragment::new(entry.row(i, ranking) + "\n").color(color));
+        }
+        if rank.is_none() {
+            let why = if self.in_campaign {
+                "Clear the stage to make the table."
+            } else if self.daily.as_ref().is_some_and(|daily| !daily.scored) {
+                "Practice run: today's scored attempt is spent."
+            } else {
+                "This run didn't make the table."
+            };
+            text.add(TextFragment::new(why).color(Color::from_rgb(180, 170, 210)));
+        }
+        text.set_scale(16.0);
+        text
+    }
+
     /// Screen-space rectangles for the three upgrade cards, in card order (index 0 = card "1").
     /// Shared by the draw code (hover highlight) and the mouse-click handler so they always agree.
     pub(crate) fn upgrade_card_rects(&self) -> [Rect; 3] {
@@ -623,7 +706,7 @@ impl MainState {
                 let title_w = title_text.measure(ctx)?.x;
                 // Subtitle
                 let mut hint_text = Text::new(
-                    "Pick fast — the beach keeps moving! Click a card or press its number",
+                    "Pick fast — the beach keeps moving! Click a card, press its number, or D-pad + A",
                 );
                 hint_text.set_scale(20.0);
                 let hint_w = hint_text.measure(ctx)?.x;
@@ -688,7 +771,8 @@ impl MainState {
                 let cx = rects[i].x;
                 let y0 = rects[i].y;
                 let m = self.mouse_pos;
-                let hovered = m.x >= cx && m.x <= cx + card_w && m.y >= y0 && m.y <= y0 + card_h;
+                let hovered = self.upgrade_focus == Some(i)
+                    || (m.x >= cx && m.x <= cx + card_w && m.y >= y0 && m.y <= y0 + card_h);
 
                 let accent = Color::from_rgb(r, g, b);
                 // Cards sit over a LIVE, moving scene now (the scrim is only half-opaque), so keep
@@ -755,4 +839,120 @@ impl MainState {
             Ok(())
         })
     }
+
+    /// The pause menu over the frozen run, or its count-in (see pause.rs).
+    pub(crate) fn draw_pause_overlay(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let (w, h) = (self.width, self.height);
+        let Some(pause) = self.pause else {
+            return Ok(());
+        };
+        let (page, selection) = match pause {
+            Pause::Menu { page, selection } => (page, selection),
+            Pause::CountIn { beats_left, .. } => {
+                // Counting back in: the frozen scene stays readable, with the beat count on top.
+                let mut count = Text::new(beats_left.to_string());
+                count.set_scale(120.0);
+                let cw = count.measure(ctx)?;
+                canvas.draw(
+                    &count,
+                    DrawParam::default()
+                        .dest(Vec2::new((w - cw.x) / 2.0, (h - cw.y) / 2.0))
+                        .color(Color::from_rgba(255, 230, 120, 230)),
+                );
+                return Ok(());
+            }
+        };
+
+        let square = unit_square(ctx)?;
+        canvas.draw(
+            square,
+            DrawParam::default()
+                .scale(Vec2::new(w, h))
+                .color(Color::from_rgba(8, 4, 22, 170)),
+        );
+        let title = match page {
+            PausePage::Main => "PAUSED",
+            PausePage::Settings => "SETTINGS",
+            PausePage::Controls => "CONTROLS",
+        };
+        let rows: Vec<String> = match page {
+            PausePage::Main => self
+                .pause_items()
+                .iter()
+                .map(|item| item.label().to_string())
+                .collect(),
+            PausePage::Settings => vec![
+                format!("MUSIC: {}", if self.music_muted { "OFF" } else { "ON" }),
+                "CONTROLS".to_string(),
+                "BACK".to_string(),
+            ],
+            PausePage::Controls => Vec::new(),
+        };
+
+        // Slot 0 is the title, the rest one per row; each is re-shaped only when its text changes.
+        thread_local! {
+            static PAUSE_TEXT_CACHE: RefCell<Vec<Option<(String, Text, f32)>>> = const { RefCell::new(Vec::new()) };
+        }
+        PAUSE_TEXT_CACHE.with(|c| -> GameResult {
+            let mut cache = c.borrow_mut();
+            let slots = cache.len().max(1 + rows.len());
+            cache.resize_with(slots, || None);
+            let mut shaped = |slot: usize, text: &str, scale: f32| -> GameResult<(Text, f32)> {
+                let entry = &mut cache[slot];
+                if entry.as_ref().is_none_or(|(s, ..)| s != text) {
+                    let mut t = Text::new(text);
+                    t.set_scale(scale);
+                    let tw = t.measure(ctx)?.x;
+                    *entry = Some((text.to_string(), t, tw));
+                }
+                let (_, text, tw) = entry.as_ref().unwrap();
+                Ok((text.clone(), *tw))
+            };
+
+            let (title_text, title_w) = shaped(0, title, 54.0)?;
+            canvas.draw(
+                &title_text,
+                DrawParam::default()
+                    .dest(Vec2::new((w - title_w) / 2.0, h * 0.12))
+                    .color(Color::from_rgb(255, 215, 50)),
+            );
+            if page == PausePage::Controls {
+                return Ok(());
+            }
+
+            let row_h = 58.0_f32;
+            let top = h * 0.5 - rows.len() as f32 * row_h / 2.0;
+            for (i, row) in rows.iter().enumerate() {
+                let y = top + i as f32 * row_h;
+                let selected = i == selection;
+                if selected {
+                    canvas.draw(
+                        square,
+                        DrawParam::default()
+                            .dest(Vec2::new(w * 0.5 - 190.0, y - 8.0))
+                            .scale(Vec2::new(380.0, row_h - 10.0))
+                            .color(Color::from_rgba(60, 180, 160, 150)),
+                    );
+                }
+                let (text, tw) = shaped(1 + i, row, 30.0)?;
+                let color = if selected {
+                    Color::from_rgb(255, 255, 225)
+                } else {
+                    Color::from_rgba(200, 210, 230, 220)
+                };
+                canvas.draw(
+                    &text,
+                    DrawParam::default()
+                        .dest(Vec2::new((w - tw) / 2.0, y))
+                        .color(color),
+                );
+            }
+            Ok(())
+        })?;
+
+        if page == PausePage::Controls {
+            crate::menu::draw_controls_list(self, ctx, canvas, w, h * 0.22)?;
+        }
+        Ok(())
+    }
 }
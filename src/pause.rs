@@ -0,0 +1,311 @@
+//! The in-run pause menu: Esc during a run freezes it and offers Resume, Restart, Settings and the
+//! two ways out.
+//!
+//! Pausing freezes everything a run is timed by — the simulation, the master beat clock and every
+//! looping music voice (the same `pause_gameplay_music` hitstop uses) — so nothing drifts while the
+//! menu is up. Resuming doesn't drop the player straight back in wherever the bar was frozen,
+//! though: it counts in on the paused beat grid, a kick per beat, up to the next downbeat, then
+//! restarts the loops from their first bar on that downbeat. The groove comes back phase-locked and
+//! the player gets the count they need to catch it.
+
+use ggez::audio::SoundSource;
+use ggez::input::keyboard::KeyCode;
+
+use crate::MainState;
+use crate::controls::handle_controls_page_key;
+use crate::event_log::RunEnd;
+use crate::state::LassoPhase;
+
+/// Which screen of the pause menu is showing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PausePage {
+    Main,
+    Settings,
+    /// The same rebinding list as the title menu's CONTROLS page.
+    Controls,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Pause {
+    Menu {
+        page: PausePage,
+        selection: usize,
+    },
+    /// Resume was chosen: still frozen, counting beats until the next downbeat.
+    CountIn {
+        beats_left: u32,
+        timer: f32,
+    },
+}
+
+/// A row on the pause menu's main page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PauseItem {
+    Resume,
+    Restart,
+    Settings,
+    QuitToMap,
+    QuitToTitle,
+}
+
+impl PauseItem {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            PauseItem::Resume => "RESUME",
+            PauseItem::Restart => "RESTART LEVEL",
+            PauseItem::Settings => "SETTINGS",
+            PauseItem::QuitToMap => "QUIT TO MAP",
+            PauseItem::QuitToTitle => "QUIT TO TITLE",
+        }
+    }
+}
+
+// Quit to title is always last, so Up from Resume reaches it on either list.
+const CAMPAIGN_ITEMS: [PauseItem; 5] = [
+    PauseItem::Resume,
+    PauseItem::Restart,
+    PauseItem::Settings,
+    PauseItem::QuitToMap,
+    PauseItem::QuitToTitle,
+];
+const ARCADE_ITEMS: [PauseItem; 4] = [
+    PauseItem::Resume,
+    PauseItem::Restart,
+    PauseItem::Settings,
+    PauseItem::QuitToTitle,
+];
+
+/// Rows on the settings page: music on/off, the controls list, back.
+pub(crate) const SETTINGS_ROWS: usize = 3;
+
+/// How many beats after beat number `beat_count` the next downbeat lands (1-4).
+fn beats_to_downbeat(beat_count: u32) -> u32 {
+    4 - beat_count % 4
+}
+
+impl MainState {
+    /// The main page's rows for the current run (there is no map to quit to outside the campaign).
+    pub(crate) fn pause_items(&self) -> &'static [PauseItem] {
+        if self.in_campaign {
+            &CAMPAIGN_ITEMS
+        } else {
+            &ARCADE_ITEMS
+        }
+    }
+
+    pub(crate) fn open_pause(&mut self) {
+        // A wind-up can't be held through the menu (its release would land on a pause screen), so
+        // drop it; the throw was never committed.
+        if self.lasso_phase == LassoPhase::Winding {
+            self.lasso_phase = LassoPhase::Idle;
+            self.lasso_charge = 0.0;
+        }
+        self.lasso_mouse_down = false;
+        self.lasso_key_down = false;
+        self.pause = Some(Pause::Menu {
+            page: PausePage::Main,
+            selection: 0,
+        });
+        self.pause_gameplay_music();
+    }
+
+    /// Leave the menu and count back in to the next downbeat (see the module docs).
+    fn resume_from_pause(&mut self) {
+        self.pause = Some(Pause::CountIn {
+            beats_left: beats_to_downbeat(self.beat_count),
+            timer: self.beat_timer,
+        });
+    }
+
+    /// The count-in reached the downbeat: put the clock one tick before it and restart the loops
+    /// so their first bar lands on it.
+    fn finish_count_in(&mut self, beats_counted: u32) {
+        self.pause = None;
+        // The step that follows fires `on_beat` for a beat count divisible by four: the downbeat.
+        self.beat_count = self.beat_count.wrapping_add(beats_counted - 1);
+        self.beat_timer = 0.0;
+        // Skip the frozen bar's remaining off-beat hats rather than firing them all at once.
+        self.hat_last_step = self.beat_count as i64 * 4 + 3;
+        for music in &self.sounds.action_music {
+            music.stop();
+        }
+        for layer in &self.music_layers {
+            layer.stop();
+        }
+        for (left, right) in &self.sounds.king_crab_motif {
+            left.stop();
+            right.stop();
+        }
+        for theme in &self.sounds.crab_themes {
+            theme.stop();
+        }
+        for source in [
+            &self.sounds.king_crab_l,
+            &self.sounds.king_crab_r,
+            &self.sounds.king_crab_soft,
+        ] {
+            source.stop();
+        }
+    }
+
+    /// The paused part of a step. Returns whether the run is still frozen; `false` means the
+    /// count-in just landed and this step should simulate as usual.
+    pub(crate) fn step_pause(&mut self, raw_dt: f32) -> bool {
+        let Some(pause) = self.pause else {
+            return false;
+        };
+        // The same clamped, scaled delta the beat clock runs on when the game isn't paused.
+        let dt = raw_dt.min(0.1) * self.time_scale;
+        // Bot scripts keep their clock (and events) running through the menu, as on other screens.
+        if self.bot.is_some() {
+            self.time_elapsed += dt;
+            self.bot_fire_events();
+            self.bot_check_done();
+        }
+        if let Pause::CountIn {
+            mut beats_left,
+            mut timer,
+        } = pause
+        {
+            let total = beats_to_downbeat(self.beat_count);
+            timer -= dt;
+            while timer <= 0.0 && beats_left > 0 {
+                beats_left -= 1;
+                if beats_left == 0 {
+                    self.finish_count_in(total);
+                    return false;
+                }
+                self.beat_synth.play_kick(false);
+                timer += self.beat_interval;
+            }
+            // A menu press during the count-in (Esc) has already replaced it.
+            if matches!(self.pause, Some(Pause::CountIn { .. })) {
+                self.pause = Some(Pause::CountIn { beats_left, timer });
+            }
+        }
+        self.pause_gameplay_music();
+        true
+    }
+
+    /// Keyboard input while paused. Every key is consumed: nothing reaches the frozen run.
+    pub(crate) fn handle_pause_key(&mut self, key: KeyCode) {
+        let Some(Pause::Menu { page, selection }) = self.pause else {
+            // Counting in: Esc goes back to the menu, anything else waits for the downbeat.
+            if key == KeyCode::Escape {
+                self.open_pause();
+            }
+            return;
+        };
+        if key == KeyCode::KeyM {
+            self.music_muted = !self.music_muted;
+            return;
+        }
+        let up = key == KeyCode::ArrowUp;
+        let down = key == KeyCode::ArrowDown;
+        let confirm = matches!(key, KeyCode::Space | KeyCode::Enter);
+        match page {
+            PausePage::Main => {
+                let items = self.pause_items();
+                let n = items.len();
+                let selection = selection.min(n - 1);
+                if key == KeyCode::Escape {
+                    self.resume_from_pause();
+                } else if up || down {
+                    let selection = if up {
+                        (selection + n - 1) % n
+                    } else {
+                        (selection + 1) % n
+                    };
+                    self.pause = Some(Pause::Menu { page, selection });
+                } else if confirm {
+                    self.choose_pause_item(items[selection]);
+                }
+            }
+            PausePage::Settings => {
+                let back = Pause::Menu {
+                    page: PausePage::Main,
+                    selection: 2,
+                };
+                if key == KeyCode::Escape {
+                    self.pause = Some(back);
+                } else if up || down {
+                    let selection = if up {
+                        (selection + SETTINGS_ROWS - 1) % SETTINGS_ROWS
+                    } else {
+                        (selection + 1) % SETTINGS_ROWS
+                    };
+                    self.pause = Some(Pause::Menu { page, selection });
+                } else if confirm || matches!(key, KeyCode::ArrowLeft | KeyCode::ArrowRight) {
+                    match selection {
+                        0 => self.music_muted = !self.music_muted,
+                        1 if confirm => {
+                            self.rebind_selection = 0;
+                            self.rebind_notice = None;
+                            self.pause = Some(Pause::Menu {
+                                page: PausePage::Controls,
+                                selection: 0,
+                            });
+                        }
+                        2 if confirm => self.pause = Some(back),
+                        _ => {}
+                    }
+                }
+            }
+            PausePage::Controls => {
+                if handle_controls_page_key(self, key) {
+                    self.pause = Some(Pause::Menu {
+                        page: PausePage::Settings,
+                        selection: 1,
+                    });
+                }
+            }
+        }
+    }
+
+    fn choose_pause_item(&mut self, item: PauseItem) {
+        match item {
+            PauseItem::Resume => self.resume_from_pause(),
+            PauseItem::Restart => {
+                self.pause = None;
+                self.log_run_end(RunEnd::Restart);
+                self.stop_level_audio();
+                if self.in_campaign {
+                    // Re-enter the selected node from scratch (a tutorial node restarts its lesson).
+                    self.tutorial = None;
+                    self.enter_campaign_level();
+                } else {
+                    self.reset_game();
+                }
+            }
+            PauseItem::Settings => {
+                self.pause = Some(Pause::Menu {
+                    page: PausePage::Settings,
+                    selection: 0,
+                });
+            }
+            PauseItem::QuitToMap => {
+                self.pause = None;
+                self.tutorial = None;
+                self.return_to_world_map(false);
+            }
+            PauseItem::QuitToTitle => {
+                self.pause = None;
+                self.return_to_main_menu();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_in_ends_on_a_downbeat() {
+        for beat_count in 0..12u32 {
+            let beats = beats_to_downbeat(beat_count);
+            assert!((1..=4).contains(&beats));
+            assert_eq!((beat_count + beats) % 4, 0);
+        }
+    }
+}
@@ -5,12 +5,11 @@
 //! Slam, Whistle/Stomp/Wave casts — live in `tool_actions.rs`. Extracted out of `main.rs`'s
 //! `impl MainState` — same methods, same behaviour, just grouped by subsystem.
 
-use ggez::Context;
-use ggez::audio::SoundSource;
 use ggez::glam::Vec2;
 
 use crate::constants::*;
 use crate::controls;
+use crate::event_log::GameEvent;
 use crate::state::MainState;
 
 impl MainState {
@@ -95,6 +94,10 @@ impl MainState {
                 self.npc_trains[i].idle_timer = if downbeat { 0.9 } else { 0.5 };
                 self.steals_parried += 1;
                 parried = true;
+                if self.logging_events() {
+                    let rival = self.npc_trains[i].name.clone();
+                    self.log_event(GameEvent::Parry { rival, downbeat });
+                }
                 // Flip the exchange into offense: mark the shoved rival with the green "chase me"
                 // revenge window so a clean parry opens a counter-steal — thread its stunned line
                 // inside the window and the steal-back pays the revenge bonus. A downbeat "big save"
@@ -261,8 +264,9 @@ impl MainState {
     /// behave identically on every screen. The paused-screen tick used to run a stripped-down copy
     /// that silently dropped Assert events (and never terminated), which hung campaign_tutorial the
     /// instant its tutorial passed and handed control back to the world map.
-    pub(crate) fn bot_fire_events(&mut self, ctx: &mut Context) {
-        use crate::bot::{BotAction, BotAssert};
+    pub(crate) fn bot_fire_events(&mut self) {
+        use crate::bot::BotAction;
+        use crate::bot_report::CheckRecord;
         // Release tap keys queued last frame.
         let taps: Vec<_> = self
             .bot
@@ -297,7 +301,7 @@ impl MainState {
                     self.bot.as_mut().unwrap().keys_held.insert(k);
                     self.bot.as_mut().unwrap().tap_release_queue.push(k);
                     // Fire as a synthetic key-down event for menu/dash/campaign actions.
-                    controls::handle_key_down_event(self, ctx, Some(k));
+                    controls::handle_key_down_event(self, Some(k));
                 }
                 BotAction::MouseMove(p) => {
                     self.bot.as_mut().unwrap().mouse_pos = p;
@@ -318,7 +322,7 @@ impl MainState {
                     if self.chain_count > 0 {
                         self.player_pos = self.pen_pos - Vec2::splat(crate::PLAYER_SIZE / 2.0);
                         self.player_vel = Vec2::ZERO;
-                        self.try_deliver_train(ctx);
+                        self.try_deliver_train();
                     }
                 }
                 BotAction::ForceNpcCross => {
@@ -348,50 +352,40 @@ impl MainState {
                 BotAction::ForceHuntCommit => {
                     self.force_hunt_commit();
                 }
+                BotAction::ForceRivalHaul => {
+                    self.force_rival_haul();
+                }
+                BotAction::ForceHaulRaid => {
+                    self.force_haul_raid();
+                }
+                BotAction::ForcePersonality(personality) => {
+                    self.force_personality(personality);
+                }
                 BotAction::ForceGameOver => {
                     self.game_over = true;
                 }
+                BotAction::SetGoal(goal) => {
+                    if let Some(i) = self
+                        .world_map
+                        .as_ref()
+                        .and_then(|m| m.selected_level_index())
+                    {
+                        self.levels[i].win_condition = goal;
+                        self.crown_goal_king();
+                    }
+                }
                 BotAction::Log(msg) => {
                     println!("[BOT t={:.1}] {}", self.time_elapsed, msg);
                 }
                 BotAction::Assert(check) => {
-                    let ok = match &check {
-                        BotAssert::GameNotOver => !self.game_over,
-                        BotAssert::ChainAtLeast(n) => self.chain_count >= *n,
-                        BotAssert::CaughtAtLeast(n) => self.total_caught >= *n,
-                        BotAssert::ChordFiredAtLeast(n) => self.chord_tools_fired >= *n,
-                        BotAssert::StolenAtLeast(n) => self.crabs_stolen_by_npc >= *n,
-                        BotAssert::MaxSingleStealAtMost(n) => self.max_single_steal_by_npc <= *n,
-                        BotAssert::StolenByPlayerAtLeast(n) => self.crabs_stolen_by_player >= *n,
-                        BotAssert::ParriedAtLeast(n) => self.steals_parried >= *n,
-                        BotAssert::WaveShovedAtLeast(n) => self.rivals_wave_shoved >= *n,
-                        BotAssert::DodgedAtLeast(n) => self.steals_dodged >= *n,
-                        BotAssert::RevengeStealAtLeast(n) => self.revenge_steals >= *n,
-                        BotAssert::RivalStealAtLeast(n) => self.rival_vs_rival_steals >= *n,
-                        BotAssert::RivalSpillAtLeast(n) => self.rival_spill_crabs >= *n,
-                        BotAssert::RivalHuntTelegraphAtLeast(n) => self.rival_hunt_telegraphs >= *n,
-                        BotAssert::HuntInterceptAtLeast(n) => self.hunt_intercepts >= *n,
-                        BotAssert::ScoreAtLeast(n) => self.score >= *n,
-                        BotAssert::SelectedNextUnlocked(want) => {
-                            self.world_map.as_ref().map_or(false, |m| {
-                                m.nodes.get(m.selected + 1).map_or(false, |n| n.unlocked)
-                            }) == *want
-                        }
-                        BotAssert::ShowWorldMap => self.show_world_map,
-                        BotAssert::MainMenu => self.show_instructions && !self.show_world_map,
-                        BotAssert::PlayRecommendation => self.show_play_recommendation,
-                        BotAssert::TitleMenuReady => {
-                            self.show_instructions
-                                && !self.show_world_map
-                                && !self.sounds.action_music.iter().any(|music| music.playing())
-                                && self.sounds.intro_music.playing()
-                        }
-                        BotAssert::TutorialActive => self.tutorial.is_some(),
-                        BotAssert::TutorialDone => self.tutorial.is_none() && self.show_world_map,
-                        BotAssert::InGame => {
-                            !self.show_instructions && !self.game_over && !self.show_world_map
-                        }
-                    };
+                    let (ok, expected, observed) = self.evaluate_bot_assert(&check);
+                    self.bot.as_mut().unwrap().checks.push(CheckRecord {
+                        at: ev.at,
+                        check: check.clone(),
+                        expected,
+                        observed,
+                        passed: ok,
+                    });
                     if !ok {
                         let msg =
                             format!("ASSERT FAILED at t={:.1}: {:?}", self.time_elapsed, check);
@@ -412,7 +406,8 @@ impl MainState {
     }
 
     /// Terminate the bot run: PASS once the script is exhausted, FAIL once the time budget is spent.
-    /// Exits the process when done, so it never returns in that case. Shared by both bot ticks.
+    /// Only flags the run as done — the windowed `tick` turns that into the process exit code and
+    /// the headless `Simulation` into a `Result`. Shared by both bot ticks.
     pub(crate) fn bot_check_done(&mut self) {
         let t = self.time_elapsed;
         let bot = self.bot.as_mut().unwrap();
@@ -425,9 +420,6 @@ impl MainState {
             bot.failed = Some("time limit exceeded".into());
             bot.done = true;
         }
-        if bot.done {
-            std::process::exit(if bot.failed.is_some() { 1 } else { 0 });
-        }
     }
 
     /// Position of the nearest free, catchable, non-boss crab, if any. The seek-catch bot autopilot
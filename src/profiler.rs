@@ -0,0 +1,242 @@
+//! Per-subsystem frame timings behind the F3 performance overlay.
+//!
+//! While the overlay is up, the hot simulation systems and the three render passes are timed
+//! (CPU wall time, summed over however many fixed steps the frame ran) and each displayed frame
+//! becomes a [`FrameSample`] in a rolling window, alongside how many instanced batches were drawn
+//! and how many instances they carried. `graphics::draw_perf_overlay` charts the window; F4 writes
+//! it to `perf-<unix time>.csv` in the working directory, one row per frame, for attaching to a
+//! perf bug. With the overlay off nothing is timed or kept — each timing site is a branch on
+//! `visible`.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::time::Instant;
+
+use crate::MainState;
+
+/// How many frames the overlay charts and F4 dumps.
+pub(crate) const HISTORY: usize = 240;
+
+/// Something the profiler times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Section {
+    UpdateCrabs,
+    NpcTrains,
+    CrabCatching,
+    ActiveTools,
+    AmbientAudio,
+    Scene,
+    Trail,
+    PostProcess,
+}
+
+impl Section {
+    pub(crate) const ALL: [Section; 8] = [
+        Section::UpdateCrabs,
+        Section::NpcTrains,
+        Section::CrabCatching,
+        Section::ActiveTools,
+        Section::AmbientAudio,
+        Section::Scene,
+        Section::Trail,
+        Section::PostProcess,
+    ];
+
+    /// The function (or render pass) it covers.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Section::UpdateCrabs => "update_crabs",
+            Section::NpcTrains => "update_npc_trains",
+            Section::CrabCatching => "handle_crab_catching",
+            Section::ActiveTools => "resolve_active_tools",
+            Section::AmbientAudio => "update_ambient_audio",
+            Section::Scene => "scene pass",
+            Section::Trail => "trail pass",
+            Section::PostProcess => "postprocess pass",
+        }
+    }
+}
+
+/// One displayed frame's timings, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct FrameSample {
+    pub sections: [f32; Section::ALL.len()],
+    /// Wall time since the previous frame.
+    pub frame: f32,
+    /// Fixed simulation steps run this frame.
+    pub steps: u32,
+    pub batches: u32,
+    pub instances: u32,
+}
+
+thread_local! {
+    // Counted by `InstancedMeshExt::draw_instanced_mesh_guarded`, the one door every instanced
+    // batch goes through; drained once per frame by `Profiler::end_frame`.
+    static BATCHES: Cell<u32> = const { Cell::new(0) };
+    static INSTANCES: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Count one instanced batch of `instances` instances.
+pub(crate) fn count_batch(instances: usize) {
+    BATCHES.with(|b| b.set(b.get() + 1));
+    INSTANCES.with(|i| i.set(i.get().saturating_add(instances as u32)));
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Profiler {
+    pub visible: bool,
+    current: FrameSample,
+    history: VecDeque<FrameSample>,
+    last_frame: Option<Instant>,
+    /// Where the last F4 dump went (or why it failed), for the overlay to show.
+    pub last_dump: Option<String>,
+}
+
+impl Profiler {
+    pub(crate) fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.history.clear();
+        self.current = FrameSample::default();
+        self.last_frame = None;
+    }
+
+    /// Start timing a section; `None` (and nothing timed) while the overlay is hidden.
+    pub(crate) fn start(&self) -> Option<Instant> {
+        self.visible.then(Instant::now)
+    }
+
+    pub(crate) fn stop(&mut self, section: Section, started: Option<Instant>) {
+        if let Some(started) = started {
+            self.current.sections[section as usize] += started.elapsed().as_secs_f32() * 1000.0;
+        }
+    }
+
+    pub(crate) fn count_step(&mut self) {
+        if self.visible {
+            self.current.steps += 1;
+        }
+    }
+
+    /// Close the displayed frame: file its sample and start the next.
+    pub(crate) fn end_frame(&mut self) {
+        let batches = BATCHES.with(|b| b.replace(0));
+        let instances = INSTANCES.with(|i| i.replace(0));
+        if !self.visible {
+            return;
+        }
+        let now = Instant::now();
+        let mut sample = std::mem::take(&mut self.current);
+        sample.frame = self
+            .last_frame
+            .map_or(0.0, |t| (now - t).as_secs_f32() * 1000.0);
+        sample.batches = batches;
+        sample.instances = instances;
+        self.last_frame = Some(now);
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(sample);
+    }
+
+    pub(crate) fn history(&self) -> &VecDeque<FrameSample> {
+        &self.history
+    }
+
+    /// Mean and worst of each column over the window.
+    pub(crate) fn summary(&self) -> (FrameSample, FrameSample) {
+        let mut mean = FrameSample::default();
+        let mut worst = FrameSample::default();
+        for s in &self.history {
+            for (i, ms) in s.sections.iter().enumerate() {
+                mean.sections[i] += ms;
+                worst.sections[i] = worst.sections[i].max(*ms);
+            }
+            mean.frame += s.frame;
+            worst.frame = worst.frame.max(s.frame);
+            mean.steps += s.steps;
+            mean.batches += s.batches;
+            worst.batches = worst.batches.max(s.batches);
+            mean.instances += s.instances;
+            worst.instances = worst.instances.max(s.instances);
+        }
+        let n = self.history.len().max(1);
+        for ms in &mut mean.sections {
+            *ms /= n as f32;
+        }
+        mean.frame /= n as f32;
+        mean.steps /= n as u32;
+        mean.batches /= n as u32;
+        mean.instances /= n as u32;
+        (mean, worst)
+    }
+
+    /// The window as CSV: a header, then one row per frame, oldest first.
+    pub(crate) fn to_csv(&self) -> String {
+        let mut out = String::from("frame_ms,steps");
+        for section in Section::ALL {
+            let _ = write!(out, ",{}", section.label().replace(' ', "_"));
+        }
+        out.push_str(",batches,instances\n");
+        for s in &self.history {
+            let _ = write!(out, "{:.3},{}", s.frame, s.steps);
+            for ms in s.sections {
+                let _ = write!(out, ",{ms:.3}");
+            }
+            let _ = writeln!(out, ",{},{}", s.batches, s.instances);
+        }
+        out
+    }
+}
+
+impl MainState {
+    /// F4: write the profiler's window to a CSV next to where the game was started.
+    pub(crate) fn dump_profile(&mut self) {
+        if !self.profiler.visible {
+            return;
+        }
+        let unix = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = format!("perf-{unix}.csv");
+        self.profiler.last_dump = Some(match std::fs::write(&path, self.profiler.to_csv()) {
+            Ok(()) => format!("saved {path}"),
+            Err(e) => format!("could not write {path}: {e}"),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_profiler_times_nothing() {
+        let mut profiler = Profiler::default();
+        assert!(profiler.start().is_none());
+        count_batch(10);
+        profiler.end_frame();
+        assert!(profiler.history().is_empty());
+    }
+
+    #[test]
+    fn frames_roll_through_a_fixed_window() {
+        let mut profiler = Profiler::default();
+        profiler.toggle();
+        for _ in 0..HISTORY + 5 {
+            let started = profiler.start();
+            profiler.stop(Section::UpdateCrabs, started);
+            profiler.count_step();
+            count_batch(3);
+            profiler.end_frame();
+        }
+        assert_eq!(profiler.history().len(), HISTORY);
+        let (mean, worst) = profiler.summary();
+        assert_eq!(mean.steps, 1);
+        assert_eq!((mean.batches, worst.instances), (1, 3));
+        let csv = profiler.to_csv();
+        assert_eq!(csv.lines().count(), HISTORY + 1);
+        assert!(csv.starts_with("frame_ms,steps,update_crabs,"));
+        assert!(csv.lines().nth(1).unwrap().ends_with(",1,3"));
+    }
+}
@@ -0,0 +1,563 @@
+//! Input recording and deterministic replay of interactive sessions.
+//!
+//! A replay is everything needed to re-run a session frame-for-frame: the RNG seed the session was
+//! started with, the fixed simulation timestep it ran at, the date and save it started from, and
+//! for every simulation frame the keys held plus the keyboard/mouse events delivered since the
+//! previous frame. Given those, the `Context`-free `MainState::step` (see `simulation.rs`)
+//! reproduces the run exactly — the same spawns, the same rival AI, the same steal or snap a tester
+//! tripped over.
+//!
+//! `--record <file>` writes one while you play; `--replay <file>` feeds it back, either in a
+//! window (watch the bug happen) or with `--headless` (print the outcome). Not to be confused
+//! with `RUSTLER_RECORD`, which is the screen-capture pacing switch for bot GIFs.
+//!
+//! The file is line-based text, so a bug report can be eyeballed or trimmed by hand:
+//!
+//! ```text
+//! rustler-replay 1
+//! seed 9417265530113468342
+//! dt 0.016666668
+//! date 2026-10-18
+//! save rustler-save 2
+//! save start_lasso_rank 2
+//! save bind move_up KeyZ
+//! f KeyW ShiftLeft
+//! e key KeyQ 113
+//! e move 640.5 480
+//! e down Left 640.5 480
+//! f KeyW
+//! p 0.8 -0.1 0 0 0.9 West RightTrigger2
+//! e pad South
+//! ```
+//!
+//! `date` is the day the session was recorded, which a daily challenge started in it deals from
+//! (see daily.rs), so a daily run replays on its own beach whatever day it's played back. `save`
+//! lines are a snapshot of the save the session started from (see save.rs), so perk ranks,
+//! unlocks, rival memories and rebound keys are the recorder's, not whoever plays it back. Playback
+//! never writes that snapshot, or anything else, to the local save. Older replays carry only
+//! `bind` lines for the key bindings and play back on an otherwise fresh save.
+//!
+//! `f` starts a frame and lists its held keys; the `e` lines after it are that frame's events, in
+//! delivery order. A `p` line after it is the gamepad's state that frame — left stick, right
+//! stick, the right trigger's travel, then the buttons held — and is left out while the pad is
+//! idle. Older replays have no trigger travel, and a right trigger they hold plays back fully
+//! squeezed. Typed text on a key
+//! event is stored as Unicode scalar values so control characters survive. Mouse positions are in
+//! view space (window pixels divided by the view scale); the world-space cursor is rebuilt against
+//! the camera at apply time, exactly as the live handler does.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use ggez::event::Button;
+use ggez::glam::Vec2;
+use ggez::input::keyboard::KeyCode;
+use ggez::input::mouse::MouseButton;
+
+use crate::gamepad::{PadState, button_from_name, button_name};
+use crate::save::SaveData;
+use crate::simulation::InputFrame;
+
+const HEADER: &str = "rustler-replay 1";
+
+/// One discrete input delivered to the game between two simulation frames.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    /// A key press. `code` is `None` for keys the game has no name for; `text` is whatever the
+    /// platform typed for it (name entry reads this).
+    Key {
+        code: Option<KeyCode>,
+        text: Option<String>,
+    },
+    /// Cursor moved to this view-space point.
+    MouseMove(Vec2),
+    MouseDown(MouseButton, Vec2),
+    MouseUp(MouseButton, Vec2),
+    /// A gamepad button press.
+    PadButton(Button),
+}
+
+/// A recorded session: the seed, timestep and save it started from, then every frame's input.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub dt: f32,
+    /// The day it was recorded (`YYYY-MM-DD`); older replays have none and play on today.
+    pub(crate) date: Option<String>,
+    pub(crate) save: SaveData,
+    pub frames: Vec<InputFrame>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, line)) if line.trim() == HEADER => {}
+            _ => return Err(format!("missing `{HEADER}` header")),
+        }
+        let mut replay = Replay::default();
+        let (mut seed, mut dt) = (None, None);
+        let mut save_text = String::new();
+        for (index, line) in lines {
+            let fail = |what: &str| format!("line {}: {what}", index + 1);
+            if let Some(save_line) = line.trim().strip_prefix("save ") {
+                save_text.push_str(save_line);
+                save_text.push('\n');
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts.as_slice() {
+                [] => {}
+                ["seed", value] => seed = Some(value.parse().map_err(|_| fail("bad seed"))?),
+                ["dt", value] => {
+                    dt = Some(
+                        value
+                            .parse::<f32>()
+                            .ok()
+                            .filter(|d| *d > 0.0)
+                            .ok_or_else(|| fail("bad timestep"))?,
+                    )
+                }
+                ["date", value] => replay.date = Some(value.to_string()),
+                ["bind", action, key] => {
+                    if !replay
+                        .save
+                        .bindings
+                        .apply_save_value(&format!("{action} {key}"))
+                    {
+                        return Err(fail("bad binding"));
+                    }
+                }
+                ["f", keys @ ..] => {
+                    let held = keys
+                        .iter()
+                        .map(|name| key_from_name(name).ok_or_else(|| fail("unknown key")))
+                        .collect::<Result<HashSet<_>, _>>()?;
+                    replay.frames.push(InputFrame {
+                        held,
+                        ..InputFrame::default()
+                    });
+                }
+                ["p", lx, ly, rx, ry, rest @ ..] => {
+                    let frame = replay
+                        .frames
+                        .last_mut()
+                        .ok_or_else(|| fail("pad state before the first frame"))?;
+                    let axis = |v: &str| v.parse::<f32>().map_err(|_| fail("bad stick value"));
+                    // Older replays go straight from the sticks to the buttons.
+                    let (travel, buttons) = match rest.split_first() {
+                        Some((travel, buttons)) if travel.parse::<f32>().is_ok() => {
+                            (Some(axis(travel)?), buttons)
+                        }
+                        _ => (None, rest),
+                    };
+                    let held: HashSet<Button> = buttons
+                        .iter()
+                        .map(|name| button_from_name(name).ok_or_else(|| fail("unknown button")))
+                        .collect::<Result<_, _>>()?;
+                    let squeezed = held.contains(&Button::RightTrigger2);
+                    frame.pad = PadState {
+                        left: Vec2::new(axis(lx)?, axis(ly)?),
+                        right: Vec2::new(axis(rx)?, axis(ry)?),
+                        right_trigger: travel.unwrap_or(if squeezed { 1.0 } else { 0.0 }),
+                        held,
+                    };
+                }
+                ["e", event @ ..] => {
+                    let frame = replay
+                        .frames
+                        .last_mut()
+                        .ok_or_else(|| fail("event before the first frame"))?;
+                    frame
+                        .events
+                        .push(parse_event(event).ok_or_else(|| fail("malformed event"))?);
+                }
+                _ => return Err(fail("unrecognised line")),
+            }
+        }
+        replay.seed = seed.ok_or("missing seed")?;
+        replay.dt = dt.ok_or("missing dt")?;
+        if !save_text.is_empty() {
+            replay.save = SaveData::parse(&save_text).map_err(|e| format!("save snapshot: {e}"))?;
+        }
+        Ok(replay)
+    }
+}
+
+/// Streams a live session to disk, one frame at a time. Each frame is flushed as it is written,
+/// so the file is complete up to the last frame even if the game crashes or is killed.
+pub struct Recorder {
+    out: BufWriter<File>,
+    /// Events handled since the last frame was written; they belong to the next one.
+    pending: Vec<InputEvent>,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path, replay: &Replay) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        write_header(&mut out, replay)?;
+        out.flush()?;
+        Ok(Self {
+            out,
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn push_event(&mut self, event: InputEvent) {
+        self.pending.push(event);
+    }
+
+    /// Close the current frame: write the held keys and pad state of `input`, and every event
+    /// since the previous call.
+    pub fn finish_frame(&mut self, input: &InputFrame) {
+        let frame = InputFrame {
+            held: input.held.clone(),
+            pad: input.pad.clone(),
+            events: std::mem::take(&mut self.pending),
+        };
+        if let Err(e) = write_frame(&mut self.out, &frame).and_then(|()| self.out.flush()) {
+            eprintln!("Failed to write replay frame: {e}");
+        }
+    }
+}
+
+/// Everything above the first frame of `replay`.
+fn write_header(out: &mut impl Write, replay: &Replay) -> io::Result<()> {
+    writeln!(out, "{HEADER}")?;
+    writeln!(out, "seed {}", replay.seed)?;
+    writeln!(out, "dt {}", replay.dt)?;
+    if let Some(date) = &replay.date {
+        writeln!(out, "date {date}")?;
+    }
+    for line in replay.save.to_text().lines() {
+        writeln!(out, "save {line}")?;
+    }
+    Ok(())
+}
+
+fn write_frame(out: &mut impl Write, frame: &InputFrame) -> io::Result<()> {
+    write!(out, "f")?;
+    // Sorted so the same input always serialises the same way (HashSet order is per-process).
+    let mut keys: Vec<&str> = frame.held.iter().filter_map(|k| key_name(*k)).collect();
+    keys.sort_unstable();
+    for key in keys {
+        write!(out, " {key}")?;
+    }
+    writeln!(out)?;
+    if !frame.pad.is_neutral() {
+        let (l, r) = (frame.pad.left, frame.pad.right);
+        write!(
+            out,
+            "p {} {} {} {} {}",
+            l.x, l.y, r.x, r.y, frame.pad.right_trigger
+        )?;
+        let mut buttons: Vec<&str> = frame.pad.held.iter().map(|b| button_name(*b)).collect();
+        buttons.sort_unstable();
+        for button in buttons {
+            write!(out, " {button}")?;
+        }
+        writeln!(out)?;
+    }
+    for event in &frame.events {
+        match event {
+            InputEvent::Key { code, text } => {
+                write!(out, "e key {}", code.and_then(key_name).unwrap_or("-"))?;
+                for c in text.iter().flat_map(|t| t.chars()) {
+                    write!(out, " {}", c as u32)?;
+                }
+                writeln!(out)?;
+            }
+            InputEvent::MouseMove(p) => writeln!(out, "e move {} {}", p.x, p.y)?,
+            InputEvent::MouseDown(button, p) => {
+                writeln!(out, "e down {} {} {}", mouse_button_name(*button), p.x, p.y)?
+            }
+            InputEvent::MouseUp(button, p) => {
+                writeln!(out, "e up {} {} {}", mouse_button_name(*button), p.x, p.y)?
+            }
+            InputEvent::PadButton(button) => writeln!(out, "e pad {}", button_name(*button))?,
+        }
+    }
+    Ok(())
+}
+
+fn parse_event(parts: &[&str]) -> Option<InputEvent> {
+    let point = |x: &str, y: &str| Some(Vec2::new(x.parse().ok()?, y.parse().ok()?));
+    match parts {
+        ["key", code, chars @ ..] => {
+            let code = match *code {
+                "-" => None,
+                name => Some(key_from_name(name)?),
+            };
+            let text = if chars.is_empty() {
+                None
+            } else {
+                Some(
+                    chars
+                        .iter()
+                        .map(|c| char::from_u32(c.parse().ok()?))
+                        .collect::<Option<String>>()?,
+                )
+            };
+            Some(InputEvent::Key { code, text })
+        }
+        ["move", x, y] => Some(InputEvent::MouseMove(point(x, y)?)),
+        ["down", button, x, y] => Some(InputEvent::MouseDown(
+            mouse_button_from_name(button)?,
+            point(x, y)?,
+        )),
+        ["up", button, x, y] => Some(InputEvent::MouseUp(
+            mouse_button_from_name(button)?,
+            point(x, y)?,
+        )),
+        ["pad", button] => Some(InputEvent::PadButton(button_from_name(button)?)),
+        _ => None,
+    }
+}
+
+fn mouse_button_name(button: MouseButton) -> String {
+    match button {
+        MouseButton::Left => "Left".into(),
+        MouseButton::Right => "Right".into(),
+        MouseButton::Middle => "Middle".into(),
+        MouseButton::Back => "Back".into(),
+        MouseButton::Forward => "Forward".into(),
+        MouseButton::Other(n) => format!("Other{n}"),
+    }
+}
+
+fn mouse_button_from_name(name: &str) -> Option<MouseButton> {
+    Some(match name {
+        "Left" => MouseButton::Left,
+        "Right" => MouseButton::Right,
+        "Middle" => MouseButton::Middle,
+        "Back" => MouseButton::Back,
+        "Forward" => MouseButton::Forward,
+        other => MouseButton::Other(other.strip_prefix("Other")?.parse().ok()?),
+    })
+}
+
+/// Both directions of the key-name table from one list, so they can't drift apart. The names are
+/// the winit variant names (what `{:?}` prints), which keeps replay files readable.
+macro_rules! key_names {
+    ($($key:ident),* $(,)?) => {
+        /// The stable name of a key, or `None` for keys outside the table (the game binds none).
+        pub(crate) fn key_name(code: KeyCode) -> Option<&'static str> {
+            match code {
+                $(KeyCode::$key => Some(stringify!($key)),)*
+                _ => None,
+            }
+        }
+
+        pub(crate) fn key_from_name(name: &str) -> Option<KeyCode> {
+            match name {
+                $(stringify!($key) => Some(KeyCode::$key),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+key_names!(
+    Backquote,
+    Backslash,
+    BracketLeft,
+    BracketRight,
+    Comma,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Equal,
+    Minus,
+    Period,
+    Quote,
+    Semicolon,
+    Slash,
+    KeyA,
+    KeyB,
+    KeyC,
+    KeyD,
+    KeyE,
+    KeyF,
+    KeyG,
+    KeyH,
+    KeyI,
+    KeyJ,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyQ,
+    KeyR,
+    KeyS,
+    KeyT,
+    KeyU,
+    KeyV,
+    KeyW,
+    KeyX,
+    KeyY,
+    KeyZ,
+    AltLeft,
+    AltRight,
+    Backspace,
+    CapsLock,
+    ControlLeft,
+    ControlRight,
+    Enter,
+    ShiftLeft,
+    ShiftRight,
+    SuperLeft,
+    SuperRight,
+    Space,
+    Tab,
+    Delete,
+    End,
+    Home,
+    Insert,
+    PageDown,
+    PageUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadDecimal,
+    NumpadDivide,
+    NumpadEnter,
+    NumpadMultiply,
+    NumpadSubtract,
+    Escape,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Replay {
+        let mut save = SaveData {
+            start_lasso_rank: 2,
+            ..SaveData::default()
+        };
+        save.bindings
+            .rebind(crate::bindings::Action::Whistle, KeyCode::KeyL)
+            .unwrap();
+        Replay {
+            seed: u64::MAX - 7,
+            dt: 1.0 / 60.0,
+            date: Some("2026-10-18".into()),
+            save,
+            frames: vec![
+                InputFrame {
+                    held: [KeyCode::KeyW, KeyCode::ShiftLeft].into_iter().collect(),
+                    events: vec![
+                        InputEvent::Key {
+                            code: Some(KeyCode::KeyQ),
+                            text: Some("q".into()),
+                        },
+                        InputEvent::Key {
+                            code: None,
+                            text: Some("é\u{8}".into()),
+                        },
+                        InputEvent::MouseMove(Vec2::new(640.5, 480.0)),
+                    ],
+                    ..InputFrame::default()
+                },
+                InputFrame::default(),
+                InputFrame {
+                    held: HashSet::new(),
+                    pad: PadState {
+                        left: Vec2::new(0.8, -0.125),
+                        right: Vec2::ZERO,
+                        right_trigger: 0.875,
+                        held: [Button::West, Button::RightTrigger2].into_iter().collect(),
+                    },
+                    events: vec![
+                        InputEvent::PadButton(Button::South),
+                        InputEvent::MouseDown(MouseButton::Left, Vec2::new(0.1, 959.9)),
+                        InputEvent::MouseUp(MouseButton::Other(4), Vec2::new(3.0, 4.0)),
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn replay_file_round_trips() {
+        let replay = sample();
+        let mut text = Vec::new();
+        write_header(&mut text, &replay).unwrap();
+        for frame in &replay.frames {
+            write_frame(&mut text, frame).unwrap();
+        }
+        let parsed = Replay::parse(std::str::from_utf8(&text).unwrap()).unwrap();
+        assert_eq!(parsed, replay);
+    }
+
+    #[test]
+    fn replays_without_a_save_snapshot_keep_their_bindings() {
+        let text = "rustler-replay 1\nseed 1\ndt 0.1\nbind whistle KeyL\nf\n";
+        let replay = Replay::parse(text).unwrap();
+        assert_eq!(
+            replay.save.bindings.key(crate::bindings::Action::Whistle),
+            KeyCode::KeyL
+        );
+        assert_eq!(replay.save.start_lasso_rank, 0);
+    }
+
+    #[test]
+    fn replays_without_trigger_travel_hold_the_trigger_fully_down() {
+        let text = "rustler-replay 1\nseed 1\ndt 0.1\nf\np 0 0 0 0 RightTrigger2\nf\np 1 0 0 0\n";
+        let replay = Replay::parse(text).unwrap();
+        assert_eq!(replay.frames[0].pad.right_trigger, 1.0);
+        assert_eq!(replay.frames[1].pad.right_trigger, 0.0);
+    }
+
+    #[test]
+    fn malformed_replays_are_rejected() {
+        assert!(Replay::parse("seed 1\ndt 0.1\n").is_err());
+        assert!(Replay::parse("rustler-replay 1\ndt 0.1\nf\n").is_err());
+        assert!(Replay::parse("rustler-replay 1\nseed 1\ndt 0.1\ne move 1 2\n").is_err());
+        assert!(Replay::parse("rustler-replay 1\nseed 1\ndt 0.1\nf KeyNope\n").is_err());
+        assert!(Replay::parse("rustler-replay 1\nseed 1\ndt 0.1\nbind dash Escape\n").is_err());
+        assert!(Replay::parse("rustler-replay 1\nseed 1\ndt 0.1\nf\np 0 0 0 0 Turbo\n").is_err());
+        assert!(Replay::parse("rustler-replay 1\nseed 1\ndt 0.1\nsave runs 3\n").is_err());
+    }
+}
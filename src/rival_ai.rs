@@ -0,0 +1,184 @@
+//! Personality-driven rival AI: the personality a rival's name implies (see rival_taunts.rs) bends
+//! how it plays, on top of the scout/wanderer/elder tier.
+//!
+//! - **Pirate** wanders from one kelp bed or rock to the next and, hidden in one, springs its strike
+//!   on a player who strays inside [`PIRATE_AMBUSH_RANGE`] instead of stalking first. On a beach
+//!   without cover it plays like anyone else.
+//! - **Pompous** only hunts, or steals from, a train smaller than its own.
+//! - **Raver** is drawn by the player's groove rather than their slips, commits its strikes and
+//!   snaps its steals on the downbeat.
+//! - **Serious** keeps to its territory and only hunts a player whose train strays within
+//!   [`SERIOUS_GUARD_RADIUS`] of its pen, committing at once.
+//! - **Silly** never hunts. It wanders erratically and steals from whatever it blunders into, from
+//!   farther away than anyone else.
+//!
+//! The hooks themselves live in `update_npc_trains`; this module holds the rules they ask. Each
+//! personality's signature move bumps [`MainState::rival_signatures`] so the bot scenarios can prove
+//! it fired.
+
+use ggez::glam::Vec2;
+
+use crate::constants::*;
+use crate::levels::TerrainKind;
+use crate::npc_conga_train::NpcCongaTrain;
+use crate::rival_taunts::{RivalPersonality, personality_for};
+use crate::state::MainState;
+
+impl RivalPersonality {
+    /// Whether a rival with `own` followers steals from a train of `prey` links it runs into.
+    pub(crate) fn will_steal(self, prey: usize, own: usize) -> bool {
+        self != RivalPersonality::Pompous || prey < own
+    }
+
+    /// Whether a rival with `own` followers goes out of its way to hunt a train of `prey` links.
+    pub(crate) fn will_hunt(self, prey: usize, own: usize) -> bool {
+        self != RivalPersonality::Silly && self.will_steal(prey, own)
+    }
+
+    /// How far it arms a steal from, as a multiple of the usual range.
+    pub(crate) fn steal_reach(self) -> f32 {
+        if self == RivalPersonality::Silly {
+            SILLY_STEAL_REACH
+        } else {
+            1.0
+        }
+    }
+
+    /// The callout when it commits to a strike on the player.
+    pub(crate) fn strike_callout(self, name: &str, sprung: bool) -> String {
+        match self {
+            RivalPersonality::Pirate if sprung => format!("☠ {name} springs an ambush!"),
+            RivalPersonality::Serious if sprung => format!("⚠ {name}: off my beach!"),
+            _ => format!("⚠ {name} is hunting you!"),
+        }
+    }
+}
+
+impl NpcCongaTrain {
+    pub fn personality(&self) -> RivalPersonality {
+        personality_for(&self.name)
+    }
+}
+
+impl MainState {
+    /// The kelp beds or rocks a Pirate can hide in: the biome's native patches on a kelp or rock
+    /// beach, none elsewhere.
+    fn cover(&self) -> &[(Vec2, f32)] {
+        if !matches!(
+            self.current_terrain(),
+            TerrainKind::Kelp | TerrainKind::Rock
+        ) {
+            return &[];
+        }
+        let native = self.tide_pools.len().saturating_sub(self.boss_flood_pools);
+        &self.tide_pools[..native]
+    }
+
+    pub(crate) fn in_cover(&self, pos: Vec2) -> bool {
+        self.cover()
+            .iter()
+            .any(|(c, r)| pos.distance_squared(*c) < *r * *r)
+    }
+
+    /// The centre of the cover patch nearest `pos`, if the beach has any.
+    pub(crate) fn nearest_cover(&self, pos: Vec2) -> Option<Vec2> {
+        self.cover().iter().map(|(c, _)| *c).min_by(|a, b| {
+            a.distance_squared(pos)
+                .partial_cmp(&b.distance_squared(pos))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// Bend a freshly picked wander target to `personality`: a Pirate makes for the nearest cover,
+    /// a Serious rival keeps close to its pen.
+    pub(crate) fn personal_wander_target(
+        &self,
+        personality: RivalPersonality,
+        territory_center: Vec2,
+        target: Vec2,
+    ) -> Vec2 {
+        match personality {
+            RivalPersonality::Pirate => self.nearest_cover(target).unwrap_or(target),
+            RivalPersonality::Serious => territory_center.lerp(target, 0.4),
+            _ => target,
+        }
+    }
+
+    /// Whether a Serious rival at `territory_center` counts the player's train as an intruder.
+    pub(crate) fn intruding(&self, territory_center: Vec2) -> bool {
+        let player_center = self.player_pos + Vec2::splat(PLAYER_SIZE / 2.0);
+        [Some(player_center), self.cached_tail_pos]
+            .into_iter()
+            .flatten()
+            .any(|pos| pos.distance(territory_center) < SERIOUS_GUARD_RADIUS)
+    }
+
+    /// Count a signature move by a `personality` rival.
+    pub(crate) fn note_signature(&mut self, personality: RivalPersonality) {
+        self.rival_signatures[personality as usize] =
+            self.rival_signatures[personality as usize].saturating_add(1);
+    }
+
+    /// Signature moves by `personality` rivals so far.
+    pub(crate) fn signatures(&self, personality: RivalPersonality) -> usize {
+        self.rival_signatures[personality as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pompous_rivals_only_pick_on_smaller_trains() {
+        assert!(RivalPersonality::Pompous.will_hunt(3, 4));
+        assert!(!RivalPersonality::Pompous.will_hunt(4, 4));
+        assert!(RivalPersonality::Pirate.will_hunt(9, 2));
+        assert!(!RivalPersonality::Silly.will_hunt(1, 9));
+        assert!(RivalPersonality::Silly.steal_reach() > RivalPersonality::Plain.steal_reach());
+    }
+
+    #[test]
+    fn a_pompous_snub_counts_once_however_long_it_lasts() {
+        let mut state = MainState::new_headless();
+        state.reset_game();
+        state.show_instructions = false;
+        state.force_personality(RivalPersonality::Pompous);
+        // Leave the forced rival alone on the beach, so only its snub can count.
+        state
+            .npc_trains
+            .retain(|t| t.name == "Gravelord Brinewraith");
+        for _ in 0..120 {
+            state.step(crate::constants::SIM_DT).unwrap();
+        }
+        assert_eq!(state.signatures(RivalPersonality::Pompous), 1);
+    }
+
+    #[test]
+    fn pirates_head_for_cover_only_where_there_is_some() {
+        let mut state = MainState::new_headless();
+        state.reset_game();
+        assert_eq!(state.current_terrain(), TerrainKind::Open);
+        let target = Vec2::new(500.0, 500.0);
+        let home = Vec2::new(100.0, 100.0);
+        let pirate = RivalPersonality::Pirate;
+        assert_eq!(state.personal_wander_target(pirate, home, target), target);
+
+        let kelp = state
+            .levels
+            .iter()
+            .position(|l| l.biome.terrain == TerrainKind::Kelp)
+            .unwrap();
+        state.current_level = kelp;
+        state.tide_pools = vec![
+            (Vec2::new(900.0, 300.0), 80.0),
+            (Vec2::new(450.0, 520.0), 80.0),
+        ];
+        state.boss_flood_pools = 0;
+        let hideout = state.personal_wander_target(pirate, home, target);
+        assert_eq!(hideout, Vec2::new(450.0, 520.0));
+        assert!(state.in_cover(hideout));
+        let serious = state.personal_wander_target(RivalPersonality::Serious, home, target);
+        assert!(serious.distance(home) < target.distance(home));
+    }
+}
@@ -0,0 +1,118 @@
+//! Rival economy: rival trains bank too.
+//!
+//! A rival whose train grows to [`RIVAL_HAUL_AT`] followers stops hunting and hauls it home to its
+//! own pen at `territory_center`, slower than it usually moves. Inside [`RIVAL_PEN_RADIUS`] it banks
+//! every follower for its score and sets out again with [`RIVAL_RESTART_FOLLOWERS`]. So a rival is
+//! worth the most, and is easiest to catch, on the way home: threading a hauling train is a raid,
+//! and the steal-back pays [`HAUL_RAID_BONUS`] more per crab (see `update_npc_trains`). The HUD
+//! keeps the rivals' banked scores beside the player's.
+
+use ggez::glam::Vec2;
+
+use crate::constants::*;
+use crate::event_log::GameEvent;
+use crate::state::MainState;
+
+impl MainState {
+    /// Start, steer and finish rival `i`'s haul home. Called for each train at the top of
+    /// `update_npc_trains`, before the train picks or steers toward a target.
+    pub(crate) fn update_rival_haul(&mut self, i: usize) {
+        let train = &mut self.npc_trains[i];
+        if train.follower_types.len() <= RIVAL_RESTART_FOLLOWERS {
+            // Raided down to nothing worth banking: back to the hunt.
+            train.hauling = false;
+            return;
+        }
+        if !train.hauling {
+            if train.follower_types.len() < RIVAL_HAUL_AT {
+                return;
+            }
+            train.hauling = true;
+            train.idle_timer = 0.0;
+            let label = format!("{} is hauling home!", train.name);
+            let at = train.leader_pos - Vec2::new(90.0, 60.0);
+            self.floating_texts
+                .spawn(label, at, 22.0, [1.0, 0.78, 0.3, 1.0]);
+            return;
+        }
+        // Beeline for the pen. Holding the fallback timer up keeps the wander pick from
+        // retargeting mid-haul; the bank below lands before the 80 px arrival check would.
+        let pen = train.territory_center;
+        train.target = pen;
+        train.target_timer = train.target_timer.max(5.0);
+        if train.leader_pos.distance(pen) > RIVAL_PEN_RADIUS {
+            return;
+        }
+        let banked = train.follower_types.len() - RIVAL_RESTART_FOLLOWERS;
+        train.follower_types.truncate(RIVAL_RESTART_FOLLOWERS);
+        train.rival_score += banked;
+        train.hauling = false;
+        train.idle_timer = 1.5;
+        let name = train.name.clone();
+        let at = train.leader_pos - Vec2::new(90.0, 60.0);
+        self.rival_crabs_banked += banked;
+        self.floating_texts.spawn(
+            format!("{name} banked {banked}!"),
+            at,
+            24.0,
+            [1.0, 0.78, 0.3, 1.0],
+        );
+        self.log_event(GameEvent::RivalBank {
+            rival: name,
+            crabs: banked,
+        });
+    }
+
+    /// The rivals that have banked this run, best first, as `(name, score)`.
+    pub(crate) fn rival_standings(&self) -> Vec<(&str, usize)> {
+        let mut standings: Vec<_> = self
+            .npc_trains
+            .iter()
+            .filter(|train| train.rival_score > 0)
+            .map(|train| (train.name.as_str(), train.rival_score))
+            .collect();
+        standings.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemies::CrabType;
+    use crate::npc_conga_train::NpcCongaTrain;
+
+    #[test]
+    fn a_full_train_hauls_home_and_banks() {
+        let mut state = MainState::new_headless();
+        state.reset_game();
+        state.npc_trains = vec![NpcCongaTrain::new_at(
+            state.world_width,
+            state.world_height,
+            0,
+        )];
+        let pen = state.npc_trains[0].territory_center;
+        state.npc_trains[0].follower_types = vec![CrabType::Normal; RIVAL_HAUL_AT - 1];
+        state.update_rival_haul(0);
+        assert!(!state.npc_trains[0].hauling);
+
+        state.npc_trains[0].follower_types.push(CrabType::Golden);
+        state.update_rival_haul(0);
+        assert!(state.npc_trains[0].hauling);
+        state.update_rival_haul(0);
+        assert_eq!(state.npc_trains[0].target, pen);
+
+        state.npc_trains[0].leader_pos = pen + Vec2::new(RIVAL_PEN_RADIUS - 1.0, 0.0);
+        state.update_rival_haul(0);
+        let train = &state.npc_trains[0];
+        assert!(!train.hauling);
+        assert_eq!(train.follower_types.len(), RIVAL_RESTART_FOLLOWERS);
+        assert_eq!(train.rival_score, RIVAL_HAUL_AT - RIVAL_RESTART_FOLLOWERS);
+        assert_eq!(state.rival_crabs_banked, train.rival_score);
+        let name = train.name.clone();
+        assert_eq!(
+            state.rival_standings(),
+            [(name.as_str(), train.rival_score)]
+        );
+    }
+}
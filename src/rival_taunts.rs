@@ -1,9 +1,13 @@
 //! Original post-clash quips for rival King Crabs, grouped by the personality implied by their name.
+//! The same personality drives how the rival plays (see rival_ai.rs). A rival with a history
+//! (see nemesis.rs) adds a callback to it, sharper the deeper the grudge runs.
 
 use rand::prelude::IndexedRandom;
 
+use crate::nemesis::Nemesis;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum RivalPersonality {
+pub enum RivalPersonality {
     Plain,
     Pirate,
     Pompous,
@@ -12,7 +16,34 @@ enum RivalPersonality {
     Silly,
 }
 
-fn personality_for(name: &str) -> RivalPersonality {
+impl RivalPersonality {
+    pub const ALL: [RivalPersonality; 6] = [
+        RivalPersonality::Plain,
+        RivalPersonality::Pirate,
+        RivalPersonality::Pompous,
+        RivalPersonality::Raver,
+        RivalPersonality::Serious,
+        RivalPersonality::Silly,
+    ];
+
+    /// The name bot scripts use for it.
+    pub fn label(self) -> &'static str {
+        match self {
+            RivalPersonality::Plain => "plain",
+            RivalPersonality::Pirate => "pirate",
+            RivalPersonality::Pompous => "pompous",
+            RivalPersonality::Raver => "raver",
+            RivalPersonality::Serious => "serious",
+            RivalPersonality::Silly => "silly",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.label() == label)
+    }
+}
+
+pub(crate) fn personality_for(name: &str) -> RivalPersonality {
     if [
         "Gravelord",
         "Devourer",
@@ -234,13 +265,80 @@ const LAST_LINK: &[&str] = &[
     "Your last follower looks nervous!",
 ];
 
-/// Sample a quip matching both the rival's generated name and the result of the clash.
+// Callbacks by grudge (`Nemesis::grudge`), 1 to 3. `{wins}`, `{stolen}`, `{met}` and `{defeats}`
+// are filled in from the history; a line only comes up when the counts it quotes aren't zero.
+const REMATCH: &[&str] = &[
+    "Back again? I remember you.",
+    "Oh, it's you. Again.",
+    "We've clashed before, haven't we?",
+];
+
+const RIVALRY: &[&str] = &[
+    "That's {wins} clashes to me now.",
+    "{stolen} of your crabs, and counting.",
+    "Beach number {met}. Same result.",
+];
+
+const NEMESIS: &[&str] = &[
+    "{wins} wins. I should charge admission.",
+    "{stolen} of your crabs so far. I named every one.",
+    "{met} beaches, and you still charge off the beat.",
+];
+
+const PAYBACK: &[&str] = &[
+    "That's for stripping me bare!",
+    "Payback, {defeats} times over!",
+];
+
+/// The callback to a nemesis's history, if it has one worth mentioning.
+fn grudge_callback(history: &Nemesis, rng: &mut impl rand::Rng) -> Option<String> {
+    let pool = match history.grudge() {
+        0 => return None,
+        1 => REMATCH,
+        2 => RIVALRY,
+        _ => NEMESIS,
+    };
+    // One in three callbacks settles the score for the times it was stripped bare.
+    let pool = if history.defeats > 0 && rng.random_ratio(1, 3) {
+        PAYBACK
+    } else {
+        pool
+    };
+    let fits: Vec<&str> = pool
+        .iter()
+        .copied()
+        .filter(|line| quotes_only_nonzero(line, history))
+        .collect();
+    let line = fits.choose(rng).or_else(|| REMATCH.choose(rng))?;
+    Some(
+        line.replace("{wins}", &history.wins.to_string())
+            .replace("{stolen}", &history.stolen.to_string())
+            .replace("{met}", &history.met.to_string())
+            .replace("{defeats}", &history.defeats.to_string()),
+    )
+}
+
+/// Whether every count `line` quotes is non-zero: a rival that has never won doesn't boast of
+/// "0 clashes".
+fn quotes_only_nonzero(line: &str, history: &Nemesis) -> bool {
+    [
+        ("{wins}", history.wins),
+        ("{stolen}", history.stolen),
+        ("{defeats}", history.defeats),
+    ]
+    .iter()
+    .all(|&(key, count)| count > 0 || !line.contains(key))
+}
+
+/// Sample a quip matching both the rival's generated name and the result of the clash, followed by
+/// a callback to `history` when the rival is a nemesis.
 pub(crate) fn clash_taunt(
     name: &str,
     crabs_lost: usize,
     remaining_crabs: usize,
+    history: Option<&Nemesis>,
     rng: &mut impl rand::Rng,
-) -> &'static str {
+) -> String {
     let pool = if crabs_lost == 0 {
         EMPTY_TRAIN
     } else if remaining_crabs <= 1 {
@@ -255,9 +353,14 @@ pub(crate) fn clash_taunt(
             RivalPersonality::Silly => SILLY,
         }
     };
-    pool.choose(rng)
+    let quip = pool
+        .choose(rng)
         .copied()
-        .expect("rival taunt pools must not be empty")
+        .expect("rival taunt pools must not be empty");
+    match history.and_then(|history| grudge_callback(history, rng)) {
+        Some(callback) => format!("{quip} {callback}"),
+        None => quip.to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -283,9 +386,47 @@ mod tests {
     #[test]
     fn clash_result_overrides_personality_when_the_train_is_empty_or_nearly_empty() {
         let mut rng = crate::rng::rng();
-        let empty = clash_taunt("DJ Bassline", 0, 0, &mut rng);
-        let last = clash_taunt("DJ Bassline", 2, 1, &mut rng);
-        assert!(EMPTY_TRAIN.contains(&empty));
-        assert!(LAST_LINK.contains(&last));
+        let empty = clash_taunt("DJ Bassline", 0, 0, None, &mut rng);
+        let last = clash_taunt("DJ Bassline", 2, 1, None, &mut rng);
+        assert!(EMPTY_TRAIN.contains(&empty.as_str()));
+        assert!(LAST_LINK.contains(&last.as_str()));
+    }
+
+    #[test]
+    fn nemesis_taunts_escalate_with_the_history() {
+        let mut rng = crate::rng::rng();
+        let mut roster = crate::nemesis::NemesisRoster::default();
+        roster.record("Kevin", 0, crate::nemesis::Encounter::Won);
+        let first = clash_taunt("Kevin", 3, 4, roster.get("Kevin"), &mut rng);
+        assert!(PLAIN.contains(&first.as_str()));
+
+        for _ in 0..12 {
+            roster.record("Kevin", 0, crate::nemesis::Encounter::Won);
+        }
+        let nemesis = roster.get("Kevin").unwrap();
+        assert_eq!(nemesis.grudge(), 3);
+        let taunt = clash_taunt("Kevin", 3, 4, Some(nemesis), &mut rng);
+        let (quip, callback) = PLAIN
+            .iter()
+            .find_map(|q| Some((*q, taunt.strip_prefix(q)?.trim())))
+            .unwrap();
+        assert!(PLAIN.contains(&quip));
+        assert!(!callback.is_empty() && !callback.contains('{'));
+    }
+
+    #[test]
+    fn callbacks_never_quote_a_zero_count() {
+        let mut rng = crate::rng::rng();
+        let mut roster = crate::nemesis::NemesisRoster::default();
+        // Stripped bare a dozen times: a nemesis that has never won or stolen anything.
+        for _ in 0..12 {
+            roster.record("Kevin", 0, crate::nemesis::Encounter::Stripped);
+        }
+        let nemesis = roster.get("Kevin").unwrap();
+        assert_eq!((nemesis.wins, nemesis.stolen, nemesis.grudge()), (0, 0, 3));
+        for _ in 0..200 {
+            let callback = grudge_callback(nemesis, &mut rng).unwrap();
+            assert!(!callback.split(' ').any(|word| word == "0"), "{callback}");
+        }
     }
 }
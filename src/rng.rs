@@ -9,7 +9,8 @@
 //! Real gameplay is untouched: when the process is NOT a seeded bot run, [`rng`] falls straight
 //! through to `rand::rng()` (the per-thread entropy RNG), so interactive play stays as random as
 //! ever. In bot mode `main` calls [`seed`] once at startup and every `crate::rng::rng()` call
-//! then draws from one deterministic `SmallRng` stream instead.
+//! then draws from one deterministic `SmallRng` stream instead. A daily challenge (see daily.rs)
+//! seeds the same way for the length of its run and [`unseed`]s when the player leaves it.
 //!
 //! Usage is a drop-in swap: replace `rand::rng()` with `crate::rng::rng()`. The returned
 //! [`GameRng`] is a zero-sized proxy that implements `rand::RngCore` (and therefore `Rng` and the
@@ -33,6 +34,17 @@ pub fn seed(seed: u64) {
     GAME_RNG.with(|cell| *cell.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
 }
 
+/// Drop the deterministic stream so draws fall back to `rand::rng()` again. Used when a daily
+/// challenge ends in a process that wasn't seeded at startup.
+pub fn unseed() {
+    GAME_RNG.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Whether a deterministic stream is installed.
+pub fn is_seeded() -> bool {
+    GAME_RNG.with(|cell| cell.borrow().is_some())
+}
+
 /// A zero-sized handle to the process RNG. In a seeded bot run it delegates to the deterministic
 /// `SmallRng`; otherwise it delegates to `rand::rng()`. Because it implements `RngCore`, it is a
 /// drop-in replacement for the `rand::rng()` handle everywhere in the codebase.
@@ -0,0 +1,648 @@
+//! The player's save: career ledger, best time, cosmetic loadout, name and campaign progress.
+//!
+//! Lives in the per-user data directory (ggez's `user_data_dir`, e.g.
+//! `~/.local/share/rustler` on Linux) as `save.txt`, so launching the game from a different
+//! working directory still finds it. The format is line-based `key value` text under a versioned
+//! header:
+//!
+//! ```text
+//! rustler-save 2
+//! best_score 412
+//! total_score 3310
+//! runs 27
+//! ...
+//! skin Cowboy Mustache StarBadge
+//! name Crabby McCrabface
+//! node tutorial-beat completed - 41.2
+//! node sunny-meadow completed 412 73.5
+//! node tide-pools unlocked - -
+//! bind move_up KeyZ
+//! daily 2026-10-18
+//! board sunny-meadow 412 - 31 73.5 2026-10-18 Cowboy Mustache StarBadge Crabby McCrabface
+//! rival 2 14 3 1 5 Cap'n Clawbeard
+//! ```
+//!
+//! `node` lines carry world-map progress: the node's id (`WorldMapNode::id`), `completed` /
+//! `unlocked`, then its best score and best goal time (`-` when it has none). `bind` lines move an
+//! action off its default key (see bindings.rs). `daily` is the date of the last scored daily
+//! challenge (see daily.rs). `board` lines are the high-score tables, each best first: the board id
+//! (`arcade`, `endless`, `daily` or a node id), score, stage reached (`-` on a node's table),
+//! longest train, time, date, skin, then the name (see leaderboard.rs). `rival` lines are the
+//! nemesis roster, most notorious first: tier, crabs stolen, clashes won, times stripped bare, runs
+//! met, then the name (see nemesis.rs).
+//!
+//! Missing keys take their default and unknown keys are ignored, so adding a field is
+//! backward-compatible without a version bump; bump [`SAVE_VERSION`] only when an existing key
+//! changes meaning, and teach [`SaveData::parse`] to migrate the old one. Version 2 keys `node` and
+//! `board` lines by the level file's `id` instead of its `stage-N` position; a version 1 save has
+//! those ids carried over as it loads.
+//!
+//! **Writes are atomic:** the new contents go to a sibling temp file, synced to disk, that is then
+//! renamed over the save (and the directory synced where the platform allows), so a crash or power
+//! cut mid-write leaves the previous save intact rather than a truncated one.
+//!
+//! **Nothing is silently reset.** A line with a bad value is skipped with a warning on stderr and
+//! the rest of the save still loads; the file is first copied to `save.skipped-<unix time>.txt`,
+//! since the next save drops the line. A save that can't be read at all (no header, or written by a
+//! newer build) is renamed aside to `save.corrupt-<unix time>.txt` before the game starts over
+//! with a fresh career, so the player — or a bug report — still has it. If the file can't even be
+//! opened, or can't be set aside, the session runs without saving rather than overwrite it.
+//!
+//! **Legacy migration:** builds before this one wrote `career.txt` and `best_time.txt` into the
+//! working directory. On first launch with no `save.txt`, those are read from the working directory
+//! (if present) and carried over into a new save. The old files are left where they are.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::bindings::Bindings;
+use crate::constants::MAX_START_RANK;
+use crate::leaderboard::Leaderboards;
+use crate::nemesis::NemesisRoster;
+use crate::skins::PlayerSkin;
+use crate::world_map::NodeProgress;
+
+/// Bump when an existing key changes meaning (see the module docs).
+pub(crate) const SAVE_VERSION: u32 = 2;
+
+const HEADER: &str = "rustler-save";
+const SAVE_FILE: &str = "save.txt";
+const LEGACY_CAREER_FILE: &str = "career.txt";
+const LEGACY_BEST_TIME_FILE: &str = "best_time.txt";
+
+/// Everything that persists between launches.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SaveData {
+    pub best_score: usize,
+    pub total_score: usize,
+    pub runs: usize,
+    pub spent: usize,
+    pub start_beam_rank: u32,
+    pub start_lasso_rank: u32,
+    pub start_whistle_rank: u32,
+    pub start_stomp_rank: u32,
+    /// Fastest time to catch all crabs; `None` until one is set.
+    pub best_time: Option<f32>,
+    pub player_skin: PlayerSkin,
+    pub player_name: String,
+    /// Campaign world-map progress; nodes never reached are simply absent.
+    pub map_progress: Vec<NodeProgress>,
+    pub bindings: Bindings,
+    /// The per-mode and per-node high-score tables.
+    pub leaderboards: Leaderboards,
+    /// The date of the last scored daily challenge.
+    pub daily_played: Option<String>,
+    /// The rivals that remember the player.
+    pub rivals: NemesisRoster,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            best_score: 0,
+            total_score: 0,
+            runs: 0,
+            spent: 0,
+            start_beam_rank: 0,
+            start_lasso_rank: 0,
+            start_whistle_rank: 0,
+            start_stomp_rank: 0,
+            best_time: None,
+            player_skin: PlayerSkin::default_skin(),
+            player_name: "Crabby".to_string(),
+            map_progress: Vec::new(),
+            bindings: Bindings::default(),
+            leaderboards: Leaderboards::default(),
+            daily_played: None,
+            rivals: NemesisRoster::default(),
+        }
+    }
+}
+
+impl SaveData {
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("{HEADER} {SAVE_VERSION}"),
+            format!("best_score {}", self.best_score),
+            format!("total_score {}", self.total_score),
+            format!("runs {}", self.runs),
+            format!("spent {}", self.spent),
+            format!("start_beam_rank {}", self.start_beam_rank),
+            format!("start_lasso_rank {}", self.start_lasso_rank),
+            format!("start_whistle_rank {}", self.start_whistle_rank),
+            format!("start_stomp_rank {}", self.start_stomp_rank),
+        ];
+        if let Some(best_time) = self.best_time {
+            lines.push(format!("best_time {best_time}"));
+        }
+        lines.push(self.player_skin.to_save_line());
+        lines.push(format!(
+            "name {}",
+            crate::normalize_player_name(&self.player_name)
+        ));
+        for node in &self.map_progress {
+            let state = if node.completed {
+                "completed"
+            } else if node.unlocked {
+                "unlocked"
+            } else {
+                "locked"
+            };
+            let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+            lines.push(format!(
+                "node {} {state} {} {}",
+                node.id,
+                or_dash(node.best_score.map(|s| s.to_string())),
+                or_dash(node.best_time.map(|t| t.to_string())),
+            ));
+        }
+        for (action, key) in self.bindings.overrides() {
+            if let Some(name) = crate::replay::key_name(key) {
+                lines.push(format!("bind {} {name}", action.id()));
+            }
+        }
+        if let Some(date) = &self.daily_played {
+            lines.push(format!("daily {date}"));
+        }
+        lines.extend(self.leaderboards.save_lines());
+        lines.extend(self.rivals.save_lines());
+        lines.join("\n") + "\n"
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Self::parse_counting(text).map(|(data, _)| data)
+    }
+
+    /// [`SaveData::parse`], also counting the lines it had to skip.
+    fn parse_counting(text: &str) -> Result<(Self, usize), String> {
+        let mut lines = text.lines();
+        let version = lines
+            .next()
+            .and_then(|l| l.trim().strip_prefix(HEADER))
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or("not a save file")?;
+        if version > SAVE_VERSION {
+            return Err(format!(
+                "written by a newer version of the game (save version {version})"
+            ));
+        }
+        let mut data = SaveData::default();
+        let mut skipped = 0;
+        for line in lines {
+            let mut line = line.trim().to_string();
+            if version < 2 {
+                line = migrate_stage_id(&line);
+            }
+            let line = line.as_str();
+            if let Err(e) = data.read_line(line) {
+                eprintln!("Skipping save line {line:?}: {e}");
+                skipped += 1;
+            }
+        }
+        Ok((data, skipped))
+    }
+
+    /// Apply one `key value` line. A bad value leaves the field as it was and is reported, so
+    /// one garbled line costs only itself (see the module docs).
+    fn read_line(&mut self, line: &str) -> Result<(), String> {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let bad = || format!("bad value for `{key}`: {value:?}");
+        let count = || value.parse::<usize>().map_err(|_| bad());
+        let rank = || {
+            value
+                .parse::<u32>()
+                .map(|r| r.min(MAX_START_RANK))
+                .map_err(|_| bad())
+        };
+        match key {
+            "best_score" => self.best_score = count()?,
+            "total_score" => self.total_score = count()?,
+            "runs" => self.runs = count()?,
+            "spent" => self.spent = count()?,
+            "start_beam_rank" => self.start_beam_rank = rank()?,
+            "start_lasso_rank" => self.start_lasso_rank = rank()?,
+            "start_whistle_rank" => self.start_whistle_rank = rank()?,
+            "start_stomp_rank" => self.start_stomp_rank = rank()?,
+            "best_time" => self.best_time = Some(value.parse().map_err(|_| bad())?),
+            "skin" => self.player_skin = PlayerSkin::from_save_line(line),
+            "name" => self.player_name = crate::normalize_player_name(value),
+            "node" => self.map_progress.push(parse_node(value).ok_or_else(bad)?),
+            "bind" => self
+                .bindings
+                .apply_save_value(value)
+                .then_some(())
+                .ok_or_else(bad)?,
+            "daily" => self.daily_played = Some(value.to_string()),
+            "board" => self.leaderboards.parse_save_value(value).ok_or_else(bad)?,
+            "rival" => self.rivals.parse_save_value(value).ok_or_else(bad)?,
+            // The endless table as it was saved before there was one table per mode.
+            "endless" => self
+                .leaderboards
+                .parse_legacy_endless(value)
+                .ok_or_else(bad)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Read the pre-versioning `career.txt` / `best_time.txt` pair. Same leniency those files
+    /// always had: the ledger is `best total runs [spent beam lasso whistle stomp]` (the
+    /// spend-side fields were added later and default to 0), then optional `skin ...` and
+    /// `name ...` lines; anything garbled falls back to the defaults.
+    fn from_legacy(career: Option<&str>, best_time: Option<&str>) -> Self {
+        let mut data = SaveData {
+            best_time: best_time
+                .and_then(|s| s.trim().parse::<f32>().ok())
+                .filter(|t| *t < f32::MAX),
+            ..SaveData::default()
+        };
+        let Some(career) = career else {
+            return data;
+        };
+        let mut it = career.split_whitespace().take(8);
+        let ledger = (|| {
+            let best = it.next()?.parse::<usize>().ok()?;
+            let total = it.next()?.parse::<usize>().ok()?;
+            let runs = it.next()?.parse::<usize>().ok()?;
+            Some((best, total, runs))
+        })();
+        if let Some((best, total, runs)) = ledger {
+            let spent = it.next().and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
+            let mut clamp_rank = || {
+                it.next()
+                    .and_then(|s| s.parse::<u32>().ok())
+                    .unwrap_or(0)
+                    .min(MAX_START_RANK)
+            };
+            data.best_score = best;
+            data.total_score = total;
+            data.runs = runs;
+            data.spent = spent;
+            data.start_beam_rank = clamp_rank();
+            data.start_lasso_rank = clamp_rank();
+            data.start_whistle_rank = clamp_rank();
+            data.start_stomp_rank = clamp_rank();
+        }
+        if let Some(line) = career.lines().find(|l| l.trim_start().starts_with("skin ")) {
+            data.player_skin = PlayerSkin::from_save_line(line.trim());
+        }
+        if let Some(name) = career
+            .lines()
+            .find_map(|l| l.trim_start().strip_prefix("name "))
+        {
+            data.player_name = crate::normalize_player_name(name);
+        }
+        data
+    }
+}
+
+/// A version 1 `node` or `board` line keyed `stage-N`, rekeyed by the level's id (see
+/// `world_map::legacy_node_id`). Every other line comes back as it was.
+fn migrate_stage_id(line: &str) -> String {
+    let mut words = line.splitn(3, ' ');
+    if let (Some(key @ ("node" | "board")), Some(id), rest) =
+        (words.next(), words.next(), words.next())
+        && let Some(new_id) = crate::world_map::legacy_node_id(id)
+    {
+        return match rest {
+            Some(rest) => format!("{key} {new_id} {rest}"),
+            None => format!("{key} {new_id}"),
+        };
+    }
+    line.to_string()
+}
+
+/// `<id> <completed|unlocked|locked> <best score|-> <best time|->`.
+fn parse_node(value: &str) -> Option<NodeProgress> {
+    let [id, state, score, time] = value.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let (unlocked, completed) = match state {
+        "completed" => (true, true),
+        "unlocked" => (true, false),
+        "locked" => (false, false),
+        _ => return None,
+    };
+    Some(NodeProgress {
+        id: id.to_string(),
+        unlocked,
+        completed,
+        best_score: parse_optional(score)?,
+        best_time: parse_optional(time)?,
+    })
+}
+
+/// `-` for none, otherwise the value; `None` if it doesn't parse.
+fn parse_optional<T: std::str::FromStr>(value: &str) -> Option<Option<T>> {
+    if value == "-" {
+        Some(None)
+    } else {
+        value.parse().ok().map(Some)
+    }
+}
+
+/// Where the save lives, and where to look for a legacy one to migrate.
+pub(crate) struct SaveStore {
+    dir: PathBuf,
+    legacy_dir: PathBuf,
+}
+
+impl SaveStore {
+    /// A store in `dir` that migrates legacy files found in the working directory.
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            legacy_dir: PathBuf::from("."),
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join(SAVE_FILE)
+    }
+
+    /// Load the save, migrating a legacy one or backing up a corrupt one as needed. A missing or
+    /// corrupt save is a fresh career. An error means the save is there but couldn't be read or
+    /// set aside, so the caller must not write over it.
+    pub fn load(&self) -> Result<SaveData, String> {
+        let path = self.path();
+        match fs::read_to_string(&path) {
+            Ok(text) => match SaveData::parse_counting(&text) {
+                Ok((data, 0)) => Ok(data),
+                Ok((data, skipped)) => {
+                    // The next save drops the skipped lines, so keep the file they came from.
+                    let backup = self.dir.join(format!("save.skipped-{}.txt", unix_time()));
+                    fs::copy(&path, &backup).map_err(|e| {
+                        format!(
+                            "save file {} has {skipped} unreadable line(s) and could not be \
+                             backed up ({e})",
+                            path.display()
+                        )
+                    })?;
+                    eprintln!(
+                        "Save file {} has {skipped} unreadable line(s); copied it to {}",
+                        path.display(),
+                        backup.display()
+                    );
+                    Ok(data)
+                }
+                Err(e) => {
+                    let backup = self.dir.join(format!("save.corrupt-{}.txt", unix_time()));
+                    fs::rename(&path, &backup).map_err(|re| {
+                        format!(
+                            "save file {} is unreadable ({e}) and could not be backed up ({re})",
+                            path.display()
+                        )
+                    })?;
+                    eprintln!(
+                        "Save file {} is unreadable ({e}); moved it to {} and started fresh",
+                        path.display(),
+                        backup.display()
+                    );
+                    Ok(SaveData::default())
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(self.migrate_legacy()),
+            Err(e) => Err(format!("could not read save file {}: {e}", path.display())),
+        }
+    }
+
+    fn migrate_legacy(&self) -> SaveData {
+        let career = fs::read_to_string(self.legacy_dir.join(LEGACY_CAREER_FILE)).ok();
+        let best_time = fs::read_to_string(self.legacy_dir.join(LEGACY_BEST_TIME_FILE)).ok();
+        if career.is_none() && best_time.is_none() {
+            return SaveData::default();
+        }
+        let data = SaveData::from_legacy(career.as_deref(), best_time.as_deref());
+        match self.save(&data) {
+            Ok(()) => println!(
+                "Migrated {LEGACY_CAREER_FILE} into {}",
+                self.path().display()
+            ),
+            Err(e) => eprintln!("Could not migrate {LEGACY_CAREER_FILE}: {e}"),
+        }
+        data
+    }
+
+    /// Write `data` atomically: to a temp file first, synced to disk, then renamed over the save.
+    pub fn save(&self, data: &SaveData) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let tmp = self.dir.join(format!("{SAVE_FILE}.tmp"));
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(data.to_text().as_bytes())?;
+        // Without this the rename can reach the disk before the data does, and a power cut in
+        // between leaves an empty save where the old one was.
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, self.path())?;
+        sync_dir(&self.dir)
+    }
+}
+
+/// Make a rename in `dir` durable. Unix can fsync a directory; elsewhere the rename is left to the
+/// filesystem.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    fs::File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+fn unix_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::Action;
+    use crate::leaderboard::LeaderboardEntry;
+    use crate::nemesis::Encounter;
+    use crate::skins::{Accessory, FacialHair, Hat};
+    use ggez::input::keyboard::KeyCode;
+
+    /// A fresh scratch directory per test, so parallel tests never share files.
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rustler-save-test-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn store(dir: &Path) -> SaveStore {
+        SaveStore {
+            dir: dir.join("data"),
+            legacy_dir: dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn save_round_trips() {
+        let mut bindings = Bindings::default();
+        bindings.rebind(Action::MoveUp, KeyCode::KeyZ).unwrap();
+        bindings.rebind(Action::Lasso, KeyCode::KeyL).unwrap();
+        let mut leaderboards = Leaderboards::default();
+        leaderboards.insert(
+            "sunny-meadow",
+            LeaderboardEntry {
+                name: "Crabby McCrabface".to_string(),
+                score: 412,
+                stage: None,
+                longest_train: 31,
+                time: 73.5,
+                date: "2026-10-18".to_string(),
+                skin: PlayerSkin::default(),
+            },
+        );
+        let mut rivals = NemesisRoster::default();
+        rivals.record("Cap'n Clawbeard", 2, Encounter::Stole(14));
+        let data = SaveData {
+            best_score: 412,
+            total_score: 3310,
+            runs: 27,
+            spent: 90,
+            start_beam_rank: 2,
+            start_lasso_rank: 1,
+            start_whistle_rank: 0,
+            start_stomp_rank: 2,
+            best_time: Some(43.25),
+            player_skin: PlayerSkin {
+                hat: Hat::Cowboy,
+                facial_hair: FacialHair::Mustache,
+                accessory: Accessory::StarBadge,
+            },
+            player_name: "Crabby McCrabface".to_string(),
+            map_progress: vec![
+                NodeProgress {
+                    id: "tutorial-beat".to_string(),
+                    unlocked: true,
+                    completed: true,
+                    best_score: None,
+                    best_time: Some(41.2),
+                },
+                NodeProgress {
+                    id: "sunny-meadow".to_string(),
+                    unlocked: true,
+                    completed: false,
+                    best_score: Some(12),
+                    best_time: None,
+                },
+            ],
+            bindings,
+            leaderboards,
+            daily_played: Some("2026-10-18".to_string()),
+            rivals,
+        };
+        assert_eq!(SaveData::parse(&data.to_text()), Ok(data));
+    }
+
+    #[test]
+    fn legacy_career_is_migrated() {
+        let dir = scratch("legacy");
+        fs::write(
+            dir.join(LEGACY_CAREER_FILE),
+            "120 400 7 60 1 9 0 2\nskin Cowboy Beard None\nname Old Timer",
+        )
+        .unwrap();
+        fs::write(dir.join(LEGACY_BEST_TIME_FILE), "88.5").unwrap();
+        let store = store(&dir);
+        let data = store.load().unwrap();
+        assert_eq!(
+            (data.best_score, data.total_score, data.runs),
+            (120, 400, 7)
+        );
+        assert_eq!(data.spent, 60);
+        assert_eq!(data.start_lasso_rank, MAX_START_RANK);
+        assert_eq!(data.best_time, Some(88.5));
+        assert_eq!(data.player_skin.hat, Hat::Cowboy);
+        assert_eq!(data.player_name, "Old Timer");
+        // The migrated career now lives in the data directory.
+        assert_eq!(
+            SaveData::parse(&fs::read_to_string(store.path()).unwrap()),
+            Ok(data)
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn version_1_stage_ids_are_carried_over() {
+        let text = "rustler-save 1\nnode tutorial-beat completed - 41.2\n\
+                    node stage-3 completed 412 73.5\nnode stage-4 unlocked - -\n\
+                    board stage-3 412 - 31 73.5 2026-10-18 Cowboy Mustache StarBadge Crabby\n";
+        let data = SaveData::parse(text).unwrap();
+        let ids: Vec<_> = data.map_progress.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["tutorial-beat", "rocky-shore", "neon-kelp-forest"]);
+        assert_eq!(data.leaderboards.table("rocky-shore")[0].score, 412);
+        // Every id the old saves can name is still a level.
+        let levels = crate::levels::load_levels().unwrap();
+        for n in 1..=11 {
+            let id = crate::world_map::legacy_node_id(&format!("stage-{n}")).unwrap();
+            assert!(levels.iter().any(|level| level.id == id), "{id}");
+        }
+    }
+
+    #[test]
+    fn a_bad_line_costs_only_itself() {
+        let text = "rustler-save 1\nbest_score 412\nruns lots\nnode stage-1 sort-of 3 -\n\
+                    rival 1 2 3 nope Kevin\nbind move_up NoSuchKey\nname Crabby McCrabface\n";
+        let data = SaveData::parse(text).unwrap();
+        assert_eq!(data.best_score, 412);
+        assert_eq!(data.runs, 0);
+        assert!(data.map_progress.is_empty());
+        assert!(data.rivals.rivals().is_empty());
+        assert_eq!(data.player_name, "Crabby McCrabface");
+    }
+
+    #[test]
+    fn corrupt_save_is_backed_up_not_lost() {
+        let dir = scratch("corrupt");
+        let store = store(&dir);
+        fs::create_dir_all(&store.dir).unwrap();
+        fs::write(store.path(), "best_score 412\nruns 27\n").unwrap();
+        assert_eq!(store.load(), Ok(SaveData::default()));
+        let backups: Vec<_> = fs::read_dir(&store.dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("save.corrupt-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert!(!store.path().exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn an_unreadable_save_is_not_loaded_as_a_fresh_one() {
+        let dir = scratch("unreadable");
+        let store = store(&dir);
+        // A directory where the save should be: there, but not readable as a file.
+        fs::create_dir_all(store.path()).unwrap();
+        assert!(store.load().is_err());
+        assert!(store.path().is_dir());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn a_save_with_bad_lines_is_copied_before_it_is_rewritten() {
+        let dir = scratch("skipped");
+        let store = store(&dir);
+        fs::create_dir_all(&store.dir).unwrap();
+        let text = "rustler-save 2\nbest_score 412\nruns lots\n";
+        fs::write(store.path(), text).unwrap();
+        assert_eq!(store.load().unwrap().best_score, 412);
+        let backups: Vec<_> = fs::read_dir(&store.dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("save.skipped-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(fs::read_to_string(backups[0].path()).unwrap(), text);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
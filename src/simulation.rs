@@ -0,0 +1,386 @@
+//! Headless simulation core — `MainState` stepped without a ggez `Context` or a window.
+//!
+//! The windowed game drives the sim through `EventHandler::update` → `MainState::tick`, which
+//! snapshots the keyboard into an [`InputFrame`] and then calls the `Context`-free
+//! `MainState::step`. [`Simulation`] calls that same `step` directly with a caller-chosen `dt` and
+//! input, on a `MainState::new_headless` (silent `Sfx` voices, no render resources). That is what
+//! lets the bot scenarios run as plain `cargo test`s: same code path, no GPU, no audio device, no
+//! `std::process::exit`.
+
+use std::collections::HashSet;
+
+use ggez::input::keyboard::{KeyCode, KeyboardContext};
+use ggez::winit::keyboard::PhysicalKey;
+
+use crate::MainState;
+use crate::bot::{BOT_DT, BotScenario};
+use crate::bot_report::BotReport;
+use crate::event_log::{EventLog, RunEnd};
+use crate::gamepad::PadState;
+use crate::replay::{InputEvent, Replay};
+
+/// The input for one simulation step: the keys held down, the gamepad's sticks and buttons, and
+/// the discrete key/mouse/pad events delivered since the previous step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputFrame {
+    pub held: HashSet<KeyCode>,
+    pub pad: PadState,
+    pub events: Vec<InputEvent>,
+}
+
+impl InputFrame {
+    /// Snapshot of the physical keys ggez currently reports as pressed.
+    pub fn from_keyboard(keyboard: &KeyboardContext) -> Self {
+        let held = keyboard
+            .pressed_physical_keys
+            .iter()
+            .filter_map(|key| match key {
+                PhysicalKey::Code(code) => Some(*code),
+                _ => None,
+            })
+            .collect();
+        Self {
+            held,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_held(&self, code: KeyCode) -> bool {
+        self.held.contains(&code)
+    }
+}
+
+/// A `MainState` owned and stepped headlessly.
+pub struct Simulation {
+    state: MainState,
+}
+
+impl Simulation {
+    /// A fresh game on the title screen, exactly as a windowed launch would start it.
+    pub fn new() -> Self {
+        Self {
+            state: MainState::new_headless(),
+        }
+    }
+
+    /// A game set up to run a bot scenario: RNG seeded, script installed, time scale and intro
+    /// skip applied — the same setup `main` does for `--bot <name>`.
+    pub fn bot(scenario: &BotScenario) -> Self {
+        crate::rng::seed(scenario.seed);
+        let mut sim = Self::new();
+        sim.state.bot_fixed_dt = Some(BOT_DT);
+        sim.state.time_scale = scenario.time_scale;
+        sim.state.bot = Some(scenario.bot_state());
+        sim.state.skip_menu_intro();
+        sim
+    }
+
+    /// A game set up to play back `replay`: RNG seeded, timestep pinned, and started from the day
+    /// and save it was recorded on. Feed it the replay's frames with [`Simulation::step`].
+    pub fn for_replay(replay: &Replay) -> Self {
+        crate::rng::seed(replay.seed);
+        let mut sim = Self {
+            state: MainState::new_headless_from(replay.save.clone()),
+        };
+        sim.state.bot_fixed_dt = Some(replay.dt);
+        sim.state.session_date = replay.date.clone();
+        sim
+    }
+
+    /// Play a whole replay back from the start and return the finished simulation, logging its
+    /// gameplay events to `event_log` if given.
+    pub fn run_replay(replay: &Replay, event_log: Option<EventLog>) -> Self {
+        let mut sim = Self::for_replay(replay);
+        sim.set_event_log(event_log);
+        for frame in &replay.frames {
+            sim.step(replay.dt, frame);
+        }
+        sim.close_event_log();
+        sim
+    }
+
+    /// Stream the run's gameplay events to `log` (see `event_log.rs`).
+    pub fn set_event_log(&mut self, log: Option<EventLog>) {
+        self.state.event_log = log;
+    }
+
+    /// End the event log's open run, if any: the session is over.
+    pub fn close_event_log(&mut self) {
+        self.state.log_run_end(RunEnd::Closed);
+    }
+
+    /// The installed bot script's report (see `bot_report.rs`); `None` outside a bot run.
+    pub fn bot_report(&self) -> Option<BotReport> {
+        self.state.bot_report()
+    }
+
+    /// One-line outcome of the run so far, for `--replay <file> --headless`.
+    pub fn summary(&self) -> String {
+        let s = &self.state;
+        format!(
+            "level {} | score {} | time {:.1}s | {}",
+            s.current_level + 1,
+            s.score,
+            s.time_elapsed,
+            if s.game_over { "game over" } else { "running" },
+        )
+    }
+
+    /// Advance the simulation by `dt` seconds of real time: deliver `input`'s events, hold its
+    /// keys, then step.
+    pub fn step(&mut self, dt: f32, input: &InputFrame) {
+        self.state.apply_input(input);
+        // `step` only fails on window operations, and the headless path performs none.
+        let _ = self.state.step(dt);
+    }
+
+    /// The game being simulated.
+    pub(crate) fn state(&self) -> &MainState {
+        &self.state
+    }
+
+    /// One step at the fixed bot timestep, with no input beyond the bot's own.
+    pub fn step_bot(&mut self) {
+        self.step(BOT_DT, &InputFrame::default());
+    }
+
+    /// Step the installed bot script at the fixed bot timestep until it passes or fails. Returns
+    /// the bot's failure reason on failure.
+    pub fn run_bot(&mut self) -> Result<(), String> {
+        loop {
+            let Some(bot) = &self.state.bot else {
+                return Err("no bot script installed".into());
+            };
+            if bot.done {
+                return match &bot.failed {
+                    Some(reason) => Err(reason.clone()),
+                    None => Ok(()),
+                };
+            }
+            self.step_bot();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &str) {
+        let scenario = BotScenario::find(name).unwrap_or_else(|e| panic!("{e}"));
+        let mut sim = Simulation::bot(&scenario);
+        if let Err(reason) = sim.run_bot() {
+            panic!("bot scenario {name} failed: {reason}");
+        }
+    }
+
+    #[test]
+    fn headless_step_advances_the_title_clock() {
+        let mut sim = Simulation::new();
+        let before = sim.state.menu_time;
+        sim.step(0.5, &InputFrame::default());
+        assert!(sim.state.menu_time > before);
+    }
+
+    #[test]
+    fn replays_start_from_the_recorded_save() {
+        let replay = Replay {
+            seed: 7,
+            dt: BOT_DT,
+            date: None,
+            save: crate::save::SaveData {
+                start_lasso_rank: 2,
+                ..Default::default()
+            },
+            frames: Vec::new(),
+        };
+        let sim = Simulation::for_replay(&replay);
+        assert_eq!(sim.state.start_lasso_rank, 2);
+        assert!(sim.state.save_store.is_none());
+    }
+
+    #[test]
+    fn a_replayed_daily_deals_the_recorded_day() {
+        let replay = Replay {
+            seed: 7,
+            dt: BOT_DT,
+            date: Some("2026-10-18".into()),
+            ..Replay::default()
+        };
+        let mut sim = Simulation::for_replay(&replay);
+        sim.state.start_daily();
+        assert_eq!(sim.state.daily.as_ref().unwrap().date, "2026-10-18");
+    }
+
+    #[test]
+    fn replay_reproduces_the_session() {
+        use ggez::glam::Vec2;
+        use ggez::input::mouse::MouseButton;
+
+        let tap = |code| InputFrame {
+            held: [code].into_iter().collect(),
+            events: vec![InputEvent::Key {
+                code: Some(code),
+                text: None,
+            }],
+            ..InputFrame::default()
+        };
+        let hold = |codes: &[KeyCode]| InputFrame {
+            held: codes.iter().copied().collect(),
+            ..InputFrame::default()
+        };
+        // Skip the intro, take the recommended run with no mutators, then walk, wind up and throw
+        // the lasso.
+        let mut frames = vec![
+            tap(KeyCode::Space),
+            hold(&[]),
+            tap(KeyCode::Space),
+            hold(&[]),
+            tap(KeyCode::Space),
+        ];
+        frames.extend(std::iter::repeat_n(hold(&[]), 30));
+        frames.push(tap(KeyCode::Space));
+        frames.extend(std::iter::repeat_n(
+            hold(&[KeyCode::KeyD, KeyCode::KeyW]),
+            120,
+        ));
+        frames.push(InputFrame {
+            held: HashSet::new(),
+            events: vec![
+                InputEvent::MouseMove(Vec2::new(900.0, 300.0)),
+                InputEvent::MouseDown(MouseButton::Left, Vec2::new(900.0, 300.0)),
+            ],
+            ..InputFrame::default()
+        });
+        frames.extend(std::iter::repeat_n(hold(&[KeyCode::KeyA]), 40));
+        frames.push(InputFrame {
+            held: HashSet::new(),
+            events: vec![InputEvent::MouseUp(
+                MouseButton::Left,
+                Vec2::new(900.0, 300.0),
+            )],
+            ..InputFrame::default()
+        });
+        frames.extend(std::iter::repeat_n(hold(&[KeyCode::KeyS]), 600));
+        let replay = Replay {
+            seed: 42,
+            dt: BOT_DT,
+            date: None,
+            save: Default::default(),
+            frames,
+        };
+
+        let first = Simulation::run_replay(&replay, None);
+        let second = Simulation::run_replay(&replay, None);
+        assert!(
+            !first.state.show_instructions,
+            "the replay should reach gameplay"
+        );
+        assert_eq!(first.summary(), second.summary());
+        assert_eq!(first.state.player_pos, second.state.player_pos);
+        assert_eq!(first.state.lasso_phase, second.state.lasso_phase);
+        let crabs =
+            |sim: &Simulation| -> Vec<Vec2> { sim.state.crabs.iter().map(|c| c.pos).collect() };
+        assert_eq!(crabs(&first), crabs(&second));
+    }
+
+    #[test]
+    fn bot_groove_dash() {
+        run("groove_dash");
+    }
+
+    #[test]
+    fn bot_menu_to_game() {
+        run("menu_to_game");
+    }
+
+    #[test]
+    fn bot_campaign_escape() {
+        run("campaign_escape");
+    }
+
+    #[test]
+    fn bot_campaign_loss() {
+        run("campaign_loss");
+    }
+
+    #[test]
+    fn bot_campaign_tutorial() {
+        run("campaign_tutorial");
+    }
+
+    #[test]
+    fn bot_campaign_full() {
+        run("campaign_full");
+    }
+
+    #[test]
+    fn bot_npc_steal() {
+        run("npc_steal");
+    }
+
+    #[test]
+    fn bot_player_steal() {
+        run("player_steal");
+    }
+
+    #[test]
+    fn bot_steal_defense() {
+        run("steal_defense");
+    }
+
+    #[test]
+    fn bot_steal_dodge() {
+        run("steal_dodge");
+    }
+
+    #[test]
+    fn bot_revenge() {
+        run("revenge");
+    }
+
+    #[test]
+    fn bot_rival_haul() {
+        run("rival_haul");
+    }
+
+    #[test]
+    fn bot_rival_pirate() {
+        run("rival_pirate");
+    }
+
+    #[test]
+    fn bot_rival_pompous() {
+        run("rival_pompous");
+    }
+
+    #[test]
+    fn bot_rival_raver() {
+        run("rival_raver");
+    }
+
+    #[test]
+    fn bot_rival_serious() {
+        run("rival_serious");
+    }
+
+    #[test]
+    fn bot_rival_silly() {
+        run("rival_silly");
+    }
+
+    #[test]
+    fn bot_nemesis() {
+        run("nemesis");
+    }
+
+    #[test]
+    fn bot_npc_vs_npc() {
+        run("npc_vs_npc");
+    }
+
+    #[test]
+    fn bot_steal_goal() {
+        run("steal_goal");
+    }
+}
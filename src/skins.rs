@@ -9,8 +9,8 @@
 //! to pick a draw recipe. Aim for options that are readable at crab-player scale (roughly 20px)
 //! and have strong silhouettes so they're instantly recognisable.
 //!
-//! **Persistence:** saved alongside career.txt as `skin <Hat> <FacialHair> <Accessory>` (Debug
-//! variant names). Corrupt lines fall back to the default (bare) skin so saves never crash.
+//! **Persistence:** saved as a `skin <Hat> <FacialHair> <Accessory>` line in the save file
+//! (save.rs; Debug variant names). Corrupt lines fall back to the default (bare) skin so saves never crash.
 //!
 //! **Unlock model (placeholder):** everything unlocked from the start so the skeleton is
 //! immediately playable. A proper unlock gate (career milestone → new cosmetic) can slot in
@@ -217,7 +217,7 @@ impl PlayerSkin {
         }
     }
 
-    /// Serialize to a single whitespace-separated line for appending to career.txt.
+    /// Serialize to a single whitespace-separated line for the save file (save.rs).
     pub fn to_save_line(&self) -> String {
         format!(
             "skin {:?} {:?} {:?}",
@@ -225,7 +225,7 @@ impl PlayerSkin {
         )
     }
 
-    /// Parse from the `skin ...` line in career.txt. Falls back to default on any error.
+    /// Parse from the `skin ...` line of the save file. Falls back to default on any error.
     pub fn from_save_line(line: &str) -> Self {
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() != 4 || parts[0] != "skin" {
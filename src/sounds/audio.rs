@@ -13,6 +13,8 @@
 use ggez::audio::{SoundData, Source};
 use ggez::{Context, GameResult};
 
+use super::Sfx;
+
 pub(crate) const SAMPLE_RATE: u32 = 44_100;
 
 // ---------------------------------------------------------------------------------------------
@@ -1085,14 +1087,14 @@ fn snare_source(ctx: &mut Context, duration: f32, gain: f32) -> GameResult<Sourc
 /// The synthesised percussion voices, built once and replayed on the beat.
 pub struct BeatSynth {
     /// The heavier, lower kick for the downbeat ("1" of the bar).
-    downbeat_kick: Source,
+    downbeat_kick: Sfx,
     /// The lighter kick for the three beats between downbeats.
-    offbeat_kick: Source,
+    offbeat_kick: Sfx,
     /// Snare hit — played on beats 2 & 4 (the backbeat) during boss fights.
-    snare: Source,
+    snare: Sfx,
     /// Closed hi-hat — the swung offbeat layer that locks the live kit to the 1/16 grid. Volume
     /// is set per-play (see `play_hihat`) so the hat thickens with train length / intensity.
-    hihat: Source,
+    hihat: Sfx,
     /// Current snare volume, 0..1. Fades in when a boss is present, fades out when cleared.
     /// Smoothly interpolated each beat so it never pops in or disappears abruptly.
     pub snare_volume: f32,
@@ -1102,24 +1104,35 @@ impl BeatSynth {
     pub fn new(ctx: &mut Context) -> GameResult<BeatSynth> {
         Ok(BeatSynth {
             // Downbeat: lower, longer, louder — the "1" you feel in your chest.
-            downbeat_kick: kick_source(ctx, 150.0, 45.0, 0.14, 0.9)?,
+            downbeat_kick: kick_source(ctx, 150.0, 45.0, 0.14, 0.9)?.into(),
             // Offbeat: higher pitched, tighter, quieter so the bar has a clear accent structure.
-            offbeat_kick: kick_source(ctx, 130.0, 55.0, 0.10, 0.55)?,
+            offbeat_kick: kick_source(ctx, 130.0, 55.0, 0.10, 0.55)?.into(),
             // Snare: tight crack, full gain baked in — volume is controlled via snare_volume.
-            snare: snare_source(ctx, 0.09, 0.75)?,
+            snare: snare_source(ctx, 0.09, 0.75)?.into(),
             // Closed hi-hat: full gain baked in, per-play volume set by the caller.
             hihat: {
                 let bytes = synth_beat_hihat_wav();
-                Source::from_data(ctx, SoundData::from_bytes(&bytes)?)?
+                Source::from_data(ctx, SoundData::from_bytes(&bytes)?)?.into()
             },
             snare_volume: 0.0,
         })
     }
 
+    /// The same kit with no audio device behind it, for the headless simulation.
+    pub fn silent() -> BeatSynth {
+        BeatSynth {
+            downbeat_kick: Sfx::silent(),
+            offbeat_kick: Sfx::silent(),
+            snare: Sfx::silent(),
+            hihat: Sfx::silent(),
+            snare_volume: 0.0,
+        }
+    }
+
     /// Play a closed hi-hat at `volume` (0..1). The caller schedules these on the swung 1/16 grid
     /// between the kicks, so the live kit grooves in the pocket instead of clicking straight
     /// quarter-notes. `volume < 0.01` is treated as silent (skipped) so a fully calm kit is free.
-    pub fn play_hihat(&mut self, _ctx: &mut Context, volume: f32) {
+    pub fn play_hihat(&mut self, volume: f32) {
         use ggez::audio::SoundSource;
         if volume < 0.01 {
             return;
@@ -1140,7 +1153,7 @@ impl BeatSynth {
     }
 
     /// Play a kick for this beat. `downbeat` picks the heavier voice on the "1".
-    pub fn play_kick(&mut self, _ctx: &mut Context, downbeat: bool) {
+    pub fn play_kick(&mut self, downbeat: bool) {
         use ggez::audio::SoundSource;
         let src = if downbeat {
             &mut self.downbeat_kick
@@ -1152,7 +1165,7 @@ impl BeatSynth {
 
     /// Play the snare if it has audible volume. `beat_index` is the beat position within the bar
     /// (0-based); the snare lands on beats 1 and 3 (the "2" and "4" of the bar in 1-based terms).
-    pub fn play_snare(&mut self, _ctx: &mut Context, beat_index: u32) {
+    pub fn play_snare(&mut self, beat_index: u32) {
         use ggez::audio::SoundSource;
         // Only fire on the backbeat (beats 2 & 4 in musical 1-based terms).
         if beat_index % 4 != 1 && beat_index % 4 != 3 {
@@ -15,6 +15,7 @@
 
 mod audio;
 mod music;
+mod sfx;
 
 // --- Public API (was `pub` in the flat `sounds.rs`) -----------------------------------------
 // Synthesis / SFX voices.
@@ -23,6 +24,8 @@ pub use audio::{
     synth_hihat, synth_lasso_throw, synth_perfect_sparkle, synth_rival_steal, synth_startup_pling,
     synth_steal_gain, synth_steal_loss, synth_stomp, synth_tool_accent, synth_whistle,
 };
+// A voice that plays through the audio device, or silently when headless.
+pub use sfx::Sfx;
 // Musical structure.
 pub use music::{
     GROOVE_SWING, biome_rival_motif_tuning, detect_bpm_from_ogg,
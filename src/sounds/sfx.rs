@@ -0,0 +1,179 @@
+//! `Sfx` — a playable voice that may or may not be backed by a real audio device.
+//!
+//! Every voice on `GameSounds` / `BeatSynth` is an `Sfx` rather than a bare ggez `Source`, so the
+//! simulation can be built and stepped without a `Context` (see `simulation.rs`). A windowed run
+//! wraps each synthesised `Source` and behaves exactly as before; a headless run uses
+//! [`Sfx::silent`], which plays nothing but still tracks volume/pitch/transport state so the mixer
+//! logic that reads `volume()` / `playing()` back (ducking, beat-locked loop restarts, the
+//! `TitleMenuReady` bot assert) takes the same branches it would with sound.
+//!
+//! `Sfx` implements ggez's own `SoundSource`, so every `self.sounds.x.play()` call site keeps
+//! compiling verbatim with the trait in scope.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use ggez::audio::{SoundSource, Source};
+
+/// Transport state of a silent voice. Mirrors what a real sink would report.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Transport {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+pub struct Sfx {
+    source: Option<Source>,
+    // Bookkeeping for the silent variant only — a real `Source` answers these itself.
+    transport: Cell<Transport>,
+    volume: f32,
+    repeat: bool,
+}
+
+impl Sfx {
+    /// A voice with no audio device behind it, for headless simulation.
+    pub fn silent() -> Self {
+        Self {
+            source: None,
+            transport: Cell::new(Transport::Stopped),
+            volume: 1.0,
+            repeat: false,
+        }
+    }
+}
+
+impl From<Source> for Sfx {
+    fn from(source: Source) -> Self {
+        Self {
+            source: Some(source),
+            transport: Cell::new(Transport::Stopped),
+            volume: 1.0,
+            repeat: false,
+        }
+    }
+}
+
+impl SoundSource for Sfx {
+    fn play_later(&self) {
+        match &self.source {
+            Some(s) => s.play_later(),
+            None => self.transport.set(Transport::Playing),
+        }
+    }
+
+    fn play_detached(self) {
+        if let Some(s) = self.source {
+            s.play_detached();
+        }
+    }
+
+    fn set_repeat(&mut self, repeat: bool) {
+        match &mut self.source {
+            Some(s) => s.set_repeat(repeat),
+            None => self.repeat = repeat,
+        }
+    }
+
+    fn set_fade_in(&mut self, dur: Duration) {
+        if let Some(s) = &mut self.source {
+            s.set_fade_in(dur);
+        }
+    }
+
+    fn set_start(&mut self, dur: Duration) {
+        if let Some(s) = &mut self.source {
+            s.set_start(dur);
+        }
+    }
+
+    fn set_pitch(&mut self, ratio: f32) {
+        if let Some(s) = &mut self.source {
+            s.set_pitch(ratio);
+        }
+    }
+
+    fn repeat(&self) -> bool {
+        match &self.source {
+            Some(s) => s.repeat(),
+            None => self.repeat,
+        }
+    }
+
+    fn pause(&self) {
+        match &self.source {
+            Some(s) => s.pause(),
+            None => {
+                if self.transport.get() == Transport::Playing {
+                    self.transport.set(Transport::Paused);
+                }
+            }
+        }
+    }
+
+    fn resume(&self) {
+        match &self.source {
+            Some(s) => s.resume(),
+            None => {
+                if self.transport.get() == Transport::Paused {
+                    self.transport.set(Transport::Playing);
+                }
+            }
+        }
+    }
+
+    fn stop(&self) {
+        match &self.source {
+            Some(s) => s.stop(),
+            None => self.transport.set(Transport::Stopped),
+        }
+    }
+
+    fn stopped(&self) -> bool {
+        match &self.source {
+            Some(s) => s.stopped(),
+            None => self.transport.get() == Transport::Stopped,
+        }
+    }
+
+    fn volume(&self) -> f32 {
+        match &self.source {
+            Some(s) => s.volume(),
+            None => self.volume,
+        }
+    }
+
+    fn set_volume(&mut self, value: f32) {
+        match &mut self.source {
+            Some(s) => s.set_volume(value),
+            None => self.volume = value,
+        }
+    }
+
+    fn paused(&self) -> bool {
+        match &self.source {
+            Some(s) => s.paused(),
+            None => self.transport.get() == Transport::Paused,
+        }
+    }
+
+    fn playing(&self) -> bool {
+        match &self.source {
+            Some(s) => s.playing(),
+            None => self.transport.get() == Transport::Playing,
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        match &self.source {
+            Some(s) => s.elapsed(),
+            None => Duration::ZERO,
+        }
+    }
+
+    fn set_query_interval(&mut self, t: Duration) {
+        if let Some(s) = &mut self.source {
+            s.set_query_interval(t);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! The shared per-frame spatial index over `self.crabs`.
+//!
+//! Every crab proximity question the simulation asks — which free crab a chain link scoops, which
+//! Magnet an ordinary crab drifts toward, which panicking neighbour a calm crab catches fear from,
+//! which body link a fleeing crab bounces off, what a whistle or stomp ring has swept — goes
+//! through one [`CrabIndex`] instead of each subsystem bucketing (or flat-scanning) the herd on
+//! its own. It is a uniform grid over the world, counting-sorted into one flat array keyed by
+//! `(cell, crab type)`, so an all-types query walks one contiguous slice per grid row and a
+//! nearest-of-type query touches only that type's crabs.
+//!
+//! The index is rebuilt once per step, just before `update_crabs`, and again whenever crabs are
+//! removed (banking, a splitter cut, a level reset) so entry indices never point past a shifted
+//! `Vec`. Entries are a snapshot: the Magnet passes in `update_crabs` want exactly that, while
+//! later systems query with [`DRIFT`] of slack and re-check the live crab. Crabs spawned since the
+//! last rebuild are invisible to queries until the next one, one step later.
+
+use ggez::glam::Vec2;
+
+use crate::MainState;
+use crate::constants::CRAB_SIZE;
+use crate::enemies::{CrabType, EnemyCrab};
+
+/// Grid cell edge in world units — about the middle of the query radii (catch reach, contagion,
+/// deflection), so small queries touch a handful of cells and the 240-unit Magnet field ~64.
+const CELL: f32 = 64.0;
+/// Crab types per cell bucket. `DancerKing` is the last `CrabType` variant.
+const KINDS: usize = CrabType::DancerKing as usize + 1;
+
+/// How far a crab can move between the rebuild and a later query in the same step. Consumers
+/// reading live positions widen their radius by this and then test the live crab.
+pub(crate) const DRIFT: f32 = CRAB_SIZE;
+
+/// One crab as it stood when the index was built.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct IndexedCrab {
+    /// Position in `self.crabs`.
+    pub index: usize,
+    pub pos: Vec2,
+    pub crab_type: CrabType,
+    pub caught: bool,
+    pub in_flashlight: bool,
+}
+
+impl IndexedCrab {
+    const EMPTY: IndexedCrab = IndexedCrab {
+        index: 0,
+        pos: Vec2::ZERO,
+        crab_type: CrabType::Normal,
+        caught: false,
+        in_flashlight: false,
+    };
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CrabIndex {
+    cols: usize,
+    rows: usize,
+    /// `starts[b]..starts[b + 1]` is bucket `b = cell * KINDS + crab type` in `crabs`.
+    starts: Vec<u32>,
+    crabs: Vec<IndexedCrab>,
+    /// Each crab's bucket, kept between rebuilds only for its allocation.
+    buckets: Vec<u32>,
+}
+
+impl CrabIndex {
+    /// Re-bucket every crab over a `world`-sized grid. Crabs outside the world (a rival's pickup
+    /// parked off-screen) land in the nearest edge cell; queries still test real distances.
+    pub(crate) fn rebuild(&mut self, crabs: &[EnemyCrab], world: Vec2) {
+        self.cols = ((world.x / CELL).ceil() as usize).max(1);
+        self.rows = ((world.y / CELL).ceil() as usize).max(1);
+        let bucket_count = self.cols * self.rows * KINDS;
+        self.starts.clear();
+        self.starts.resize(bucket_count + 1, 0);
+        self.buckets.clear();
+        for crab in crabs {
+            let bucket = self.cell(crab.pos) * KINDS + crab.crab_type as usize;
+            self.buckets.push(bucket as u32);
+            self.starts[bucket] += 1;
+        }
+        // Counts become bucket ends; filling back to front then walks each end down to its start,
+        // which keeps every bucket in `self.crabs` order.
+        let mut end = 0;
+        for start in &mut self.starts {
+            end += *start;
+            *start = end;
+        }
+        self.crabs.clear();
+        self.crabs.resize(crabs.len(), IndexedCrab::EMPTY);
+        for (index, crab) in crabs.iter().enumerate().rev() {
+            let slot = &mut self.starts[self.buckets[index] as usize];
+            *slot -= 1;
+            self.crabs[*slot as usize] = IndexedCrab {
+                index,
+                pos: crab.pos,
+                crab_type: crab.crab_type,
+                caught: crab.caught,
+                in_flashlight: crab.in_flashlight,
+            };
+        }
+    }
+
+    fn col(&self, x: f32) -> usize {
+        ((x / CELL) as usize).min(self.cols - 1)
+    }
+
+    fn row(&self, y: f32) -> usize {
+        ((y / CELL) as usize).min(self.rows - 1)
+    }
+
+    fn cell(&self, pos: Vec2) -> usize {
+        self.row(pos.y) * self.cols + self.col(pos.x)
+    }
+
+    /// Column and row spans of the cells overlapping `min..=max`, empty before the first rebuild.
+    fn cell_span(&self, min: Vec2, max: Vec2) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        if self.crabs.is_empty() {
+            return (0..0, 0..0);
+        }
+        (
+            self.col(min.x)..self.col(max.x) + 1,
+            self.row(min.y)..self.row(max.y) + 1,
+        )
+    }
+
+    /// Every crab in the cells overlapping `min..=max`, one contiguous slice per row.
+    fn in_box(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = &IndexedCrab> + '_ {
+        let (cols, rows) = self.cell_span(min, max);
+        rows.flat_map(move |row| {
+            let first = (row * self.cols + cols.start) * KINDS;
+            let last = (row * self.cols + cols.end) * KINDS;
+            &self.crabs[self.starts[first] as usize..self.starts[last] as usize]
+        })
+    }
+
+    /// Crabs of one type in the cells overlapping `min..=max`.
+    fn in_box_of_type(
+        &self,
+        crab_type: CrabType,
+        min: Vec2,
+        max: Vec2,
+    ) -> impl Iterator<Item = &IndexedCrab> + '_ {
+        let (cols, rows) = self.cell_span(min, max);
+        rows.flat_map(move |row| {
+            cols.clone().flat_map(move |col| {
+                let bucket = (row * self.cols + col) * KINDS + crab_type as usize;
+                &self.crabs[self.starts[bucket] as usize..self.starts[bucket + 1] as usize]
+            })
+        })
+    }
+
+    /// Crabs strictly within `radius` of `center`, in no particular order.
+    pub(crate) fn within(
+        &self,
+        center: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = &IndexedCrab> + '_ {
+        let r2 = radius * radius;
+        self.in_box(center - Vec2::splat(radius), center + Vec2::splat(radius))
+            .filter(move |c| c.pos.distance_squared(center) < r2)
+    }
+
+    /// Crabs of `crab_type` strictly within `radius` of `center`, in no particular order.
+    pub(crate) fn within_of_type(
+        &self,
+        crab_type: CrabType,
+        center: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = &IndexedCrab> + '_ {
+        let r2 = radius * radius;
+        self.in_box_of_type(
+            crab_type,
+            center - Vec2::splat(radius),
+            center + Vec2::splat(radius),
+        )
+        .filter(move |c| c.pos.distance_squared(center) < r2)
+    }
+
+    /// Replace `out` with the `self.crabs` indices within `radius` of `center`, in herd order.
+    pub(crate) fn collect_within(&self, center: Vec2, radius: f32, out: &mut Vec<usize>) {
+        out.clear();
+        out.extend(self.within(center, radius).map(|c| c.index));
+        out.sort_unstable();
+    }
+
+    /// The nearest crab of `crab_type` within `radius` that `keep` accepts, with its squared
+    /// distance.
+    pub(crate) fn nearest_of_type(
+        &self,
+        crab_type: CrabType,
+        center: Vec2,
+        radius: f32,
+        mut keep: impl FnMut(&IndexedCrab) -> bool,
+    ) -> Option<(f32, &IndexedCrab)> {
+        self.within_of_type(crab_type, center, radius)
+            .filter(|c| keep(c))
+            .map(|c| (c.pos.distance_squared(center), c))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+    }
+
+    /// Crabs within `radius` of the segment `from..to` — what something sweeping along that
+    /// line (a lunging boss, one link of a train) passes over. Meant for short segments: the
+    /// candidates are the cells of the segment's bounding box.
+    pub(crate) fn along(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = &IndexedCrab> + '_ {
+        let r2 = radius * radius;
+        let pad = Vec2::splat(radius);
+        self.in_box(from.min(to) - pad, from.max(to) + pad)
+            .filter(move |c| distance_squared_to_segment(c.pos, from, to) < r2)
+    }
+}
+
+fn distance_squared_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len2 = ab.length_squared();
+    let t = if len2 > 0.0 {
+        ((p - a).dot(ab) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance_squared(a + ab * t)
+}
+
+impl MainState {
+    /// Rebuild `crab_index` from the current herd. Called once per step before `update_crabs`,
+    /// and after anything that removes crabs from the `Vec`.
+    pub(crate) fn rebuild_crab_index(&mut self) {
+        self.crab_index
+            .rebuild(&self.crabs, Vec2::new(self.world_width, self.world_height));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spawnings::spawn_scattered_crab;
+
+    fn herd(count: usize) -> Vec<EnemyCrab> {
+        crate::rng::seed(7);
+        let mut rng = crate::rng::rng();
+        let types = [CrabType::Normal, CrabType::Magnet, CrabType::Armored];
+        (0..count)
+            .map(|i| {
+                use rand::Rng;
+                let pos = Vec2::new(
+                    rng.random_range(-50.0..1650.0),
+                    rng.random_range(-50.0..1250.0),
+                );
+                let mut crab = spawn_scattered_crab(pos, Vec2::ZERO, types[i % 3], &mut rng);
+                crab.caught = i % 7 == 0;
+                crab
+            })
+            .collect()
+    }
+
+    #[test]
+    fn queries_match_a_flat_scan() {
+        let crabs = herd(900);
+        let mut index = CrabIndex::default();
+        index.rebuild(&crabs, Vec2::new(1600.0, 1200.0));
+        assert_eq!(index.crabs.len(), crabs.len());
+        for (center, radius) in [
+            (Vec2::new(800.0, 600.0), 110.0),
+            (Vec2::new(0.0, 0.0), 240.0),
+            (Vec2::new(1590.0, 20.0), 45.0),
+            (Vec2::new(-400.0, 600.0), 500.0),
+        ] {
+            let mut found: Vec<usize> = index.within(center, radius).map(|c| c.index).collect();
+            found.sort_unstable();
+            let expected: Vec<usize> = (0..crabs.len())
+                .filter(|&i| crabs[i].pos.distance(center) < radius)
+                .collect();
+            assert_eq!(found, expected);
+
+            let magnet = index
+                .nearest_of_type(CrabType::Magnet, center, radius, |c| !c.caught)
+                .map(|(_, c)| c.index);
+            let expected = (0..crabs.len())
+                .filter(|&i| {
+                    crabs[i].crab_type == CrabType::Magnet
+                        && !crabs[i].caught
+                        && crabs[i].pos.distance(center) < radius
+                })
+                .min_by(|&a, &b| {
+                    let da = crabs[a].pos.distance_squared(center);
+                    da.total_cmp(&crabs[b].pos.distance_squared(center))
+                });
+            assert_eq!(magnet, expected);
+        }
+    }
+
+    #[test]
+    fn segment_sweep_covers_the_capsule() {
+        let crabs = herd(600);
+        let mut index = CrabIndex::default();
+        index.rebuild(&crabs, Vec2::new(1600.0, 1200.0));
+        let (from, to) = (Vec2::new(200.0, 900.0), Vec2::new(700.0, 650.0));
+        let mut found: Vec<usize> = index.along(from, to, 60.0).map(|c| c.index).collect();
+        found.sort_unstable();
+        let expected: Vec<usize> = (0..crabs.len())
+            .filter(|&i| distance_squared_to_segment(crabs[i].pos, from, to) < 3600.0)
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn an_unbuilt_index_is_empty() {
+        let index = CrabIndex::default();
+        assert_eq!(index.within(Vec2::ZERO, 1000.0).count(), 0);
+        assert!(
+            index
+                .nearest_of_type(CrabType::Magnet, Vec2::ZERO, 1000.0, |_| true)
+                .is_none()
+        );
+    }
+}
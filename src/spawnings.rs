@@ -3,7 +3,7 @@ use crate::tutorial::TutorialKind;
 use ggez::glam::Vec2;
 use rand::Rng;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub enum SpawnPattern {
     UniformRandom,
     SineWave,
@@ -8,7 +8,8 @@
 
 use ggez::glam::Vec2;
 
-use crate::enemies::CrabType;
+use crate::enemies::{CrabType, EnemyCrab};
+use crate::spatial::DRIFT;
 use crate::state::MainState;
 
 impl MainState {
@@ -120,27 +121,22 @@ impl MainState {
         const MAX_INFECTIONS_PER_BEAT: usize = 8;
         // How much harder a fleeing Golden crab's fear ripples than an ordinary panicking crab.
         const GOLDEN_PANIC_AMP: f32 = 1.6;
-        // Snapshot of panicking crabs whose fear can jump to a neighbour this beat, into a
-        // reused buffer instead of a fresh collect() every beat. Each carrier remembers a panic
-        // amplitude so a Golden's amplified fear (and the amplified crabs it already startled)
-        // keeps rippling harder than the baseline as the wave marches on.
-        let mut carriers = std::mem::take(&mut self.contagion_carriers_buf);
-        carriers.clear();
-        carriers.extend(
-            self.crabs
-                .iter()
-                .filter(|c| !c.caught && !c.is_boss() && (c.fleeing || c.startle_timer > 0.0))
-                .map(|c| {
-                    let amp = if c.is_golden() {
-                        GOLDEN_PANIC_AMP
-                    } else {
-                        c.panic_amp.max(1.0)
-                    };
-                    (c.pos, amp)
-                }),
-        );
-        if carriers.is_empty() {
-            self.contagion_carriers_buf = carriers;
+        // The largest amplitude a carrier can hold: a fleeing Golden. Infected crabs carry a
+        // decayed slice of it (at most 1 + 0.6 * 0.7), so this bounds every carrier's reach.
+        const MAX_REACH: f32 = CONTAGION_RADIUS * GOLDEN_PANIC_AMP;
+        // Panicking crabs whose fear can jump to a neighbour, each with a panic amplitude so a
+        // Golden's amplified fear (and the amplified crabs it already startled) keeps rippling
+        // harder than the baseline as the wave marches on.
+        let carrier_amp = |c: &EnemyCrab| -> Option<f32> {
+            if c.caught || c.is_boss() || !(c.fleeing || c.startle_timer > 0.0) {
+                None
+            } else if c.is_golden() {
+                Some(GOLDEN_PANIC_AMP)
+            } else {
+                Some(c.panic_amp.max(1.0))
+            }
+        };
+        if !self.crabs.iter().any(|c| carrier_amp(c).is_some()) {
             return;
         }
 
@@ -150,62 +146,20 @@ impl MainState {
         // crab becomes a real crowd-control play, the flipside of the Golden/Dancer chaos engines.
         // The Armored crab earns a role in the herd beyond "shell you have to crack".
         const SHELTER_RADIUS: f32 = 82.0;
-        let mut anchors = std::mem::take(&mut self.armored_anchors_buf);
-        anchors.clear();
-        anchors.extend(
-            self.crabs
-                .iter()
-                .filter(|c| !c.caught && !c.is_boss() && c.is_armored())
-                .map(|c| c.pos),
-        );
-
-        // Bucket carriers into a spatial grid (same pattern as catch_by_chain and
-        // deflect_fleeing_off_chain) so each calm crab only tests nearby carriers instead of the
-        // whole panicking set — the herd has no size cap, so a flat scan here got slower the
-        // longer a session ran and the bigger a stampede got, which is exactly when frame time
-        // matters most for game feel.
-        let cell_size = CONTAGION_RADIUS.max(1.0);
-        let cell_of = |p: Vec2| -> (i32, i32) {
-            (
-                (p.x / cell_size).floor() as i32,
-                (p.y / cell_size).floor() as i32,
-            )
-        };
-        // Clear the whole map, not just each bucket's contents — keeping only the values cleared
-        // let the key set (one entry per grid cell ever visited by a carrier) grow unbounded over
-        // a long session as the herd wanders the full level, slowly bloating the hash table and
-        // its load factor even though the actual per-beat working set stays tiny. A full clear()
-        // still keeps the map's allocated capacity (same pooling win, no realloc most beats) but
-        // resets the key count to "cells touched this beat" instead of "cells touched ever".
-        self.contagion_grid_buf.clear();
-        for (i, &(pos, _)) in carriers.iter().enumerate() {
-            self.contagion_grid_buf
-                .entry(cell_of(pos))
-                .or_default()
-                .push(i);
-        }
-
-        // Bucket anchors into the same grid pattern, so the shelter check below only tests
-        // Armored crabs near this calm crab instead of every free Armored crab in the herd —
-        // without this a session salted with several Armored crabs turned the shelter check
-        // into a flat scan re-run per calm crab evaluated that beat.
-        // Same unbounded-key fix as contagion_grid_buf above: clear the whole map (keeps its
-        // capacity, resets its key count) instead of only clearing each bucket's Vec.
-        let mut anchor_grid = std::mem::take(&mut self.armored_anchor_grid_buf);
-        anchor_grid.clear();
-        for (i, &pos) in anchors.iter().enumerate() {
-            anchor_grid.entry(cell_of(pos)).or_default().push(i);
-        }
 
         let mut infected_pops = std::mem::take(&mut self.contagion_pops_buf);
         infected_pops.clear();
+        // Crabs infected this beat. They're panicking now, but carriers are whoever was already
+        // panicking when the beat landed, so these are skipped as sources below.
+        let mut infected: Vec<usize> = Vec::new();
         // Crabs an Armored anchor sheltered from the ripple this beat — drives a calm-puff cue.
         // Beat-gated (not per-frame), so a plain local Vec is fine, matching pried_by_magnet.
         let mut sheltered_pops: Vec<Vec2> = Vec::new();
-        for crab in &mut self.crabs {
+        for i in 0..self.crabs.len() {
             if infected_pops.len() >= MAX_INFECTIONS_PER_BEAT {
                 break;
             }
+            let crab = &self.crabs[i];
             // Only calm, catchable crabs outside the beam can be freshly infected.
             // A crab still soothed by a recent whistle pulse shrugs off the panic — this is what
             // makes the whistle a real crowd-control counter to a spreading stampede.
@@ -218,29 +172,30 @@ impl MainState {
             {
                 continue;
             }
-            // Nearest carrier within reach becomes the source the crab bolts away from,
-            // restricted to the 3x3 neighbourhood of grid cells around the crab.
+            // Nearest carrier within reach becomes the source the crab bolts away from, out of
+            // the crabs the shared index has near it.
             // A Golden's amplified fear reaches beyond the baseline radius, so the closest carrier
             // is scored by how far its own reach extends, not just raw distance — an amplified
             // carrier can out-pull a nearer ordinary one and grab crabs an ordinary crab couldn't.
-            let (cx, cy) = cell_of(crab.pos);
             let mut nearest: Option<(f32, Vec2, f32)> = None; // (reach-score, source pos, amp)
-            for dx in -1..=1 {
-                for dy in -1..=1 {
-                    if let Some(candidates) = self.contagion_grid_buf.get(&(cx + dx, cy + dy)) {
-                        for &i in candidates {
-                            let (source, amp) = carriers[i];
-                            let d = source.distance(crab.pos);
-                            let reach = CONTAGION_RADIUS * amp;
-                            if d < reach {
-                                // Lower score = stronger pull: normalize distance by the carrier's
-                                // own reach so amplified carriers win ties within their bigger radius.
-                                let score = d / amp;
-                                if nearest.map_or(true, |(ns, _, _)| score < ns) {
-                                    nearest = Some((score, source, amp));
-                                }
-                            }
-                        }
+            for candidate in self.crab_index.within(crab.pos, MAX_REACH + DRIFT) {
+                let Some(carrier) = self.crabs.get(candidate.index) else {
+                    continue;
+                };
+                let Some(amp) = carrier_amp(carrier) else {
+                    continue;
+                };
+                if infected.contains(&candidate.index) {
+                    continue;
+                }
+                let d = carrier.pos.distance(crab.pos);
+                let reach = CONTAGION_RADIUS * amp;
+                if d < reach {
+                    // Lower score = stronger pull: normalize distance by the carrier's
+                    // own reach so amplified carriers win ties within their bigger radius.
+                    let score = d / amp;
+                    if nearest.map_or(true, |(ns, _, _)| score < ns) {
+                        nearest = Some((score, carrier.pos, amp));
                     }
                 }
             }
@@ -255,18 +210,15 @@ impl MainState {
                 } else {
                     SHELTER_RADIUS
                 };
-                // Shelter radius is always <= CONTAGION_RADIUS (the grid's cell size), so any
-                // anchor within range is guaranteed to fall in the crab's own cell or one of its
-                // 8 neighbours — the same 3x3 sweep used for carriers above.
-                let sheltered = (-1..=1).any(|dx| {
-                    (-1..=1).any(|dy| {
-                        anchor_grid.get(&(cx + dx, cy + dy)).is_some_and(|bucket| {
-                            bucket
-                                .iter()
-                                .any(|&i| anchors[i].distance(crab.pos) < shelter_r)
-                        })
-                    })
-                });
+                let sheltered = self
+                    .crab_index
+                    .within_of_type(CrabType::Armored, crab.pos, shelter_r + DRIFT)
+                    .filter_map(|anchor| self.crabs.get(anchor.index))
+                    .any(|anchor| {
+                        anchor.is_armored()
+                            && !anchor.caught
+                            && anchor.pos.distance(crab.pos) < shelter_r
+                    });
                 if sheltered {
                     // Sheltered: the crab shrugs the ripple off entirely. Deliberately leave its
                     // calm state untouched (no startle_timer bump) so it doesn't turn into a phantom
@@ -274,6 +226,7 @@ impl MainState {
                     sheltered_pops.push(crab.pos);
                     continue;
                 }
+                let crab = &mut self.crabs[i];
                 let outward = (crab.pos - source).normalize_or_zero();
                 let outward = if outward == Vec2::ZERO {
                     Vec2::new(0.0, -1.0)
@@ -290,6 +243,7 @@ impl MainState {
                 // stays hotter than baseline for a couple more hops before fading to ordinary fear.
                 crab.panic_amp = (1.0 + (amp - 1.0) * 0.7).max(1.0);
                 infected_pops.push((crab.pos, amp > 1.05));
+                infected.push(i);
             }
         }
         // Alarm rings + "!" pops so the crab-to-crab ripple reads at a glance. Amplified
@@ -315,9 +269,6 @@ impl MainState {
                 self.particle_system.spawn_soothe_puff(pos, &mut rng);
             }
         }
-        self.contagion_carriers_buf = carriers;
         self.contagion_pops_buf = infected_pops;
-        self.armored_anchors_buf = anchors;
-        self.armored_anchor_grid_buf = anchor_grid;
     }
 }
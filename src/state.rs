@@ -3,7 +3,6 @@ use std::collections::VecDeque;
 use crevice::std140::AsStd140;
 use ggez::Context;
 use ggez::audio::SoundSource;
-use ggez::audio::Source;
 use ggez::glam::Vec2;
 use ggez::graphics::{Image, ShaderParams};
 
@@ -31,91 +30,145 @@ pub struct TrailUniform {
     pub strength: f32,
 }
 
+use crate::bindings::Bindings;
 use crate::bot::BotState;
+use crate::daily::DailyChallenge;
 // Re-exported so existing `use crate::state::*` consumers keep resolving these after the
 // NpcCongaTrain cluster moved to its own module.
 use crate::enemies::{CrabType, EnemyCrab};
+use crate::event_log::EventLog;
+use crate::gamepad::PadState;
 use crate::graphics::{FloatingTextSystem, ParticleSystem, PennedMarcherSystem};
+use crate::interpolation::RenderPositions;
+use crate::leaderboard::Leaderboards;
 use crate::levels::Level;
+use crate::mutators::{Mutators, PreRun};
+use crate::nemesis::NemesisRoster;
 pub use crate::npc_conga_train::{NpcCongaTrain, gen_king_crab_name};
+use crate::pause::Pause;
+use crate::profiler::Profiler;
+use crate::replay::Recorder;
+use crate::save::SaveStore;
+use crate::simulation::InputFrame;
 use crate::skins::PlayerSkin;
 use crate::sounds;
+use crate::sounds::Sfx;
+use crate::spatial::CrabIndex;
 use crate::tutorial::Tutorial;
-use crate::world_map::WorldMap;
+use crate::world_map::{NodeProgress, WorldMap};
 
 pub struct GameSounds {
-    pub(crate) intro_music: Source,
-    pub(crate) action_music: Vec<Source>,
-    pub(crate) outro_music: Source,
-    pub(crate) upgrade: Source,
-    pub(crate) success: Source,
-    pub(crate) success2: Source,
+    pub(crate) intro_music: Sfx,
+    pub(crate) action_music: Vec<Sfx>,
+    pub(crate) outro_music: Sfx,
+    pub(crate) upgrade: Sfx,
+    pub(crate) success: Sfx,
+    pub(crate) success2: Sfx,
     /// Ambient NPC King Crab conga train rumble — left-panned version.
     /// Volume is driven each frame by distance AND the leader's bearing (equal-power pan),
     /// so the train is heard swelling *and* placed left/right — the "heard before seen" radar.
-    pub(crate) king_crab_rumble_l: Source,
+    pub(crate) king_crab_rumble_l: Sfx,
     /// Ambient NPC King Crab conga train rumble — right-panned version. Paired with `_l`.
-    pub(crate) king_crab_rumble_r: Source,
-    pub(crate) hihat: Source,
+    pub(crate) king_crab_rumble_r: Sfx,
+    pub(crate) hihat: Sfx,
     /// Short bright chirp for the flashlight toggle (F key) — a snappy UI beep.
-    pub(crate) flashlight_toggle: Source,
+    pub(crate) flashlight_toggle: Sfx,
     /// Feather-light studio-logo sparkle heard once during the startup cinematic.
-    pub(crate) startup_pling: Source,
+    pub(crate) startup_pling: Sfx,
     /// Synthesized FM-bell arpeggio, an alternative "coin get" chime layered in alongside the
     /// sampled `success`/`success2` catch sounds for extra retro sparkle.
-    pub(crate) coin_chime: Source,
+    pub(crate) coin_chime: Sfx,
     /// Bright octave-up twinkle layered over `coin_chime` only when a catch lands in the tight
     /// PERFECT window — makes precision *audible*, not just a `perfect_flash` on screen (the visual
     /// side already existed). Pitched up per flawless step so an in-the-pocket run sounds like it
     /// climbs. See `play_perfect_sparkle`.
-    pub(crate) perfect_chime: Source,
+    pub(crate) perfect_chime: Sfx,
     /// Crisp woodblock "tok" layered over a ranged tool cast (whistle/stomp/wave/lasso) the instant
     /// it lands on the beat — the audible "each tool key is a drum pad" accent. Pitched up per
     /// on-beat streak at the call site (`play_tool_accent`) so a hot run of casts climbs.
-    pub(crate) tool_accent: Source,
+    pub(crate) tool_accent: Sfx,
     /// Ambient synth pad played on entering the campaign world map — a calm, atmospheric moment
     /// between levels, long swell/tail with a slow filter sweep, delay and stereo auto-pan.
-    pub(crate) world_map_pad: Source,
+    pub(crate) world_map_pad: Sfx,
     /// Synthesised finger-whistle for the Whistle tool.
-    pub(crate) whistle_sfx: Source,
+    pub(crate) whistle_sfx: Sfx,
     /// Synthesised stomp thud (kick + noise crack) for the Stomp tool.
-    pub(crate) stomp_sfx: Source,
+    pub(crate) stomp_sfx: Sfx,
     /// Synthesised whoosh for the Lasso throw release.
-    pub(crate) lasso_sfx: Source,
+    pub(crate) lasso_sfx: Sfx,
     /// Descending sting played when a rival train rustles crabs off your tail — the "loss" half of
     /// the core steal moment (paired with `steal_gain_sfx`), so losing crabs reads audibly.
-    pub(crate) steal_loss_sfx: Source,
+    pub(crate) steal_loss_sfx: Sfx,
     /// Rising sting played when you rustle crabs back off a rival — the triumphant "gain" half.
-    pub(crate) steal_gain_sfx: Source,
+    pub(crate) steal_gain_sfx: Sfx,
     /// Hard-left / hard-right variants of the neutral rival-vs-rival theft clack — a third-party
     /// steal out on the field. The audio pass sets their per-play volumes (equal-power pan by the
     /// collision's bearing, faded by distance) and `play_detached`es both, so a far-off rival steal
     /// reads as a faint directional tick the player looks toward and swoops into (agar.io "radar").
-    pub(crate) rival_steal_l: Source,
-    pub(crate) rival_steal_r: Source,
+    pub(crate) rival_steal_l: Sfx,
+    pub(crate) rival_steal_r: Sfx,
     /// Five crab-theme loops (Duck Game / Deus Ex ABA melodies), one per archetype group.
     /// 0=normal/fast/big  1=dancer/splitter  2=thief/sneaky  3=boss/armored  4=golden/magnet/hermit
-    pub(crate) crab_themes: [Source; 5],
+    pub(crate) crab_themes: [Sfx; 5],
     /// Spatial King Crab boss rumble — left-panned bright version.
     /// Volume driven per-frame by boss distance and angle relative to player.
-    pub(crate) king_crab_l: Source,
+    pub(crate) king_crab_l: Sfx,
     /// Spatial King Crab boss rumble — right-panned bright version.
-    pub(crate) king_crab_r: Source,
+    pub(crate) king_crab_r: Sfx,
     /// Spatial King Crab boss rumble — soft/distant version with baked room echo.
     /// Crossfades in as the boss moves further away (brightness rolloff approximation).
-    pub(crate) king_crab_soft: Source,
+    pub(crate) king_crab_soft: Sfx,
     /// Per-rival spatial MUSIC — one beat-locked melodic motif per ambient NPC King Crab train,
     /// indexed by train (0 scout / 1 wanderer / 2 elder). Each entry is a hard-left / hard-right
     /// pair like `king_crab_rumble_*`; the audio pass equal-power pans it by the leader's bearing
     /// and scales its volume by distance AND the train's length/tier, so a big rival train
     /// broadcasts a louder, fuller motif from across the field (INSPIRATION.md: "the dominant train
     /// dominates the mix"). Layered on top of the creature rumble — the melodic half of the radar.
-    pub(crate) king_crab_motif: Vec<(Source, Source)>,
+    pub(crate) king_crab_motif: Vec<(Sfx, Sfx)>,
+}
+
+impl GameSounds {
+    /// A full set of silent voices for the headless simulation. `action_music` and
+    /// `king_crab_motif` keep their real per-level shapes (one groove per level, three motif tiers
+    /// per level) so every index the mixer computes stays in bounds.
+    pub(crate) fn silent(level_count: usize) -> GameSounds {
+        GameSounds {
+            intro_music: Sfx::silent(),
+            action_music: (0..level_count).map(|_| Sfx::silent()).collect(),
+            outro_music: Sfx::silent(),
+            upgrade: Sfx::silent(),
+            success: Sfx::silent(),
+            success2: Sfx::silent(),
+            king_crab_rumble_l: Sfx::silent(),
+            king_crab_rumble_r: Sfx::silent(),
+            hihat: Sfx::silent(),
+            flashlight_toggle: Sfx::silent(),
+            startup_pling: Sfx::silent(),
+            coin_chime: Sfx::silent(),
+            perfect_chime: Sfx::silent(),
+            tool_accent: Sfx::silent(),
+            world_map_pad: Sfx::silent(),
+            whistle_sfx: Sfx::silent(),
+            stomp_sfx: Sfx::silent(),
+            lasso_sfx: Sfx::silent(),
+            steal_loss_sfx: Sfx::silent(),
+            steal_gain_sfx: Sfx::silent(),
+            rival_steal_l: Sfx::silent(),
+            rival_steal_r: Sfx::silent(),
+            crab_themes: std::array::from_fn(|_| Sfx::silent()),
+            king_crab_l: Sfx::silent(),
+            king_crab_r: Sfx::silent(),
+            king_crab_soft: Sfx::silent(),
+            king_crab_motif: (0..level_count * 3)
+                .map(|_| (Sfx::silent(), Sfx::silent()))
+                .collect(),
+        }
+    }
 }
 
 impl MainState {
     pub(crate) fn action_music_index(&self) -> usize {
-        // MainState::new synthesizes exactly one track for every entry from get_levels(); creation
+        // MainState::new synthesizes exactly one track for every level it loads; creation
         // fails before MainState exists if any synthesis fails, so live state can never be empty.
         assert!(
             !self.sounds.action_music.is_empty(),
@@ -152,12 +205,7 @@ impl MainState {
 /// Free function (not a `&mut self` method) so it can be called from inside loops that already
 /// hold a disjoint mutable borrow of another field of `MainState` (e.g. `for crab in &mut
 /// self.crabs`), where a whole-`self` method call wouldn't type-check.
-pub fn play_catch_sound(
-    sounds: &mut GameSounds,
-    _ctx: &mut Context,
-    rng: &mut impl rand::Rng,
-    beat_streak: u32,
-) {
+pub fn play_catch_sound(sounds: &mut GameSounds, rng: &mut impl rand::Rng, beat_streak: u32) {
     // Major pentatonic ratios (root, 2nd, 3rd, 5th, 6th) — a scale that sounds pleasant no matter
     // which step a rapid multi-catch lands on. Steps climb an octave every 5 catches, and each
     // higher octave doubles the ratio, so a long streak sweeps upward and resolves cleanly.
@@ -227,6 +275,24 @@ pub struct GameTextures {
     pub(crate) player: Image,
 }
 
+/// GPU-side resources built once at startup: the scene/post-process/trail shaders, their params,
+/// the offscreen render targets, and the level/player textures. Split out of `MainState` so the
+/// headless simulation can exist without a graphics context.
+pub struct RenderResources {
+    pub(crate) shader: ggez::graphics::Shader, // Shader for grass rendering
+    pub(crate) flashlight_shader: ggez::graphics::Shader, // Shader for flashlight rendering
+    pub(crate) flashlight_cone_image: ggez::graphics::Image, // Offscreen target for flashlight cone (isolated from scene canvas to avoid wgpu group-3 bind leak)
+    pub(crate) scene_image: ggez::graphics::Image, // Offscreen render target for post-processing
+    pub(crate) postprocess_shader: ggez::graphics::Shader, // Screen-space post-process shader
+    pub(crate) postprocess_params: ShaderParams<PostProcessUniform>, // Params for post-process shader
+    pub(crate) trail_shader: ggez::graphics::Shader, // Conga trail / echo-afterimage accumulation shader
+    pub(crate) trail_params: ShaderParams<TrailUniform>, // Params for the trail shader
+    pub(crate) trail_image_a: ggez::graphics::Image, // Ping-pong accumulation target A
+    pub(crate) trail_image_b: ggez::graphics::Image, // Ping-pong accumulation target B
+    pub(crate) trail_swap: bool, // Toggles which trail image is read vs written each frame
+    pub(crate) textures: GameTextures, // Textures for grass, sand, and player
+}
+
 /// Weather ambience state. Transitions are smooth: the discrete `target` a random walk picks
 /// each step is what `weather_intensity` eases toward, so the visuals never hard-cut between states.
 /// Ordered calm→wild so escalation is just "step the index".
@@ -307,22 +373,50 @@ pub struct MainState {
     pub(crate) show_how_to_play_text: bool, // Show plain-text How to Play card instead of Home menu
     pub(crate) show_play_recommendation: bool, // Recommend Campaign before starting arcade mode
     pub(crate) continue_button_focused: bool,
+    // The mutator screen before an arcade or endless run (see mutators.rs): which run it starts,
+    // the highlighted row (one past the last mutator is START), and the picks, which are kept
+    // between runs. `mutators` is the set in force for the run in play.
+    pub(crate) pre_run: Option<PreRun>,
+    pub(crate) mutator_selection: usize,
+    pub(crate) mutator_picks: Mutators,
+    pub(crate) mutators: Mutators,
     // Active cosmetic loadout for the player character (hat, facial hair, accessory).
-    // Loaded from career.txt on startup; changed from the title screen customisation menu.
+    // Loaded from the save (save.rs) on startup; changed from the title screen customisation menu.
     // Purely visual — never affects gameplay.
     pub(crate) player_skin: PlayerSkin,
     // Player crab name shown on the title screen and above the crab in-game.
     pub(crate) player_name: String,
     // Which cosmetic column the title-screen skin picker currently focuses: 0=Hat, 1=FacialHair, 2=Accessory.
     pub(crate) skin_slot: usize,
-    // Which menu page is active on the title screen: 0=Home, 1=Loadout.
+    // Which menu page is active on the title screen: 0=Home, 1=Loadout, 2=Controls, 3=Rivals.
     pub(crate) menu_page: usize,
     // Which button is highlighted in the Home page button list (0..NUM_MENU_BUTTONS).
     pub(crate) menu_selection: usize,
+    // The key bound to each player action (see bindings.rs). Loaded with the career.
+    pub(crate) bindings: Bindings,
+    // Controls page: the highlighted row (an index into `Action::ALL`), and whether the next key
+    // pressed is captured as that action's new binding.
+    pub(crate) rebind_selection: usize,
+    pub(crate) rebind_capture: bool,
+    // Why the last capture was refused ("M is reserved"), shown under the list until the next one.
+    pub(crate) rebind_notice: Option<String>,
     // Campaign world map — `Some` once the player has entered campaign mode from the title.
     // Persists across runs so node completion carries over. `show_world_map` gates whether the
     // map screen is currently visible; `in_campaign` is true during an active campaign run.
     pub(crate) world_map: Option<WorldMap>,
+    // Saved node progress (completion, unlocks, bests). Loaded with the career and applied when
+    // the map is first built; refreshed from the map whenever a node's progress changes.
+    pub(crate) map_progress: Vec<NodeProgress>,
+    // The per-mode and per-node high-score tables (see leaderboard.rs), loaded with the career.
+    // `last_entry` is the table and rank the last recorded run took, so the game-over screen and
+    // the world map can pick it out.
+    pub(crate) leaderboards: Leaderboards,
+    pub(crate) last_entry: Option<(String, usize)>,
+    // The date of the last scored daily challenge (see daily.rs); later attempts that day are
+    // practice.
+    pub(crate) daily_played: Option<String>,
+    // The rivals that remember the player between runs (see nemesis.rs), loaded with the career.
+    pub(crate) nemeses: NemesisRoster,
     pub(crate) show_world_map: bool,
     pub(crate) in_campaign: bool,
     // --- Campaign win-condition tracking (see `Level::win_condition`). All per-run counters,
@@ -333,11 +427,27 @@ pub struct MainState {
     pub(crate) banked_crabs_run: usize,
     pub(crate) shells_cracked_run: usize,
     pub(crate) hold_train_timer: f32,
+    // The steal-ecology goals: `outgrow_timer` is the OutgrowRivals streak clock (kept like
+    // `hold_train_timer`), `dethroned_kings` names every rival the player stripped bare this run,
+    // and `escorted_run` counts crabs of the Escort archetype banked this run.
+    pub(crate) outgrow_timer: f32,
+    pub(crate) dethroned_kings: Vec<String>,
+    pub(crate) escorted_run: usize,
     // Latch + celebration countdown once the level goal is met: the win fires exactly once, a
     // short "LEVEL COMPLETE!" beat plays out, then the run returns to the world map (which marks
     // the node complete and unlocks the next).
     pub(crate) level_complete: bool,
     pub(crate) level_complete_timer: f32,
+    // `time_elapsed` at the moment the goal (or tutorial) was met — the time a world-map node
+    // keeps as its best, without the celebration beat that follows.
+    pub(crate) win_time: f32,
+    // Balance telemetry for `--balance` (see balance.rs), per run like the counters above: the
+    // longest the train got, how often its tail snapped off (any cause), when the
+    // current boss arrived, and how long each caught boss took from its arrival.
+    pub(crate) longest_train_run: usize,
+    pub(crate) snaps_run: usize,
+    pub(crate) boss_arrived_at: Option<f32>,
+    pub(crate) boss_catch_times: Vec<f32>,
     // Active "How to Play" tutorial session, if any. `Some` while a scripted learn-session runs;
     // it uses the normal live update/draw path but constrains the run (no bosses, no wave
     // escalation, no level advance) and tracks its own machine-readable pass condition. `None`
@@ -351,8 +461,15 @@ pub struct MainState {
     pub(crate) sprint_stamina: f32, // Shift sprint meter: drains while sprinting, refills after
     pub(crate) levels: Vec<Level>, // List of levels with patterns
     pub(crate) current_level: usize, // Current level index
-    /// One-based stage number in endless arcade mode. Unlike `current_level`, this never wraps.
+    /// One-based stage number in arcade and endless runs. Unlike `current_level`, this never wraps.
     pub(crate) arcade_stage: usize,
+    /// The procedurally assembled stage in play during an endless run (see endless.rs); `None` in
+    /// every other mode. `current_level` then names the level whose biome the stage borrows.
+    pub(crate) endless: Option<Level>,
+    /// The daily challenge in play, which runs on the `endless` slot; `None` in every other mode.
+    pub(crate) daily: Option<DailyChallenge>,
+    /// Uncaught crabs at the last update; the run is overrun at `OVERRUN_FREE_CRABS`.
+    pub(crate) free_crab_count: usize,
     pub(crate) current_pattern: usize, // Current pattern index within the level
     pub(crate) pattern_timer: f32,     // Timer for current pattern duration
     pub(crate) debug_mode: bool,       // Debug mode flag
@@ -362,6 +479,10 @@ pub struct MainState {
     // so the cards stay stable instead of reshuffling every frame. Read by draw_upgrade_screen and
     // apply_upgrade so both always agree on which three are on offer.
     pub(crate) offered_upgrades: [usize; 3],
+    // The card a gamepad's D-pad has highlighted on the upgrade screen (0-2). `None` until the
+    // pad touches it; the mouse and the 1-3 keys don't need it.
+    pub(crate) upgrade_focus: Option<usize>,
+    pub(crate) pause: Option<Pause>, // The in-run pause menu or its count-in (see pause.rs)
     /// King Crab colors captured this run, used to tint the train and branch its tool loadout.
     pub(crate) king_crab_count: usize,
     /// Per-color King Crab captures, ordered Fire, Tide, Rhythm, Hermit, Dancer.
@@ -374,8 +495,8 @@ pub struct MainState {
     pub(crate) next_upgrade_score: usize, // Score threshold that triggers the next upgrade (rises each unlock)
     pub(crate) best_time: f32,            // Fastest time to catch all crabs
     // --- Meta-progression: a single persistent thread that survives across runs, so ending a
-    // run (win or loss) still banks progress into a career you carry forward. Persisted to
-    // career.txt as three whitespace-separated integers: best_score total_score runs.
+    // run (win or loss) still banks progress into a career you carry forward. Persisted in the
+    // save file (save.rs) as the best_score / total_score / runs keys.
     pub(crate) career_best_score: usize, // Highest single-run score ever reached
     pub(crate) career_total_score: usize, // Sum of every run's final score (lifetime crabs banked)
     pub(crate) career_runs: usize,       // How many runs have ended
@@ -386,7 +507,7 @@ pub struct MainState {
     // runs, so even a losing run buys you closer to your next unlock. `career_spent` is the ledger
     // of crabs already committed; available = career_total_score - career_spent. The four
     // start_*_rank fields are the ranks a fresh run begins each tool at (capped low so it's a
-    // leg-up, not a run-trivializer). Persisted alongside best/total/runs in career.txt.
+    // leg-up, not a run-trivializer). Persisted alongside best/total/runs in the save (save.rs).
     pub(crate) career_spent: usize,
     pub(crate) start_beam_rank: u32,
     pub(crate) start_lasso_rank: u32,
@@ -400,21 +521,13 @@ pub struct MainState {
     pub(crate) world_width: f32, // Full playfield width — larger than the viewport; the camera scrolls across it
     pub(crate) world_height: f32, // Full playfield height — larger than the viewport
     pub(crate) camera_origin: Vec2, // Top-left world coord of the visible viewport this frame (player-following, clamped to world bounds). Read by draw() and the mouse handlers to map screen<->world.
-    pub(crate) shader: ggez::graphics::Shader, // Shader for grass rendering
-    pub(crate) flashlight_shader: ggez::graphics::Shader, // Shader for flashlight rendering
-    pub(crate) flashlight_cone_image: ggez::graphics::Image, // Offscreen target for flashlight cone (isolated from scene canvas to avoid wgpu group-3 bind leak)
-    pub(crate) scene_image: ggez::graphics::Image, // Offscreen render target for post-processing
-    pub(crate) postprocess_shader: ggez::graphics::Shader, // Screen-space post-process shader
-    pub(crate) postprocess_params: ShaderParams<PostProcessUniform>, // Params for post-process shader
-    pub(crate) trail_shader: ggez::graphics::Shader, // Conga trail / echo-afterimage accumulation shader
-    pub(crate) trail_params: ShaderParams<TrailUniform>, // Params for the trail shader
-    pub(crate) trail_image_a: ggez::graphics::Image, // Ping-pong accumulation target A
-    pub(crate) trail_image_b: ggez::graphics::Image, // Ping-pong accumulation target B
-    pub(crate) trail_swap: bool, // Toggles which trail image is read vs written each frame
+    // Shaders, offscreen targets and textures — only present with a window. The headless
+    // simulation runs with `None`; everything that reads these is on the draw path.
+    pub(crate) gfx: Option<RenderResources>,
     pub(crate) particle_system: ParticleSystem, // Particle effects system
-    pub(crate) level_title: String, // Title of the current level
-    pub(crate) level_title_timer: f32, // Timer for displaying level title
-    pub(crate) subtitle: String, // Random subtitle for instructions screen
+    pub(crate) level_title: String,             // Title of the current level
+    pub(crate) level_title_timer: f32,          // Timer for displaying level title
+    pub(crate) subtitle: String,                // Random subtitle for instructions screen
     pub(crate) position_history: VecDeque<Vec2>,
     pub(crate) chain_count: usize,
     /// Monotonic count of every crab caught this run — unlike `chain_count`, it never drops when the
@@ -474,6 +587,19 @@ pub struct MainState {
     /// instead of chasing where it currently is. Never drops, so the bot playtest can assert the
     /// interception path fired without racing the live hunt state, which resets after each strike.
     pub(crate) hunt_intercepts: usize,
+    /// Monotonic count of crabs rival trains have banked at their own pens (see rival_economy.rs).
+    /// Never drops, so the bot playtest can assert a haul landed without racing the rival scores,
+    /// which start over with every run.
+    pub(crate) rival_crabs_banked: usize,
+    /// Monotonic count of steal-backs off a rival hauling home — the raid bonus. Never drops.
+    pub(crate) haul_raids: usize,
+    /// Monotonic count of each rival personality's signature move, indexed by `RivalPersonality`
+    /// (see rival_ai.rs): Pirate ambushes, Pompous snubs (frames it passed up a bigger train),
+    /// Raver downbeat steals, Serious guards and Silly opportunistic steals. Never drops.
+    pub(crate) rival_signatures: [usize; crate::rival_taunts::RivalPersonality::ALL.len()],
+    /// Monotonic count of nemeses handed a rival train when a beach started (see nemesis.rs).
+    /// Never drops.
+    pub(crate) nemeses_recalled: usize,
     /// Monotonic tally of rival leaders shoved by the Wave's proactive crowd-control (fire_wave).
     /// Never drops, so a bot playtest can prove the shove path fired without racing live state.
     pub(crate) rivals_wave_shoved: usize,
@@ -538,7 +664,7 @@ pub struct MainState {
     pub(crate) groove_was_full: bool,
     pub(crate) groove_full_flash: f32,
     pub(crate) music_muted: bool, // Whether music playback is muted (M key toggle)
-    pub(crate) music_layers: Vec<Source>,
+    pub(crate) music_layers: Vec<Sfx>,
     // Playback speed currently applied to the music sources (action groove + layers), = the
     // gameplay tempo multiplier so the loop stays tempo-locked to the beat grid as the intensity
     // stage ramps `beat_interval`. 1.0 at WARM-UP; rises with each stage. Re-applied (set_pitch +
@@ -581,7 +707,6 @@ pub struct MainState {
     pub(crate) marcher_arrivals_buf: Vec<(Vec2, [f32; 3])>,
     pub(crate) combo_count: usize,
     pub(crate) combo_timer: f32,
-    pub(crate) textures: GameTextures, // Textures for grass, sand, and player
     pub(crate) level_textures: Vec<LevelTexture>, // Textures for each level
     // Beat Wave ability
     pub(crate) beat_count: u32, // Counts beats fired, every 4th triggers wave
@@ -644,6 +769,7 @@ pub struct MainState {
     // Charge-throw fields: the player holds the mouse to wind up, releasing fires the throw.
     pub(crate) lasso_charge: f32, // 0..LASSO_MAX_CHARGE_TIME, grows while mouse is held
     pub(crate) lasso_mouse_down: bool, // True while left mouse button is held (winding)
+    pub(crate) lasso_key_down: bool, // True while a wind-up started from the Lasso key is held
     pub(crate) lasso_spin: f32,   // Accumulated rope spin angle in radians, for visual
     pub(crate) lasso_on_beat_bonus: f32, // 1.0 normally; LASSO_ONBEAT_BONUS if released on-beat
     // Crabs bitten by the current throw, mid-reel-in: (crab index, snag point, per-crab age seconds).
@@ -868,24 +994,21 @@ pub struct MainState {
     pub(crate) tide_pulses: Vec<(Vec2, f32)>,
     pub(crate) zoom_punch: f32, // camera zoom-in kick on catch, springs back to 0 (juice)
     pub(crate) fullscreen_applied: bool, // deferred until the first update tick, see update()
+    // The one spatial index every crab proximity query goes through (catching, Magnet pulls,
+    // contagion, chain deflection, rival pickups, the whistle/stomp rings). Rebuilt once per step
+    // before update_crabs and after any crab removal — see spatial.rs.
+    pub(crate) crab_index: CrabIndex,
     // Scratch buffers for catch_by_chain, reused every frame instead of being freshly
-    // allocated each call. The play area is fixed-size so the grid's cell count (and thus
-    // its Vec<usize> bucket count) stabilizes quickly — clearing beats rebuilding from scratch.
+    // allocated each call.
     pub(crate) chain_positions_buf: Vec<Vec2>,
-    pub(crate) catch_grid_buf: std::collections::HashMap<(i32, i32), Vec<usize>>,
-    // Tracks which cells were written to in catch_grid_buf this frame so the next frame's
-    // "clear" can call .clear() on each touched Vec rather than dropping it via HashMap::clear().
-    // Reusing the inner Vecs avoids ~40-50 small heap allocations per frame (one per crab-occupied
-    // cell), since crabs move slowly and typically revisit the same cells frame-to-frame.
-    pub(crate) catch_grid_keys_buf: Vec<(i32, i32)>,
     pub(crate) caught_now_buf: Vec<bool>,
-    // Reused buffer of solid conga-body segment positions, rebuilt each frame for the
-    // fleeing-crab wall-deflection pass (see deflect_fleeing_off_chain).
-    pub(crate) deflect_body_buf: Vec<Vec2>,
-    // Spatial grid over deflect_body_buf (same idea as catch_grid_buf below) so each fleeing
-    // crab only tests nearby body segments instead of the whole chain — chain length has no
-    // cap, so a linear scan there gets slower the longer a session runs.
-    pub(crate) deflect_grid_buf: std::collections::HashMap<(i32, i32), Vec<usize>>,
+    // Crab indices a whistle or stomp ring could have swept this frame, reused by both rings
+    // (see resolve_active_tools).
+    pub(crate) ring_swept_buf: Vec<usize>,
+    // Reused (crab index, distance, body link) hits for the fleeing-crab wall-deflection pass —
+    // every free crab near each solid body link, nearest link kept per crab (see
+    // deflect_fleeing_off_chain).
+    pub(crate) deflect_hits_buf: Vec<(usize, f32, Vec2)>,
     // Reused scratch buffer for bounce-ring spawn positions collected during the deflection
     // pass, avoiding a fresh Vec allocation every frame.
     pub(crate) deflect_bounce_buf: Vec<Vec2>,
@@ -893,9 +1016,10 @@ pub struct MainState {
     // pockets, where they collide with *each other*. This pass ricochets colliding fleeing crabs
     // apart and cross-startles them, so herding a panicking crowd into the conga wall sets off a
     // pinball cascade. deflect_ricochet_buf holds the (index, pos) of crabs deflected this frame;
-    // deflect_ricochet_grid_buf buckets them so each only tests nearby neighbors, not all of them.
+    // deflect_ricochet_slot_buf maps a crab index back to its slot there (usize::MAX if it wasn't
+    // deflected), so each only tests the deflected neighbours the crab index turns up.
     pub(crate) deflect_ricochet_buf: Vec<(usize, Vec2)>,
-    pub(crate) deflect_ricochet_grid_buf: std::collections::HashMap<(i32, i32), Vec<usize>>,
+    pub(crate) deflect_ricochet_slot_buf: Vec<usize>,
     // Cold ring positions where two deflected crabs cracked into each other, spawned after the
     // ricochet pass so the collision reads without a per-frame allocation.
     pub(crate) deflect_collide_buf: Vec<Vec2>,
@@ -928,25 +1052,16 @@ pub struct MainState {
     pub(crate) boss_windups_buf: Vec<Vec2>,
     pub(crate) boss_launches_buf: Vec<Vec2>,
     pub(crate) boss_charge_dust_buf: Vec<(Vec2, Vec2)>,
-    // A boss just crossed into its enrage phase this frame — (pos, is_tide) so the callout/burst
+    // A boss just crossed into its enrage phase this frame — (pos, boss type) so the callout/burst
     // can color itself. Almost always empty; reused like the other event buffers.
-    pub(crate) boss_enrages_buf: Vec<(Vec2, bool)>,
+    pub(crate) boss_enrages_buf: Vec<(Vec2, CrabType)>,
     pub(crate) tide_fires_buf: Vec<Vec2>,
     pub(crate) tide_swells_buf: Vec<Vec2>,
-    // Free Magnet-crab positions each frame, reused instead of reallocating — drives the
-    // magnet-pull pass in update_crabs (ordinary crabs drift toward the nearest one).
-    pub(crate) magnet_positions_buf: Vec<Vec2>,
-    // Free-roaming Golden positions each frame, reused instead of reallocating — drives the
-    // Golden-lures-Magnet pass in update_crabs (a roaming Magnet drifts toward the nearest one).
-    pub(crate) golden_lure_positions_buf: Vec<Vec2>,
     // Positions of "charged" Magnets each frame — a Magnet currently pinning a snared Golden deep
     // in its field. Reused instead of reallocating. Drives the Golden-supercharges-Magnet crossover
     // in update_crabs: the shine energizes the lodestone so it vacuums the surrounding herd in
     // harder while it holds the prize (see the charged-radius branch of the magnet-pull pass).
     pub(crate) charged_magnet_positions_buf: Vec<Vec2>,
-    // Free Armored crab positions each frame, reused instead of reallocating — drives the
-    // Armored-body-blocks-King-Crab-charge crossover (a shell in the lunge's lane stops it cold).
-    pub(crate) armored_positions_buf: Vec<Vec2>,
     // (boss_pos, shell_pos) for each King Crab charge blocked by an Armored shell this frame, so
     // the shell-clang feedback and shell knockback fire after the &mut self.crabs loop ends.
     pub(crate) boss_blocks_buf: Vec<(Vec2, Vec2)>,
@@ -969,23 +1084,6 @@ pub struct MainState {
     // Landing spots of fleeing Dancers each beat, reused instead of reallocating — drives the
     // per-beat Dancer-hop startle ripple (see the beat block in update).
     pub(crate) dancer_hop_scratch: Vec<Vec2>,
-    // Scratch buffers for beat_startle_contagion, mirroring the catch_by_chain/
-    // deflect_fleeing_off_chain grid pattern: carriers are bucketed into a spatial grid so each
-    // calm crab only tests nearby carriers instead of every panicking crab in the herd.
-    // Each carrier is (pos, panic amplitude): a fleeing Golden crab carries an amplified fear
-    // that ripples through the herd harder than an ordinary panicking crab (see below).
-    pub(crate) contagion_carriers_buf: Vec<(Vec2, f32)>,
-    pub(crate) contagion_grid_buf: std::collections::HashMap<(i32, i32), Vec<usize>>,
-    // Emergent crossover: free Armored crabs act as calm anchors that shelter the herd from the
-    // panic ripple. Their positions are snapshotted each beat into this reused buffer so the
-    // contagion pass can spare any calm crab sheltering in an Armored shell's shadow from
-    // infection — see beat_startle_contagion.
-    pub(crate) armored_anchors_buf: Vec<Vec2>,
-    // Spatial grid over armored_anchors_buf (same pattern as contagion_grid_buf) so the shelter
-    // check only tests nearby anchors instead of every free Armored crab in the herd — without
-    // this a session salted with several Armored crabs turned the per-crab shelter check into a
-    // flat scan multiplied across every calm crab evaluated that beat.
-    pub(crate) armored_anchor_grid_buf: std::collections::HashMap<(i32, i32), Vec<usize>>,
     // (pos, amplified?) — amplified pops came from a Golden's panic bomb and get a hot golden
     // "!" so the player sees the shiny prize detonating the herd, not just an ordinary scare.
     pub(crate) contagion_pops_buf: Vec<(Vec2, bool)>,
@@ -1072,11 +1170,6 @@ pub struct MainState {
     // the whistle — crack it first (Stomp), then herd it," so the wrong tool reads as clearly as a match.
     pub(crate) whistle_shell_deflect_hits_buf: Vec<Vec2>,
     pub(crate) magnet_cluster_hits_buf: Vec<Vec2>,
-    // Scratch per-magnet nearby-crab tally reused by the cluster-detection pass in update_crabs —
-    // sized to magnet_positions_buf and reset to 0 each on-beat frame instead of being freshly
-    // allocated, so the O(magnets) counting loop that replaced the old O(magnets * crabs) scan
-    // doesn't itself become a per-beat-frame Vec allocation.
-    pub(crate) magnet_cluster_counts_buf: Vec<u32>,
     pub(crate) stomp_cracked_buf: Vec<Vec2>,
     // Positions where a shelled Hermit was cracked open THIS frame, from any of its three intended
     // ecosystem verbs (Stomp / Dancer hop / charged Magnet rip). Collected inside the &mut crabs
@@ -1166,6 +1259,11 @@ pub struct MainState {
     pub(crate) perf_last_worst_ms: f32,
     #[cfg(debug_assertions)]
     pub(crate) perf_last_fps: f32,
+    // Debug builds reload edited level files while the game runs (see level_reload.rs).
+    #[cfg(debug_assertions)]
+    pub(crate) level_watch: crate::level_reload::LevelWatch,
+    // F3 overlay: per-subsystem frame timings and batch counts (all builds; see profiler.rs).
+    pub(crate) profiler: Profiler,
 
     // Bot playtest harness: scripted inputs + time acceleration.
     pub(crate) bot: Option<BotState>,
@@ -1175,6 +1273,36 @@ pub struct MainState {
     // reproducible; `None` in real gameplay keeps the variable wall-clock delta for smooth
     // rendering. Set once at startup in `main`.
     pub(crate) bot_fixed_dt: Option<f32>,
+    /// Wall-clock time not yet spent on a whole fixed step (interactive play only; see `tick`).
+    pub(crate) sim_accumulator: f32,
+    /// How far between the last two steps the next drawn frame sits: `sim_accumulator` as a
+    /// fraction of a step.
+    pub(crate) interp_alpha: f32,
+    /// Moving-body positions from just before the latest step, for render interpolation. `None`
+    /// until the fixed-step loop has run (bot runs draw raw positions).
+    pub(crate) prev_positions: Option<RenderPositions>,
+    /// The true post-step positions while `draw` shows the blended ones; kept to reuse its buffers.
+    pub(crate) stepped_positions: RenderPositions,
+    /// Keys held for the current simulation step. Snapshotted from the ggez keyboard by `tick`,
+    /// or handed in directly by the headless `Simulation`.
+    pub(crate) input: InputFrame,
+    /// The gamepad as the ggez callbacks last reported it; `tick` copies it into `input.pad`.
+    pub(crate) live_pad: PadState,
+    /// Set by the title menu's Quit; the windowed `tick` forwards it to `ctx.request_quit()`.
+    pub(crate) quit_requested: bool,
+    /// Where career changes are written. `None` for the headless simulation, so tests never
+    /// touch a player's save.
+    pub(crate) save_store: Option<SaveStore>,
+    /// `--record <file>`: every frame's input is streamed here as it is played.
+    pub(crate) recorder: Option<Recorder>,
+    /// `--event-log <file>`: gameplay events are streamed here as they happen (see event_log.rs).
+    pub(crate) event_log: Option<EventLog>,
+    /// `--replay <file>`: the frames still to play back. While `Some`, live keyboard/mouse input
+    /// is ignored; once it runs dry the keyboard takes over again.
+    pub(crate) replay: Option<VecDeque<InputFrame>>,
+    /// The calendar date a recorded or replayed session is pinned to, so a daily challenge deals
+    /// the recording's beach whenever it is played back; `None` reads the clock.
+    pub(crate) session_date: Option<String>,
 }
 
 impl MainState {
@@ -1184,13 +1312,20 @@ impl MainState {
         self.menu_intro_pling_played = true;
     }
 
-    /// The per-frame simulation delta, in seconds. In a deterministic bot run this is a fixed
-    /// constant (so the sim advances identically regardless of machine speed or ggez version);
-    /// in real gameplay it's the true wall-clock frame delta for smooth motion. Every in-game
-    /// `ctx.time.delta()` used to drive the sim funnels through here.
+    /// The per-frame simulation delta for a bot run, in seconds. In a deterministic bot run this
+    /// is a fixed constant (so the sim advances identically regardless of machine speed or ggez
+    /// version); a `RUSTLER_RECORD` clip uses the true wall-clock frame delta. Interactive play
+    /// doesn't come through here: it runs whole fixed steps off an accumulator (see `tick`).
     #[inline]
     pub(crate) fn frame_dt(&self, ctx: &Context) -> f32 {
         self.bot_fixed_dt
             .unwrap_or_else(|| ctx.time.delta().as_secs_f32())
     }
+
+    /// The render resources. Only the draw path calls this, and it only runs with a window.
+    pub(crate) fn gfx(&self) -> &RenderResources {
+        self.gfx
+            .as_ref()
+            .expect("drawing requires render resources")
+    }
 }
@@ -3,8 +3,12 @@
 //! Extracted verbatim from `state.rs` so that file stays focused on the `MainState`
 //! struct definition and the small per-frame helpers. Pure structural move: the
 //! constructor's behaviour is unchanged.
+//!
+//! Construction is two halves: `new_with_progress` (or `new_headless`) gathers everything that
+//! needs a window, an audio device or the disk into a `Startup`, and `from_startup` builds the
+//! world around it.
 
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use ggez::audio::{SoundSource, Source};
 use ggez::glam::Vec2;
@@ -16,44 +20,69 @@ use rand::prelude::IndexedRandom;
 use crate::constants::*;
 use crate::enemies::EnemyCrab;
 use crate::graphics::{FloatingTextSystem, ParticleSystem, PennedMarcherSystem};
-use crate::levels::MapSize;
+use crate::levels::{Level, MapSize, load_levels};
+use crate::mutators::Mutators;
 use crate::npc_conga_train::NpcCongaTrain;
-use crate::skins::PlayerSkin;
+use crate::save::{SaveData, SaveStore};
+use crate::simulation::InputFrame;
 use crate::sounds;
+use crate::sounds::Sfx;
 use crate::state::{
     Flashlight, GameSounds, GameTextures, LassoPhase, LevelTexture, MainState, PostProcessUniform,
-    TrailUniform, WeatherState,
+    RenderResources, TrailUniform, WeatherState,
 };
 use crate::upgrade::UPGRADE_FIRST_AT;
-use crate::{get_levels, pick_pen_pos, pick_tide_pools};
+use crate::{pick_pen_pos, pick_tide_pools};
+
+/// Logical view size. The offscreen render targets and the opening world are sized from it.
+const VIEW_WIDTH: f32 = 1280.0;
+const VIEW_HEIGHT: f32 = 960.0;
+
+/// Everything the constructor loads from the context or the disk before building the world.
+struct Startup {
+    sounds: GameSounds,
+    beat_synth: sounds::BeatSynth,
+    music_layers: Vec<Sfx>,
+    gfx: Option<RenderResources>,
+    beat_interval: f32,
+    save: SaveData,
+    save_store: Option<SaveStore>,
+}
 
 impl MainState {
     pub fn new(ctx: &mut Context) -> GameResult<MainState> {
         Self::new_with_progress(ctx, |_, _, _| Ok(()))
     }
 
+    /// The windowed game for playing back a replay: started from the recorder's save `snapshot`
+    /// instead of the local one, which it never writes.
+    pub(crate) fn new_for_replay(ctx: &mut Context, snapshot: SaveData) -> GameResult<MainState> {
+        Self::build(ctx, Some(snapshot), |_, _, _| Ok(()))
+    }
+
     /// Builds the game state while reporting completed startup work to the caller.
     ///
     /// The callback keeps initialization synchronous (which ggez resource creation requires), while
     /// allowing an interactive caller to present meaningful progress between expensive stages.
-    pub fn new_with_progress<F>(ctx: &mut Context, mut report_progress: F) -> GameResult<MainState>
+    pub fn new_with_progress<F>(ctx: &mut Context, report_progress: F) -> GameResult<MainState>
     where
         F: FnMut(&mut Context, f32, &str) -> GameResult,
     {
-        report_progress(ctx, 0.05, "TUNING THE TIDE...")?;
-        let width = 1280.0;
-        let height = 960.0;
-        // The opening campaign level uses the medium map; individual levels and tutorials replace
-        // these bounds when they begin.
-        let world_width = width * MapSize::Medium.viewport_multiplier();
-        let world_height = height * MapSize::Medium.viewport_multiplier();
-
-        // Player starts in the center of the WORLD always.
-        let player_pos = Vec2::new(
-            world_width / 2.0 - PLAYER_SIZE / 2.0,
-            world_height / 2.0 - PLAYER_SIZE / 2.0,
-        );
+        Self::build(ctx, None, report_progress)
+    }
 
+    /// [`MainState::new_with_progress`], on the save `snapshot` with no save store when given one.
+    fn build<F>(
+        ctx: &mut Context,
+        snapshot: Option<SaveData>,
+        mut report_progress: F,
+    ) -> GameResult<MainState>
+    where
+        F: FnMut(&mut Context, f32, &str) -> GameResult,
+    {
+        report_progress(ctx, 0.05, "TUNING THE TIDE...")?;
+        // Levels first: a broken level file should stop startup before the audio is baked.
+        let levels = load_levels().map_err(ggez::GameError::ResourceLoadError)?;
         // BPM detection is kept only for the informational startup log line below. The
         // groove is NOT baked at this tempo — see `action_bpm` after the block: the music
         // must match the gameplay beat grid, whose base is the BEAT_INTERVAL constant
@@ -102,7 +131,6 @@ impl MainState {
         report_progress(ctx, 0.15, "MIXING THE RAVE...")?;
         // detected_beat_interval still seeds the pre-game beat clock (reset_game overrides it to
         // BEAT_INTERVAL on entry) and the log line above; it no longer drives the music tempo.
-        let levels = get_levels();
 
         // TODO Load all sound effects.
         let (king_crab_l, king_crab_r, king_crab_soft) = sounds::synth_king_crab_spatial(ctx)?;
@@ -115,20 +143,16 @@ impl MainState {
         for level in &levels {
             let (root_midi, note_offsets) = sounds::biome_rival_motif_tuning(level.biome.music);
             for tier in 0..3 {
-                king_crab_motif.push(sounds::synth_rival_motif(
-                    ctx,
-                    action_bpm,
-                    root_midi,
-                    note_offsets,
-                    tier,
-                )?);
+                let (left, right) =
+                    sounds::synth_rival_motif(ctx, action_bpm, root_midi, note_offsets, tier)?;
+                king_crab_motif.push((left.into(), right.into()));
             }
         }
-        let intro_music = {
+        let intro_music: Sfx = {
             use std::io::Read as _;
             let mut bytes = Vec::new();
             ctx.fs.open("/intro.ogg")?.read_to_end(&mut bytes)?;
-            sounds::synth_intro_menu(ctx, &bytes)?
+            sounds::synth_intro_menu(ctx, &bytes)?.into()
         };
         let sounds = GameSounds {
             intro_music,
@@ -136,38 +160,42 @@ impl MainState {
             // harmony, lead timbre, and arrangement as the map changes.
             action_music: levels
                 .iter()
-                .map(|level| sounds::synth_biome_action_groove(ctx, action_bpm, level.biome.music))
+                .map(|level| {
+                    sounds::synth_biome_action_groove(ctx, action_bpm, level.biome.music)
+                        .map(Sfx::from)
+                })
                 .collect::<GameResult<Vec<_>>>()?,
-            outro_music: Source::new(ctx, "/outro.ogg")?,
-            upgrade: Source::new(ctx, "/upgrade.ogg")?,
-            success: Source::new(ctx, "/success.ogg")?,
-            success2: Source::new(ctx, "/success2.ogg")?,
-            king_crab_rumble_l,
-            king_crab_rumble_r,
-            hihat: sounds::synth_hihat(ctx)?,
-            flashlight_toggle: sounds::synth_flashlight_toggle(ctx)?,
-            startup_pling: sounds::synth_startup_pling(ctx)?,
-            coin_chime: sounds::synth_coin_chime(ctx)?,
-            perfect_chime: sounds::synth_perfect_sparkle(ctx)?,
-            tool_accent: sounds::synth_tool_accent(ctx)?,
-            world_map_pad: sounds::synth_ambient_pad(ctx, sounds::PadPreset::WarmPad, 220.0, 2.0)?,
-            whistle_sfx: sounds::synth_whistle(ctx)?,
-            stomp_sfx: sounds::synth_stomp(ctx)?,
-            lasso_sfx: sounds::synth_lasso_throw(ctx)?,
-            steal_loss_sfx: sounds::synth_steal_loss(ctx)?,
-            steal_gain_sfx: sounds::synth_steal_gain(ctx)?,
-            rival_steal_l,
-            rival_steal_r,
+            outro_music: Source::new(ctx, "/outro.ogg")?.into(),
+            upgrade: Source::new(ctx, "/upgrade.ogg")?.into(),
+            success: Source::new(ctx, "/success.ogg")?.into(),
+            success2: Source::new(ctx, "/success2.ogg")?.into(),
+            king_crab_rumble_l: king_crab_rumble_l.into(),
+            king_crab_rumble_r: king_crab_rumble_r.into(),
+            hihat: sounds::synth_hihat(ctx)?.into(),
+            flashlight_toggle: sounds::synth_flashlight_toggle(ctx)?.into(),
+            startup_pling: sounds::synth_startup_pling(ctx)?.into(),
+            coin_chime: sounds::synth_coin_chime(ctx)?.into(),
+            perfect_chime: sounds::synth_perfect_sparkle(ctx)?.into(),
+            tool_accent: sounds::synth_tool_accent(ctx)?.into(),
+            world_map_pad: sounds::synth_ambient_pad(ctx, sounds::PadPreset::WarmPad, 220.0, 2.0)?
+                .into(),
+            whistle_sfx: sounds::synth_whistle(ctx)?.into(),
+            stomp_sfx: sounds::synth_stomp(ctx)?.into(),
+            lasso_sfx: sounds::synth_lasso_throw(ctx)?.into(),
+            steal_loss_sfx: sounds::synth_steal_loss(ctx)?.into(),
+            steal_gain_sfx: sounds::synth_steal_gain(ctx)?.into(),
+            rival_steal_l: rival_steal_l.into(),
+            rival_steal_r: rival_steal_r.into(),
             crab_themes: [
-                sounds::synth_theme_duck_bounce(ctx)?,  // 0 — normal/fast/big
-                sounds::synth_theme_duck_funky(ctx)?,   // 1 — dancer/splitter
-                sounds::synth_theme_deus_tense(ctx)?,   // 2 — thief/sneaky
-                sounds::synth_theme_deus_ambient(ctx)?, // 3 — boss/armored/hermit
-                sounds::synth_theme_duck_golden(ctx)?,  // 4 — golden/magnet
+                sounds::synth_theme_duck_bounce(ctx)?.into(), // 0 — normal/fast/big
+                sounds::synth_theme_duck_funky(ctx)?.into(),  // 1 — dancer/splitter
+                sounds::synth_theme_deus_tense(ctx)?.into(),  // 2 — thief/sneaky
+                sounds::synth_theme_deus_ambient(ctx)?.into(), // 3 — boss/armored/hermit
+                sounds::synth_theme_duck_golden(ctx)?.into(), // 4 — golden/magnet
             ],
-            king_crab_l,
-            king_crab_r,
-            king_crab_soft,
+            king_crab_l: king_crab_l.into(),
+            king_crab_r: king_crab_r.into(),
+            king_crab_soft: king_crab_soft.into(),
             king_crab_motif,
         };
         report_progress(ctx, 0.55, "LOADING THE SHORE...")?;
@@ -184,115 +212,32 @@ impl MainState {
         };
         report_progress(ctx, 0.68, "SETTLING THE CRABS...")?;
 
-        // Delivery pen + tide-pool hazards for the opening level, placed before `levels` is moved
-        // into the struct so we can read the first zone's difficulty for the pool count.
-        let init_pen = pick_pen_pos(
-            world_width,
-            world_height,
-            player_pos + Vec2::splat(PLAYER_SIZE / 2.0),
-            &mut crate::rng::rng(),
-        );
-        let init_tide_pools = pick_tide_pools(
-            world_width,
-            world_height,
-            init_pen,
-            player_pos + Vec2::splat(PLAYER_SIZE / 2.0),
-            levels.first().map(|l| l.difficulty).unwrap_or(0),
-            &mut crate::rng::rng(),
-        );
-
-        // Randomly select a texture for each level
-        let mut rng = crate::rng::rng();
-        let level_textures: Vec<LevelTexture> = (0..levels.len())
-            .map(|_| {
-                if rng.random_range(0..2) == 0 {
-                    LevelTexture::Grass
-                } else {
-                    LevelTexture::Sand
+        // Load the player's save (career, best time, loadout, name) from the per-user data
+        // directory. A missing save migrates the legacy files or starts fresh, and a corrupt one
+        // is backed up first (see save.rs). One that can't be read or backed up is left alone:
+        // the session plays on a fresh career and saves nothing. A replay brings its own.
+        let (save, save_store) = match snapshot {
+            Some(save) => (save, None),
+            None => {
+                let save_store = SaveStore::new(ctx.fs.user_data_dir());
+                match save_store.load() {
+                    Ok(save) => (save, Some(save_store)),
+                    Err(e) => {
+                        eprintln!("{e}; progress won't be saved this session");
+                        (SaveData::default(), None)
+                    }
                 }
-            })
-            .collect();
-
-        // Load best time from file.
-        let best_time = fs::read_to_string("best_time.txt")
-            .ok()
-            .and_then(|s| s.parse::<f32>().ok())
-            .unwrap_or(f32::MAX);
-
-        // Load the persistent career (meta-progression). Missing/garbled file just starts a
-        // fresh career at zero — the game must never fail to launch over a save file.
-        // Format: best total runs [spent beam lasso whistle stomp]. The trailing spend-side
-        // fields were added later, so an old three-number save still parses — the extras just
-        // default to 0 (no perks purchased yet). Starting ranks are clamped to their cap on load
-        // so a hand-edited or future save can never over-buy a run.
-        let career_text = fs::read_to_string("career.txt").ok();
-        let (
-            career_best_score,
-            career_total_score,
-            career_runs,
-            career_spent,
-            start_beam_rank,
-            start_lasso_rank,
-            start_whistle_rank,
-            start_stomp_rank,
-        ) = career_text
-            .as_deref()
-            .and_then(|s| {
-                let mut it = s.split_whitespace().take(8);
-                let best = it.next()?.parse::<usize>().ok()?;
-                let total = it.next()?.parse::<usize>().ok()?;
-                let runs = it.next()?.parse::<usize>().ok()?;
-                let spent = it.next().and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
-                let clamp_rank = |v: Option<&str>| {
-                    v.and_then(|s| s.parse::<u32>().ok())
-                        .unwrap_or(0)
-                        .min(MAX_START_RANK)
-                };
-                let beam = clamp_rank(it.next());
-                let lasso = clamp_rank(it.next());
-                let whistle = clamp_rank(it.next());
-                let stomp = clamp_rank(it.next());
-                Some((best, total, runs, spent, beam, lasso, whistle, stomp))
-            })
-            .unwrap_or((0, 0, 0, 0, 0, 0, 0, 0));
-
-        // Load the saved cosmetic loadout — stored as its own `skin ...` line in career.txt.
-        let player_skin = career_text
-            .as_deref()
-            .and_then(|s| {
-                s.lines()
-                    .find(|l| l.trim_start().starts_with("skin "))
-                    .map(|l| PlayerSkin::from_save_line(l.trim()))
-            })
-            .unwrap_or_else(PlayerSkin::default_skin);
-
-        let player_name = career_text
-            .as_deref()
-            .and_then(|s| {
-                s.lines().find_map(|line| {
-                    line.trim_start()
-                        .strip_prefix("name ")
-                        .map(crate::normalize_player_name)
-                })
-            })
-            .unwrap_or_else(|| "Crabby".to_string());
-
-        let crabs: Vec<EnemyCrab> = [].to_vec();
-
-        // Pre-fill position history with initial player position
-        let mut position_history: VecDeque<Vec2> = VecDeque::new();
-        for _ in 0..2000 {
-            position_history.push_back(player_pos);
-        }
+            }
+        };
 
         // Try to load optional music layers (graceful — game works without them)
         // Place layer1.ogg, layer2.ogg, layer3.ogg in resources/ for layered crab rave
-        let mut music_layers: Vec<Source> = Vec::new();
+        let mut music_layers: Vec<Sfx> = Vec::new();
         for i in 1..=3usize {
             if let Ok(mut src) = Source::new(ctx, &format!("/layer{}.ogg", i)) {
                 src.set_repeat(true);
                 src.set_volume(0.0);
-                music_layers.push(src);
+                music_layers.push(src.into());
             }
         }
         report_progress(ctx, 0.78, "LIGHTING THE DANCE FLOOR...")?;
@@ -311,12 +256,12 @@ impl MainState {
         // group-3 bind never touches the scene canvas (ggez 0.9.3 set_default_shader doesn't clear
         // shader_bind_group, which would poison every subsequent instanced draw on the same canvas).
         let flashlight_cone_image =
-            ggez::graphics::Image::new_canvas_image(ctx, width as u32, height as u32, 1);
+            ggez::graphics::Image::new_canvas_image(ctx, VIEW_WIDTH as u32, VIEW_HEIGHT as u32, 1);
 
         // Use logical size (1280x960) for the offscreen render target, consistent with the viewport.
         // The postprocess pass will handle any HiDPI scaling when blitting to screen.
         let scene_image =
-            ggez::graphics::Image::new_canvas_image(ctx, width as u32, height as u32, 1);
+            ggez::graphics::Image::new_canvas_image(ctx, VIEW_WIDTH as u32, VIEW_HEIGHT as u32, 1);
         let postprocess_shader = ShaderBuilder::new()
             .vertex_path("/postprocess.wgsl")
             .fragment_path("/postprocess.wgsl")
@@ -324,8 +269,8 @@ impl MainState {
         let initial_pp_uniform = PostProcessUniform {
             groove: 0.0,
             time: 0.0,
-            screen_width: width,
-            screen_height: height,
+            screen_width: VIEW_WIDTH,
+            screen_height: VIEW_HEIGHT,
             title_card_t: 0.0,
             menu_bloom: 0.0,
             menu_moon_x: 0.82,
@@ -344,11 +289,127 @@ impl MainState {
         let initial_trail_uniform = TrailUniform { strength: 0.0 };
         let trail_params = ShaderParamsBuilder::new(&initial_trail_uniform).build(ctx);
         let trail_image_a =
-            ggez::graphics::Image::new_canvas_image(ctx, width as u32, height as u32, 1);
+            ggez::graphics::Image::new_canvas_image(ctx, VIEW_WIDTH as u32, VIEW_HEIGHT as u32, 1);
         let trail_image_b =
-            ggez::graphics::Image::new_canvas_image(ctx, width as u32, height as u32, 1);
+            ggez::graphics::Image::new_canvas_image(ctx, VIEW_WIDTH as u32, VIEW_HEIGHT as u32, 1);
         report_progress(ctx, 0.93, "POLISHING THE SHELLS...")?;
 
+        let gfx = RenderResources {
+            shader,
+            flashlight_shader,
+            flashlight_cone_image,
+            scene_image,
+            postprocess_shader,
+            postprocess_params,
+            trail_shader,
+            trail_params,
+            trail_image_a,
+            trail_image_b,
+            trail_swap: false,
+            textures,
+        };
+
+        Ok(Self::from_startup(
+            levels,
+            Startup {
+                sounds,
+                beat_synth,
+                music_layers,
+                gfx: Some(gfx),
+                beat_interval: detected_beat_interval,
+                save,
+                save_store,
+            },
+        ))
+    }
+
+    /// The startup state with no window, audio device or save files behind it: silent voices, no
+    /// render resources, a fresh career. Draws from `crate::rng` in the same order as
+    /// [`MainState::new`], so a seeded headless run matches a seeded windowed one.
+    pub fn new_headless() -> MainState {
+        Self::new_headless_from(SaveData::default())
+    }
+
+    /// [`MainState::new_headless`] on `save` instead of a fresh career, e.g. a replay's snapshot.
+    pub(crate) fn new_headless_from(save: SaveData) -> MainState {
+        let levels = load_levels().unwrap_or_else(|e| panic!("{e}"));
+        let sounds = GameSounds::silent(levels.len());
+        Self::from_startup(
+            levels,
+            Startup {
+                sounds,
+                beat_synth: sounds::BeatSynth::silent(),
+                music_layers: Vec::new(),
+                gfx: None,
+                beat_interval: BEAT_INTERVAL,
+                save,
+                save_store: None,
+            },
+        )
+    }
+
+    /// Assembles the opening world around whatever `startup` loaded. Shared by the windowed and
+    /// headless constructors, and the only place startup draws from the RNG.
+    fn from_startup(levels: Vec<Level>, startup: Startup) -> MainState {
+        let Startup {
+            sounds,
+            beat_synth,
+            music_layers,
+            gfx,
+            beat_interval,
+            save,
+            save_store,
+        } = startup;
+        let width = VIEW_WIDTH;
+        let height = VIEW_HEIGHT;
+        // The opening campaign level uses the medium map; individual levels and tutorials replace
+        // these bounds when they begin.
+        let world_width = width * MapSize::Medium.viewport_multiplier();
+        let world_height = height * MapSize::Medium.viewport_multiplier();
+
+        // Player starts in the center of the WORLD always.
+        let player_pos = Vec2::new(
+            world_width / 2.0 - PLAYER_SIZE / 2.0,
+            world_height / 2.0 - PLAYER_SIZE / 2.0,
+        );
+
+        // Delivery pen + tide-pool hazards for the opening level, placed before `levels` is moved
+        // into the struct so we can read the first zone's difficulty for the pool count.
+        let init_pen = pick_pen_pos(
+            world_width,
+            world_height,
+            player_pos + Vec2::splat(PLAYER_SIZE / 2.0),
+            &mut crate::rng::rng(),
+        );
+        let init_tide_pools = pick_tide_pools(
+            world_width,
+            world_height,
+            init_pen,
+            player_pos + Vec2::splat(PLAYER_SIZE / 2.0),
+            levels.first().map(|l| l.difficulty).unwrap_or(0),
+            &mut crate::rng::rng(),
+        );
+
+        // Randomly select a texture for each level
+        let mut rng = crate::rng::rng();
+        let level_textures: Vec<LevelTexture> = (0..levels.len())
+            .map(|_| {
+                if rng.random_range(0..2) == 0 {
+                    LevelTexture::Grass
+                } else {
+                    LevelTexture::Sand
+                }
+            })
+            .collect();
+
+        let crabs: Vec<EnemyCrab> = [].to_vec();
+
+        // Pre-fill position history with initial player position
+        let mut position_history: VecDeque<Vec2> = VecDeque::new();
+        for _ in 0..2000 {
+            position_history.push_back(player_pos);
+        }
+
         let flashlight = Flashlight {
             on: false,
             cone_upgrade: 0.0,
@@ -376,7 +437,7 @@ impl MainState {
             .unwrap()
             .to_string();
 
-        Ok(MainState {
+        MainState {
             player_pos,
             player_vel: Vec2::ZERO,
             mouse_pos: Vec2::ZERO,
@@ -398,19 +459,40 @@ impl MainState {
             show_how_to_play_text: false,
             show_play_recommendation: false,
             continue_button_focused: true,
-            player_skin,
-            player_name,
+            pre_run: None,
+            mutator_selection: 0,
+            mutator_picks: Mutators::default(),
+            mutators: Mutators::default(),
+            player_skin: save.player_skin,
+            player_name: save.player_name,
             skin_slot: 0,
             menu_page: 0,
             menu_selection: 0,
+            bindings: save.bindings,
+            rebind_selection: 0,
+            rebind_capture: false,
+            rebind_notice: None,
             world_map: None,
+            map_progress: save.map_progress,
+            leaderboards: save.leaderboards,
+            last_entry: None,
+            daily_played: save.daily_played,
+            nemeses: save.rivals,
             show_world_map: false,
             in_campaign: false,
             banked_crabs_run: 0,
             shells_cracked_run: 0,
             hold_train_timer: 0.0,
+            outgrow_timer: 0.0,
+            dethroned_kings: Vec::new(),
+            escorted_run: 0,
             level_complete: false,
             level_complete_timer: 0.0,
+            win_time: 0.0,
+            longest_train_run: 0,
+            snaps_run: 0,
+            boss_arrived_at: None,
+            boss_catch_times: Vec::new(),
             tutorial: None,
             last_dir: Vec2::ZERO,
             shake_timer: 0.0,
@@ -421,25 +503,30 @@ impl MainState {
             levels,
             current_level: 0,
             arcade_stage: 1,
+            endless: None,
+            daily: None,
+            free_crab_count: 0,
             current_pattern: 0,
             pattern_timer: 0.0,
             debug_mode: false,
             pending_upgrade: false,
             offered_upgrades: [0, 1, 2],
+            upgrade_focus: None,
+            pause: None,
             king_crab_count: 0,
             king_crab_powers: [0; 5],
             conga_tint: [0.0, 0.0, 0.0],
             speed_mult: 1.0,
             next_upgrade_score: UPGRADE_FIRST_AT,
-            best_time,
-            career_best_score,
-            career_total_score,
-            career_runs,
-            career_spent,
-            start_beam_rank,
-            start_lasso_rank,
-            start_whistle_rank,
-            start_stomp_rank,
+            best_time: save.best_time.unwrap_or(f32::MAX),
+            career_best_score: save.best_score,
+            career_total_score: save.total_score,
+            career_runs: save.runs,
+            career_spent: save.spent,
+            start_beam_rank: save.start_beam_rank,
+            start_lasso_rank: save.start_lasso_rank,
+            start_whistle_rank: save.start_whistle_rank,
+            start_stomp_rank: save.start_stomp_rank,
             shop_flash: 0.0,
             jam_timer: 0.0,
             shop_denied: 0.0,
@@ -450,21 +537,10 @@ impl MainState {
             world_width,
             world_height,
             camera_origin: Vec2::ZERO,
-            shader,
-            flashlight_shader,
-            flashlight_cone_image,
-            scene_image,
-            postprocess_shader,
-            postprocess_params,
-            trail_shader,
-            trail_params,
-            trail_image_a,
-            trail_image_b,
-            trail_swap: false,
+            gfx,
             particle_system: ParticleSystem::new(),
             level_title: String::new(),
             level_title_timer: 0.0,
-            textures,
             level_textures,
             subtitle,
             position_history,
@@ -481,13 +557,17 @@ impl MainState {
             rival_spill_crabs: 0,
             rival_hunt_telegraphs: 0,
             hunt_intercepts: 0,
+            rival_crabs_banked: 0,
+            haul_raids: 0,
+            rival_signatures: Default::default(),
+            nemeses_recalled: 0,
             rivals_wave_shoved: 0,
             on_beat_tool_sfx: false,
             steal_loss_sfx: false,
             steal_gain_sfx: false,
             rival_steal_sfx: None,
-            beat_timer: detected_beat_interval,
-            beat_interval: detected_beat_interval,
+            beat_timer: beat_interval,
+            beat_interval,
             beat_intensity: 0.0,
             music_intensity: 0.0,
             music_pitch: 1.0,
@@ -516,10 +596,10 @@ impl MainState {
             cleave_gold: false,
             // Runs begin at the permanent starting ranks bought with banked crabs (the spend side
             // of meta-progression), not flat zero.
-            beam_rank: start_beam_rank,
-            lasso_rank: start_lasso_rank,
-            whistle_rank: start_whistle_rank,
-            stomp_rank: start_stomp_rank,
+            beam_rank: save.start_beam_rank,
+            lasso_rank: save.start_lasso_rank,
+            whistle_rank: save.start_whistle_rank,
+            stomp_rank: save.start_stomp_rank,
             floating_texts: FloatingTextSystem::new(),
             penned_marchers: PennedMarcherSystem::new(),
             marcher_arrivals_buf: Vec::new(),
@@ -550,6 +630,7 @@ impl MainState {
             lasso_origin: Vec2::ZERO,
             lasso_charge: 0.0,
             lasso_mouse_down: false,
+            lasso_key_down: false,
             lasso_spin: 0.0,
             lasso_on_beat_bonus: 1.0,
             lasso_drag_buf: Vec::new(),
@@ -639,15 +720,14 @@ impl MainState {
             tide_pulses: Vec::new(),
             zoom_punch: 0.0,
             fullscreen_applied: false,
+            crab_index: Default::default(),
             chain_positions_buf: Vec::new(),
-            catch_grid_buf: std::collections::HashMap::new(),
-            catch_grid_keys_buf: Vec::new(),
             caught_now_buf: Vec::new(),
-            deflect_body_buf: Vec::new(),
-            deflect_grid_buf: std::collections::HashMap::new(),
+            ring_swept_buf: Vec::new(),
+            deflect_hits_buf: Vec::new(),
             deflect_bounce_buf: Vec::new(),
             deflect_ricochet_buf: Vec::new(),
-            deflect_ricochet_grid_buf: std::collections::HashMap::new(),
+            deflect_ricochet_slot_buf: Vec::new(),
             deflect_collide_buf: Vec::new(),
             deflect_resolve_buf: Vec::new(),
             flee_pops_buf: Vec::new(),
@@ -665,11 +745,8 @@ impl MainState {
             boss_enrages_buf: Vec::new(),
             tide_fires_buf: Vec::new(),
             tide_swells_buf: Vec::new(),
-            magnet_positions_buf: Vec::new(),
-            golden_lure_positions_buf: Vec::new(),
             charged_magnet_positions_buf: Vec::new(),
             magnet_grind_buf: Vec::new(),
-            armored_positions_buf: Vec::new(),
             boss_blocks_buf: Vec::new(),
             boss_stuns_buf: Vec::new(),
             golden_panic_positions_buf: Vec::new(),
@@ -677,11 +754,7 @@ impl MainState {
             spooked_by_golden_buf: Vec::new(),
             lured_by_golden_buf: Vec::new(),
             dancer_hop_scratch: Vec::new(),
-            contagion_carriers_buf: Vec::new(),
-            contagion_grid_buf: std::collections::HashMap::new(),
             contagion_pops_buf: Vec::new(),
-            armored_anchors_buf: Vec::new(),
-            armored_anchor_grid_buf: std::collections::HashMap::new(),
             dancer_startle_grid_buf: std::collections::HashMap::new(),
             dancer_spooked_buf: Vec::new(),
             dancer_jolt_buf: Vec::new(),
@@ -707,7 +780,6 @@ impl MainState {
             lasso_shell_deflect_hits_buf: Vec::new(),
             whistle_shell_deflect_hits_buf: Vec::new(),
             magnet_cluster_hits_buf: Vec::new(),
-            magnet_cluster_counts_buf: Vec::new(),
             stomp_cracked_buf: Vec::new(),
             hermit_popped_buf: Vec::new(),
             lasso_catch_buf: Vec::new(),
@@ -746,9 +818,24 @@ impl MainState {
             perf_last_worst_ms: 0.0,
             #[cfg(debug_assertions)]
             perf_last_fps: 0.0,
+            #[cfg(debug_assertions)]
+            level_watch: Default::default(),
+            profiler: Default::default(),
             bot: None,
             time_scale: 1.0,
             bot_fixed_dt: None,
-        })
+            sim_accumulator: 0.0,
+            interp_alpha: 0.0,
+            prev_positions: None,
+            stepped_positions: Default::default(),
+            input: InputFrame::default(),
+            live_pad: Default::default(),
+            quit_requested: false,
+            save_store,
+            recorder: None,
+            event_log: None,
+            replay: None,
+            session_date: None,
+        }
     }
 }
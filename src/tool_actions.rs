@@ -5,13 +5,13 @@
 //! and bot-driver hooks while this one holds the *verbs* — same methods on `impl MainState`, same
 //! behaviour, just grouped by subsystem.
 
-use ggez::Context;
 use ggez::audio::SoundSource;
 use ggez::glam::Vec2;
 use rand::Rng;
 
 use crate::constants::*;
 use crate::enemies::CrabType;
+use crate::mutators::Mutator;
 use crate::state::MainState;
 
 impl MainState {
@@ -305,7 +305,7 @@ impl MainState {
     /// wide radius straight into the conga train at once (a mass catch), pays out a score bonus, and
     /// drains the whole meter. This is the spectacle payoff for playing in the pocket. Off-beat, or
     /// with a meter that isn't topped out, it fizzles with a distinct message so the miss reads.
-    pub(crate) fn downbeat_slam(&mut self, _ctx: &mut Context) {
+    pub(crate) fn downbeat_slam(&mut self) {
         let center = self.player_pos + Vec2::splat(PLAYER_SIZE / 2.0);
         // Gate 1: needs high groove (75%+) — earnable without farming to 100%.
         if self.groove < 0.75 {
@@ -428,7 +428,7 @@ impl MainState {
     /// Whistle: yank nearby crabs toward the player. Great for skittish Sneaky crabs. On-beat casts
     /// reach farther and pull harder (see reward_on_beat_action).
     pub(crate) fn fire_whistle(&mut self) {
-        if self.whistle_cooldown > 0.0 {
+        if self.whistle_cooldown > 0.0 || self.mutators.has(Mutator::NoWhistle) {
             return;
         }
         self.whistle_center =
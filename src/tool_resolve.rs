@@ -10,12 +10,13 @@
 
 use ggez::glam::Vec2;
 
+use crate::spatial::DRIFT;
 use crate::*;
 
 impl MainState {
     /// Advance every active player-tool effect one frame. Called from `tick` after the visual-effect
     /// decay pass and before the chain-tail catch, exactly where the inline blocks used to run.
-    pub(crate) fn resolve_active_tools(&mut self, ctx: &mut Context, dt: f32) {
+    pub(crate) fn resolve_active_tools(&mut self, dt: f32) {
         // Whistle: an expanding sonic pulse from the player that yanks free crabs inward. The pull
         // strength is per-archetype (CrabType::whistle_pull) so it's the go-to tool for skittish
         // Sneaky crabs but only nudges the heavy Big ones — a soft counter, never a hard requirement.
@@ -46,7 +47,15 @@ impl MainState {
             // the whistle is active.
             let mut thief_snatched = std::mem::take(&mut self.whistle_thief_snatch_buf);
             thief_snatched.clear();
-            for (i, crab) in self.crabs.iter_mut().enumerate() {
+            // Only crabs the crab index has inside the ring can have been swept, visited in herd
+            // order so the capped feedback buffers fill the same way a full pass would.
+            let mut swept = std::mem::take(&mut self.ring_swept_buf);
+            self.crab_index
+                .collect_within(center, self.whistle_radius + DRIFT, &mut swept);
+            for &i in &swept {
+                let Some(crab) = self.crabs.get_mut(i) else {
+                    continue;
+                };
                 if crab.caught {
                     continue;
                 }
@@ -125,6 +134,7 @@ impl MainState {
                     }
                 }
             }
+            self.ring_swept_buf = swept; // hand the buffer back for reuse next frame
             // On-beat whistle catches its shaken Thieves: enlist each into the train and pay a bonus.
             for (i, pos) in thief_snatched.drain(..) {
                 self.snatch_thief_on_beat(i, pos);
@@ -209,7 +219,14 @@ impl MainState {
             // Hermit King crack/deflect events this frame (rare — at most one King on the field).
             let mut king_cracks: Vec<(Vec2, f32)> = Vec::new();
             let mut king_deflects: Vec<Vec2> = Vec::new();
-            for (i, crab) in self.crabs.iter_mut().enumerate() {
+            // Same candidate set as the whistle ring: what the crab index has inside the front.
+            let mut swept = std::mem::take(&mut self.ring_swept_buf);
+            self.crab_index
+                .collect_within(center, self.stomp_radius + DRIFT, &mut swept);
+            for &i in &swept {
+                let Some(crab) = self.crabs.get_mut(i) else {
+                    continue;
+                };
                 // The Hermit King is the one boss the Stomp DOES touch — it's the whole fight.
                 // One shell layer per pound, gated by phase: Sturdy takes any Stomp, Rattled and
                 // Panicked only crack to an ON-BEAT Stomp (the same beat window every tool uses).
@@ -276,6 +293,7 @@ impl MainState {
                 crab.spooked_timer = crab.spooked_timer.max(0.4);
                 crab.fleeing = false;
             }
+            self.ring_swept_buf = swept; // hand the buffer back for reuse next frame
             for (i, pos) in thief_snatched.drain(..) {
                 self.snatch_thief_on_beat(i, pos);
             }
@@ -346,8 +364,10 @@ impl MainState {
             let player_center = self.player_pos + Vec2::new(PLAYER_SIZE / 2.0, PLAYER_SIZE / 2.0);
             match self.lasso_phase {
                 LassoPhase::Winding => {
-                    // Grow charge and spin faster as it builds; cap at max.
-                    self.lasso_charge = (self.lasso_charge + dt).min(LASSO_MAX_CHARGE_TIME);
+                    // Grow charge and spin faster as it builds; cap at max. A right trigger
+                    // charges it with how hard it's squeezed (see gamepad.rs).
+                    let charge = dt * self.lasso_charge_rate();
+                    self.lasso_charge = (self.lasso_charge + charge).min(LASSO_MAX_CHARGE_TIME);
                     let charge_frac = self.lasso_charge / LASSO_MAX_CHARGE_TIME;
                     // Loop spins faster as charge builds (cowboy wind-up feel).
                     self.lasso_spin += dt * (8.0 + charge_frac * 20.0);
@@ -455,8 +475,8 @@ impl MainState {
                             self.shake_timer = 0.15;
                             self.hitstop_timer = self.hitstop_timer.max(0.06);
                             self.time_since_catch = 0.0;
-                            play_catch_sound(&mut self.sounds, ctx, &mut rng, self.beat_streak);
-                            self.check_upgrade_unlock(ctx);
+                            play_catch_sound(&mut self.sounds, &mut rng, self.beat_streak);
+                            self.check_upgrade_unlock();
                         }
                         for &origin in lasso_startle_origins.iter() {
                             self.emit_catch_startle(origin);
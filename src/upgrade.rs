@@ -10,10 +10,10 @@
 //! `impl MainState` block below can freely read and mutate the struct's private fields and call
 //! its private methods — Rust privacy is module-scoped and a child sees its ancestor's privates.
 
-use ggez::Context;
 use ggez::audio::SoundSource;
 
 use crate::MainState;
+use crate::event_log::GameEvent;
 
 // Upgrade cadence. The first upgrade lands at UPGRADE_FIRST_AT, each subsequent one costs
 // UPGRADE_STEP more (a rising threshold), so upgrades are rarer and feel earned as a run goes on.
@@ -31,7 +31,7 @@ pub const UPGRADE_STEP: usize = 1000;
 // effect below is expressed through stat knobs the game already reads (the four tool ranks,
 // catch_radius_upgrade, the flashlight cone/range, and a single player speed multiplier), so no
 // new mechanic is introduced — consistent with the mechanics freeze.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UpgradeId {
     BeamFocus,
     LassoFocus,
@@ -143,7 +143,7 @@ impl MainState {
     /// the threshold by a rising step so later upgrades are rarer and earned. Uses `>=` because
     /// score can overshoot the threshold in one banked jump (combo-multiplier steps). Call this
     /// after any score increase; it's the single knob for upgrade cadence.
-    pub fn check_upgrade_unlock(&mut self, _ctx: &mut Context) {
+    pub fn check_upgrade_unlock(&mut self) {
         // The upgrade choice is now a LIVE overlay — the world keeps running while the player
         // deliberates — so a big combo can still push the score past the next threshold before they
         // pick. Guard on `!pending_upgrade` so we never re-roll the offer (swapping the cards out
@@ -167,6 +167,7 @@ impl MainState {
             // and would otherwise reshuffle the offer 60×/sec.
             self.roll_upgrade_offer();
             self.pending_upgrade = true;
+            self.upgrade_focus = None;
         }
     }
 
@@ -187,6 +188,7 @@ impl MainState {
             return;
         }
         let id = UPGRADE_POOL[self.offered_upgrades[slot - 1]];
+        self.log_event(GameEvent::UpgradeChosen { upgrade: id });
         match id {
             UpgradeId::BeamFocus => self.rank_beam_lane(),
             // Lasso lane (chain catcher): wider passive chain reach AND a bigger lasso grab window.
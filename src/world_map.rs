@@ -1,23 +1,56 @@
 //! Campaign world map — the screen between the title and a campaign run.
 //!
-//! Wraps the existing `Level` list as a linear chain of `WorldMapNode`s laid out on a map
-//! canvas. The player navigates with arrow keys, selects a node, and launches it as a campaign
-//! run. Completing a node unlocks the next. The same `Level` metadata also drives arcade title
-//! cards; campaign resets at each selected node, while arcade keeps the train and upgrades alive
-//! as it crosses the same sequence.
+//! Wraps the existing `Level` list as a graph of `WorldMapNode`s laid out on a map canvas and
+//! joined by `Edge`s. The player follows the edges with the arrow keys, selects a node, and
+//! launches it as a campaign run. Winning a node opens the edges leading out of it whose
+//! `UnlockRule` the winning run met. The same `Level` metadata also drives arcade title cards;
+//! campaign resets at each selected node, while arcade keeps the train and upgrades alive as it
+//! crosses the levels in file order.
 //!
 //! The first four nodes are tutorial sandboxes — the new-player on-ramp lives here, not on a
 //! separate "How to Play" menu screen. Each one teaches one core mechanic, then hands off to
-//! the regular campaign levels that follow.
+//! the main route of regular campaign levels. Levels with a `branch` hang off the main route as
+//! side paths (challenges, boss gauntlets, and a secret the map hides until its route opens);
+//! their `route` lines say where they branch from and what opens them.
 
-use crate::levels::get_levels;
+use ggez::glam::Vec2;
+
+use crate::levels::{Branch, Level, UnlockRule};
 use crate::tutorial::TutorialKind;
 
+/// The tutorial sandboxes, in the order they chain into the main route.
+const TUTORIALS: [(TutorialKind, &str, (f32, f32)); 4] = [
+    (
+        TutorialKind::BeatTiming,
+        "The Beach — Catch the Beat",
+        (0.10, 0.72),
+    ),
+    (
+        TutorialKind::LassoGrab,
+        "The Docks — Throw the Lasso",
+        (0.19, 0.57),
+    ),
+    (
+        TutorialKind::ChainDeliver,
+        "The Cove — Build a Train",
+        (0.29, 0.67),
+    ),
+    (
+        TutorialKind::ShellCrack,
+        "The Reef — Crack the Shells",
+        (0.40, 0.53),
+    ),
+];
+
+/// How much shorter a step down the chart is on screen than the same step across it (the map
+/// squeezes its canvas into a band of the widescreen window), so directions read as drawn.
+const CHART_ASPECT: f32 = 0.36;
+
 /// What a world-map node launches when the player confirms. Tutorial nodes run a scripted
-/// sandbox; campaign nodes load a `Level` from `get_levels()`.
+/// sandbox; campaign nodes load a `Level` from `MainState::levels`.
 pub enum NodeKind {
     Tutorial(TutorialKind),
-    /// Index into `get_levels()`.
+    /// Index into the campaign's levels.
     Level(usize),
 }
 
@@ -25,15 +58,55 @@ pub enum NodeKind {
 pub struct WorldMapNode {
     /// What this node launches.
     pub kind: NodeKind,
+    /// Stable identifier for saves, high-score tables and `route` lines: the tutorial's mechanic,
+    /// or the level file's `id`.
+    pub id: String,
     /// Short display name shown on the map.
     pub name: String,
     /// Normalized position (0..1, 0..1) on the map canvas. Converted to screen coords at draw time.
     pub position: (f32, f32),
+    /// The level's biome tint, which colours the node and its trail; `None` on tutorial nodes.
+    pub tint: Option<(u8, u8, u8)>,
     pub completed: bool,
     pub unlocked: bool,
+    /// Highest score of a winning run on this node, if it has been won.
+    pub best_score: Option<usize>,
+    /// Fastest time (seconds into the run) this node's goal was met in, if it has been won.
+    pub best_time: Option<f32>,
+    /// The side path the node sits on; `None` on the main route and for tutorials.
+    pub branch: Option<Branch>,
+}
+
+/// A route between two nodes: winning `from` with a run that meets `rule` unlocks `to`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub rule: UnlockRule,
+}
+
+/// One node's saved progress, keyed by [`WorldMapNode::id`] so a save survives level files being
+/// added or reordered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeProgress {
+    pub id: String,
+    pub unlocked: bool,
+    pub completed: bool,
+    pub best_score: Option<usize>,
+    pub best_time: Option<f32>,
 }
 
 impl WorldMapNode {
+    /// "BEST 412 • 1:13.5" once the node has been won; `None` before.
+    pub fn best_text(&self) -> Option<String> {
+        let time = self.best_time?;
+        let time = crate::leaderboard::clock(time);
+        Some(match self.best_score {
+            Some(score) => format!("BEST {score}  •  {time}"),
+            None => format!("BEST {time}"),
+        })
+    }
+
     /// Returns the level index if this is a campaign node, or None for tutorial nodes.
     pub fn level_index(&self) -> Option<usize> {
         match self.kind {
@@ -49,11 +122,57 @@ impl WorldMapNode {
             NodeKind::Level(_) => None,
         }
     }
+
+    /// A locked secret stays off the map: no node, no route, no way to select it.
+    pub fn hidden(&self) -> bool {
+        self.branch == Some(Branch::Secret) && !self.unlocked
+    }
+}
+
+fn tutorial_id(kind: TutorialKind) -> &'static str {
+    match kind {
+        TutorialKind::BeatTiming => "tutorial-beat",
+        TutorialKind::LassoGrab => "tutorial-lasso",
+        TutorialKind::ChainDeliver => "tutorial-train",
+        TutorialKind::ShellCrack => "tutorial-shells",
+    }
+}
+
+/// Whether `id` names one of the tutorial nodes, which level ids must not clash with.
+pub fn is_tutorial_id(id: &str) -> bool {
+    TUTORIALS.iter().any(|&(kind, ..)| tutorial_id(kind) == id)
+}
+
+/// Whether `id` names a node on a map of `levels`, for checking `route` lines.
+pub fn is_node_id(id: &str, levels: &[Level]) -> bool {
+    is_tutorial_id(id) || levels.iter().any(|level| level.id == id)
+}
+
+/// The level id a node had in saves written while nodes were keyed by position (`stage-N`, save
+/// version 1), for [`crate::save::SaveData::parse`] to carry the old progress over. Frozen: it
+/// lists the level files as they stood then, and never grows.
+pub fn legacy_node_id(id: &str) -> Option<&'static str> {
+    const STAGES: [&str; 11] = [
+        "sunny-meadow",
+        "tide-pools",
+        "rocky-shore",
+        "neon-kelp-forest",
+        "moonlit-ballroom",
+        "shellgrave-warren",
+        "sunken-treasury",
+        "splitters-causeway",
+        "desktop",
+        "smugglers-cut",
+        "crown-shoals",
+    ];
+    let n = id.strip_prefix("stage-")?.parse::<usize>().ok()?;
+    STAGES.get(n.checked_sub(1)?).copied()
 }
 
-/// The campaign world map. Owns the node list and tracks which node is selected.
+/// The campaign world map. Owns the node graph and tracks which node is selected.
 pub struct WorldMap {
     pub nodes: Vec<WorldMapNode>,
+    pub edges: Vec<Edge>,
     /// Index of the currently highlighted node.
     pub selected: usize,
     /// Soft "skip ahead" confirm. When a *locked* node is selected and Confirm is pressed, this is
@@ -65,115 +184,159 @@ pub struct WorldMap {
 
 impl WorldMap {
     /// Build the world map. The first four nodes are tutorial sandboxes (the player's on-ramp);
-    /// the remaining nodes wrap the regular campaign levels. First node always unlocked; the rest
-    /// start locked until the previous one is completed.
-    pub fn new() -> Self {
-        // Tutorial nodes — teach one mechanic each, in escalating complexity.
-        let tutorial_nodes: &[(TutorialKind, &'static str, (f32, f32))] = &[
-            (
-                TutorialKind::BeatTiming,
-                "The Beach — Catch the Beat",
-                (0.10, 0.72),
-            ),
-            (
-                TutorialKind::LassoGrab,
-                "The Docks — Throw the Lasso",
-                (0.19, 0.57),
-            ),
-            (
-                TutorialKind::ChainDeliver,
-                "The Cove — Build a Train",
-                (0.29, 0.67),
-            ),
-            (
-                TutorialKind::ShellCrack,
-                "The Reef — Crack the Shells",
-                (0.40, 0.53),
-            ),
-        ];
-
-        // Campaign nodes follow a clockwise island circuit: the quiet landing opens into the
-        // northern coast, rounds the stormy eastern headland, then returns through the jungle.
-        // The Desktop remains a deliberately strange, remote finale beyond the chart's shore.
-        let campaign_positions: &[(f32, f32)] = &[
-            (0.51, 0.43),
-            (0.63, 0.29),
-            (0.77, 0.23),
-            (0.88, 0.41),
-            (0.82, 0.61),
-            // The circuit rounds the southern caves and treasury before climbing the eastern
-            // causeway, putting the remote Desktop finale directly beyond the last island stop.
-            (0.59, 0.72),
-            (0.72, 0.77),
-            (0.88, 0.63),
-            // The Desktop sits off on its own, past the "end" of the map — you shouldn't be here.
-            (0.94, 0.10),
-        ];
-        let levels = get_levels();
-        let total = tutorial_nodes.len() + levels.len();
+    /// the remaining nodes wrap the regular campaign `levels`, each at its own `map_position`.
+    /// First node always unlocked; the rest start locked until a route into them opens.
+    pub fn new(levels: &[Level]) -> Self {
+        let total = TUTORIALS.len() + levels.len();
         let mut nodes: Vec<WorldMapNode> = Vec::with_capacity(total);
 
-        for (i, &(kind, name, position)) in tutorial_nodes.iter().enumerate() {
+        // Tutorial nodes — teach one mechanic each, in escalating complexity.
+        for (i, &(kind, name, position)) in TUTORIALS.iter().enumerate() {
             nodes.push(WorldMapNode {
                 kind: NodeKind::Tutorial(kind),
+                id: tutorial_id(kind).to_string(),
                 name: format!("Tutorial {} — {}", i + 1, name),
                 position,
+                tint: None,
                 completed: false,
                 unlocked: i == 0,
+                best_score: None,
+                best_time: None,
+                branch: None,
             });
         }
 
         for (i, level) in levels.iter().enumerate() {
-            let map_i = tutorial_nodes.len() + i;
             nodes.push(WorldMapNode {
                 kind: NodeKind::Level(i),
-                name: format!("Stage {} — {}", i + 1, level.biome.name),
-                position: campaign_positions.get(i).copied().unwrap_or((0.5, 0.5)),
+                id: level.id.clone(),
+                name: stage_name(i, level),
+                position: level.map_position,
+                tint: Some(level.biome.tint),
                 completed: false,
-                unlocked: map_i == 0,
+                unlocked: false,
+                best_score: None,
+                best_time: None,
+                branch: level.branch,
             });
         }
 
+        let edges = build_edges(&nodes, levels);
         WorldMap {
             nodes,
+            edges,
             selected: 0,
             skip_warn_timer: 0.0,
         }
     }
 
+    /// Pick up edited `levels` (see `level_reload.rs`): campaign nodes take their level's new name,
+    /// map position, tint and branch and keep their progress, and the routes are redrawn.
+    pub fn refresh_levels(&mut self, levels: &[Level]) {
+        for node in &mut self.nodes {
+            if let NodeKind::Level(i) = node.kind
+                && let Some(level) = levels.get(i)
+            {
+                node.name = stage_name(i, level);
+                node.position = level.map_position;
+                node.tint = Some(level.biome.tint);
+                node.branch = level.branch;
+            }
+        }
+        self.edges = build_edges(&self.nodes, levels);
+    }
+
     /// The `Level` index that should be loaded when the player confirms from this map.
     /// Returns None if the selected node is a tutorial node.
     pub fn selected_level_index(&self) -> Option<usize> {
         self.nodes[self.selected].level_index()
     }
 
+    /// The selected node's save id, which also names its high-score table.
+    pub fn selected_id(&self) -> String {
+        self.nodes[self.selected].id.clone()
+    }
+
     /// The tutorial kind for the selected node, if it is a tutorial node.
     pub fn selected_tutorial_kind(&self) -> Option<TutorialKind> {
         self.nodes[self.selected].tutorial_kind()
     }
 
-    /// Mark the currently selected node complete and unlock the next one.
-    pub fn complete_selected(&mut self) {
-        self.nodes[self.selected].completed = true;
-        let next = self.selected + 1;
-        if next < self.nodes.len() {
-            self.nodes[next].unlocked = true;
+    /// Mark the currently selected node complete and open every route out of it whose rule the
+    /// winning run met, keeping the run's `score` and goal `time` if they beat the node's bests.
+    /// `train` is the run's longest train. Tutorials have no score.
+    pub fn complete_selected(&mut self, score: Option<usize>, time: f32, train: usize) {
+        let node = &mut self.nodes[self.selected];
+        node.completed = true;
+        if let Some(score) = score {
+            node.best_score = Some(node.best_score.map_or(score, |best| best.max(score)));
+        }
+        node.best_time = Some(node.best_time.map_or(time, |best| best.min(time)));
+        for edge in &self.edges {
+            if edge.from == self.selected && edge.rule.met(score.unwrap_or(0), time, train) {
+                self.nodes[edge.to].unlocked = true;
+            }
         }
     }
 
-    /// Move selection left (delta = -1) or right (delta = +1) to the *adjacent* node — locked or
-    /// not (the campaign is an on-ramp, not a hard gate; a playtester or impatient player can walk
-    /// to any node and skip ahead with a soft warning, see `arm_skip_warning`). Clamps at the ends
-    /// so it never wraps. Any move cancels a pending skip warning.
-    pub fn move_selection(&mut self, delta: i32) {
-        let len = self.nodes.len() as i32;
-        let target = self.selected as i32 + delta;
-        if target >= 0 && target < len {
-            self.selected = target as usize;
+    /// The nodes one edge away from `node`, either way along the edge.
+    pub fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges.iter().filter_map(move |edge| {
+            if edge.from == node {
+                Some(edge.to)
+            } else if edge.to == node {
+                Some(edge.from)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Follow the edge that best points along `dir` (screen axes, y down) to the node at its far
+    /// end — locked or not (the campaign is an on-ramp, not a hard gate; a playtester or impatient
+    /// player can walk to any node and skip ahead with a soft warning, see `arm_skip_warning`).
+    /// Hidden secrets can't be walked to, and a direction no edge leans toward stays put. Any move
+    /// cancels a pending skip warning.
+    pub fn move_toward(&mut self, dir: Vec2) {
+        let (x, y) = self.nodes[self.selected].position;
+        let best = self
+            .neighbours(self.selected)
+            .filter(|&n| !self.nodes[n].hidden())
+            .map(|n| {
+                let (nx, ny) = self.nodes[n].position;
+                let step = Vec2::new(nx - x, (ny - y) * CHART_ASPECT).normalize_or_zero();
+                (n, step.dot(dir))
+            })
+            .filter(|&(_, lean)| lean > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((n, _)) = best {
+            self.selected = n;
         }
         self.skip_warn_timer = 0.0;
     }
 
+    /// Whether any route out of the selected node is open.
+    pub fn next_unlocked(&self) -> bool {
+        self.edges
+            .iter()
+            .any(|edge| edge.from == self.selected && self.nodes[edge.to].unlocked)
+    }
+
+    /// What opens the selected node while it is locked: "win Stage 6 with a train ≥ 20", one per
+    /// route into it, joined with "or". `None` once it's unlocked.
+    pub fn unlock_hint(&self) -> Option<String> {
+        if self.selected_unlocked() {
+            return None;
+        }
+        let routes: Vec<String> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.to == self.selected)
+            .map(|edge| edge.rule.describe(short_name(&self.nodes[edge.from])))
+            .collect();
+        (!routes.is_empty()).then(|| routes.join(" or "))
+    }
+
     /// True when the currently selected node is already unlocked (Confirm launches it directly).
     pub fn selected_unlocked(&self) -> bool {
         self.nodes[self.selected].unlocked
@@ -202,33 +365,202 @@ impl WorldMap {
         }
     }
 
-    /// Commit a skip-ahead: unlock AND complete every node from the start up to and including the
-    /// selected one, so the world map reflects that the earlier nodes were skipped over. The caller
-    /// then launches the selected node as usual. Clears the pending warning.
+    /// Commit a skip-ahead: unlock AND complete the selected node and every node with a route
+    /// leading to it, so the world map reflects that the earlier nodes were skipped over. The
+    /// caller then launches the selected node as usual. Clears the pending warning.
     pub fn unlock_through_selected(&mut self) {
-        for node in self.nodes.iter_mut().take(self.selected + 1) {
-            node.unlocked = true;
-            node.completed = true;
+        let mut seen = vec![false; self.nodes.len()];
+        let mut pending = vec![self.selected];
+        while let Some(i) = pending.pop() {
+            if std::mem::replace(&mut seen[i], true) {
+                continue;
+            }
+            self.nodes[i].unlocked = true;
+            self.nodes[i].completed = true;
+            pending.extend(self.edges.iter().filter(|e| e.to == i).map(|e| e.from));
         }
         self.skip_warn_timer = 0.0;
     }
 
-    /// True once every node has been completed (end of campaign).
-    pub fn is_complete(&self) -> bool {
-        self.nodes.iter().all(|n| n.completed)
+    /// Every node's progress, for the save file.
+    pub fn progress(&self) -> Vec<NodeProgress> {
+        self.nodes
+            .iter()
+            .map(|node| NodeProgress {
+                id: node.id.clone(),
+                unlocked: node.unlocked,
+                completed: node.completed,
+                best_score: node.best_score,
+                best_time: node.best_time,
+            })
+            .collect()
+    }
+
+    /// Restore saved progress onto the freshly built map. Entries for nodes that no longer exist
+    /// are ignored; nodes the save doesn't mention keep their defaults.
+    pub fn apply_progress(&mut self, progress: &[NodeProgress]) {
+        for node in &mut self.nodes {
+            if let Some(saved) = progress.iter().find(|p| p.id == node.id) {
+                node.unlocked = node.unlocked || saved.unlocked || saved.completed;
+                node.completed = saved.completed;
+                node.best_score = saved.best_score;
+                node.best_time = saved.best_time;
+            }
+        }
+    }
+}
+
+fn stage_name(index: usize, level: &Level) -> String {
+    match level.branch {
+        None => format!("Stage {} — {}", index + 1, level.biome.name),
+        Some(Branch::Challenge) => format!("Challenge — {}", level.biome.name),
+        Some(Branch::Boss) => format!("Boss — {}", level.biome.name),
+        Some(Branch::Secret) => format!("Secret — {}", level.biome.name),
+    }
+}
+
+/// A node's name before the " — " and its biome: "Stage 6", "Tutorial 2", "Challenge".
+fn short_name(node: &WorldMapNode) -> &str {
+    node.name.split(" — ").next().unwrap_or(&node.name)
+}
+
+/// The map's routes. The tutorials chain one into the next and into the first main-route level;
+/// a level with `route` lines is opened by them, and any other level follows the main-route node
+/// before it. Ids were checked against the level list when it loaded.
+fn build_edges(nodes: &[WorldMapNode], levels: &[Level]) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    let mut main_tail = None;
+    for (i, node) in nodes.iter().enumerate() {
+        let routes = node
+            .level_index()
+            .and_then(|l| levels.get(l))
+            .map_or(&[][..], |level| &level.routes[..]);
+        if routes.is_empty() {
+            if let Some(from) = main_tail {
+                edges.push(Edge {
+                    from,
+                    to: i,
+                    rule: UnlockRule::Win,
+                });
+            }
+        } else {
+            for route in routes {
+                if let Some(from) = nodes.iter().position(|n| n.id == route.from) {
+                    edges.push(Edge {
+                        from,
+                        to: i,
+                        rule: route.rule,
+                    });
+                }
+            }
+        }
+        if node.branch.is_none() {
+            main_tail = Some(i);
+        }
     }
+    edges
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::levels::load_levels;
 
     #[test]
     fn map_nodes_are_enumerated_tutorials_then_campaign_stages() {
-        let map = WorldMap::new();
+        let map = WorldMap::new(&load_levels().unwrap());
         assert!(map.nodes[0].name.starts_with("Tutorial 1 —"));
         assert!(map.nodes[3].name.starts_with("Tutorial 4 —"));
         assert!(map.nodes[4].name.starts_with("Stage 1 —"));
-        assert!(map.nodes.last().unwrap().name.starts_with("Stage 9 —"));
+        assert!(map.nodes[11].name.starts_with("Stage 8 —"));
+        assert!(map.nodes[12].name.starts_with("Secret —"));
+        assert!(map.nodes.last().unwrap().name.starts_with("Boss —"));
+    }
+
+    #[test]
+    fn progress_restores_onto_a_fresh_map() {
+        let mut map = WorldMap::new(&load_levels().unwrap());
+        map.complete_selected(None, 42.0, 0);
+        map.selected = 4;
+        map.unlock_through_selected();
+        map.complete_selected(Some(300), 95.5, 12);
+        map.complete_selected(Some(250), 120.0, 12);
+
+        let mut restored = WorldMap::new(&load_levels().unwrap());
+        restored.apply_progress(&map.progress());
+        assert_eq!(restored.progress(), map.progress());
+        assert!(restored.nodes[5].unlocked && !restored.nodes[5].completed);
+        assert_eq!(restored.nodes[4].best_score, Some(300));
+        assert_eq!(restored.nodes[4].best_time, Some(95.5));
+        assert_eq!(
+            restored.nodes[4].best_text().unwrap(),
+            "BEST 300  •  1:35.5"
+        );
+    }
+
+    #[test]
+    fn routes_branch_off_the_main_line_and_open_by_rule() {
+        let mut map = WorldMap::new(&load_levels().unwrap());
+        let node = |map: &WorldMap, id: &str| map.nodes.iter().position(|n| n.id == id).unwrap();
+        let (s6, s7, s8) = (
+            node(&map, "shellgrave-warren"),
+            node(&map, "sunken-treasury"),
+            node(&map, "splitters-causeway"),
+        );
+        let (desktop, cut, court) = (
+            node(&map, "desktop"),
+            node(&map, "smugglers-cut"),
+            node(&map, "crown-shoals"),
+        );
+        assert!(map.edges.contains(&Edge {
+            from: s7,
+            to: s8,
+            rule: UnlockRule::Win
+        }));
+        assert!(map.edges.contains(&Edge {
+            from: s6,
+            to: cut,
+            rule: UnlockRule::Train(20)
+        }));
+
+        // A short train wins the March but leaves the smugglers' cut shut.
+        map.selected = s6;
+        map.complete_selected(Some(200), 100.0, 19);
+        assert!(map.nodes[s7].unlocked && !map.nodes[cut].unlocked);
+        map.selected = cut;
+        assert_eq!(
+            map.unlock_hint().as_deref(),
+            Some("win Stage 6 with a train ≥ 20")
+        );
+        map.selected = s6;
+        map.complete_selected(Some(200), 100.0, 20);
+        assert!(map.nodes[cut].unlocked);
+
+        // The arrows follow the edges the way they point; the secret can't be walked to locked.
+        map.move_toward(Vec2::NEG_X);
+        assert_eq!(map.selected, cut);
+        map.move_toward(Vec2::X);
+        assert_eq!(map.selected, s6);
+        map.move_toward(Vec2::X);
+        assert_eq!(map.selected, s7);
+        map.move_toward(Vec2::Y);
+        assert_eq!(map.selected, court);
+        map.move_toward(Vec2::NEG_Y);
+        map.move_toward(Vec2::X);
+        assert_eq!(map.selected, s8);
+        assert!(map.nodes[desktop].hidden());
+        map.move_toward(Vec2::NEG_Y);
+        assert_eq!(map.selected, s8);
+        map.complete_selected(Some(500), 200.0, 30);
+        assert!(!map.nodes[desktop].hidden());
+        map.move_toward(Vec2::NEG_Y);
+        assert_eq!(map.selected, desktop);
+
+        // Skipping to a side path completes the route that leads to it, not its siblings.
+        let mut map = WorldMap::new(&load_levels().unwrap());
+        map.selected = court;
+        map.unlock_through_selected();
+        assert!(map.nodes[..=s7].iter().all(|n| n.completed));
+        assert!(!map.nodes[s8].completed && !map.nodes[cut].completed);
     }
 }